@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX seeds_parent_seed_id_idx;
+
+ALTER TABLE seeds
+DROP CONSTRAINT seeds_parent_seed_id_fkey;
+
+ALTER TABLE seeds
+DROP COLUMN parent_seed_id;
@@ -0,0 +1,10 @@
+ALTER TABLE seeds
+ADD COLUMN parent_seed_id INTEGER;
+
+ALTER TABLE seeds
+ADD CONSTRAINT seeds_parent_seed_id_fkey
+FOREIGN KEY (parent_seed_id)
+REFERENCES seeds (id)
+ON DELETE SET NULL;
+
+CREATE INDEX seeds_parent_seed_id_idx ON seeds (parent_seed_id);
@@ -0,0 +1,12 @@
+ALTER TABLE plants
+DROP COLUMN genus,
+DROP COLUMN material_uses_and_functions,
+DROP COLUMN botanic,
+DROP COLUMN soil_water_retention,
+DROP COLUMN flower_type,
+DROP COLUMN plant_references,
+DROP COLUMN is_tree,
+DROP COLUMN article_last_modified_at;
+
+DROP TYPE soil_water_retention;
+DROP TYPE flower_type;
@@ -0,0 +1,21 @@
+CREATE TYPE flower_type AS ENUM (
+    'dioecious',
+    'monoecious',
+    'hermaphrodite'
+);
+
+CREATE TYPE soil_water_retention AS ENUM (
+    'well drained',
+    'moist',
+    'wet'
+);
+
+ALTER TABLE plants
+ADD COLUMN genus TEXT,
+ADD COLUMN material_uses_and_functions TEXT,
+ADD COLUMN botanic TEXT,
+ADD COLUMN soil_water_retention SOIL_WATER_RETENTION [],
+ADD COLUMN flower_type FLOWER_TYPE,
+ADD COLUMN plant_references TEXT [],
+ADD COLUMN is_tree BOOLEAN,
+ADD COLUMN article_last_modified_at TIMESTAMP;
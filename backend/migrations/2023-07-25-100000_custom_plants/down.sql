@@ -0,0 +1,16 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX plants_owner_id_unique_name_key;
+
+DROP INDEX plants_catalogue_unique_name_key;
+
+ALTER TABLE plants
+ADD CONSTRAINT plant_unique_name_key UNIQUE (unique_name);
+
+ALTER TABLE plants
+DROP CONSTRAINT plants_base_plant_id_fkey;
+
+ALTER TABLE plants
+DROP COLUMN owner_id,
+DROP COLUMN base_plant_id,
+DROP COLUMN submitted_for_review;
@@ -0,0 +1,19 @@
+ALTER TABLE plants
+ADD COLUMN owner_id UUID,
+ADD COLUMN base_plant_id INTEGER,
+ADD COLUMN submitted_for_review BOOLEAN NOT NULL DEFAULT false;
+
+ALTER TABLE plants
+ADD CONSTRAINT plants_base_plant_id_fkey
+FOREIGN KEY (base_plant_id)
+REFERENCES plants (id)
+ON DELETE SET NULL;
+
+-- Unique names are only unique within the shared catalogue and within the custom plants of a user.
+ALTER TABLE plants
+DROP CONSTRAINT plant_unique_name_key;
+
+CREATE UNIQUE INDEX plants_catalogue_unique_name_key ON plants (unique_name)
+WHERE owner_id IS NULL;
+
+CREATE UNIQUE INDEX plants_owner_id_unique_name_key ON plants (owner_id, unique_name);
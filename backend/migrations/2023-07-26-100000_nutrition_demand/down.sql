@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE plants
+DROP COLUMN nutrition_demand;
@@ -0,0 +1,2 @@
+ALTER TABLE plants
+ADD COLUMN nutrition_demand NUTRITION_DEMAND;
@@ -0,0 +1,7 @@
+DROP TRIGGER plantings_update_map_harvested ON plantings;
+DROP FUNCTION plantings_update_map_harvested;
+DROP TABLE harvests;
+DROP FUNCTION harvests_update_map_harvested;
+DROP FUNCTION update_map_harvested;
+DROP TYPE harvest_quality;
+DROP TYPE harvest_unit;
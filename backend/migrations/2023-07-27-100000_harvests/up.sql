@@ -0,0 +1,84 @@
+CREATE TYPE harvest_unit AS ENUM ('pieces', 'bunches', 'grams', 'kilograms', 'liters');
+CREATE TYPE harvest_quality AS ENUM ('excellent', 'good', 'fair', 'poor');
+
+CREATE TABLE harvests (
+    id serial PRIMARY KEY,
+    planting_id uuid NOT NULL REFERENCES plantings (id) ON DELETE CASCADE,
+    harvest_date date NOT NULL,
+    quantity real NOT NULL CHECK (quantity > 0),
+    unit harvest_unit NOT NULL,
+    quality harvest_quality,
+    notes text,
+    created_by uuid NOT NULL
+);
+
+CREATE INDEX harvests_planting_id_idx ON harvests (planting_id);
+
+-- Keep maps.harvested equal to the number of plantings on the map with at least one harvest.
+CREATE FUNCTION update_map_harvested(harvested_map_id integer) RETURNS void
+LANGUAGE plpgsql
+AS $$
+BEGIN
+    UPDATE maps
+    SET harvested = LEAST(
+        (
+            SELECT count(DISTINCT harvests.planting_id)
+            FROM harvests
+            INNER JOIN plantings ON plantings.id = harvests.planting_id
+            INNER JOIN layers ON layers.id = plantings.layer_id
+            WHERE layers.map_id = harvested_map_id
+        ),
+        32767
+    )
+    WHERE id = harvested_map_id;
+END;
+$$;
+
+CREATE FUNCTION harvests_update_map_harvested() RETURNS trigger
+LANGUAGE plpgsql
+AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM update_map_harvested((
+            SELECT layers.map_id
+            FROM plantings
+            INNER JOIN layers ON layers.id = plantings.layer_id
+            WHERE plantings.id = OLD.planting_id
+        ));
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM update_map_harvested((
+            SELECT layers.map_id
+            FROM plantings
+            INNER JOIN layers ON layers.id = plantings.layer_id
+            WHERE plantings.id = NEW.planting_id
+        ));
+    END IF;
+    RETURN NULL;
+END;
+$$;
+
+CREATE FUNCTION plantings_update_map_harvested() RETURNS trigger
+LANGUAGE plpgsql
+AS $$
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        IF NEW.layer_id = OLD.layer_id THEN
+            RETURN NULL;
+        END IF;
+        PERFORM update_map_harvested((SELECT map_id FROM layers WHERE id = NEW.layer_id));
+    END IF;
+    PERFORM update_map_harvested((SELECT map_id FROM layers WHERE id = OLD.layer_id));
+    RETURN NULL;
+END;
+$$;
+
+CREATE TRIGGER harvests_update_map_harvested
+AFTER INSERT OR UPDATE OR DELETE ON harvests
+FOR EACH ROW EXECUTE FUNCTION harvests_update_map_harvested();
+
+-- Harvests of deleted plantings are removed by the cascade before this trigger runs.
+-- Plantings moved to another layer may move their harvests to another map.
+CREATE TRIGGER plantings_update_map_harvested
+AFTER UPDATE OF layer_id OR DELETE ON plantings
+FOR EACH ROW EXECUTE FUNCTION plantings_update_map_harvested();
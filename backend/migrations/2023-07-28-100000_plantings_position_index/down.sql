@@ -0,0 +1 @@
+DROP INDEX plantings_position_idx;
@@ -0,0 +1,2 @@
+-- Spatial queries on plantings filter by ST_MakePoint(x, y), so this expression is indexed.
+CREATE INDEX plantings_position_idx ON plantings USING gist (st_makepoint(x, y));
@@ -0,0 +1,7 @@
+ALTER TABLE plantings
+DROP COLUMN photos,
+DROP COLUMN seed_id,
+DROP COLUMN status,
+DROP COLUMN notes;
+
+DROP TYPE planting_status;
@@ -0,0 +1,7 @@
+CREATE TYPE planting_status AS ENUM ('planned', 'sown', 'established', 'failed', 'dead');
+
+ALTER TABLE plantings
+ADD COLUMN notes TEXT,
+ADD COLUMN status PLANTING_STATUS NOT NULL DEFAULT 'planned',
+ADD COLUMN seed_id INTEGER REFERENCES seeds (id) ON DELETE SET NULL,
+ADD COLUMN photos TEXT [] NOT NULL DEFAULT '{}' CHECK (array_position(photos, NULL) IS NULL);
@@ -0,0 +1,8 @@
+DROP TABLE guild_template_members;
+DROP TABLE guild_templates;
+DROP INDEX plantings_guild_id_idx;
+
+ALTER TABLE plantings
+DROP COLUMN guild_id;
+
+DROP TABLE guilds;
@@ -0,0 +1,30 @@
+CREATE TABLE guilds (
+    id UUID PRIMARY KEY,
+    layer_id INTEGER NOT NULL REFERENCES layers (id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    created_by UUID NOT NULL
+);
+
+ALTER TABLE plantings
+ADD COLUMN guild_id UUID REFERENCES guilds (id) ON DELETE SET NULL;
+
+CREATE INDEX plantings_guild_id_idx ON plantings (guild_id);
+
+CREATE TABLE guild_templates (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    owner_id UUID NOT NULL
+);
+
+CREATE TABLE guild_template_members (
+    id SERIAL PRIMARY KEY,
+    template_id INTEGER NOT NULL REFERENCES guild_templates (id) ON DELETE CASCADE,
+    plant_id INTEGER NOT NULL REFERENCES plants (id) ON DELETE CASCADE,
+    x INTEGER NOT NULL,
+    y INTEGER NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    rotation REAL NOT NULL,
+    scale_x REAL NOT NULL,
+    scale_y REAL NOT NULL
+);
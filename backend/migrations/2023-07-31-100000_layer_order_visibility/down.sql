@@ -0,0 +1,4 @@
+ALTER TABLE layers
+DROP COLUMN opacity,
+DROP COLUMN is_visible,
+DROP COLUMN order_index;
@@ -0,0 +1,14 @@
+ALTER TABLE layers
+ADD COLUMN order_index INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN is_visible BOOLEAN NOT NULL DEFAULT true,
+ADD COLUMN opacity REAL NOT NULL DEFAULT 1 CHECK (opacity >= 0 AND opacity <= 1);
+
+UPDATE layers
+SET order_index = ordered.order_index
+FROM (
+    SELECT
+        id,
+        (row_number() OVER (PARTITION BY map_id ORDER BY id) - 1)::INTEGER AS order_index
+    FROM layers
+) AS ordered
+WHERE layers.id = ordered.id;
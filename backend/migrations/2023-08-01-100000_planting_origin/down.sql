@@ -0,0 +1,2 @@
+ALTER TABLE plantings
+DROP COLUMN origin_id;
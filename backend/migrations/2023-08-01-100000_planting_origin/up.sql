@@ -0,0 +1,4 @@
+-- The planting a planting was copied from when its layer was forked into an alternative.
+-- No foreign key, the original planting may be deleted while the copy stays comparable.
+ALTER TABLE plantings
+ADD COLUMN origin_id UUID;
@@ -0,0 +1,24 @@
+DROP TRIGGER layers_update_map_harvested ON layers;
+DROP FUNCTION layers_update_map_harvested;
+
+CREATE OR REPLACE FUNCTION update_map_harvested(harvested_map_id integer) RETURNS void
+LANGUAGE plpgsql
+AS $$
+BEGIN
+    UPDATE maps
+    SET harvested = LEAST(
+        (
+            SELECT count(DISTINCT harvests.planting_id)
+            FROM harvests
+            INNER JOIN plantings ON plantings.id = harvests.planting_id
+            INNER JOIN layers ON layers.id = plantings.layer_id
+            WHERE layers.map_id = harvested_map_id
+        ),
+        32767
+    )
+    WHERE id = harvested_map_id;
+END;
+$$;
+
+ALTER TABLE layers
+DROP COLUMN deletion_date;
@@ -0,0 +1,38 @@
+-- Deleted layers are kept for 30 days so they can be restored, see `cleanup_layers`.
+ALTER TABLE layers
+ADD COLUMN deletion_date DATE;
+
+-- Harvests of plantings on deleted layers do not count for maps.harvested.
+CREATE OR REPLACE FUNCTION update_map_harvested(harvested_map_id integer) RETURNS void
+LANGUAGE plpgsql
+AS $$
+BEGIN
+    UPDATE maps
+    SET harvested = LEAST(
+        (
+            SELECT count(DISTINCT harvests.planting_id)
+            FROM harvests
+            INNER JOIN plantings ON plantings.id = harvests.planting_id
+            INNER JOIN layers ON layers.id = plantings.layer_id
+            WHERE layers.map_id = harvested_map_id AND layers.deletion_date IS NULL
+        ),
+        32767
+    )
+    WHERE id = harvested_map_id;
+END;
+$$;
+
+CREATE FUNCTION layers_update_map_harvested() RETURNS trigger
+LANGUAGE plpgsql
+AS $$
+BEGIN
+    PERFORM update_map_harvested(NEW.map_id);
+    RETURN NULL;
+END;
+$$;
+
+CREATE TRIGGER layers_update_map_harvested
+AFTER UPDATE OF deletion_date ON layers
+FOR EACH ROW
+WHEN (NEW.deletion_date IS DISTINCT FROM OLD.deletion_date)
+EXECUTE FUNCTION layers_update_map_harvested();
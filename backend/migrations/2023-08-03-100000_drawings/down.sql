@@ -0,0 +1,2 @@
+DROP TABLE drawings;
+DROP TYPE shape_type;
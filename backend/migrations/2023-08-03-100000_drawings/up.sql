@@ -0,0 +1,24 @@
+CREATE TYPE shape_type AS ENUM (
+    'line', 'polyline', 'polygon', 'rectangle', 'ellipse', 'text'
+);
+
+CREATE TABLE drawings (
+    id UUID PRIMARY KEY,
+    layer_id INTEGER NOT NULL REFERENCES layers (id) ON DELETE CASCADE,
+    shape_type SHAPE_TYPE NOT NULL,
+    -- A point for ellipses and texts, a line string for lines and polylines
+    -- and a polygon for polygons and rectangles, coordinates are in cm.
+    geometry GEOMETRY (GEOMETRY, 4326) NOT NULL,
+    rotation REAL NOT NULL DEFAULT 0,
+    radius_x REAL,
+    radius_y REAL,
+    text TEXT,
+    font_size REAL,
+    stroke_color TEXT NOT NULL,
+    stroke_width REAL NOT NULL CHECK (stroke_width >= 0),
+    fill_color TEXT,
+    CHECK (shape_type != 'ellipse' OR (radius_x > 0 AND radius_y > 0)),
+    CHECK (shape_type != 'text' OR (text IS NOT NULL AND font_size > 0))
+);
+
+CREATE INDEX drawings_layer_id_idx ON drawings (layer_id);
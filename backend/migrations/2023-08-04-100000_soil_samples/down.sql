@@ -0,0 +1 @@
+DROP TABLE soil_samples;
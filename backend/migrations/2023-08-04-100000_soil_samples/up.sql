@@ -0,0 +1,14 @@
+CREATE TABLE soil_samples (
+    id UUID PRIMARY KEY,
+    layer_id INTEGER NOT NULL REFERENCES layers (id) ON DELETE CASCADE,
+    x INTEGER NOT NULL,
+    y INTEGER NOT NULL,
+    ph REAL CHECK (ph BETWEEN 0 AND 14),
+    soil_texture SOIL_TEXTURE,
+    water_retention SOIL_WATER_RETENTION,
+    -- Percentage of organic matter in the soil.
+    organic_matter REAL CHECK (organic_matter BETWEEN 0 AND 100),
+    sample_date DATE
+);
+
+CREATE INDEX soil_samples_layer_id_idx ON soil_samples (layer_id);
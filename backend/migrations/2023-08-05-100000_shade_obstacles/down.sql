@@ -0,0 +1 @@
+DROP TABLE shade_obstacles;
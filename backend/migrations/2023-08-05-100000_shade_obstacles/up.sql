@@ -0,0 +1,11 @@
+CREATE TABLE shade_obstacles (
+    id UUID PRIMARY KEY,
+    layer_id INTEGER NOT NULL REFERENCES layers (id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    -- The footprint of the obstacle, coordinates are in cm.
+    geometry GEOMETRY (POLYGON, 4326) NOT NULL,
+    -- The height of the obstacle in cm.
+    height INTEGER NOT NULL CHECK (height > 0)
+);
+
+CREATE INDEX shade_obstacles_layer_id_idx ON shade_obstacles (layer_id);
@@ -0,0 +1,7 @@
+CREATE TABLE map_collaborators (
+    map_id INTEGER NOT NULL REFERENCES maps (id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    PRIMARY KEY (map_id, user_id)
+);
+
+CREATE INDEX map_collaborators_user_id_idx ON map_collaborators (user_id);
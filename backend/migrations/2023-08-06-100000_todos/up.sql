@@ -0,0 +1,27 @@
+CREATE TYPE todo_status AS ENUM (
+    'open',
+    'done',
+    'archived'
+);
+
+CREATE TABLE todos (
+    id UUID PRIMARY KEY,
+    layer_id INTEGER NOT NULL REFERENCES layers (id) ON DELETE CASCADE,
+    -- A todo is either pinned to a position on the map or to a planting.
+    x INTEGER,
+    y INTEGER,
+    planting_id UUID REFERENCES plantings (id) ON DELETE CASCADE,
+    title VARCHAR(255) NOT NULL,
+    description TEXT,
+    due_date DATE,
+    assignee_id UUID,
+    status TODO_STATUS NOT NULL DEFAULT 'open',
+    -- Number of days after which a recurring todo is due again once it is done.
+    recurrence_days INTEGER CHECK (recurrence_days > 0),
+    created_by UUID NOT NULL,
+    CHECK ((x IS NULL) = (y IS NULL)),
+    CHECK ((x IS NULL) <> (planting_id IS NULL))
+);
+
+CREATE INDEX todos_layer_id_idx ON todos (layer_id);
+CREATE INDEX todos_assignee_id_idx ON todos (assignee_id) WHERE status = 'open';
@@ -0,0 +1,15 @@
+CREATE TABLE photos (
+    id UUID PRIMARY KEY,
+    layer_id INTEGER NOT NULL REFERENCES layers (id) ON DELETE CASCADE,
+    x INTEGER NOT NULL,
+    y INTEGER NOT NULL,
+    planting_id UUID REFERENCES plantings (id) ON DELETE SET NULL,
+    -- Path of the image in Nextcloud, as for base layer images.
+    path TEXT NOT NULL,
+    description TEXT,
+    -- Local time the photo was taken at, as written by the camera.
+    captured_at TIMESTAMP,
+    created_by UUID NOT NULL
+);
+
+CREATE INDEX photos_layer_id_captured_at_idx ON photos (layer_id, captured_at);
@@ -0,0 +1 @@
+DROP TABLE wind_rose_entries;
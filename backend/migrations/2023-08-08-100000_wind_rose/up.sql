@@ -0,0 +1,10 @@
+CREATE TABLE wind_rose_entries (
+    layer_id INTEGER NOT NULL REFERENCES layers (id) ON DELETE CASCADE,
+    -- The compass direction the wind comes from in degrees, clockwise from north.
+    direction INTEGER NOT NULL CHECK (direction >= 0 AND direction < 360),
+    -- The share of the time in percent the wind comes from the direction.
+    frequency REAL NOT NULL CHECK (frequency BETWEEN 0 AND 100),
+    -- The mean speed in m/s of the wind from the direction.
+    speed REAL NOT NULL CHECK (speed >= 0),
+    PRIMARY KEY (layer_id, direction)
+);
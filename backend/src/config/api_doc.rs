@@ -10,23 +10,65 @@ use utoipa_swagger_ui::SwaggerUi;
 use super::auth::Config;
 use crate::{
     controller::{
-        base_layer_image, blossoms, config, guided_tours, layers, map, plant_layer,
-        planting_suggestions, plantings, plants, seed, users,
+        base_layer_image, blossoms, calendar, config, drawings, guided_tours, guilds, harvests,
+        layers, map, photo_layer, plant_layer, planting_suggestions, plantings, plants, rotation,
+        seed, shade_layer, soil_layer, spacing, todo_layer, users, winds_layer,
     },
     model::{
         dto::{
+            drawings::{
+                DeleteDrawingDto, DrawingDto, EllipseShapeDto, LineShapeDto, NewDrawingDto,
+                PolygonShapeDto, PolylineShapeDto, RectangleShapeDto, ShapeDto, ShapePointDto,
+                ShapeStyleDto, TextShapeDto, UpdateDrawingDto,
+            },
+            guilds::{
+                ApplyGuildTemplateDto, DeleteGuildDto, DuplicateGuildDto, GuildDto,
+                GuildTemplateDto, GuildTemplateMemberDto, MoveGuildDto, NewGuildDto,
+                NewGuildTemplateDto, RotateGuildDto, UpdateGuildDto,
+            },
+            map_collaborators::{MapCollaboratorDto, NewMapCollaboratorDto},
+            photos::{DeletePhotoDto, NewPhotoDto, PhotoDto, PhotoMetadataDto, UpdatePhotoDto},
             plantings::{
                 MovePlantingDto, NewPlantingDto, PlantingDto, TransformPlantingDto,
-                UpdatePlantingDto,
+                UpdateNotesPlantingDto, UpdatePhotosPlantingDto, UpdatePlantingDto,
+                UpdateSeedPlantingDto, UpdateStatusPlantingDto,
+            },
+            shade::{
+                DeleteShadeObstacleDto, NewShadeObstacleDto, ShadeCellDto, ShadeObstacleDto,
+                ShadeRasterDto, UpdateShadeObstacleDto,
+            },
+            soil_samples::{
+                DeleteSoilSampleDto, NewSoilSampleDto, SoilCellDto, SoilRasterDto, SoilSampleDto,
+                UpdateSoilSampleDto,
             },
-            BaseLayerImageDto, ConfigDto, Coordinates, GainedBlossomsDto, GuidedToursDto, LayerDto,
-            MapDto, NewLayerDto, NewMapDto, NewSeedDto, PageLayerDto, PageMapDto,
-            PagePlantsSummaryDto, PageSeedDto, PlantsSummaryDto, RelationDto, RelationsDto,
-            SeedDto, UpdateBaseLayerImageDto, UpdateGuidedToursDto, UpdateMapDto, UsersDto,
+            todos::{AssignedTodoDto, DeleteTodoDto, NewTodoDto, TodoDto, UpdateTodoDto},
+            winds::{UpdateWindRoseDto, WindCellDto, WindRasterDto, WindRoseDto, WindRoseEntryDto},
+            BaseLayerImageDto, CalendarDto, CalendarEntryDto, CalendarPlantingDto,
+            CompanionSuggestionDto, ConfigDto, Coordinates, DeleteLayerDto, DiversityCriterion,
+            DiversityGapDto, DiversitySuggestionDto, FacetDto, FacetValueDto,
+            FacetedPagePlantsSummaryDto, GainedBlossomsDto, GuidedToursDto, HarvestDto,
+            HarvestGrouping, HarvestSummaryDto, ImportPlantDto, ImportRelationDto, LayerDiffDto,
+            LayerDto, LayerOrderDto, MapDto, MovedPlantingDto, NewAlternativeLayerDto,
+            NewCustomPlantDto, NewHarvestDto, NewLayerDto, NewMapDto, NewSeedDto,
+            PageCompanionSuggestionDto, PageDiversitySuggestionDto, PageLayerDto, PageMapDto,
+            PagePlantsSummaryDto, PageSeedDto, PlantDetailDto, PlantsDatasetDto,
+            PlantsImportReportDto, PlantsSummaryDto, PromoteLayerDto, RelationDto, RelationsDto,
+            RestoreLayerDto, RotationConflictDto, RotationConflictKind, RotationGroup,
+            RotationHistoryDto, RotationScheduleDto, RotationYearDto, SeedDto, SeedLineageDto,
+            SeedLineageNodeDto, SpacingMode, SpacingWarningDto, SpacingWarningKind,
+            UpdateBaseLayerImageDto, UpdateGuidedToursDto, UpdateLayerDto, UpdateMapDto, UsersDto,
         },
         r#enum::{
-            privacy_option::PrivacyOption, quality::Quality, quantity::Quantity,
-            relation_type::RelationType,
+            deciduous_or_evergreen::DeciduousOrEvergreen, external_source::ExternalSource,
+            fertility::Fertility, flower_type::FlowerType, growth_rate::GrowthRate,
+            harvest_quality::HarvestQuality, harvest_unit::HarvestUnit,
+            herbaceous_or_woody::HerbaceousOrWoody, life_cycle::LifeCycle,
+            light_requirement::LightRequirement, nutrition_demand::NutritionDemand,
+            plant_height::PlantHeight, plant_spread::PlantSpread, planting_status::PlantingStatus,
+            privacy_option::PrivacyOption, propagation_method::PropagationMethod, quality::Quality,
+            quantity::Quantity, relation_type::RelationType, shade::Shade, soil_ph::SoilPh,
+            soil_texture::SoilTexture, soil_water_retention::SoilWaterRetention,
+            todo_status::TodoStatus, water_requirement::WaterRequirement,
         },
     },
 };
@@ -42,6 +84,7 @@ struct ConfigApiDoc;
     paths(
         seed::find,
         seed::find_by_id,
+        seed::find_lineage,
         seed::create,
         seed::delete_by_id
     ),
@@ -49,6 +92,8 @@ struct ConfigApiDoc;
         schemas(
             PageSeedDto,
             SeedDto,
+            SeedLineageDto,
+            SeedLineageNodeDto,
             NewSeedDto,
             Quality,
             Quantity
@@ -63,12 +108,42 @@ struct SeedApiDoc;
 #[openapi(
     paths(
         plants::find,
-        plants::find_by_id
+        plants::find_by_id,
+        plants::find_details_by_id,
+        plants::create_custom,
+        plants::submit_for_review,
+        plants::import
     ),
     components(
         schemas(
             PagePlantsSummaryDto,
-            PlantsSummaryDto
+            FacetedPagePlantsSummaryDto,
+            FacetDto,
+            FacetValueDto,
+            PlantsSummaryDto,
+            PlantDetailDto,
+            NewCustomPlantDto,
+            PlantsDatasetDto,
+            ImportPlantDto,
+            ImportRelationDto,
+            PlantsImportReportDto,
+            Shade,
+            SoilPh,
+            SoilTexture,
+            DeciduousOrEvergreen,
+            HerbaceousOrWoody,
+            LifeCycle,
+            GrowthRate,
+            PlantHeight,
+            Fertility,
+            LightRequirement,
+            WaterRequirement,
+            PropagationMethod,
+            PlantSpread,
+            ExternalSource,
+            NutritionDemand,
+            SoilWaterRetention,
+            FlowerType
         )
     ),
     modifiers(&SecurityAddon)
@@ -82,7 +157,10 @@ struct PlantsApiDoc;
         map::find,
         map::find_by_id,
         map::create,
-        map::update
+        map::update,
+        map::find_collaborators,
+        map::add_collaborator,
+        map::remove_collaborator
     ),
     components(
         schemas(
@@ -91,7 +169,9 @@ struct PlantsApiDoc;
             NewMapDto,
             UpdateMapDto,
             PrivacyOption,
-            Coordinates
+            Coordinates,
+            MapCollaboratorDto,
+            NewMapCollaboratorDto
         )
     ),
     modifiers(&SecurityAddon)
@@ -105,12 +185,26 @@ struct MapApiDoc;
         layers::find,
         layers::find_by_id,
         layers::create,
-        layers::delete
+        layers::update,
+        layers::reorder,
+        layers::fork,
+        layers::compare,
+        layers::promote,
+        layers::delete,
+        layers::restore
     ),
     components(
         schemas(
             LayerDto,
             NewLayerDto,
+            UpdateLayerDto,
+            LayerOrderDto,
+            NewAlternativeLayerDto,
+            PromoteLayerDto,
+            DeleteLayerDto,
+            RestoreLayerDto,
+            LayerDiffDto,
+            MovedPlantingDto,
             PageLayerDto
         )
     ),
@@ -170,13 +264,224 @@ struct BaseLayerImagesApiDoc;
             NewPlantingDto,
             UpdatePlantingDto,
             TransformPlantingDto,
-            MovePlantingDto
+            MovePlantingDto,
+            UpdateNotesPlantingDto,
+            UpdateStatusPlantingDto,
+            UpdateSeedPlantingDto,
+            UpdatePhotosPlantingDto,
+            PlantingStatus
         )
     ),
     modifiers(&SecurityAddon)
 )]
 struct PlantingsApiDoc;
 
+/// Struct used by [`utoipa`] to generate `OpenApi` documentation for all guild endpoints.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        guilds::find,
+        guilds::create,
+        guilds::update,
+        guilds::delete,
+        guilds::duplicate,
+        guilds::create_template,
+        guilds::apply_template,
+        guilds::find_templates,
+        guilds::delete_template
+    ),
+    components(
+        schemas(
+            GuildDto,
+            NewGuildDto,
+            UpdateGuildDto,
+            MoveGuildDto,
+            RotateGuildDto,
+            DuplicateGuildDto,
+            DeleteGuildDto,
+            GuildTemplateDto,
+            GuildTemplateMemberDto,
+            NewGuildTemplateDto,
+            ApplyGuildTemplateDto
+        )
+    ),
+    modifiers(&SecurityAddon)
+)]
+struct GuildsApiDoc;
+
+/// Struct used by [`utoipa`] to generate `OpenApi` documentation for all drawing endpoints.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        drawings::find,
+        drawings::create,
+        drawings::update,
+        drawings::delete
+    ),
+    components(
+        schemas(
+            DrawingDto,
+            NewDrawingDto,
+            UpdateDrawingDto,
+            DeleteDrawingDto,
+            ShapeDto,
+            ShapePointDto,
+            LineShapeDto,
+            PolylineShapeDto,
+            PolygonShapeDto,
+            RectangleShapeDto,
+            EllipseShapeDto,
+            TextShapeDto,
+            ShapeStyleDto
+        )
+    ),
+    modifiers(&SecurityAddon)
+)]
+struct DrawingsApiDoc;
+
+/// Struct used by [`utoipa`] to generate `OpenApi` documentation for all soil layer endpoints.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        soil_layer::find_samples,
+        soil_layer::create_sample,
+        soil_layer::update_sample,
+        soil_layer::delete_sample,
+        soil_layer::raster
+    ),
+    components(
+        schemas(
+            SoilSampleDto,
+            NewSoilSampleDto,
+            UpdateSoilSampleDto,
+            DeleteSoilSampleDto,
+            SoilRasterDto,
+            SoilCellDto,
+            SoilTexture,
+            SoilWaterRetention,
+            SoilPh
+        )
+    ),
+    modifiers(&SecurityAddon)
+)]
+struct SoilLayerApiDoc;
+
+/// Struct used by [`utoipa`] to generate `OpenApi` documentation for all shade layer endpoints.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        shade_layer::find_obstacles,
+        shade_layer::create_obstacle,
+        shade_layer::update_obstacle,
+        shade_layer::delete_obstacle,
+        shade_layer::raster
+    ),
+    components(
+        schemas(
+            ShadeObstacleDto,
+            NewShadeObstacleDto,
+            UpdateShadeObstacleDto,
+            DeleteShadeObstacleDto,
+            ShadeRasterDto,
+            ShadeCellDto,
+            Shade
+        )
+    ),
+    modifiers(&SecurityAddon)
+)]
+struct ShadeLayerApiDoc;
+
+/// Struct used by [`utoipa`] to generate `OpenApi` documentation for all todo layer endpoints.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        todo_layer::find,
+        todo_layer::create,
+        todo_layer::update,
+        todo_layer::delete,
+        todo_layer::find_assigned
+    ),
+    components(
+        schemas(
+            TodoDto,
+            AssignedTodoDto,
+            NewTodoDto,
+            UpdateTodoDto,
+            DeleteTodoDto,
+            TodoStatus
+        )
+    ),
+    modifiers(&SecurityAddon)
+)]
+struct TodoLayerApiDoc;
+
+/// Struct used by [`utoipa`] to generate `OpenApi` documentation for all photo layer endpoints.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        photo_layer::find,
+        photo_layer::create,
+        photo_layer::update,
+        photo_layer::delete,
+        photo_layer::read_metadata
+    ),
+    components(
+        schemas(
+            PhotoDto,
+            NewPhotoDto,
+            UpdatePhotoDto,
+            DeletePhotoDto,
+            PhotoMetadataDto
+        )
+    ),
+    modifiers(&SecurityAddon)
+)]
+struct PhotoLayerApiDoc;
+
+/// Struct used by [`utoipa`] to generate `OpenApi` documentation for all winds layer endpoints.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        winds_layer::find_rose,
+        winds_layer::replace_rose,
+        winds_layer::raster
+    ),
+    components(
+        schemas(
+            WindRoseDto,
+            WindRoseEntryDto,
+            UpdateWindRoseDto,
+            WindRasterDto,
+            WindCellDto
+        )
+    ),
+    modifiers(&SecurityAddon)
+)]
+struct WindsLayerApiDoc;
+
+/// Struct used by [`utoipa`] to generate `OpenApi` documentation for all harvest endpoints.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        harvests::find,
+        harvests::create,
+        harvests::delete,
+        harvests::summarize
+    ),
+    components(
+        schemas(
+            HarvestDto,
+            NewHarvestDto,
+            HarvestSummaryDto,
+            HarvestGrouping,
+            HarvestUnit,
+            HarvestQuality
+        )
+    ),
+    modifiers(&SecurityAddon)
+)]
+struct HarvestsApiDoc;
+
 /// Struct used by [`utoipa`] to generate `OpenApi` documentation for all suggestions endpoints.
 #[derive(OpenApi)]
 #[openapi(
@@ -186,6 +491,12 @@ struct PlantingsApiDoc;
     components(
         schemas(
             PagePlantsSummaryDto,
+            PageDiversitySuggestionDto,
+            PageCompanionSuggestionDto,
+            DiversitySuggestionDto,
+            CompanionSuggestionDto,
+            DiversityGapDto,
+            DiversityCriterion
         )
     ),
     tags((name = "planting_suggestions")),
@@ -193,6 +504,61 @@ struct PlantingsApiDoc;
 )]
 struct PlantingSuggestionsApiDoc;
 
+/// Struct used by [`utoipa`] to generate `OpenApi` documentation for all calendar endpoints.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        calendar::find
+    ),
+    components(
+        schemas(
+            CalendarDto,
+            CalendarEntryDto,
+            CalendarPlantingDto
+        )
+    ),
+    modifiers(&SecurityAddon)
+)]
+struct CalendarApiDoc;
+
+/// Struct used by [`utoipa`] to generate `OpenApi` documentation for all crop rotation endpoints.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        rotation::check,
+        rotation::schedule
+    ),
+    components(
+        schemas(
+            RotationConflictDto,
+            RotationConflictKind,
+            RotationScheduleDto,
+            RotationHistoryDto,
+            RotationYearDto,
+            RotationGroup
+        )
+    ),
+    modifiers(&SecurityAddon)
+)]
+struct RotationApiDoc;
+
+/// Struct used by [`utoipa`] to generate `OpenApi` documentation for all planting spacing endpoints.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        spacing::check
+    ),
+    components(
+        schemas(
+            SpacingWarningDto,
+            SpacingWarningKind,
+            SpacingMode
+        )
+    ),
+    modifiers(&SecurityAddon)
+)]
+struct SpacingApiDoc;
+
 /// Struct used by [`utoipa`] to generate `OpenApi` documentation for all user data endpoints.
 #[derive(OpenApi)]
 #[openapi(
@@ -248,10 +614,21 @@ pub fn config(cfg: &mut web::ServiceConfig) {
     openapi.merge(PlantsApiDoc::openapi());
     openapi.merge(PlantingSuggestionsApiDoc::openapi());
     openapi.merge(MapApiDoc::openapi());
+    openapi.merge(CalendarApiDoc::openapi());
     openapi.merge(LayerApiDoc::openapi());
     openapi.merge(PlantLayerApiDoc::openapi());
     openapi.merge(BaseLayerImagesApiDoc::openapi());
     openapi.merge(PlantingsApiDoc::openapi());
+    openapi.merge(GuildsApiDoc::openapi());
+    openapi.merge(DrawingsApiDoc::openapi());
+    openapi.merge(SoilLayerApiDoc::openapi());
+    openapi.merge(ShadeLayerApiDoc::openapi());
+    openapi.merge(TodoLayerApiDoc::openapi());
+    openapi.merge(PhotoLayerApiDoc::openapi());
+    openapi.merge(WindsLayerApiDoc::openapi());
+    openapi.merge(HarvestsApiDoc::openapi());
+    openapi.merge(RotationApiDoc::openapi());
+    openapi.merge(SpacingApiDoc::openapi());
     openapi.merge(UsersApiDoc::openapi());
 
     cfg.service(SwaggerUi::new("/doc/api/swagger/ui/{_:.*}").url("/doc/api/openapi.json", openapi));
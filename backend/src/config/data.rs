@@ -1,5 +1,7 @@
 //! Configurations for the app data that is available to all controllers.
 
+use crate::service::shade_layer::ShadeRasterCache;
+use crate::service::winds_layer::WindRasterCache;
 use crate::sse::broadcaster::Broadcaster;
 use actix_web::web::Data;
 
@@ -11,6 +13,10 @@ pub struct AppDataInner {
     pub pool: connection::Pool,
     /// Server-Sent Events broadcaster.
     pub broadcaster: Broadcaster,
+    /// Shade rasters computed for heatmaps.
+    pub shade_rasters: ShadeRasterCache,
+    /// Wind rasters computed for heatmaps.
+    pub wind_rasters: WindRasterCache,
 }
 
 /// Initializes the app data that is available to all controllers.
@@ -22,5 +28,10 @@ pub fn init(database_url: &str) -> Data<AppDataInner> {
     let pool = connection::init_pool(database_url);
     let broadcaster = Broadcaster::new();
 
-    Data::new(AppDataInner { pool, broadcaster })
+    Data::new(AppDataInner {
+        pool,
+        broadcaster,
+        shade_rasters: ShadeRasterCache::default(),
+        wind_rasters: WindRasterCache::default(),
+    })
 }
@@ -1,16 +1,23 @@
 //! Routes in the backend.
 
 use actix_utils::future::ready;
-use actix_web::{middleware::NormalizePath, web};
+use actix_web::{middleware::NormalizePath, web, Scope};
 use actix_web_httpauth::middleware::HttpAuthentication;
 
 use crate::controller::{
-    base_layer_image, blossoms, config, guided_tours, layers, map, plant_layer,
-    planting_suggestions, plantings, plants, seed, sse, users,
+    base_layer_image, blossoms, calendar, config, drawings, guided_tours, guilds, harvests, layers,
+    map, photo_layer, plant_layer, planting_suggestions, plantings, plants, rotation, seed,
+    shade_layer, soil_layer, spacing, sse, todo_layer, users, winds_layer,
 };
 
 use super::auth::middleware::validator;
 
+/// The maximum size of plant datasets in bytes.
+const PLANTS_IMPORT_PAYLOAD_LIMIT: usize = 64 * 1024 * 1024;
+
+/// The maximum size of images to read the metadata of in bytes.
+const PHOTO_METADATA_PAYLOAD_LIMIT: usize = 32 * 1024 * 1024;
+
 /// Defines all routes of the backend and which functions they map to.
 pub fn config(cfg: &mut web::ServiceConfig) {
     let auth = HttpAuthentication::bearer(|req, credentials| ready(validator(req, &credentials)));
@@ -21,12 +28,18 @@ pub fn config(cfg: &mut web::ServiceConfig) {
                 .service(seed::find)
                 .service(seed::create)
                 .service(seed::delete_by_id)
-                .service(seed::find_by_id),
+                .service(seed::find_by_id)
+                .service(seed::find_lineage),
         )
         .service(
             web::scope("/plants")
                 .service(plants::find)
-                .service(plants::find_by_id),
+                .service(plants::find_by_id)
+                .service(plants::find_details_by_id)
+                .service(plants::create_custom)
+                .service(plants::submit_for_review)
+                .service(plants::import)
+                .app_data(web::PayloadConfig::new(PLANTS_IMPORT_PAYLOAD_LIMIT)),
         )
         .service(
             web::scope("/maps")
@@ -34,12 +47,32 @@ pub fn config(cfg: &mut web::ServiceConfig) {
                 .service(map::find_by_id)
                 .service(map::create)
                 .service(map::update)
+                .service(map::find_collaborators)
+                .service(map::add_collaborator)
+                .service(map::remove_collaborator)
+                .service(web::scope("/{map_id}/calendar").service(calendar::find))
+                .service(web::scope("/{map_id}/harvests").service(harvests::summarize))
                 .service(
                     web::scope("/{map_id}/layers")
                         .service(layers::find)
+                        .service(layers::reorder)
+                        .service(
+                            web::scope("/drawings")
+                                .service(drawings::find)
+                                .service(drawings::create)
+                                .service(drawings::update)
+                                .service(drawings::delete),
+                        )
+                        .service(todo_layer_routes())
+                        .service(photo_layer_routes())
                         .service(layers::find_by_id)
                         .service(layers::create)
+                        .service(layers::update)
+                        .service(layers::fork)
+                        .service(layers::compare)
+                        .service(layers::promote)
                         .service(layers::delete)
+                        .service(layers::restore)
                         .service(
                             web::scope("/base/images")
                                 .service(base_layer_image::create)
@@ -49,29 +82,38 @@ pub fn config(cfg: &mut web::ServiceConfig) {
                         .service(
                             web::scope("/base/{layer_id}/images").service(base_layer_image::find),
                         )
+                        .service(plant_layer_routes())
+                        .service(
+                            web::scope("/soil")
+                                .service(soil_layer::find_samples)
+                                .service(soil_layer::create_sample)
+                                .service(soil_layer::update_sample)
+                                .service(soil_layer::delete_sample)
+                                .service(soil_layer::raster),
+                        )
                         .service(
-                            web::scope("/plants")
-                                .service(plant_layer::heatmap)
-                                .service(plant_layer::find_relations)
-                                .service(
-                                    web::scope("/suggestions").service(planting_suggestions::find),
-                                )
-                                .service(
-                                    web::scope("/plantings")
-                                        .service(plantings::find)
-                                        .service(plantings::create)
-                                        .service(plantings::update)
-                                        .service(plantings::delete),
-                                ),
-                        ),
+                            web::scope("/shade")
+                                .service(shade_layer::find_obstacles)
+                                .service(shade_layer::create_obstacle)
+                                .service(shade_layer::update_obstacle)
+                                .service(shade_layer::delete_obstacle)
+                                .service(shade_layer::raster),
+                        )
+                        .service(winds_layer_routes()),
                 ),
         )
+        .service(
+            web::scope("/guild-templates")
+                .service(guilds::find_templates)
+                .service(guilds::delete_template),
+        )
         .service(
             web::scope("/tours")
                 .service(guided_tours::setup)
                 .service(guided_tours::find_by_user)
                 .service(guided_tours::update),
         )
+        .service(web::scope("/todos").service(todo_layer::find_assigned))
         .service(web::scope("/users").service(users::create))
         .service(web::scope("/blossoms").service(blossoms::gain))
         .wrap(NormalizePath::trim())
@@ -82,3 +124,72 @@ pub fn config(cfg: &mut web::ServiceConfig) {
 
     cfg.service(sse_route).service(config_route).service(routes);
 }
+
+/// Defines the routes of the plant layer of a map.
+fn plant_layer_routes() -> Scope {
+    web::scope("/plants")
+        .service(plant_layer::heatmap)
+        .service(plant_layer::find_relations)
+        .service(web::scope("/suggestions").service(planting_suggestions::find))
+        .service(web::scope("/spacing").service(spacing::check))
+        .service(
+            web::scope("/rotation")
+                .service(rotation::check)
+                .service(rotation::schedule),
+        )
+        .service(
+            web::scope("/guilds")
+                .service(guilds::apply_template)
+                .service(guilds::find)
+                .service(guilds::create)
+                .service(guilds::update)
+                .service(guilds::delete)
+                .service(guilds::duplicate)
+                .service(guilds::create_template),
+        )
+        .service(
+            web::scope("/plantings")
+                .service(
+                    web::scope("/{planting_id}/harvests")
+                        .service(harvests::find)
+                        .service(harvests::create)
+                        .service(harvests::delete),
+                )
+                .service(plantings::find)
+                .service(plantings::create)
+                .service(plantings::update)
+                .service(plantings::delete),
+        )
+}
+
+/// Defines the routes of the todo layer of a map.
+///
+/// Registered before the layer routes, as `/todos` would otherwise be taken as a layer id.
+fn todo_layer_routes() -> Scope {
+    web::scope("/todos")
+        .service(todo_layer::find)
+        .service(todo_layer::create)
+        .service(todo_layer::update)
+        .service(todo_layer::delete)
+}
+
+/// Defines the routes of the photo layer of a map.
+///
+/// Registered before the layer routes, as `/photos` would otherwise be taken as a layer id.
+fn photo_layer_routes() -> Scope {
+    web::scope("/photos")
+        .service(photo_layer::read_metadata)
+        .service(photo_layer::find)
+        .service(photo_layer::create)
+        .service(photo_layer::update)
+        .service(photo_layer::delete)
+        .app_data(web::PayloadConfig::new(PHOTO_METADATA_PAYLOAD_LIMIT))
+}
+
+/// Defines the routes of the winds layer of a map.
+fn winds_layer_routes() -> Scope {
+    web::scope("/winds")
+        .service(winds_layer::find_rose)
+        .service(winds_layer::replace_rose)
+        .service(winds_layer::raster)
+}
@@ -0,0 +1,63 @@
+//! Calendar endpoint of maps.
+
+use actix_web::{
+    get,
+    http::header::{ContentDisposition, DispositionParam, DispositionType},
+    web::{Data, Path, Query},
+    HttpResponse, Result,
+};
+
+use crate::{
+    config::data::AppDataInner,
+    model::dto::{CalendarFormat, CalendarParameters},
+    service,
+};
+
+/// Endpoint for the sowing and harvest calendar of a [`Map`](crate::model::entity::Map).
+/// For each half of a month it lists which plantings can be sown or harvested and which are added to or removed from the map.
+/// With `format=ics` the calendar is returned as iCalendar file.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/calendar",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map"),
+        CalendarParameters
+    ),
+    responses(
+        (status = 200, description = "Fetch the calendar of a map", body = CalendarDto),
+        (status = 200, description = "Export the calendar of a map", content_type = "text/calendar", body = String)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("")]
+pub async fn find(
+    map_id: Path<i32>,
+    calendar_query: Query<CalendarParameters>,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let map_id = map_id.into_inner();
+    let CalendarParameters { year, format } = calendar_query.into_inner();
+
+    match format.unwrap_or_default() {
+        CalendarFormat::Json => {
+            let response = service::calendar::find(map_id, year, &app_data).await?;
+            Ok(HttpResponse::Ok().json(response))
+        }
+        CalendarFormat::Ics => {
+            let response = service::calendar::find_ics(map_id, year, &app_data).await?;
+            Ok(HttpResponse::Ok()
+                .content_type("text/calendar; charset=utf-8")
+                .insert_header(ContentDisposition {
+                    disposition: DispositionType::Attachment,
+                    parameters: vec![DispositionParam::Filename(format!(
+                        "map-{map_id}-{year}.ics"
+                    ))],
+                })
+                .body(response))
+        }
+    }
+}
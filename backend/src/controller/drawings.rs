@@ -0,0 +1,179 @@
+//! `Drawing` endpoints.
+
+use actix_web::{
+    delete, get, patch, post,
+    web::{Data, Json, Path, Query},
+    HttpResponse, Result,
+};
+use uuid::Uuid;
+
+use crate::{
+    config::{auth::user_info::UserInfo, data::AppDataInner},
+    model::dto::{
+        actions::{
+            Action, CreateDrawingActionPayload, DeleteDrawingActionPayload,
+            UpdateDrawingActionPayload,
+        },
+        drawings::{DeleteDrawingDto, DrawingSearchParameters, NewDrawingDto, UpdateDrawingDto},
+    },
+    service::drawings,
+};
+
+/// Endpoint for listing the drawings of a layer.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/drawings",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        DrawingSearchParameters
+    ),
+    responses(
+        (status = 200, description = "Find drawings", body = Vec<DrawingDto>)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("")]
+pub async fn find(
+    path: Path<i32>,
+    search_query: Query<DrawingSearchParameters>,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response = drawings::find(path.into_inner(), search_query.layer_id, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Endpoint for creating a new `Drawing`.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/drawings",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+    ),
+    request_body = NewDrawingDto,
+    responses(
+        (status = 201, description = "Create a drawing", body = DrawingDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("")]
+pub async fn create(
+    path: Path<i32>,
+    json: Json<NewDrawingDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let map_id = path.into_inner();
+    let new_drawing = json.0;
+    let action_id = new_drawing.action_id;
+    let dto = drawings::create(map_id, new_drawing, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::CreateDrawing(CreateDrawingActionPayload::new(
+                &dto,
+                user_info.id,
+                action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Created().json(dto))
+}
+
+/// Endpoint for changing the shape or style of a `Drawing`.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/drawings",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("drawing_id" = Uuid, Path, description = "The id of the drawing"),
+    ),
+    request_body = UpdateDrawingDto,
+    responses(
+        (status = 200, description = "Update a drawing", body = DrawingDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[patch("/{drawing_id}")]
+pub async fn update(
+    path: Path<(i32, Uuid)>,
+    json: Json<UpdateDrawingDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, drawing_id) = path.into_inner();
+    let update_drawing = json.0;
+    let action_id = update_drawing.action_id;
+    let dto = drawings::update(map_id, drawing_id, update_drawing, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::UpdateDrawing(UpdateDrawingActionPayload::new(
+                &dto,
+                user_info.id,
+                action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().json(dto))
+}
+
+/// Endpoint for deleting a `Drawing`.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/drawings",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("drawing_id" = Uuid, Path, description = "The id of the drawing"),
+    ),
+    request_body = DeleteDrawingDto,
+    responses(
+        (status = 200, description = "Delete a drawing")
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[delete("/{drawing_id}")]
+pub async fn delete(
+    path: Path<(i32, Uuid)>,
+    json: Json<DeleteDrawingDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, drawing_id) = path.into_inner();
+    drawings::delete_by_id(map_id, drawing_id, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::DeleteDrawing(DeleteDrawingActionPayload::new(
+                drawing_id,
+                user_info.id,
+                json.action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().finish())
+}
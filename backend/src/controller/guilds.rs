@@ -0,0 +1,343 @@
+//! `Guild` endpoints.
+
+use actix_web::{
+    delete, get, patch, post,
+    web::{Data, Json, Path, Query},
+    HttpResponse, Result,
+};
+use uuid::Uuid;
+
+use crate::{
+    config::{auth::user_info::UserInfo, data::AppDataInner},
+    model::dto::{
+        actions::{
+            Action, CreateGuildActionPayload, DeleteGuildActionPayload, TransformGuildActionPayload,
+        },
+        guilds::{
+            ApplyGuildTemplateDto, DeleteGuildDto, DuplicateGuildDto, GuildSearchParameters,
+            NewGuildDto, NewGuildTemplateDto, UpdateGuildDto,
+        },
+    },
+    service::guilds,
+};
+
+/// Endpoint for listing the guilds of a plant layer.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/plants/guilds",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        GuildSearchParameters
+    ),
+    responses(
+        (status = 200, description = "Find guilds", body = Vec<GuildDto>)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("")]
+pub async fn find(
+    path: Path<i32>,
+    search_query: Query<GuildSearchParameters>,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response = guilds::find(path.into_inner(), search_query.layer_id, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Endpoint for grouping existing plantings into a new `Guild`.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/plants/guilds",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+    ),
+    request_body = NewGuildDto,
+    responses(
+        (status = 201, description = "Create a guild", body = GuildDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("")]
+pub async fn create(
+    path: Path<i32>,
+    json: Json<NewGuildDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let map_id = path.into_inner();
+    let new_guild = json.0;
+    let action_id = new_guild.action_id;
+    let dto = guilds::create(map_id, new_guild, user_info.id, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::CreateGuild(CreateGuildActionPayload::new(&dto, user_info.id, action_id)),
+        )
+        .await;
+
+    Ok(HttpResponse::Created().json(dto))
+}
+
+/// Endpoint for moving or rotating all plantings of a `Guild`.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/plants/guilds",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("guild_id" = Uuid, Path, description = "The id of the guild"),
+    ),
+    request_body = UpdateGuildDto,
+    responses(
+        (status = 200, description = "Update a guild", body = GuildDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[patch("/{guild_id}")]
+pub async fn update(
+    path: Path<(i32, Uuid)>,
+    json: Json<UpdateGuildDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, guild_id) = path.into_inner();
+    let update_guild = json.0;
+    let action_id = match update_guild {
+        UpdateGuildDto::Move(action_dto) => action_dto.action_id,
+        UpdateGuildDto::Rotate(action_dto) => action_dto.action_id,
+    };
+    let dto = guilds::update(map_id, guild_id, update_guild, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::TransformGuild(TransformGuildActionPayload::new(
+                &dto,
+                user_info.id,
+                action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().json(dto))
+}
+
+/// Endpoint for ungrouping a `Guild`, its plantings stay on the map.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/plants/guilds",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("guild_id" = Uuid, Path, description = "The id of the guild"),
+    ),
+    request_body = DeleteGuildDto,
+    responses(
+        (status = 200, description = "Ungroup a guild")
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[delete("/{guild_id}")]
+pub async fn delete(
+    path: Path<(i32, Uuid)>,
+    json: Json<DeleteGuildDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, guild_id) = path.into_inner();
+    guilds::delete_by_id(map_id, guild_id, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::DeleteGuild(DeleteGuildActionPayload::new(
+                guild_id,
+                user_info.id,
+                json.action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Endpoint for duplicating a `Guild` with all of its plantings.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/plants/guilds",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("guild_id" = Uuid, Path, description = "The id of the guild to duplicate"),
+    ),
+    request_body = DuplicateGuildDto,
+    responses(
+        (status = 201, description = "Duplicate a guild", body = GuildDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("/{guild_id}/duplicate")]
+pub async fn duplicate(
+    path: Path<(i32, Uuid)>,
+    json: Json<DuplicateGuildDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, guild_id) = path.into_inner();
+    let duplicate_guild = json.0;
+    let dto = guilds::duplicate(map_id, guild_id, duplicate_guild, user_info.id, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::CreateGuild(CreateGuildActionPayload::new(
+                &dto,
+                user_info.id,
+                duplicate_guild.action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Created().json(dto))
+}
+
+/// Endpoint for saving a `Guild` as a template of the user.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/plants/guilds",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("guild_id" = Uuid, Path, description = "The id of the guild to save"),
+    ),
+    request_body = NewGuildTemplateDto,
+    responses(
+        (status = 201, description = "Save a guild as a template", body = GuildTemplateDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("/{guild_id}/template")]
+pub async fn create_template(
+    path: Path<(i32, Uuid)>,
+    json: Json<NewGuildTemplateDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, guild_id) = path.into_inner();
+    let response =
+        guilds::create_template(map_id, guild_id, json.0, user_info.id, &app_data).await?;
+    Ok(HttpResponse::Created().json(response))
+}
+
+/// Endpoint for placing a new `Guild` from a template of the user.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/plants/guilds",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+    ),
+    request_body = ApplyGuildTemplateDto,
+    responses(
+        (status = 201, description = "Place a guild from a template", body = GuildDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("/from-template")]
+pub async fn apply_template(
+    path: Path<i32>,
+    json: Json<ApplyGuildTemplateDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let map_id = path.into_inner();
+    let apply_template = json.0;
+    let action_id = apply_template.action_id;
+    let dto = guilds::apply_template(map_id, apply_template, user_info.id, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::CreateGuild(CreateGuildActionPayload::new(&dto, user_info.id, action_id)),
+        )
+        .await;
+
+    Ok(HttpResponse::Created().json(dto))
+}
+
+/// Endpoint for listing the guild templates of the user.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/guild-templates",
+    responses(
+        (status = 200, description = "Find the guild templates of the user", body = Vec<GuildTemplateDto>)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("")]
+pub async fn find_templates(
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let response = guilds::find_templates(user_info.id, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Endpoint for deleting a guild template of the user.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/guild-templates",
+    params(
+        ("template_id" = i32, Path, description = "The id of the template"),
+    ),
+    responses(
+        (status = 200, description = "Delete a guild template")
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[delete("/{template_id}")]
+pub async fn delete_template(
+    path: Path<i32>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    guilds::delete_template(path.into_inner(), user_info.id, &app_data).await?;
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,123 @@
+//! `Harvest` endpoints.
+
+use actix_web::{
+    delete, get, post,
+    web::{Data, Json, Path, Query},
+    HttpResponse, Result,
+};
+use uuid::Uuid;
+
+use crate::{
+    config::{auth::user_info::UserInfo, data::AppDataInner},
+    model::dto::{HarvestSummaryParameters, NewHarvestDto},
+    service::harvests,
+};
+
+/// Endpoint for listing the harvests of a planting, the latest first.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/plants/plantings/{planting_id}/harvests",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("planting_id" = Uuid, Path, description = "The id of the harvested planting"),
+    ),
+    responses(
+        (status = 200, description = "Find the harvests of a planting", body = Vec<HarvestDto>)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("")]
+pub async fn find(path: Path<(i32, Uuid)>, app_data: Data<AppDataInner>) -> Result<HttpResponse> {
+    let (map_id, planting_id) = path.into_inner();
+    let response = harvests::find(map_id, planting_id, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Endpoint for logging a harvest of a planting.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/plants/plantings/{planting_id}/harvests",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("planting_id" = Uuid, Path, description = "The id of the harvested planting"),
+    ),
+    request_body = NewHarvestDto,
+    responses(
+        (status = 201, description = "Log a harvest", body = HarvestDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("")]
+pub async fn create(
+    path: Path<(i32, Uuid)>,
+    json: Json<NewHarvestDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, planting_id) = path.into_inner();
+    let response = harvests::create(map_id, planting_id, json.0, user_info.id, &app_data).await?;
+    Ok(HttpResponse::Created().json(response))
+}
+
+/// Endpoint for deleting a harvest of a planting.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/plants/plantings/{planting_id}/harvests",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("planting_id" = Uuid, Path, description = "The id of the harvested planting"),
+        ("harvest_id" = i32, Path, description = "The id of the harvest"),
+    ),
+    responses(
+        (status = 200, description = "Delete a harvest")
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[delete("/{harvest_id}")]
+pub async fn delete(
+    path: Path<(i32, Uuid, i32)>,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let (map_id, planting_id, harvest_id) = path.into_inner();
+    harvests::delete_by_id(map_id, planting_id, harvest_id, &app_data).await?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Endpoint for aggregating the harvests of a map by plant, layer, map or season.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/harvests",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map"),
+        HarvestSummaryParameters
+    ),
+    responses(
+        (status = 200, description = "Aggregate the harvests of a map", body = Vec<HarvestSummaryDto>)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("/summary")]
+pub async fn summarize(
+    map_id: Path<i32>,
+    summary_query: Query<HarvestSummaryParameters>,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response = harvests::summarize(*map_id, summary_query.into_inner(), &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
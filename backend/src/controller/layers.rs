@@ -1,12 +1,22 @@
 //! Layer endpoints.
 
 use actix_web::{
-    delete, get, post,
+    delete, get, patch, post, put,
     web::{Data, Json, Path, Query},
     HttpResponse, Result,
 };
 
-use crate::{config::data::AppDataInner, model::dto::LayerSearchParameters};
+use crate::{
+    config::{auth::user_info::UserInfo, data::AppDataInner},
+    model::dto::{
+        actions::{
+            Action, DeleteLayerActionPayload, ForkLayerActionPayload, PromoteLayerActionPayload,
+            ReorderLayersActionPayload, RestoreLayerActionPayload, UpdateLayerActionPayload,
+        },
+        DeleteLayerDto, LayerComparisonParameters, LayerOrderDto, LayerSearchParameters,
+        NewAlternativeLayerDto, PromoteLayerDto, RestoreLayerDto, UpdateLayerDto,
+    },
+};
 use crate::{model::dto::NewLayerDto, service::layer};
 
 /// Endpoint for searching layers.
@@ -91,7 +101,7 @@ pub async fn create(
     Ok(HttpResponse::Created().json(dto))
 }
 
-/// Endpoint for deleting a layer.
+/// Endpoint for renaming a layer or changing its position, visibility or opacity.
 ///
 /// # Errors
 /// * If the connection to the database could not be established.
@@ -99,17 +109,284 @@ pub async fn create(
     context_path = "/api/maps/{map_id}/layers",
     params(
         ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("id" = i32, Path, description = "The id of the layer"),
     ),
+    request_body = UpdateLayerDto,
     responses(
-        (status = 200, description = "Delete a layer")
+        (status = 200, description = "Update a layer", body = LayerDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[patch("/{id}")]
+pub async fn update(
+    path: Path<(i32, i32)>,
+    json: Json<UpdateLayerDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, layer_id) = path.into_inner();
+    let update_layer = json.0;
+    let action_id = update_layer.action_id;
+    let dto = layer::update(map_id, layer_id, update_layer, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::UpdateLayer(UpdateLayerActionPayload::new(&dto, user_info.id, action_id)),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().json(dto))
+}
+
+/// Endpoint for rewriting the order of all layers of a map at once.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layers are on"),
+    ),
+    request_body = LayerOrderDto,
+    responses(
+        (status = 200, description = "Reorder the layers of a map", body = Vec<LayerDto>)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[put("/order")]
+pub async fn reorder(
+    path: Path<i32>,
+    json: Json<LayerOrderDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let map_id = path.into_inner();
+    let layer_order = json.0;
+    let action_id = layer_order.action_id;
+    let dto = layer::reorder(map_id, layer_order, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::ReorderLayers(ReorderLayersActionPayload::new(
+                &dto,
+                user_info.id,
+                action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().json(dto))
+}
+
+/// Endpoint for forking a plant layer into an alternative with copies of all its plantings.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("id" = i32, Path, description = "The id of the plant layer to fork"),
+    ),
+    request_body = NewAlternativeLayerDto,
+    responses(
+        (status = 201, description = "Fork a plant layer", body = LayerDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("/{id}/alternatives")]
+pub async fn fork(
+    path: Path<(i32, i32)>,
+    json: Json<NewAlternativeLayerDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, layer_id) = path.into_inner();
+    let new_alternative = json.0;
+    let action_id = new_alternative.action_id;
+    let dto = layer::fork(map_id, layer_id, new_alternative, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::ForkLayer(ForkLayerActionPayload::new(
+                layer_id,
+                &dto,
+                user_info.id,
+                action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Created().json(dto))
+}
+
+/// Endpoint for comparing the plantings of two plant layers.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layers are on"),
+        ("id" = i32, Path, description = "The id of the plant layer to compare"),
+        LayerComparisonParameters,
+    ),
+    responses(
+        (status = 200, description = "Compare two plant layers", body = LayerDiffDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("/{id}/compare")]
+pub async fn compare(
+    path: Path<(i32, i32)>,
+    query: Query<LayerComparisonParameters>,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let (map_id, layer_id) = path.into_inner();
+    let response = layer::compare(map_id, layer_id, query.other_layer_id, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Endpoint for promoting an alternative to be the main layer of its type.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("id" = i32, Path, description = "The id of the layer to promote"),
+    ),
+    request_body = PromoteLayerDto,
+    responses(
+        (status = 200, description = "Promote a layer", body = Vec<LayerDto>)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("/{id}/promote")]
+pub async fn promote(
+    path: Path<(i32, i32)>,
+    json: Json<PromoteLayerDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, layer_id) = path.into_inner();
+    let dto = layer::promote(map_id, layer_id, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::PromoteLayer(PromoteLayerActionPayload::new(
+                layer_id,
+                &dto,
+                user_info.id,
+                json.action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().json(dto))
+}
+
+/// Endpoint for deleting a layer, it can be restored for 30 days.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("id" = i32, Path, description = "The id of the layer"),
+    ),
+    request_body = DeleteLayerDto,
+    responses(
+        (status = 200, description = "Delete a layer", body = LayerDto)
     ),
     security(
         ("oauth2" = [])
     )
 )]
 #[delete("/{id}")]
-pub async fn delete(path: Path<(i32, i32)>, app_data: Data<AppDataInner>) -> Result<HttpResponse> {
-    let (_, layer_id) = path.into_inner();
-    layer::delete_by_id(layer_id, &app_data).await?;
-    Ok(HttpResponse::Ok().finish())
+pub async fn delete(
+    path: Path<(i32, i32)>,
+    json: Json<DeleteLayerDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, layer_id) = path.into_inner();
+    let dto = layer::delete_by_id(map_id, layer_id, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::DeleteLayer(DeleteLayerActionPayload::new(
+                layer_id,
+                user_info.id,
+                json.action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().json(dto))
+}
+
+/// Endpoint for restoring a deleted layer with its plantings and images.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("id" = i32, Path, description = "The id of the deleted layer"),
+    ),
+    request_body = RestoreLayerDto,
+    responses(
+        (status = 200, description = "Restore a layer", body = LayerDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("/{id}/restore")]
+pub async fn restore(
+    path: Path<(i32, i32)>,
+    json: Json<RestoreLayerDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, layer_id) = path.into_inner();
+    let dto = layer::restore(map_id, layer_id, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::RestoreLayer(RestoreLayerActionPayload::new(
+                &dto,
+                user_info.id,
+                json.action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().json(dto))
 }
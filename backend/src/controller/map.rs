@@ -2,13 +2,15 @@
 
 use actix_web::web::Query;
 use actix_web::{
-    get, patch, post,
+    delete, get, patch, post,
     web::{Data, Json, Path},
     HttpResponse, Result,
 };
+use uuid::Uuid;
 
 use crate::config::auth::user_info::UserInfo;
 use crate::config::data::AppDataInner;
+use crate::model::dto::map_collaborators::NewMapCollaboratorDto;
 use crate::model::dto::{MapSearchParameters, PageParameters, UpdateMapDto};
 use crate::{model::dto::NewMapDto, service};
 
@@ -119,3 +121,88 @@ pub async fn update(
     .await?;
     Ok(HttpResponse::Ok().json(response))
 }
+
+/// Endpoint for listing the collaborators of a [`Map`](crate::model::entity::Map).
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map"),
+    ),
+    responses(
+        (status = 200, description = "Find the collaborators of a map", body = Vec<MapCollaboratorDto>)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("/{map_id}/collaborators")]
+pub async fn find_collaborators(
+    map_id: Path<i32>,
+    user_info: UserInfo,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response =
+        service::map::find_collaborators(map_id.into_inner(), user_info.id, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Endpoint for adding a collaborator to a [`Map`](crate::model::entity::Map) of the user.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map"),
+    ),
+    request_body = NewMapCollaboratorDto,
+    responses(
+        (status = 201, description = "Add a collaborator to a map", body = MapCollaboratorDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("/{map_id}/collaborators")]
+pub async fn add_collaborator(
+    map_id: Path<i32>,
+    json: Json<NewMapCollaboratorDto>,
+    user_info: UserInfo,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response =
+        service::map::add_collaborator(map_id.into_inner(), json.0, user_info.id, &app_data)
+            .await?;
+    Ok(HttpResponse::Created().json(response))
+}
+
+/// Endpoint for removing a collaborator from a [`Map`](crate::model::entity::Map).
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map"),
+        ("user_id" = Uuid, Path, description = "The id of the collaborator"),
+    ),
+    responses(
+        (status = 200, description = "Remove a collaborator from a map")
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[delete("/{map_id}/collaborators/{user_id}")]
+pub async fn remove_collaborator(
+    path: Path<(i32, Uuid)>,
+    user_info: UserInfo,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let (map_id, collaborator_id) = path.into_inner();
+    service::map::remove_collaborator(map_id, collaborator_id, user_info.id, &app_data).await?;
+    Ok(HttpResponse::Ok().finish())
+}
@@ -2,14 +2,25 @@
 
 pub mod base_layer_image;
 pub mod blossoms;
+pub mod calendar;
 pub mod config;
+pub mod drawings;
 pub mod guided_tours;
+pub mod guilds;
+pub mod harvests;
 pub mod layers;
 pub mod map;
+pub mod photo_layer;
 pub mod plant_layer;
 pub mod planting_suggestions;
 pub mod plantings;
 pub mod plants;
+pub mod rotation;
 pub mod seed;
+pub mod shade_layer;
+pub mod soil_layer;
+pub mod spacing;
 pub mod sse;
+pub mod todo_layer;
 pub mod users;
+pub mod winds_layer;
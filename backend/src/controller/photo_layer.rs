@@ -0,0 +1,202 @@
+//! Photo layer endpoints.
+
+use actix_web::{
+    delete, get, patch, post,
+    web::{Bytes, Data, Json, Path, Query},
+    HttpResponse, Result,
+};
+use uuid::Uuid;
+
+use crate::{
+    config::{auth::user_info::UserInfo, data::AppDataInner},
+    model::dto::{
+        actions::{
+            Action, CreatePhotoActionPayload, DeletePhotoActionPayload, UpdatePhotoActionPayload,
+        },
+        photos::{DeletePhotoDto, NewPhotoDto, PhotoSearchParameters, UpdatePhotoDto},
+    },
+    service::photo_layer,
+};
+
+/// Endpoint for listing the photos of a photo layer.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/photos",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        PhotoSearchParameters
+    ),
+    responses(
+        (status = 200, description = "Find photos", body = Vec<PhotoDto>)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("")]
+pub async fn find(
+    path: Path<i32>,
+    search_query: Query<PhotoSearchParameters>,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response =
+        photo_layer::find(path.into_inner(), search_query.into_inner(), &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Endpoint for pinning a new photo to the map.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/photos",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+    ),
+    request_body = NewPhotoDto,
+    responses(
+        (status = 201, description = "Create a photo", body = PhotoDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("")]
+pub async fn create(
+    path: Path<i32>,
+    json: Json<NewPhotoDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let map_id = path.into_inner();
+    let new_photo = json.0;
+    let action_id = new_photo.action_id;
+    let dto = photo_layer::create(map_id, new_photo, user_info.id, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::CreatePhoto(CreatePhotoActionPayload::new(&dto, user_info.id, action_id)),
+        )
+        .await;
+
+    Ok(HttpResponse::Created().json(dto))
+}
+
+/// Endpoint for moving a photo or changing its details.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/photos",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("photo_id" = Uuid, Path, description = "The id of the photo"),
+    ),
+    request_body = UpdatePhotoDto,
+    responses(
+        (status = 200, description = "Update a photo", body = PhotoDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[patch("/{photo_id}")]
+pub async fn update(
+    path: Path<(i32, Uuid)>,
+    json: Json<UpdatePhotoDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, photo_id) = path.into_inner();
+    let update_photo = json.0;
+    let action_id = update_photo.action_id;
+    let dto = photo_layer::update(map_id, photo_id, update_photo, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::UpdatePhoto(UpdatePhotoActionPayload::new(&dto, user_info.id, action_id)),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().json(dto))
+}
+
+/// Endpoint for deleting a photo.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/photos",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("photo_id" = Uuid, Path, description = "The id of the photo"),
+    ),
+    request_body = DeletePhotoDto,
+    responses(
+        (status = 200, description = "Delete a photo")
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[delete("/{photo_id}")]
+pub async fn delete(
+    path: Path<(i32, Uuid)>,
+    json: Json<DeletePhotoDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, photo_id) = path.into_inner();
+    photo_layer::delete_by_id(map_id, photo_id, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::DeletePhoto(DeletePhotoActionPayload::new(
+                photo_id,
+                user_info.id,
+                json.action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Endpoint for reading the capture time and position of an image from its EXIF data.
+/// The position is converted to coordinates of the map, which requires the map to have a location.
+///
+/// Only JPEG and TIFF images are supported.
+/// As the EXIF data is at the start of the file, it is enough to send the first 128 KiB of the image.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/photos",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the photo should be pinned to"),
+    ),
+    request_body(content = Vec<u8>, description = "The image", content_type = "application/octet-stream"),
+    responses(
+        (status = 200, description = "Read the metadata of an image", body = PhotoMetadataDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("/metadata")]
+pub async fn read_metadata(
+    path: Path<i32>,
+    body: Bytes,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response = photo_layer::read_metadata(path.into_inner(), &body, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -2,29 +2,35 @@
 
 use actix_web::{
     get,
-    web::{Data, Query},
+    web::{Data, Path, Query},
     HttpResponse, Result,
 };
 
 use crate::{
     config::{auth::user_info::UserInfo, data::AppDataInner},
-    model::dto::{Page, PageParameters, PlantSuggestionsSearchParameters, SuggestionType},
+    model::dto::{PageParameters, PlantSuggestionsSearchParameters, SuggestionType},
     service,
 };
 
 /// Endpoint for listing suggestions.
 /// Suggestions are `Plants` that are suitable for a given `Map`.
 ///
+/// Diversity suggestions explain which gaps in the diversity of the plantings they fill,
+/// companion suggestions reference the plantings they work well or badly with.
+///
 /// # Errors
 /// * If the connection to the database could not be established.
 #[utoipa::path(
     context_path = "/api/maps/{map_id}/layers/plants/suggestions",
     params(
+        ("map_id" = i32, Path, description = "The id of the map"),
         PlantSuggestionsSearchParameters,
         PageParameters,
     ),
     responses(
-        (status = 200, description = "Retrieve planting suggestions", body = PagePlantsSummaryDto)
+        (status = 200, description = "Retrieve available planting suggestions", body = PagePlantsSummaryDto),
+        (status = 200, description = "Retrieve diversity planting suggestions", body = PageDiversitySuggestionDto),
+        (status = 200, description = "Retrieve companion planting suggestions", body = PageCompanionSuggestionDto)
     ),
     security(
         ("oauth2" = [])
@@ -32,6 +38,7 @@ use crate::{
 )]
 #[get("")]
 pub async fn find(
+    map_id: Path<i32>,
     search_query: Query<PlantSuggestionsSearchParameters>,
     page_query: Query<PageParameters>,
     app_data: Data<AppDataInner>,
@@ -39,23 +46,38 @@ pub async fn find(
 ) -> Result<HttpResponse> {
     let search_query = search_query.into_inner();
 
-    let response = match search_query.suggestion_type {
+    match search_query.suggestion_type {
         SuggestionType::Available => {
-            service::plants::find_available_seasonal(
+            let response = service::plants::find_available_seasonal(
                 search_query,
                 page_query.into_inner(),
                 user_info.id,
                 &app_data,
             )
-            .await?
+            .await?;
+            Ok(HttpResponse::Ok().json(response))
         }
-        SuggestionType::Diversity => Page {
-            page: 1,
-            total_pages: 0,
-            per_page: 10,
-            results: vec![],
-        },
-    };
-
-    Ok(HttpResponse::Ok().json(response))
+        SuggestionType::Diversity => {
+            let response = service::planting_suggestions::find_diversity(
+                map_id.into_inner(),
+                search_query,
+                page_query.into_inner(),
+                user_info.id,
+                &app_data,
+            )
+            .await?;
+            Ok(HttpResponse::Ok().json(response))
+        }
+        SuggestionType::Companion => {
+            let response = service::planting_suggestions::find_companions(
+                map_id.into_inner(),
+                search_query,
+                page_query.into_inner(),
+                user_info.id,
+                &app_data,
+            )
+            .await?;
+            Ok(HttpResponse::Ok().json(response))
+        }
+    }
 }
@@ -10,7 +10,9 @@ use uuid::Uuid;
 use crate::{
     config::auth::user_info::UserInfo,
     model::dto::actions::{
-        Action, UpdatePlantingAddDateActionPayload, UpdatePlantingRemoveDateActionPayload,
+        Action, UpdatePlantingAddDateActionPayload, UpdatePlantingNotesActionPayload,
+        UpdatePlantingPhotosActionPayload, UpdatePlantingRemoveDateActionPayload,
+        UpdatePlantingSeedActionPayload, UpdatePlantingStatusActionPayload,
     },
 };
 use crate::{
@@ -21,8 +23,11 @@ use crate::{
     },
 };
 use crate::{
-    model::dto::plantings::{
-        DeletePlantingDto, NewPlantingDto, PlantingSearchParameters, UpdatePlantingDto,
+    model::dto::{
+        plantings::{
+            DeletePlantingDto, NewPlantingDto, PlantingSearchParameters, UpdatePlantingDto,
+        },
+        SpacingParameters,
     },
     service::plantings,
 };
@@ -56,15 +61,18 @@ pub async fn find(
 /// Endpoint for creating a new `Planting`.
 ///
 /// # Errors
+/// * If the spacing mode is strict and the planting has spacing problems.
 /// * If the connection to the database could not be established.
 #[utoipa::path(
     context_path = "/api/maps/{map_id}/layers/plants/plantings",
     params(
         ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        SpacingParameters
     ),
     request_body = NewPlantingDto,
     responses(
-        (status = 201, description = "Create a planting", body = PlantingDto)
+        (status = 201, description = "Create a planting", body = PlantingDto),
+        (status = 409, description = "The planting has spacing problems in strict mode")
     ),
     security(
         ("oauth2" = [])
@@ -74,18 +82,27 @@ pub async fn find(
 pub async fn create(
     path: Path<i32>,
     json: Json<NewPlantingDto>,
+    spacing_query: Query<SpacingParameters>,
     app_data: Data<AppDataInner>,
     user_info: UserInfo,
 ) -> Result<HttpResponse> {
+    let map_id = path.into_inner();
     let new_planting = json.0;
-    let dto = plantings::create(new_planting.clone(), &app_data).await?;
+    let dto = plantings::create(
+        map_id,
+        new_planting.clone(),
+        spacing_query.spacing.unwrap_or_default(),
+        user_info.id,
+        &app_data,
+    )
+    .await?;
 
     app_data
         .broadcaster
         .broadcast(
-            path.into_inner(),
+            map_id,
             Action::CreatePlanting(CreatePlantActionPayload::new(
-                dto,
+                &dto,
                 user_info.id,
                 new_planting.action_id,
             )),
@@ -98,15 +115,18 @@ pub async fn create(
 /// Endpoint for updating a `Planting`.
 ///
 /// # Errors
+/// * If the spacing mode is strict and the moved or transformed planting has spacing problems.
 /// * If the connection to the database could not be established.
 #[utoipa::path(
     context_path = "/api/maps/{map_id}/layers/plants/plantings",
     params(
         ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        SpacingParameters
     ),
     request_body = UpdatePlantingDto,
     responses(
-        (status = 200, description = "Update a planting", body = PlantingDto)
+        (status = 200, description = "Update a planting", body = PlantingDto),
+        (status = 409, description = "The planting has spacing problems in strict mode")
     ),
     security(
         ("oauth2" = [])
@@ -116,33 +136,54 @@ pub async fn create(
 pub async fn update(
     path: Path<(i32, Uuid)>,
     json: Json<UpdatePlantingDto>,
+    spacing_query: Query<SpacingParameters>,
     app_data: Data<AppDataInner>,
     user_info: UserInfo,
 ) -> Result<HttpResponse> {
     let (map_id, planting_id) = path.into_inner();
     let update_planting = json.0;
 
-    let planting = plantings::update(planting_id, update_planting, &app_data).await?;
+    let planting = plantings::update(
+        map_id,
+        planting_id,
+        update_planting.clone(),
+        spacing_query.spacing.unwrap_or_default(),
+        user_info.id,
+        &app_data,
+    )
+    .await?;
 
     let action = match update_planting {
         UpdatePlantingDto::Transform(action_dto) => Action::TransformPlanting(
-            TransformPlantActionPayload::new(planting, user_info.id, action_dto.action_id),
+            TransformPlantActionPayload::new(&planting, user_info.id, action_dto.action_id),
         ),
         UpdatePlantingDto::Move(action_dto) => Action::MovePlanting(MovePlantActionPayload::new(
-            planting,
+            &planting,
             user_info.id,
             action_dto.action_id,
         )),
         UpdatePlantingDto::UpdateAddDate(action_dto) => Action::UpdatePlantingAddDate(
-            UpdatePlantingAddDateActionPayload::new(planting, user_info.id, action_dto.action_id),
+            UpdatePlantingAddDateActionPayload::new(&planting, user_info.id, action_dto.action_id),
         ),
         UpdatePlantingDto::UpdateRemoveDate(action_dto) => {
             Action::UpdatePlantingRemoveDate(UpdatePlantingRemoveDateActionPayload::new(
-                planting,
+                &planting,
                 user_info.id,
                 action_dto.action_id,
             ))
         }
+        UpdatePlantingDto::UpdateNotes(action_dto) => Action::UpdatePlantingNotes(
+            UpdatePlantingNotesActionPayload::new(&planting, user_info.id, action_dto.action_id),
+        ),
+        UpdatePlantingDto::UpdateStatus(action_dto) => Action::UpdatePlantingStatus(
+            UpdatePlantingStatusActionPayload::new(&planting, user_info.id, action_dto.action_id),
+        ),
+        UpdatePlantingDto::UpdateSeed(action_dto) => Action::UpdatePlantingSeed(
+            UpdatePlantingSeedActionPayload::new(&planting, user_info.id, action_dto.action_id),
+        ),
+        UpdatePlantingDto::UpdatePhotos(action_dto) => Action::UpdatePlantingPhotos(
+            UpdatePlantingPhotosActionPayload::new(&planting, user_info.id, action_dto.action_id),
+        ),
     };
 
     app_data.broadcaster.broadcast(map_id, action).await;
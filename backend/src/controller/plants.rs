@@ -1,19 +1,26 @@
 //! `Plants` endpoints.
 
+use crate::config::auth::user_info::UserInfo;
 use crate::config::data::AppDataInner;
-use crate::model::dto::{PageParameters, PlantsSearchParameters};
+use crate::model::dto::{
+    Language, NewCustomPlantDto, PageParameters, PlantsImportParameters, PlantsSearchParameters,
+};
 use crate::service::plants;
 
 use actix_web::{
     get,
-    web::{Data, Path, Query},
+    http::header::AcceptLanguage,
+    post,
+    web::{Bytes, Data, Header, Json, Path, Query},
     HttpResponse, Result,
 };
+use actix_web_grants::proc_macro::has_permissions;
 
 /// Endpoint for fetching or searching [`PlantsSummaryDto`](crate::model::dto::PlantsSummaryDto).
 /// Search parameters are taken from the URLs query string (e.g. .../api/plants?name=example&per_page=5).
 /// If no page parameters are provided, the first page is returned.
-/// If no page parameters are provided, the first page is returned.
+/// The response also contains facets, i.e. how many of the matching plants have each attribute value.
+/// Common names are returned in the language given by the `lang` parameter or else the `Accept-Language` header.
 ///
 /// # Errors
 /// * If the connection to the database could not be established.
@@ -24,7 +31,7 @@ use actix_web::{
         PageParameters,
     ),
     responses(
-        (status = 200, description = "Fetch or search for all plants", body = PagePlantsSummaryDto),
+        (status = 200, description = "Fetch or search for all plants", body = FacetedPagePlantsSummaryDto),
     ),
     security(
         ("oauth2" = [])
@@ -34,11 +41,19 @@ use actix_web::{
 pub async fn find(
     search_query: Query<PlantsSearchParameters>,
     page_query: Query<PageParameters>,
+    accept_language: Option<Header<AcceptLanguage>>,
     app_data: Data<AppDataInner>,
+    user_info: UserInfo,
 ) -> Result<HttpResponse> {
+    let mut search_parameters = search_query.into_inner();
+    if search_parameters.lang.is_none() {
+        search_parameters.lang =
+            accept_language.and_then(|header| Language::from_accept_language(&header));
+    }
     let payload = plants::find(
-        search_query.into_inner(),
+        search_parameters,
         page_query.into_inner(),
+        user_info.id,
         &app_data,
     )
     .await?;
@@ -59,7 +74,115 @@ pub async fn find(
     )
 )]
 #[get("/{id}")]
-pub async fn find_by_id(id: Path<i32>, app_data: Data<AppDataInner>) -> Result<HttpResponse> {
-    let response = plants::find_by_id(*id, &app_data).await?;
+pub async fn find_by_id(
+    id: Path<i32>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let response = plants::find_by_id(*id, user_info.id, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Endpoint for fetching all details of a [`Plant`](crate::model::entity::Plants).
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/plants",
+    responses(
+        (status = 200, description = "Fetch all details of a plant by id", body = PlantDetailDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("/{id}/details")]
+pub async fn find_details_by_id(
+    id: Path<i32>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let response = plants::find_details_by_id(*id, user_info.id, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Endpoint for creating a custom plant or variety.
+/// The custom plant inherits all attributes not given from its base plant and is only visible to its creator.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/plants",
+    request_body = NewCustomPlantDto,
+    responses(
+        (status = 201, description = "Create a custom plant", body = PlantDetailDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("")]
+pub async fn create_custom(
+    new_plant_json: Json<NewCustomPlantDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let response = plants::create_custom(new_plant_json.0, user_info.id, &app_data).await?;
+    Ok(HttpResponse::Created().json(response))
+}
+
+/// Endpoint for submitting a custom plant for review to be added to the shared catalogue.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/plants",
+    responses(
+        (status = 200, description = "Submit a custom plant for review", body = PlantDetailDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("/{id}/submit")]
+pub async fn submit_for_review(
+    id: Path<i32>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let response = plants::submit_for_review(*id, user_info.id, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Endpoint for importing a plant dataset into the plant catalogue.
+/// Only available to administrators.
+///
+/// The import is idempotent: importing the same dataset twice does not change anything.
+/// The response reports which plants were added, changed or are no longer part of the dataset.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+/// * If the dataset could not be parsed.
+#[utoipa::path(
+    context_path = "/api/plants",
+    params(
+        PlantsImportParameters,
+    ),
+    request_body(content = PlantsDatasetDto, description = "The dataset as JSON or CSV"),
+    responses(
+        (status = 200, description = "Import a plant dataset", body = PlantsImportReportDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("/import")]
+#[has_permissions("admin")]
+pub async fn import(
+    import_query: Query<PlantsImportParameters>,
+    body: Bytes,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response = plants::import(import_query.into_inner(), &body, &app_data).await?;
     Ok(HttpResponse::Ok().json(response))
 }
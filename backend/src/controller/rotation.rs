@@ -0,0 +1,81 @@
+//! Crop rotation endpoints.
+
+use actix_web::{
+    get,
+    web::{Data, Path, Query},
+    HttpResponse, Result,
+};
+
+use crate::{
+    config::{auth::user_info::UserInfo, data::AppDataInner},
+    model::dto::{RotationCheckParameters, RotationScheduleParameters},
+    service,
+};
+
+/// Endpoint for checking a new or moved planting for crop rotation conflicts.
+///
+/// Reports earlier plantings at the same spot of the layer that are of the same family
+/// or, if the plant is a heavy feeder, were heavy feeders in the previous season.
+///
+/// # Errors
+/// * If the layer is not a plant layer of the map.
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/plants/rotation",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        RotationCheckParameters
+    ),
+    responses(
+        (status = 200, description = "Find crop rotation conflicts", body = Vec<RotationConflictDto>)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("/check")]
+pub async fn check(
+    path: Path<i32>,
+    check_query: Query<RotationCheckParameters>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let response = service::rotation::check(
+        path.into_inner(),
+        check_query.into_inner(),
+        user_info.id,
+        &app_data,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Endpoint for a suggested crop rotation schedule of a bed.
+///
+/// # Errors
+/// * If the layer is not a plant layer of the map.
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/plants/rotation",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        RotationScheduleParameters
+    ),
+    responses(
+        (status = 200, description = "Suggest a crop rotation schedule", body = RotationScheduleDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("/schedule")]
+pub async fn schedule(
+    path: Path<i32>,
+    schedule_query: Query<RotationScheduleParameters>,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response =
+        service::rotation::schedule(path.into_inner(), schedule_query.into_inner(), &app_data)
+            .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
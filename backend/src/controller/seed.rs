@@ -70,6 +70,30 @@ pub async fn find_by_id(
     Ok(HttpResponse::Ok().json(response))
 }
 
+/// Endpoint for fetching the lineage of a [`Seed`](crate::model::entity::Seed).
+/// Contains all ancestors of the seed and a tree of all seeds harvested from it.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/seeds",
+    responses(
+        (status = 200, description = "Fetch the lineage of a seed", body = SeedLineageDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("/{id}/lineage")]
+pub async fn find_lineage(
+    id: Path<i32>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let response = service::seed::find_lineage(*id, user_info.id, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
 /// Endpoint for creating a new [`Seed`](crate::model::entity::Seed).
 ///
 /// # Errors
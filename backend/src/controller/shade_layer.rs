@@ -0,0 +1,209 @@
+//! Shade layer endpoints.
+
+use actix_web::{
+    delete, get, patch, post,
+    web::{Data, Json, Path, Query},
+    HttpResponse, Result,
+};
+use uuid::Uuid;
+
+use crate::{
+    config::{auth::user_info::UserInfo, data::AppDataInner},
+    model::dto::{
+        actions::{
+            Action, CreateShadeObstacleActionPayload, DeleteShadeObstacleActionPayload,
+            UpdateShadeObstacleActionPayload,
+        },
+        shade::{
+            DeleteShadeObstacleDto, NewShadeObstacleDto, ShadeObstacleSearchParameters,
+            ShadeRasterParameters, UpdateShadeObstacleDto,
+        },
+    },
+    service::shade_layer,
+};
+
+/// Endpoint for listing the obstacles of a shade layer.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/shade",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ShadeObstacleSearchParameters
+    ),
+    responses(
+        (status = 200, description = "Find shade obstacles", body = Vec<ShadeObstacleDto>)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("/obstacles")]
+pub async fn find_obstacles(
+    path: Path<i32>,
+    search_query: Query<ShadeObstacleSearchParameters>,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response = shade_layer::find(path.into_inner(), search_query.layer_id, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Endpoint for placing a new shade obstacle.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/shade",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+    ),
+    request_body = NewShadeObstacleDto,
+    responses(
+        (status = 201, description = "Create a shade obstacle", body = ShadeObstacleDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("/obstacles")]
+pub async fn create_obstacle(
+    path: Path<i32>,
+    json: Json<NewShadeObstacleDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let map_id = path.into_inner();
+    let new_obstacle = json.0;
+    let action_id = new_obstacle.action_id;
+    let dto = shade_layer::create(map_id, new_obstacle, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::CreateShadeObstacle(CreateShadeObstacleActionPayload::new(
+                &dto,
+                user_info.id,
+                action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Created().json(dto))
+}
+
+/// Endpoint for moving, resizing or renaming a shade obstacle.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/shade",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("obstacle_id" = Uuid, Path, description = "The id of the obstacle"),
+    ),
+    request_body = UpdateShadeObstacleDto,
+    responses(
+        (status = 200, description = "Update a shade obstacle", body = ShadeObstacleDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[patch("/obstacles/{obstacle_id}")]
+pub async fn update_obstacle(
+    path: Path<(i32, Uuid)>,
+    json: Json<UpdateShadeObstacleDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, obstacle_id) = path.into_inner();
+    let update_obstacle = json.0;
+    let action_id = update_obstacle.action_id;
+    let dto = shade_layer::update(map_id, obstacle_id, update_obstacle, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::UpdateShadeObstacle(UpdateShadeObstacleActionPayload::new(
+                &dto,
+                user_info.id,
+                action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().json(dto))
+}
+
+/// Endpoint for deleting a shade obstacle.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/shade",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("obstacle_id" = Uuid, Path, description = "The id of the obstacle"),
+    ),
+    request_body = DeleteShadeObstacleDto,
+    responses(
+        (status = 200, description = "Delete a shade obstacle")
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[delete("/obstacles/{obstacle_id}")]
+pub async fn delete_obstacle(
+    path: Path<(i32, Uuid)>,
+    json: Json<DeleteShadeObstacleDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, obstacle_id) = path.into_inner();
+    shade_layer::delete_by_id(map_id, obstacle_id, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::DeleteShadeObstacle(DeleteShadeObstacleActionPayload::new(
+                obstacle_id,
+                user_info.id,
+                json.action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Endpoint for computing the hours of direct sunlight across the map on a day.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/shade",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ShadeRasterParameters
+    ),
+    responses(
+        (status = 200, description = "Compute the shade of the map", body = ShadeRasterDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("/raster")]
+pub async fn raster(
+    path: Path<i32>,
+    query: Query<ShadeRasterParameters>,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response = shade_layer::raster(path.into_inner(), query.into_inner(), &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
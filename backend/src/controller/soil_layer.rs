@@ -0,0 +1,209 @@
+//! Soil layer endpoints.
+
+use actix_web::{
+    delete, get, patch, post,
+    web::{Data, Json, Path, Query},
+    HttpResponse, Result,
+};
+use uuid::Uuid;
+
+use crate::{
+    config::{auth::user_info::UserInfo, data::AppDataInner},
+    model::dto::{
+        actions::{
+            Action, CreateSoilSampleActionPayload, DeleteSoilSampleActionPayload,
+            UpdateSoilSampleActionPayload,
+        },
+        soil_samples::{
+            DeleteSoilSampleDto, NewSoilSampleDto, SoilRasterParameters,
+            SoilSampleSearchParameters, UpdateSoilSampleDto,
+        },
+    },
+    service::soil_layer,
+};
+
+/// Endpoint for listing the samples of a soil layer.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/soil",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        SoilSampleSearchParameters
+    ),
+    responses(
+        (status = 200, description = "Find soil samples", body = Vec<SoilSampleDto>)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("/samples")]
+pub async fn find_samples(
+    path: Path<i32>,
+    search_query: Query<SoilSampleSearchParameters>,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response = soil_layer::find(path.into_inner(), search_query.layer_id, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Endpoint for placing a new soil sample.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/soil",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+    ),
+    request_body = NewSoilSampleDto,
+    responses(
+        (status = 201, description = "Create a soil sample", body = SoilSampleDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("/samples")]
+pub async fn create_sample(
+    path: Path<i32>,
+    json: Json<NewSoilSampleDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let map_id = path.into_inner();
+    let new_sample = json.0;
+    let action_id = new_sample.action_id;
+    let dto = soil_layer::create(map_id, new_sample, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::CreateSoilSample(CreateSoilSampleActionPayload::new(
+                &dto,
+                user_info.id,
+                action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Created().json(dto))
+}
+
+/// Endpoint for moving a soil sample or changing its measurements.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/soil",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("sample_id" = Uuid, Path, description = "The id of the sample"),
+    ),
+    request_body = UpdateSoilSampleDto,
+    responses(
+        (status = 200, description = "Update a soil sample", body = SoilSampleDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[patch("/samples/{sample_id}")]
+pub async fn update_sample(
+    path: Path<(i32, Uuid)>,
+    json: Json<UpdateSoilSampleDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, sample_id) = path.into_inner();
+    let update_sample = json.0;
+    let action_id = update_sample.action_id;
+    let dto = soil_layer::update(map_id, sample_id, update_sample, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::UpdateSoilSample(UpdateSoilSampleActionPayload::new(
+                &dto,
+                user_info.id,
+                action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().json(dto))
+}
+
+/// Endpoint for deleting a soil sample.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/soil",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("sample_id" = Uuid, Path, description = "The id of the sample"),
+    ),
+    request_body = DeleteSoilSampleDto,
+    responses(
+        (status = 200, description = "Delete a soil sample")
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[delete("/samples/{sample_id}")]
+pub async fn delete_sample(
+    path: Path<(i32, Uuid)>,
+    json: Json<DeleteSoilSampleDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, sample_id) = path.into_inner();
+    soil_layer::delete_by_id(map_id, sample_id, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::DeleteSoilSample(DeleteSoilSampleActionPayload::new(
+                sample_id,
+                user_info.id,
+                json.action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Endpoint for interpolating the samples of a soil layer across the map.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/soil",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        SoilRasterParameters
+    ),
+    responses(
+        (status = 200, description = "Interpolate the soil of the map", body = SoilRasterDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("/raster")]
+pub async fn raster(
+    path: Path<i32>,
+    query: Query<SoilRasterParameters>,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response = soil_layer::raster(path.into_inner(), query.into_inner(), &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -0,0 +1,48 @@
+//! Planting spacing endpoints.
+
+use actix_web::{
+    get,
+    web::{Data, Path, Query},
+    HttpResponse, Result,
+};
+
+use crate::{
+    config::{auth::user_info::UserInfo, data::AppDataInner},
+    model::dto::SpacingCheckParameters,
+    service,
+};
+
+/// Endpoint for checking the spacing of a new or moved planting.
+///
+/// Reports neighbours on the same layer that are too close considering the mature spread of the plants
+/// and whether the planting is outside of the map.
+/// In strict mode the request fails if there are any problems.
+///
+/// # Errors
+/// * If the mode is strict and there are spacing problems.
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/plants/spacing",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        SpacingCheckParameters
+    ),
+    responses(
+        (status = 200, description = "Find spacing problems", body = Vec<SpacingWarningDto>),
+        (status = 409, description = "The planting has spacing problems in strict mode")
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("/check")]
+pub async fn check(
+    map_id: Path<i32>,
+    check_query: Query<SpacingCheckParameters>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let response =
+        service::spacing::check(*map_id, check_query.into_inner(), user_info.id, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
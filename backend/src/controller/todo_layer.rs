@@ -0,0 +1,193 @@
+//! Todo layer endpoints.
+
+use actix_web::{
+    delete, get, patch, post,
+    web::{Data, Json, Path, Query},
+    HttpResponse, Result,
+};
+use uuid::Uuid;
+
+use crate::{
+    config::{auth::user_info::UserInfo, data::AppDataInner},
+    model::dto::{
+        actions::{
+            Action, CreateTodoActionPayload, DeleteTodoActionPayload, UpdateTodoActionPayload,
+        },
+        todos::{DeleteTodoDto, NewTodoDto, TodoSearchParameters, UpdateTodoDto},
+    },
+    service::todo_layer,
+};
+
+/// Endpoint for listing the todos of a todo layer.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/todos",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        TodoSearchParameters
+    ),
+    responses(
+        (status = 200, description = "Find todos", body = Vec<TodoDto>)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("")]
+pub async fn find(
+    path: Path<i32>,
+    search_query: Query<TodoSearchParameters>,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response =
+        todo_layer::find(path.into_inner(), search_query.into_inner(), &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Endpoint for pinning a new todo to the map.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/todos",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+    ),
+    request_body = NewTodoDto,
+    responses(
+        (status = 201, description = "Create a todo", body = TodoDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[post("")]
+pub async fn create(
+    path: Path<i32>,
+    json: Json<NewTodoDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let map_id = path.into_inner();
+    let new_todo = json.0;
+    let action_id = new_todo.action_id;
+    let dto = todo_layer::create(map_id, new_todo, user_info.id, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::CreateTodo(CreateTodoActionPayload::new(&dto, user_info.id, action_id)),
+        )
+        .await;
+
+    Ok(HttpResponse::Created().json(dto))
+}
+
+/// Endpoint for editing, moving or completing a todo.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/todos",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("todo_id" = Uuid, Path, description = "The id of the todo"),
+    ),
+    request_body = UpdateTodoDto,
+    responses(
+        (status = 200, description = "Update a todo", body = TodoDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[patch("/{todo_id}")]
+pub async fn update(
+    path: Path<(i32, Uuid)>,
+    json: Json<UpdateTodoDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, todo_id) = path.into_inner();
+    let update_todo = json.0;
+    let action_id = update_todo.action_id;
+    let dto = todo_layer::update(map_id, todo_id, update_todo, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::UpdateTodo(UpdateTodoActionPayload::new(&dto, user_info.id, action_id)),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().json(dto))
+}
+
+/// Endpoint for deleting a todo.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/todos",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        ("todo_id" = Uuid, Path, description = "The id of the todo"),
+    ),
+    request_body = DeleteTodoDto,
+    responses(
+        (status = 200, description = "Delete a todo")
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[delete("/{todo_id}")]
+pub async fn delete(
+    path: Path<(i32, Uuid)>,
+    json: Json<DeleteTodoDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let (map_id, todo_id) = path.into_inner();
+    todo_layer::delete_by_id(map_id, todo_id, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::DeleteTodo(DeleteTodoActionPayload::new(
+                todo_id,
+                user_info.id,
+                json.action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Endpoint for listing the open todos assigned to the user on all maps.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/todos",
+    responses(
+        (status = 200, description = "Find the open todos of the user", body = Vec<AssignedTodoDto>)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("")]
+pub async fn find_assigned(
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let response = todo_layer::find_assigned(user_info.id, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
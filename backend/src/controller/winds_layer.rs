@@ -0,0 +1,115 @@
+//! Winds layer endpoints.
+
+use actix_web::{
+    get, put,
+    web::{Data, Json, Path, Query},
+    HttpResponse, Result,
+};
+
+use crate::{
+    config::{auth::user_info::UserInfo, data::AppDataInner},
+    model::dto::{
+        actions::{Action, UpdateWindRoseActionPayload},
+        winds::{UpdateWindRoseDto, WindRasterParameters, WindRoseSearchParameters},
+    },
+    service::winds_layer,
+};
+
+/// Endpoint for getting the wind rose of a winds layer.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/winds",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        WindRoseSearchParameters
+    ),
+    responses(
+        (status = 200, description = "Find the wind rose", body = WindRoseDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("/rose")]
+pub async fn find_rose(
+    path: Path<i32>,
+    search_query: Query<WindRoseSearchParameters>,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response =
+        winds_layer::find_rose(path.into_inner(), search_query.layer_id, &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Endpoint for replacing the wind rose of a winds layer.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/winds",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+    ),
+    request_body = UpdateWindRoseDto,
+    responses(
+        (status = 200, description = "Replace the wind rose", body = WindRoseDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[put("/rose")]
+pub async fn replace_rose(
+    path: Path<i32>,
+    json: Json<UpdateWindRoseDto>,
+    app_data: Data<AppDataInner>,
+    user_info: UserInfo,
+) -> Result<HttpResponse> {
+    let map_id = path.into_inner();
+    let update_rose = json.0;
+    let action_id = update_rose.action_id;
+    let dto = winds_layer::replace_rose(map_id, update_rose, &app_data).await?;
+
+    app_data
+        .broadcaster
+        .broadcast(
+            map_id,
+            Action::UpdateWindRose(UpdateWindRoseActionPayload::new(
+                &dto,
+                user_info.id,
+                action_id,
+            )),
+        )
+        .await;
+
+    Ok(HttpResponse::Ok().json(dto))
+}
+
+/// Endpoint for computing the wind exposure across the map, sheltered by tall plantings.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+#[utoipa::path(
+    context_path = "/api/maps/{map_id}/layers/winds",
+    params(
+        ("map_id" = i32, Path, description = "The id of the map the layer is on"),
+        WindRasterParameters
+    ),
+    responses(
+        (status = 200, description = "Compute the wind exposure of the map", body = WindRasterDto)
+    ),
+    security(
+        ("oauth2" = [])
+    )
+)]
+#[get("/raster")]
+pub async fn raster(
+    path: Path<i32>,
+    query: Query<WindRasterParameters>,
+    app_data: Data<AppDataInner>,
+) -> Result<HttpResponse> {
+    let response = winds_layer::raster(path.into_inner(), query.into_inner(), &app_data).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
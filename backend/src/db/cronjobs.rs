@@ -8,11 +8,17 @@ use log::debug;
 use std::time::Duration;
 
 use super::connection::Pool;
-use crate::schema::maps;
+use crate::schema::{layers, maps};
 
 /// How often the deleted maps are cleaned up in seconds.
 const CLEANUP_MAPS_INTERVAL: u64 = 60 * 60 * 24;
 
+/// How often the deleted layers are cleaned up in seconds.
+const CLEANUP_LAYERS_INTERVAL: u64 = 60 * 60 * 24;
+
+/// How many days deleted layers are kept before they are cleaned up.
+pub const DELETED_LAYERS_RETENTION_DAYS: u64 = 30;
+
 /// Permanently remove deleted maps older than 30 days from the database.
 /// Runs every [`CLEANUP_MAPS_INTERVAL`] seconds.
 pub async fn cleanup_maps(pool: Pool) -> ! {
@@ -45,3 +51,39 @@ pub async fn cleanup_maps(pool: Pool) -> ! {
         }
     }
 }
+
+/// Permanently remove deleted layers older than [`DELETED_LAYERS_RETENTION_DAYS`] together with their plantings and images.
+/// Runs every [`CLEANUP_LAYERS_INTERVAL`] seconds.
+pub async fn cleanup_layers(pool: Pool) -> ! {
+    loop {
+        tokio::time::sleep(Duration::from_secs(CLEANUP_LAYERS_INTERVAL)).await;
+
+        log::info!("Running layers cleanup...");
+
+        let Some(one_month_ago) = Utc::now()
+            .date_naive()
+            .checked_sub_days(Days::new(DELETED_LAYERS_RETENTION_DAYS))
+        else {
+            log::error!("Failed to calculate date one month ago");
+            continue;
+        };
+        let query = diesel::delete(
+            layers::table.filter(
+                layers::deletion_date
+                    .is_not_null()
+                    .and(layers::deletion_date.lt(one_month_ago)),
+            ),
+        );
+        debug!("{}", debug_query::<Pg, _>(&query));
+
+        match pool.get().await {
+            Ok(mut conn) => match query.execute(&mut conn).await {
+                Ok(delete_rows) => log::info!("Removed {delete_rows} layers"),
+                Err(e) => log::error!("Failed to execute query: {}", e),
+            },
+            Err(e) => {
+                log::error!("Failed to get connection from pool: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! Count the distinct values of queries.
+
+use diesel::pg::Pg;
+use diesel::query_builder::{AstPass, Query, QueryFragment};
+use diesel::sql_types::BigInt;
+use diesel::{QueryId, QueryResult};
+
+/// An executable query counting how often each value of the inner query occurs.
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct CountedValuesQuery<T> {
+    /// Executable query selecting exactly one column.
+    query: T,
+}
+
+/// A trait intended for counting values (e.g. for facets) in diesel's query builder.
+pub trait CountValues: Sized {
+    /// Return a query that groups the selected values and counts their occurrences.
+    ///
+    /// The query has to select exactly one column.
+    fn count_values(self) -> CountedValuesQuery<Self>;
+}
+
+impl<T> CountValues for T {
+    fn count_values(self) -> CountedValuesQuery<Self> {
+        CountedValuesQuery { query: self }
+    }
+}
+
+impl<T: Query> Query for CountedValuesQuery<T> {
+    type SqlType = (T::SqlType, BigInt);
+}
+
+impl<T> QueryFragment<Pg> for CountedValuesQuery<T>
+where
+    T: QueryFragment<Pg>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, Pg>) -> QueryResult<()> {
+        out.push_sql("SELECT t.value, COUNT(*) FROM (");
+        self.query.walk_ast(out.reborrow())?;
+        out.push_sql(") t (value) GROUP BY t.value ORDER BY t.value");
+        Ok(())
+    }
+}
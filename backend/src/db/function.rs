@@ -4,9 +4,10 @@ use diesel::{
     expression::AsExpression,
     pg::Pg,
     sql_function,
-    sql_types::{Array, Float, Nullable, SqlType, Text},
+    sql_types::{Array, Double, Float, Integer, Nullable, SingleValue, SqlType, Text},
     Expression,
 };
+use postgis_diesel::sql_types::Geometry;
 
 sql_function! {
     /// The SQL function `array_to_string`.
@@ -18,6 +19,15 @@ sql_function! {
     ) -> Text
 }
 
+sql_function! {
+    /// The SQL function `unnest`.
+    ///
+    /// Used to expand an array to a set of rows.
+    fn unnest<T: SingleValue>(
+        array: Nullable<Array<Nullable<T>>>
+    ) -> Nullable<T>
+}
+
 sql_function! {
     /// The `pg_trgm` SQL function `similarity`.
     ///
@@ -51,10 +61,48 @@ sql_function! {
         t1: Float,
         t2: Float,
         t3: Float,
-        t4: Float
+        t4: Float,
+        t5: Float
     ) -> Float
 }
 
+sql_function! {
+    /// The `PostGIS` SQL function `ST_MakePoint`.
+    ///
+    /// Used to create a point geometry from the coordinates of a planting.
+    /// The plantings table has a spatial index on `st_makepoint(x, y)`.
+    #[sql_name = "st_makepoint"]
+    fn st_make_point(
+        x: Integer,
+        y: Integer
+    ) -> Geometry
+}
+
+sql_function! {
+    /// The `PostGIS` SQL function `ST_MakeEnvelope`.
+    ///
+    /// Used to create a rectangular polygon from its minimum and maximum coordinates.
+    #[sql_name = "st_makeenvelope"]
+    fn st_make_envelope(
+        x_min: Double,
+        y_min: Double,
+        x_max: Double,
+        y_max: Double
+    ) -> Geometry
+}
+
+sql_function! {
+    /// The `PostGIS` SQL function `ST_DWithin`.
+    ///
+    /// Used to find geometries within a distance of another geometry.
+    #[sql_name = "st_dwithin"]
+    fn st_d_within(
+        left: Geometry,
+        right: Geometry,
+        distance: Double
+    ) -> Bool
+}
+
 diesel::infix_operator!(PgTrgmFuzzy, " % ", backend: Pg);
 
 /// Implements `pg_trgm` methods for diesel
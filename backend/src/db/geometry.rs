@@ -0,0 +1,84 @@
+//! Geometries that share a single `geometry` column of any geometry type.
+
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    serialize::{self, Output, ToSql},
+};
+use postgis_diesel::{
+    sql_types::Geometry,
+    types::{LineString, Point, Polygon},
+};
+
+/// The EWKB type code of points.
+const EWKB_POINT: u32 = 1;
+/// The EWKB type code of line strings.
+const EWKB_LINE_STRING: u32 = 2;
+/// The EWKB type code of polygons.
+const EWKB_POLYGON: u32 = 3;
+
+/// A point, line string or polygon stored in a `geometry` column.
+///
+/// [`postgis_diesel`] can only read and write columns of a single geometry type,
+/// so the geometry type is read from the EWKB header and the matching type is used.
+#[derive(Debug, Clone, PartialEq, FromSqlRow, AsExpression)]
+#[diesel(sql_type = Geometry)]
+pub enum ShapeGeometry {
+    /// A single position.
+    Point(Point),
+    /// An open line through several positions.
+    LineString(LineString<Point>),
+    /// A closed area.
+    Polygon(Polygon<Point>),
+}
+
+impl ShapeGeometry {
+    /// All points of the geometry, for polygons the points of the outer ring.
+    #[must_use]
+    pub fn points(&self) -> Vec<Point> {
+        match self {
+            Self::Point(point) => vec![*point],
+            Self::LineString(line_string) => line_string.points.clone(),
+            Self::Polygon(polygon) => polygon.rings.first().cloned().unwrap_or_default(),
+        }
+    }
+}
+
+impl ToSql<Geometry, Pg> for ShapeGeometry {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        match self {
+            Self::Point(point) => ToSql::<Geometry, Pg>::to_sql(point, out),
+            Self::LineString(line_string) => ToSql::<Geometry, Pg>::to_sql(line_string, out),
+            Self::Polygon(polygon) => ToSql::<Geometry, Pg>::to_sql(polygon, out),
+        }
+    }
+}
+
+impl FromSql<Geometry, Pg> for ShapeGeometry {
+    fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+        // EWKB starts with the byte order followed by the geometry type,
+        // the upper bits of the type are flags for dimensions and SRID.
+        let bytes = value.as_bytes();
+        let (Some(byte_order), Some(type_bytes)) = (bytes.first(), bytes.get(1..5)) else {
+            return Err("Geometry is too short to contain an EWKB header".into());
+        };
+        let type_bytes: [u8; 4] = type_bytes.try_into()?;
+        let geometry_type = if *byte_order == 0 {
+            u32::from_be_bytes(type_bytes)
+        } else {
+            u32::from_le_bytes(type_bytes)
+        } & 0xff;
+
+        match geometry_type {
+            EWKB_POINT => <Point as FromSql<Geometry, Pg>>::from_sql(value).map(Self::Point),
+            EWKB_LINE_STRING => {
+                <LineString<Point> as FromSql<Geometry, Pg>>::from_sql(value).map(Self::LineString)
+            }
+            EWKB_POLYGON => {
+                <Polygon<Point> as FromSql<Geometry, Pg>>::from_sql(value).map(Self::Polygon)
+            }
+            other => Err(format!("Unsupported geometry type {other}").into()),
+        }
+    }
+}
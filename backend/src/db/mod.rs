@@ -2,5 +2,7 @@
 
 pub mod connection;
 pub mod cronjobs;
+pub mod facet;
 pub mod function;
+pub mod geometry;
 pub mod pagination;
@@ -1,6 +1,7 @@
 //! Error of the server and their implementation.
 
 use actix_web::{
+    error::BlockingError,
     http::{header::ContentType, StatusCode},
     HttpResponse, ResponseError,
 };
@@ -64,3 +65,10 @@ impl From<DieselError> for ServiceError {
         Self::new(status_code, value.to_string())
     }
 }
+
+impl From<BlockingError> for ServiceError {
+    fn from(value: BlockingError) -> Self {
+        log::error!("Error running blocking task: {}", value.to_string());
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, value.to_string())
+    }
+}
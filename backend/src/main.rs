@@ -52,7 +52,10 @@
 use actix_cors::Cors;
 use actix_web::{http, middleware::Logger, App, HttpServer};
 use config::{api_doc, auth::Config, routes};
-use db::{connection::Pool, cronjobs::cleanup_maps};
+use db::{
+    connection::Pool,
+    cronjobs::{cleanup_layers, cleanup_maps},
+};
 use log::info;
 
 pub mod config;
@@ -121,5 +124,6 @@ fn cors_configuration() -> Cors {
 
 /// Start all scheduled jobs that get run in the backend.
 fn start_cronjobs(pool: Pool) {
-    tokio::spawn(cleanup_maps(pool));
+    tokio::spawn(cleanup_maps(pool.clone()));
+    tokio::spawn(cleanup_layers(pool));
 }
@@ -1,7 +1,7 @@
 //! DTOs of `PermaplanT`.
 #![allow(clippy::module_name_repetitions)] // There needs to be a difference between DTOs and entities otherwise imports will be messy.
 
-use chrono::NaiveDate;
+use chrono::{NaiveDate, NaiveDateTime};
 use postgis_diesel::types::{Point, Polygon};
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
@@ -11,28 +11,57 @@ use uuid::Uuid;
 use self::plantings::PlantingDto;
 
 use super::r#enum::{
-    experience::Experience, layer_type::LayerType, membership::Membership,
-    plant_spread::PlantSpread, privacy_option::PrivacyOption, quality::Quality, quantity::Quantity,
-    relation_type::RelationType, salutation::Salutation,
+    deciduous_or_evergreen::DeciduousOrEvergreen, experience::Experience,
+    external_source::ExternalSource, fertility::Fertility, flower_type::FlowerType,
+    growth_rate::GrowthRate, harvest_quality::HarvestQuality, harvest_unit::HarvestUnit,
+    herbaceous_or_woody::HerbaceousOrWoody, layer_type::LayerType, life_cycle::LifeCycle,
+    light_requirement::LightRequirement, membership::Membership, nutrition_demand::NutritionDemand,
+    plant_height::PlantHeight, plant_spread::PlantSpread, privacy_option::PrivacyOption,
+    propagation_method::PropagationMethod, quality::Quality, quantity::Quantity,
+    relation_type::RelationType, salutation::Salutation, shade::Shade, soil_ph::SoilPh,
+    soil_texture::SoilTexture, soil_water_retention::SoilWaterRetention,
+    water_requirement::WaterRequirement,
 };
 
 pub mod actions;
 pub mod base_layer_images_impl;
 pub mod blossoms_impl;
+pub mod calendar_impl;
 pub mod coordinates_impl;
+pub mod drawings;
+pub mod drawings_impl;
+pub mod facet_impl;
 pub mod guided_tours_impl;
+pub mod guilds;
+pub mod guilds_impl;
+pub mod harvests_impl;
+pub mod language_impl;
 pub mod layer_impl;
+pub mod map_collaborators;
+pub mod map_collaborators_impl;
 pub mod map_impl;
+pub mod new_custom_plant_impl;
 pub mod new_layer_impl;
 pub mod new_map_impl;
 pub mod new_seed_impl;
 pub mod page_impl;
+pub mod photos;
+pub mod photos_impl;
 pub mod plantings;
 pub mod plantings_impl;
+pub mod plants_dataset_impl;
 pub mod plants_impl;
 pub mod seed_impl;
+pub mod shade;
+pub mod shade_impl;
+pub mod soil_samples;
+pub mod soil_samples_impl;
+pub mod todos;
+pub mod todos_impl;
 pub mod update_map_impl;
 pub mod users_impl;
+pub mod winds;
+pub mod winds_impl;
 
 /// Contains configuration the frontend needs to run.
 #[typeshare]
@@ -80,6 +109,8 @@ pub struct SeedDto {
     pub notes: Option<String>,
     /// The id of the owner of the seed.
     pub owner_id: Uuid,
+    /// The id of the seed this seed was harvested from.
+    pub parent_seed_id: Option<i32>,
 }
 
 #[allow(clippy::missing_docs_in_private_items)] // TODO: See #97.
@@ -99,6 +130,30 @@ pub struct NewSeedDto {
     pub quality: Option<Quality>,
     pub price: Option<i16>,
     pub notes: Option<String>,
+    pub parent_seed_id: Option<i32>,
+}
+
+/// The ancestry and descendants of a seed lot.
+#[typeshare]
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct SeedLineageDto {
+    /// The seed the lineage was requested for.
+    pub seed: SeedDto,
+    /// All ancestors of the seed, starting with its direct parent.
+    pub ancestors: Vec<SeedDto>,
+    /// All seeds that were harvested from this seed, as a tree.
+    pub descendants: Vec<SeedLineageNodeDto>,
+}
+
+/// A seed in a lineage tree together with the seeds harvested from it.
+#[allow(clippy::use_self)] // `Self` can't be used because typeshare needs the type name.
+#[typeshare]
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct SeedLineageNodeDto {
+    /// The seed of this generation.
+    pub seed: SeedDto,
+    /// The seeds that were harvested from this seed.
+    pub children: Vec<SeedLineageNodeDto>,
 }
 
 /// The essential identifying information of a plant.
@@ -111,17 +166,362 @@ pub struct PlantsSummaryDto {
     pub unique_name: String,
     /// A list of common english names (E.g. "Bread wheat", "Sour cherry")
     pub common_name_en: Option<Vec<Option<String>>>,
+    /// A list of common names in the requested language.
+    /// Falls back to the english names if there are no names in the requested language.
+    pub common_name: Option<Vec<Option<String>>>,
     //TODO: add icon_path: String
     /// How far a plant spreads (The 'width' of a plant)
     pub spread: Option<PlantSpread>,
 }
 
+/// All information about a plant.
+#[typeshare]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PlantDetailDto {
+    /// The plants database id.
+    pub id: i32,
+    /// Biological name of this plant (E.g. "Triticum aestivum", "Prunus cerasus")
+    pub unique_name: String,
+    /// A list of common english names (E.g. "Bread wheat", "Sour cherry")
+    pub common_name_en: Option<Vec<Option<String>>>,
+    /// A list of common german names (E.g. "Weichweizen", "Sauerkirsche")
+    pub common_name_de: Option<Vec<Option<String>>>,
+    /// The family of the plant.
+    pub family: Option<String>,
+    /// The genus of the plant.
+    pub genus: Option<String>,
+    /// Which food types can be produced from this plant.
+    pub edible_uses_en: Option<String>,
+    /// The ecological and environmental functions of the plant (e.g. nitrogen fixer).
+    pub functions: Option<String>,
+    /// The material uses and functions of the plant, only for reference.
+    pub material_uses_and_functions: Option<String>,
+    /// Botanical notes on the plant, only for reference.
+    pub botanic: Option<String>,
+    /// The heat zone the plant endures.
+    pub heat_zone: Option<i16>,
+    /// The shade tolerance of the plant.
+    pub shade: Option<Shade>,
+    /// The soil PH values the plant tolerates.
+    pub soil_ph: Option<Vec<Option<SoilPh>>>,
+    /// The soil textures the plant tolerates.
+    pub soil_texture: Option<Vec<Option<SoilTexture>>>,
+    /// How well the soil the plant tolerates holds water.
+    pub soil_water_retention: Option<Vec<Option<SoilWaterRetention>>>,
+    /// The (vertical) layer the plant usually inhabits.
+    pub ecosystem_niche: Option<String>,
+    /// Whether the plant loses its leaves in winter.
+    pub deciduous_or_evergreen: Option<DeciduousOrEvergreen>,
+    /// Whether the plant grows woody parts.
+    pub herbaceous_or_woody: Option<HerbaceousOrWoody>,
+    /// Whether the plant is a tree and can be used in the tree layer.
+    pub is_tree: Option<bool>,
+    /// The life span of the plant.
+    pub life_cycle: Option<Vec<Option<LifeCycle>>>,
+    /// How fast the plant grows.
+    pub growth_rate: Option<Vec<Option<GrowthRate>>>,
+    /// How high a plant grows.
+    pub height: Option<PlantHeight>,
+    /// Whether the plant is self fertile.
+    pub fertility: Option<Vec<Option<Fertility>>>,
+    /// Whether the flowers of the plant are dioecious, monoecious or hermaphrodite.
+    pub flower_type: Option<FlowerType>,
+    /// Whether the plant tolerates drought.
+    pub has_drought_tolerance: Option<bool>,
+    /// Whether the plant tolerates wind.
+    pub tolerates_wind: Option<bool>,
+    /// The permaculture zone (-1..6) the plant fits best.
+    pub preferable_permaculture_zone: Option<i16>,
+    /// The USDA hardiness zone of the plant (e.g. "[4,9)").
+    pub hardiness_zone: Option<String>,
+    /// The light requirement of the plant.
+    pub light_requirement: Option<Vec<Option<LightRequirement>>>,
+    /// The water requirement of the plant.
+    pub water_requirement: Option<Vec<Option<WaterRequirement>>>,
+    /// How the plant can be propagated.
+    pub propagation_method: Option<Vec<Option<PropagationMethod>>>,
+    /// Alternate names of the plant.
+    pub alternate_name: Option<String>,
+    /// Whether the plant is edible.
+    pub edible: Option<bool>,
+    /// Which parts of the plant can be eaten (e.g. root, leaves).
+    pub edible_parts: Option<Vec<Option<String>>>,
+    /// How far a plant spreads (The 'width' of a plant)
+    pub spread: Option<PlantSpread>,
+    /// Warnings for human, animal or environmental well-being (e.g. toxic, invasive).
+    pub warning: Option<String>,
+    /// The half months (0-23) in which the plant can be sown outdoors.
+    pub sowing_outdoors: Option<Vec<Option<i16>>>,
+    /// The half months (0-23) in which the plant can be harvested.
+    pub harvest_time: Option<Vec<Option<i16>>>,
+    /// The weight of thousand seeds in gram.
+    pub seed_weight_1000: Option<f64>,
+    /// How many nutrients the plant needs, used for crop rotation.
+    pub nutrition_demand: Option<NutritionDemand>,
+    /// The source the plant was fetched from.
+    pub external_source: Option<ExternalSource>,
+    /// The references the information about the plant was taken from.
+    pub plant_references: Option<Vec<Option<String>>>,
+    /// When the article about the plant was last modified in the source.
+    pub article_last_modified_at: Option<NaiveDateTime>,
+    /// The version of the scraped dataset the plant was imported from.
+    pub version: Option<i16>,
+    /// When the plant was added.
+    pub created_at: NaiveDateTime,
+    /// When the plant was last modified.
+    pub updated_at: NaiveDateTime,
+    /// The user owning this custom plant or variety, `None` for plants of the shared catalogue.
+    pub owner_id: Option<Uuid>,
+    /// The plant this custom plant or variety inherited its attributes from.
+    pub base_plant_id: Option<i32>,
+    /// Whether this custom plant was submitted for review to be added to the shared catalogue.
+    pub submitted_for_review: bool,
+}
+
+/// Used to create a custom plant or variety.
+///
+/// All attributes not given are inherited from the base plant.
+#[typeshare]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NewCustomPlantDto {
+    /// The plant to inherit all attributes from.
+    pub base_plant_id: i32,
+    /// Biological name of this variety (E.g. "Solanum lycopersicum 'Black Krim'")
+    pub unique_name: String,
+    /// A list of common english names.
+    pub common_name_en: Option<Vec<Option<String>>>,
+    /// A list of common german names.
+    pub common_name_de: Option<Vec<Option<String>>>,
+    /// How high the variety grows.
+    pub height: Option<PlantHeight>,
+    /// How far the variety spreads.
+    pub spread: Option<PlantSpread>,
+    /// How fast the variety grows.
+    pub growth_rate: Option<Vec<Option<GrowthRate>>>,
+    /// The half months (0-23) in which the variety can be sown outdoors.
+    pub sowing_outdoors: Option<Vec<Option<i16>>>,
+    /// The half months (0-23) in which the variety can be harvested.
+    pub harvest_time: Option<Vec<Option<i16>>>,
+    /// Warnings for human, animal or environmental well-being.
+    pub warning: Option<String>,
+}
+
 /// Query parameters for searching plants.
+///
+/// All given parameters have to match for a plant to be returned.
 #[typeshare]
-#[derive(Debug, Deserialize, IntoParams)]
+#[derive(Debug, Default, Deserialize, IntoParams)]
 pub struct PlantsSearchParameters {
-    /// The system will check if this string occurs in the plants common name or unique name.
+    /// The system will check if this string occurs in the plants common names, alternate name or unique name.
     pub name: Option<String>,
+    /// The language of the returned common names.
+    /// If not set, the `Accept-Language` header is used.
+    #[param(inline)]
+    pub lang: Option<Language>,
+    /// Only return plants with this life cycle.
+    #[param(inline)]
+    pub life_cycle: Option<LifeCycle>,
+    /// Only return plants with this growth rate.
+    #[param(inline)]
+    pub growth_rate: Option<GrowthRate>,
+    /// Only return plants with this light requirement.
+    #[param(inline)]
+    pub light_requirement: Option<LightRequirement>,
+    /// Only return plants with this water requirement.
+    #[param(inline)]
+    pub water_requirement: Option<WaterRequirement>,
+    /// Only return plants with this fertility.
+    #[param(inline)]
+    pub fertility: Option<Fertility>,
+    /// Only return plants that are (or are not) edible.
+    pub edible: Option<bool>,
+    /// Only return plants that are hardy in this USDA hardiness zone.
+    pub hardiness_zone: Option<i32>,
+    /// Only return plants that grow at least this high.
+    #[param(inline)]
+    pub min_height: Option<PlantHeight>,
+    /// Only return plants that grow at most this high.
+    #[param(inline)]
+    pub max_height: Option<PlantHeight>,
+    /// Only return plants that spread at least this wide.
+    #[param(inline)]
+    pub min_spread: Option<PlantSpread>,
+    /// Only return plants that spread at most this wide.
+    #[param(inline)]
+    pub max_spread: Option<PlantSpread>,
+}
+
+/// A plant of a dataset imported into the plant catalogue.
+///
+/// Plants are identified by their `unique_name`.
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ImportPlantDto {
+    /// Biological name of this plant (E.g. "Triticum aestivum", "Prunus cerasus")
+    pub unique_name: String,
+    /// A list of common english names (E.g. "Bread wheat", "Sour cherry")
+    pub common_name_en: Option<Vec<Option<String>>>,
+    /// A list of common german names (E.g. "Weichweizen", "Sauerkirsche")
+    pub common_name_de: Option<Vec<Option<String>>>,
+    /// The family of the plant.
+    pub family: Option<String>,
+    /// Which food types can be produced from this plant.
+    pub edible_uses_en: Option<String>,
+    /// The ecological and environmental functions of the plant.
+    pub functions: Option<String>,
+    /// The heat zone the plant endures.
+    pub heat_zone: Option<i16>,
+    /// The shade tolerance of the plant.
+    pub shade: Option<Shade>,
+    /// The soil PH values the plant tolerates.
+    pub soil_ph: Option<Vec<Option<SoilPh>>>,
+    /// The soil textures the plant tolerates.
+    pub soil_texture: Option<Vec<Option<SoilTexture>>>,
+    /// The (vertical) layer the plant usually inhabits.
+    pub ecosystem_niche: Option<String>,
+    /// Whether the plant loses its leaves in winter.
+    pub deciduous_or_evergreen: Option<DeciduousOrEvergreen>,
+    /// Whether the plant grows woody parts.
+    pub herbaceous_or_woody: Option<HerbaceousOrWoody>,
+    /// The life span of the plant.
+    pub life_cycle: Option<Vec<Option<LifeCycle>>>,
+    /// How fast the plant grows.
+    pub growth_rate: Option<Vec<Option<GrowthRate>>>,
+    /// How high a plant grows.
+    pub height: Option<PlantHeight>,
+    /// Whether the plant is self fertile.
+    pub fertility: Option<Vec<Option<Fertility>>>,
+    /// Whether the plant tolerates drought.
+    pub has_drought_tolerance: Option<bool>,
+    /// Whether the plant tolerates wind.
+    pub tolerates_wind: Option<bool>,
+    /// The permaculture zone (-1..6) the plant fits best.
+    pub preferable_permaculture_zone: Option<i16>,
+    /// The USDA hardiness zone of the plant (e.g. "[4,9)").
+    pub hardiness_zone: Option<String>,
+    /// The light requirement of the plant.
+    pub light_requirement: Option<Vec<Option<LightRequirement>>>,
+    /// The water requirement of the plant.
+    pub water_requirement: Option<Vec<Option<WaterRequirement>>>,
+    /// How the plant can be propagated.
+    pub propagation_method: Option<Vec<Option<PropagationMethod>>>,
+    /// Alternate names of the plant.
+    pub alternate_name: Option<String>,
+    /// Whether the plant is edible.
+    pub edible: Option<bool>,
+    /// Which parts of the plant can be eaten.
+    pub edible_parts: Option<Vec<Option<String>>>,
+    /// How far a plant spreads (The 'width' of a plant)
+    pub spread: Option<PlantSpread>,
+    /// Warnings for human, animal or environmental well-being.
+    pub warning: Option<String>,
+    /// The half months (0-23) in which the plant can be sown outdoors.
+    pub sowing_outdoors: Option<Vec<Option<i16>>>,
+    /// The half months (0-23) in which the plant can be harvested.
+    pub harvest_time: Option<Vec<Option<i16>>>,
+    /// The weight of thousand seeds in gram.
+    pub seed_weight_1000: Option<f64>,
+    /// How many nutrients the plant needs.
+    pub nutrition_demand: Option<NutritionDemand>,
+}
+
+/// A relation between two plants of a dataset imported into the plant catalogue.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportRelationDto {
+    /// The unique name of the first plant.
+    pub plant1: String,
+    /// The unique name of the second plant.
+    pub plant2: String,
+    /// The type of the relation.
+    pub relation: RelationType,
+    /// An optional note about the relation.
+    pub note: Option<String>,
+}
+
+/// A dataset of plants and their relations imported into the plant catalogue.
+#[typeshare]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct PlantsDatasetDto {
+    /// All plants of the dataset.
+    pub plants: Vec<ImportPlantDto>,
+    /// All relations between plants of the dataset.
+    #[serde(default)]
+    pub relations: Vec<ImportRelationDto>,
+}
+
+/// The format of an imported dataset.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DatasetFormat {
+    /// A [`PlantsDatasetDto`] as JSON.
+    Json,
+    /// A CSV file containing one plant per row, using the field names of [`ImportPlantDto`] as header.
+    /// Lists are separated by commas.
+    Csv,
+}
+
+/// Query parameters for importing a plant dataset.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PlantsImportParameters {
+    /// The source of the dataset.
+    /// Only plants of this source are updated or reported as removed.
+    #[param(inline)]
+    pub external_source: ExternalSource,
+    /// The format of the dataset.
+    #[param(inline)]
+    pub format: DatasetFormat,
+}
+
+/// The changes to the plant catalogue caused by an import.
+#[typeshare]
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct PlantsImportReportDto {
+    /// Unique names of the plants added to the catalogue.
+    pub added: Vec<String>,
+    /// Unique names of the plants whose attributes changed.
+    pub changed: Vec<String>,
+    /// Unique names of the plants of the source that are no longer part of the dataset.
+    /// They are not deleted, as they might still be used in plantings or seeds.
+    pub removed: Vec<String>,
+    /// Unique names of the plants that were skipped as they belong to another source or user.
+    pub skipped: Vec<String>,
+    /// The number of imported relations.
+    pub relations: i32,
+}
+
+/// Language used for the names of plants.
+#[typeshare]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// English
+    #[default]
+    En,
+    /// German
+    De,
+}
+
+/// The number of records having a certain value.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct FacetValueDto {
+    /// The value as it is used in the search parameters.
+    pub value: String,
+    /// The number of records having this value.
+    pub count: i32,
+}
+
+/// The number of records per value of an attribute.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct FacetDto {
+    /// The name of the attribute as it is used in the search parameters.
+    pub attribute: String,
+    /// All values of the attribute that occur in the records.
+    pub values: Vec<FacetValueDto>,
 }
 
 /// Query parameters for searching plant relations.
@@ -180,7 +580,9 @@ pub struct PageParameters {
     PagePlantsSummaryDto = Page<PlantsSummaryDto>,
     PageSeedDto = Page<SeedDto>,
     PageMapDto = Page<MapDto>,
-    PageLayerDto = Page<LayerDto>
+    PageLayerDto = Page<LayerDto>,
+    PageDiversitySuggestionDto = Page<DiversitySuggestionDto>,
+    PageCompanionSuggestionDto = Page<CompanionSuggestionDto>
 )]
 pub struct Page<T> {
     /// Resulting records.
@@ -193,6 +595,25 @@ pub struct Page<T> {
     pub total_pages: i32,
 }
 
+/// A page of results together with facets of all records matching the search.
+#[typeshare]
+#[derive(Debug, Serialize, PartialEq, Eq, Deserialize, ToSchema)]
+#[aliases(
+    FacetedPagePlantsSummaryDto = FacetedPage<PlantsSummaryDto>,
+)]
+pub struct FacetedPage<T> {
+    /// Resulting records.
+    pub results: Vec<T>,
+    /// Current page number.
+    pub page: i32,
+    /// Results per page.
+    pub per_page: i32,
+    /// Number of pages in total.
+    pub total_pages: i32,
+    /// The number of matching records per attribute value.
+    pub facets: Vec<FacetDto>,
+}
+
 /// A page of results bounded by time.
 #[typeshare]
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -282,6 +703,12 @@ pub struct NewMapDto {
     #[typeshare(serialized_as = "object")]
     #[schema(value_type = Object)]
     pub geometry: Polygon<Point>,
+    /// The types of the layers the map is initialized with, one layer per type.
+    /// If None, a base and a plants layer are created.
+    pub layer_types: Option<Vec<LayerType>>,
+    /// A map the layers are cloned from instead of initializing them by type.
+    /// The map has to be owned by the user or public.
+    pub template_map_id: Option<i32>,
 }
 
 /// The information for updating a map.
@@ -330,7 +757,7 @@ pub struct Coordinates {
 
 /// The whole information of a map version.
 #[typeshare]
-#[derive(Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LayerDto {
     /// The id of the layer.
     pub id: i32,
@@ -342,6 +769,14 @@ pub struct LayerDto {
     pub name: String,
     /// A flag indicating if this layer is an user created alternative.
     pub is_alternative: bool,
+    /// The position of the layer in the layer stack of the map, higher layers are drawn on top.
+    pub order_index: i32,
+    /// Whether the layer is shown on the map.
+    pub is_visible: bool,
+    /// The opacity of the layer from 0 (transparent) to 1 (opaque).
+    pub opacity: f32,
+    /// The date the layer was deleted, it can be restored for 30 days.
+    pub deletion_date: Option<NaiveDate>,
 }
 
 /// The information of a layer neccessary for its creation.
@@ -358,6 +793,100 @@ pub struct NewLayerDto {
     pub is_alternative: bool,
 }
 
+/// Used to change the name, position, visibility or opacity of a layer.
+/// Fields that are None are not changed.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateLayerDto {
+    /// The name of the layer.
+    pub name: Option<String>,
+    /// The position of the layer in the layer stack of the map, higher layers are drawn on top.
+    pub order_index: Option<i32>,
+    /// Whether the layer is shown on the map.
+    pub is_visible: Option<bool>,
+    /// The opacity of the layer from 0 (transparent) to 1 (opaque).
+    pub opacity: Option<f32>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to rewrite the order of all layers of a map.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LayerOrderDto {
+    /// The ids of all layers of the map from the bottom to the top.
+    pub layer_ids: Vec<i32>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to fork a plant layer into an alternative with copies of all its plantings.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NewAlternativeLayerDto {
+    /// The name of the alternative layer.
+    pub name: String,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to delete a layer.
+/// The id of the layer is passed in the path.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+pub struct DeleteLayerDto {
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to restore a deleted layer.
+/// The id of the layer is passed in the path.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+pub struct RestoreLayerDto {
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to promote an alternative layer to be the main layer of its type.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+pub struct PromoteLayerDto {
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Query parameters for comparing two plant layers.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct LayerComparisonParameters {
+    /// The layer that is compared to the layer in the path, e.g. an alternative of it.
+    pub other_layer_id: i32,
+}
+
+/// The differences between the plantings of two plant layers.
+/// Plantings are matched by the planting they were originally copied from.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LayerDiffDto {
+    /// Plantings that only exist on the other layer.
+    pub added: Vec<PlantingDto>,
+    /// Plantings that only exist on the compared layer.
+    pub removed: Vec<PlantingDto>,
+    /// Plantings that exist on both layers but at different positions.
+    pub moved: Vec<MovedPlantingDto>,
+}
+
+/// A planting that is at different positions on two plant layers.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MovedPlantingDto {
+    /// The planting on the compared layer.
+    pub from: PlantingDto,
+    /// The planting on the other layer.
+    pub to: PlantingDto,
+}
+
 /// Query parameters for searching layers.
 #[typeshare]
 #[derive(Debug, Deserialize, IntoParams)]
@@ -368,6 +897,8 @@ pub struct LayerSearchParameters {
     pub type_: Option<LayerType>,
     /// Whether or not the layer is an alternative.
     pub is_alternative: Option<bool>,
+    /// Whether to search deleted layers that can still be restored instead of active ones.
+    pub is_deleted: Option<bool>,
 }
 
 /// Query parameters for connecting to a map.
@@ -390,6 +921,9 @@ pub struct PlantSuggestionsSearchParameters {
     /// Date representing the season to search for.
     /// Only the month and day are used, nevertheless it must be an existing date.
     pub relative_to_date: NaiveDate,
+    /// The plant layer whose plantings are analysed for diversity and companion suggestions.
+    /// If not set, all plant layers of the map are analysed.
+    pub layer_id: Option<i32>,
 }
 
 /// Kind of suggestion.
@@ -401,6 +935,64 @@ pub enum SuggestionType {
     Available,
     /// Suggests plants based on diversity criteria.
     Diversity,
+    /// Suggests plants based on their companions and antagonists among the plantings.
+    Companion,
+}
+
+/// A plant suggested because of its relations to the plantings on a layer.
+#[typeshare]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CompanionSuggestionDto {
+    /// The suggested plant.
+    pub plant: PlantsSummaryDto,
+    /// The number of companion plantings minus the number of antagonist plantings.
+    pub score: i32,
+    /// The plantings the plant works well with.
+    pub companion_plantings: Vec<Uuid>,
+    /// The plantings the plant doesn't work well with.
+    pub antagonist_plantings: Vec<Uuid>,
+}
+
+/// Criteria used to measure the diversity of the plantings on a layer.
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DiversityCriterion {
+    /// The family of the plants.
+    Family,
+    /// The ecological functions of the plants, e.g. nitrogen fixer.
+    Function,
+    /// The vertical layer the plants inhabit, e.g. canopy.
+    EcosystemNiche,
+    /// Whether the plants are herbaceous or woody.
+    HerbaceousOrWoody,
+}
+
+/// A gap in the diversity of the plantings on a layer that a plant fills.
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DiversityGapDto {
+    /// The criterion the gap belongs to.
+    pub criterion: DiversityCriterion,
+    /// The value of the plant for this criterion.
+    pub value: String,
+    /// True if no planting on the layer has this value,
+    /// false if the value is under-represented.
+    pub missing: bool,
+}
+
+/// A plant suggested to increase the diversity of the plantings on a layer.
+#[typeshare]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DiversitySuggestionDto {
+    /// The suggested plant.
+    pub plant: PlantsSummaryDto,
+    /// How much the plant increases the diversity, higher is better.
+    pub score: i32,
+    /// The gaps filled by the plant.
+    pub gaps: Vec<DiversityGapDto>,
+    /// A short explanation of the gaps filled by the plant.
+    pub explanation: String,
 }
 
 /// Contains information about an image displayed on the base layer.
@@ -455,6 +1047,9 @@ pub struct HeatMapQueryParams {
     pub layer_id: i32,
     /// The id of the plant you want to plant.
     pub plant_id: i32,
+    /// The day the light conditions and windbreaks of the map are computed for.
+    /// Defaults to today.
+    pub date: Option<NaiveDate>,
 }
 
 #[typeshare]
@@ -512,3 +1107,380 @@ pub struct GainedBlossomsDto {
     /// The date on which the user gained this Blossom.
     pub gained_date: NaiveDate,
 }
+
+/// The format of a map calendar.
+#[typeshare]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CalendarFormat {
+    /// A [`CalendarDto`] as JSON.
+    #[default]
+    Json,
+    /// An iCalendar (RFC 5545) file that can be imported into calendar applications.
+    Ics,
+}
+
+/// Query parameters for the calendar of a map.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct CalendarParameters {
+    /// The year the calendar is created for.
+    pub year: i32,
+    /// The format of the calendar.
+    /// Defaults to JSON.
+    #[param(inline)]
+    pub format: Option<CalendarFormat>,
+}
+
+/// A planting of a map as shown in its calendar.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CalendarPlantingDto {
+    /// The id of the planting.
+    pub planting_id: Uuid,
+    /// The id of the planted plant.
+    pub plant_id: i32,
+    /// The unique name of the planted plant.
+    pub unique_name: String,
+    /// The English common names of the planted plant.
+    pub common_name_en: Option<Vec<Option<String>>>,
+    /// The date the planting is added to the map.
+    pub add_date: Option<NaiveDate>,
+    /// The date the planting is removed from the map.
+    pub remove_date: Option<NaiveDate>,
+}
+
+/// The work to be done on a map in one half of a month.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CalendarEntryDto {
+    /// The half month, a number between 0 (first half of January) and 23 (second half of December).
+    pub half_month: i32,
+    /// The first day of the half month.
+    pub start: NaiveDate,
+    /// The last day of the half month.
+    pub end: NaiveDate,
+    /// Plantings whose plant can be sown outdoors.
+    pub sow: Vec<CalendarPlantingDto>,
+    /// Plantings whose plant can be harvested.
+    pub harvest: Vec<CalendarPlantingDto>,
+    /// Plantings scheduled to be added to the map.
+    pub add: Vec<CalendarPlantingDto>,
+    /// Plantings scheduled to be removed from the map.
+    pub remove: Vec<CalendarPlantingDto>,
+}
+
+/// The sowing and harvest calendar of a map for one year.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CalendarDto {
+    /// The id of the map.
+    pub map_id: i32,
+    /// The year of the calendar.
+    pub year: i32,
+    /// One entry for each half of a month, starting with the first half of January.
+    pub entries: Vec<CalendarEntryDto>,
+}
+
+/// Query parameters for checking a new or moved planting for crop rotation conflicts.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RotationCheckParameters {
+    /// The plant layer the planting is placed on.
+    pub layer_id: i32,
+    /// The plant that is planted.
+    pub plant_id: i32,
+    /// The x coordinate of the position on the map.
+    pub x: i32,
+    /// The y coordinate of the position on the map.
+    pub y: i32,
+    /// The width of the plant on the map.
+    pub width: i32,
+    /// The height of the plant on the map.
+    pub height: i32,
+    /// The x scale of the plant on the map, defaults to 1.
+    pub scale_x: Option<f32>,
+    /// The y scale of the plant on the map, defaults to 1.
+    pub scale_y: Option<f32>,
+    /// The date the planting is added to the map.
+    pub add_date: NaiveDate,
+    /// The id of the planting if an existing planting is moved.
+    /// It is not reported as conflicting with itself.
+    pub planting_id: Option<Uuid>,
+}
+
+/// The kind of a crop rotation conflict.
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationConflictKind {
+    /// A plant of the same family grew at the same spot too recently.
+    SameFamily,
+    /// A heavy feeder grew at the same spot in the previous season.
+    HeavyFeeder,
+}
+
+/// An earlier planting at the same spot violating the crop rotation.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RotationConflictDto {
+    /// The kind of the conflict.
+    pub kind: RotationConflictKind,
+    /// The id of the earlier planting.
+    pub planting_id: Uuid,
+    /// The id of the plant of the earlier planting.
+    pub plant_id: i32,
+    /// The unique name of the plant of the earlier planting.
+    pub unique_name: String,
+    /// The date the earlier planting was added to the map.
+    pub add_date: Option<NaiveDate>,
+    /// The date the earlier planting was removed from the map, `None` if it is still there.
+    pub remove_date: Option<NaiveDate>,
+    /// A human readable description of the conflict.
+    pub message: String,
+}
+
+/// Query parameters for the suggested crop rotation of a bed.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RotationScheduleParameters {
+    /// The plant layer the bed is on.
+    pub layer_id: i32,
+    /// The lowest x coordinate of the bed.
+    pub x_min: i32,
+    /// The lowest y coordinate of the bed.
+    pub y_min: i32,
+    /// The highest x coordinate of the bed.
+    pub x_max: i32,
+    /// The highest y coordinate of the bed.
+    pub y_max: i32,
+    /// The first year of the schedule.
+    pub start_year: i32,
+    /// The number of years to schedule, defaults to 4.
+    pub years: Option<i32>,
+}
+
+/// The groups of a crop rotation, in the order they follow each other.
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationGroup {
+    /// Plants with a high nutrition demand, e.g. tomatoes or cabbage.
+    HeavyFeeder,
+    /// Plants with a moderate nutrition demand, e.g. carrots or lettuce.
+    ModerateFeeder,
+    /// Plants with a low nutrition demand, e.g. herbs or onions.
+    LightFeeder,
+    /// Legumes and green manure restoring the soil, e.g. beans or clover.
+    SoilImprover,
+}
+
+/// The plantings of a bed in one year.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RotationHistoryDto {
+    /// The year.
+    pub year: i32,
+    /// The families grown in the bed.
+    pub families: Vec<String>,
+    /// The rotation groups grown in the bed.
+    pub groups: Vec<RotationGroup>,
+}
+
+/// The suggested rotation group of a bed in one year.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RotationYearDto {
+    /// The year.
+    pub year: i32,
+    /// The rotation group that should be grown in the bed.
+    pub group: RotationGroup,
+    /// Families grown in the bed too recently to be grown again.
+    pub avoid_families: Vec<String>,
+}
+
+/// A suggested crop rotation schedule of a bed.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RotationScheduleDto {
+    /// The plantings of the bed in the years before the schedule.
+    pub history: Vec<RotationHistoryDto>,
+    /// The suggested rotation group for each scheduled year.
+    pub schedule: Vec<RotationYearDto>,
+}
+
+/// A harvest of a planting.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct HarvestDto {
+    /// The id of the harvest.
+    pub id: i32,
+    /// The planting that was harvested.
+    pub planting_id: Uuid,
+    /// The date of the harvest.
+    pub harvest_date: NaiveDate,
+    /// The harvested quantity in `unit`.
+    pub quantity: f32,
+    /// The unit of the quantity.
+    pub unit: HarvestUnit,
+    /// How good the harvest was.
+    pub quality: Option<HarvestQuality>,
+    /// Notes about the harvest.
+    pub notes: Option<String>,
+    /// The id of the user who logged the harvest.
+    pub created_by: Uuid,
+}
+
+/// Used to log a new harvest of a planting.
+/// The planting is passed in the path.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NewHarvestDto {
+    /// The date of the harvest.
+    pub harvest_date: NaiveDate,
+    /// The harvested quantity in `unit`, must be positive.
+    pub quantity: f32,
+    /// The unit of the quantity.
+    pub unit: HarvestUnit,
+    /// How good the harvest was.
+    pub quality: Option<HarvestQuality>,
+    /// Notes about the harvest.
+    pub notes: Option<String>,
+}
+
+/// What harvests are aggregated by.
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HarvestGrouping {
+    /// One summary per plant.
+    Plant,
+    /// One summary per layer, including alternative layers.
+    Layer,
+    /// One summary for the whole map.
+    Map,
+    /// One summary per year.
+    Season,
+}
+
+/// Query parameters for aggregating the harvests of a map.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct HarvestSummaryParameters {
+    /// What the harvests are aggregated by.
+    pub group_by: HarvestGrouping,
+    /// Only aggregate harvests of plantings on this layer.
+    /// If not set, harvests on alternative layers are only used when grouping by layer.
+    pub layer_id: Option<i32>,
+    /// Only aggregate harvests of this plant.
+    pub plant_id: Option<i32>,
+    /// Only aggregate harvests of this year.
+    pub season: Option<i32>,
+}
+
+/// The harvests of a group in one unit.
+/// Harvests in different units are summarized separately.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct HarvestSummaryDto {
+    /// The plant, if grouped by plant.
+    pub plant_id: Option<i32>,
+    /// The unique name of the plant, if grouped by plant.
+    pub unique_name: Option<String>,
+    /// The layer, if grouped by layer.
+    pub layer_id: Option<i32>,
+    /// The year, if grouped by season.
+    pub season: Option<i32>,
+    /// The unit of the total quantity.
+    pub unit: HarvestUnit,
+    /// The total harvested quantity.
+    pub total_quantity: f64,
+    /// The number of harvests.
+    pub harvests: i32,
+    /// The number of harvested plantings.
+    pub plantings: i32,
+}
+
+/// How spacing problems of a new or moved planting are handled.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SpacingMode {
+    /// Spacing problems are only reported.
+    #[default]
+    Advisory,
+    /// Plantings with spacing problems are rejected.
+    Strict,
+}
+
+/// Query parameters for creating or moving plantings.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SpacingParameters {
+    /// How spacing problems are handled, defaults to advisory.
+    /// In strict mode plantings that are too close to their neighbours or outside the map are rejected.
+    pub spacing: Option<SpacingMode>,
+}
+
+/// Query parameters for checking the spacing of a new or moved planting.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SpacingCheckParameters {
+    /// The plant layer the planting is placed on.
+    pub layer_id: i32,
+    /// The plant that is planted.
+    pub plant_id: i32,
+    /// The x coordinate of the position on the map.
+    pub x: i32,
+    /// The y coordinate of the position on the map.
+    pub y: i32,
+    /// The width of the plant on the map.
+    pub width: i32,
+    /// The height of the plant on the map.
+    pub height: i32,
+    /// The x scale of the plant on the map, defaults to 1.
+    pub scale_x: Option<f32>,
+    /// The y scale of the plant on the map, defaults to 1.
+    pub scale_y: Option<f32>,
+    /// Neighbours existing at this date are checked.
+    pub date: NaiveDate,
+    /// The id of the planting if an existing planting is moved.
+    /// It is not checked against itself.
+    pub planting_id: Option<Uuid>,
+    /// How spacing problems are handled, defaults to advisory.
+    /// In strict mode the check fails if there are any warnings.
+    pub mode: Option<SpacingMode>,
+}
+
+/// The kind of a spacing problem.
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SpacingWarningKind {
+    /// The planting is closer to a neighbour than half of their mature spacing.
+    Crowding,
+    /// The mature canopies of the planting and a neighbour overlap.
+    CanopyOverlap,
+    /// The planting is outside of the map.
+    OutsideMap,
+}
+
+/// A spacing problem of a new or moved planting.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SpacingWarningDto {
+    /// The kind of the problem.
+    pub kind: SpacingWarningKind,
+    /// The neighbouring planting, unless the planting is outside of the map.
+    pub planting_id: Option<Uuid>,
+    /// The plant of the neighbouring planting.
+    pub plant_id: Option<i32>,
+    /// The unique name of the plant of the neighbouring planting.
+    pub unique_name: Option<String>,
+    /// The distance between the planting and its neighbour.
+    pub distance: Option<f64>,
+    /// The distance at which the mature canopies just touch.
+    pub required_distance: Option<f64>,
+    /// A human readable description of the problem.
+    pub message: String,
+}
@@ -8,12 +8,20 @@
 #![allow(clippy::missing_const_for_fn)]
 
 use crate::model::dto::plantings::PlantingDto;
+use crate::model::r#enum::planting_status::PlantingStatus;
 use chrono::NaiveDate;
 use serde::Serialize;
 use typeshare::typeshare;
 use uuid::Uuid;
 
-use super::BaseLayerImageDto;
+use super::drawings::{DrawingDto, ShapeDto, ShapeStyleDto};
+use super::guilds::GuildDto;
+use super::photos::PhotoDto;
+use super::shade::ShadeObstacleDto;
+use super::soil_samples::SoilSampleDto;
+use super::todos::TodoDto;
+use super::winds::WindRoseDto;
+use super::{BaseLayerImageDto, LayerDto};
 
 #[typeshare]
 #[derive(Debug, Serialize, Clone)]
@@ -39,6 +47,64 @@ pub enum Action {
     UpdatePlantingAddDate(UpdatePlantingAddDateActionPayload),
     /// An action used to update the `remove_date` of a plant.
     UpdatePlantingRemoveDate(UpdatePlantingRemoveDateActionPayload),
+    /// An action used to update the notes of a plant.
+    UpdatePlantingNotes(UpdatePlantingNotesActionPayload),
+    /// An action used to update the status of a plant.
+    UpdatePlantingStatus(UpdatePlantingStatusActionPayload),
+    /// An action used to update the seed of a plant.
+    UpdatePlantingSeed(UpdatePlantingSeedActionPayload),
+    /// An action used to update the photos of a plant.
+    UpdatePlantingPhotos(UpdatePlantingPhotosActionPayload),
+    /// An action used to broadcast creation or duplication of a guild with its plantings.
+    CreateGuild(CreateGuildActionPayload),
+    /// An action used to broadcast movement or rotation of all plantings of a guild.
+    TransformGuild(TransformGuildActionPayload),
+    /// An action used to broadcast ungrouping of a guild.
+    DeleteGuild(DeleteGuildActionPayload),
+    /// An action used to broadcast renaming or a change of visibility or opacity of a layer.
+    UpdateLayer(UpdateLayerActionPayload),
+    /// An action used to broadcast the new order of all layers of a map.
+    ReorderLayers(ReorderLayersActionPayload),
+    /// An action used to broadcast forking of a plant layer into an alternative.
+    ForkLayer(ForkLayerActionPayload),
+    /// An action used to broadcast promotion of an alternative to be the main layer.
+    PromoteLayer(PromoteLayerActionPayload),
+    /// An action used to broadcast deletion of a layer.
+    DeleteLayer(DeleteLayerActionPayload),
+    /// An action used to broadcast restoring of a deleted layer.
+    RestoreLayer(RestoreLayerActionPayload),
+    /// An action used to broadcast creation of a vector shape.
+    CreateDrawing(CreateDrawingActionPayload),
+    /// An action used to broadcast a change of the geometry or appearance of a vector shape.
+    UpdateDrawing(UpdateDrawingActionPayload),
+    /// An action used to broadcast deletion of a vector shape.
+    DeleteDrawing(DeleteDrawingActionPayload),
+    /// An action used to broadcast creation of a soil sample.
+    CreateSoilSample(CreateSoilSampleActionPayload),
+    /// An action used to broadcast a change of the position or measurements of a soil sample.
+    UpdateSoilSample(UpdateSoilSampleActionPayload),
+    /// An action used to broadcast deletion of a soil sample.
+    DeleteSoilSample(DeleteSoilSampleActionPayload),
+    /// An action used to broadcast creation of a shade obstacle.
+    CreateShadeObstacle(CreateShadeObstacleActionPayload),
+    /// An action used to broadcast a change of the footprint, height or name of a shade obstacle.
+    UpdateShadeObstacle(UpdateShadeObstacleActionPayload),
+    /// An action used to broadcast deletion of a shade obstacle.
+    DeleteShadeObstacle(DeleteShadeObstacleActionPayload),
+    /// An action used to broadcast creation of a todo.
+    CreateTodo(CreateTodoActionPayload),
+    /// An action used to broadcast a change of a todo, including its completion.
+    UpdateTodo(UpdateTodoActionPayload),
+    /// An action used to broadcast deletion of a todo.
+    DeleteTodo(DeleteTodoActionPayload),
+    /// An action used to broadcast pinning of a photo.
+    CreatePhoto(CreatePhotoActionPayload),
+    /// An action used to broadcast a change of the position or details of a photo.
+    UpdatePhoto(UpdatePhotoActionPayload),
+    /// An action used to broadcast deletion of a photo.
+    DeletePhoto(DeletePhotoActionPayload),
+    /// An action used to broadcast a change of the wind rose of a winds layer.
+    UpdateWindRose(UpdateWindRoseActionPayload),
 }
 
 impl Action {
@@ -55,6 +121,35 @@ impl Action {
             Self::DeleteBaseLayerImage(payload) => payload.action_id,
             Self::UpdatePlantingAddDate(payload) => payload.action_id,
             Self::UpdatePlantingRemoveDate(payload) => payload.action_id,
+            Self::UpdatePlantingNotes(payload) => payload.action_id,
+            Self::UpdatePlantingStatus(payload) => payload.action_id,
+            Self::UpdatePlantingSeed(payload) => payload.action_id,
+            Self::UpdatePlantingPhotos(payload) => payload.action_id,
+            Self::CreateGuild(payload) => payload.action_id,
+            Self::TransformGuild(payload) => payload.action_id,
+            Self::DeleteGuild(payload) => payload.action_id,
+            Self::UpdateLayer(payload) => payload.action_id,
+            Self::ReorderLayers(payload) => payload.action_id,
+            Self::ForkLayer(payload) => payload.action_id,
+            Self::PromoteLayer(payload) => payload.action_id,
+            Self::DeleteLayer(payload) => payload.action_id,
+            Self::RestoreLayer(payload) => payload.action_id,
+            Self::CreateDrawing(payload) => payload.action_id,
+            Self::UpdateDrawing(payload) => payload.action_id,
+            Self::DeleteDrawing(payload) => payload.action_id,
+            Self::CreateSoilSample(payload) => payload.action_id,
+            Self::UpdateSoilSample(payload) => payload.action_id,
+            Self::DeleteSoilSample(payload) => payload.action_id,
+            Self::CreateShadeObstacle(payload) => payload.action_id,
+            Self::UpdateShadeObstacle(payload) => payload.action_id,
+            Self::DeleteShadeObstacle(payload) => payload.action_id,
+            Self::CreateTodo(payload) => payload.action_id,
+            Self::UpdateTodo(payload) => payload.action_id,
+            Self::DeleteTodo(payload) => payload.action_id,
+            Self::CreatePhoto(payload) => payload.action_id,
+            Self::UpdatePhoto(payload) => payload.action_id,
+            Self::DeletePhoto(payload) => payload.action_id,
+            Self::UpdateWindRose(payload) => payload.action_id,
         }
     }
 }
@@ -83,7 +178,7 @@ pub struct CreatePlantActionPayload {
 
 impl CreatePlantActionPayload {
     #[must_use]
-    pub fn new(payload: PlantingDto, user_id: Uuid, action_id: Uuid) -> Self {
+    pub fn new(payload: &PlantingDto, user_id: Uuid, action_id: Uuid) -> Self {
         Self {
             user_id,
             action_id,
@@ -138,7 +233,7 @@ pub struct MovePlantActionPayload {
 
 impl MovePlantActionPayload {
     #[must_use]
-    pub fn new(payload: PlantingDto, user_id: Uuid, action_id: Uuid) -> Self {
+    pub fn new(payload: &PlantingDto, user_id: Uuid, action_id: Uuid) -> Self {
         Self {
             user_id,
             action_id,
@@ -166,7 +261,7 @@ pub struct TransformPlantActionPayload {
 
 impl TransformPlantActionPayload {
     #[must_use]
-    pub fn new(payload: PlantingDto, user_id: Uuid, action_id: Uuid) -> Self {
+    pub fn new(payload: &PlantingDto, user_id: Uuid, action_id: Uuid) -> Self {
         Self {
             user_id,
             action_id,
@@ -273,7 +368,7 @@ pub struct UpdatePlantingAddDateActionPayload {
 
 impl UpdatePlantingAddDateActionPayload {
     #[must_use]
-    pub fn new(payload: PlantingDto, user_id: Uuid, action_id: Uuid) -> Self {
+    pub fn new(payload: &PlantingDto, user_id: Uuid, action_id: Uuid) -> Self {
         Self {
             user_id,
             action_id,
@@ -296,7 +391,7 @@ pub struct UpdatePlantingRemoveDateActionPayload {
 
 impl UpdatePlantingRemoveDateActionPayload {
     #[must_use]
-    pub fn new(payload: PlantingDto, user_id: Uuid, action_id: Uuid) -> Self {
+    pub fn new(payload: &PlantingDto, user_id: Uuid, action_id: Uuid) -> Self {
         Self {
             user_id,
             action_id,
@@ -305,3 +400,661 @@ impl UpdatePlantingRemoveDateActionPayload {
         }
     }
 }
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::UpdatePlantingNotes`].
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePlantingNotesActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: Uuid,
+    notes: Option<String>,
+}
+
+impl UpdatePlantingNotesActionPayload {
+    #[must_use]
+    pub fn new(payload: &PlantingDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id: payload.id,
+            notes: payload.notes.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::UpdatePlantingStatus`].
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePlantingStatusActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: Uuid,
+    status: PlantingStatus,
+}
+
+impl UpdatePlantingStatusActionPayload {
+    #[must_use]
+    pub fn new(payload: &PlantingDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id: payload.id,
+            status: payload.status,
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::UpdatePlantingSeed`].
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePlantingSeedActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: Uuid,
+    seed_id: Option<i32>,
+}
+
+impl UpdatePlantingSeedActionPayload {
+    #[must_use]
+    pub fn new(payload: &PlantingDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id: payload.id,
+            seed_id: payload.seed_id,
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::UpdatePlantingPhotos`].
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePlantingPhotosActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: Uuid,
+    photos: Vec<String>,
+}
+
+impl UpdatePlantingPhotosActionPayload {
+    #[must_use]
+    pub fn new(payload: &PlantingDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id: payload.id,
+            photos: payload.photos.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::CreateGuild`].
+#[serde(rename_all = "camelCase")]
+pub struct CreateGuildActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: Uuid,
+    layer_id: i32,
+    name: String,
+    plantings: Vec<PlantingDto>,
+}
+
+impl CreateGuildActionPayload {
+    #[must_use]
+    pub fn new(payload: &GuildDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id: payload.id,
+            layer_id: payload.layer_id,
+            name: payload.name.clone(),
+            plantings: payload.plantings.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::TransformGuild`].
+#[serde(rename_all = "camelCase")]
+pub struct TransformGuildActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: Uuid,
+    layer_id: i32,
+    name: String,
+    plantings: Vec<PlantingDto>,
+}
+
+impl TransformGuildActionPayload {
+    #[must_use]
+    pub fn new(payload: &GuildDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id: payload.id,
+            layer_id: payload.layer_id,
+            name: payload.name.clone(),
+            plantings: payload.plantings.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::DeleteGuild`].
+#[serde(rename_all = "camelCase")]
+pub struct DeleteGuildActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: Uuid,
+}
+
+impl DeleteGuildActionPayload {
+    #[must_use]
+    pub fn new(id: Uuid, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id,
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::UpdateLayer`].
+#[serde(rename_all = "camelCase")]
+pub struct UpdateLayerActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: i32,
+    name: String,
+    order_index: i32,
+    is_visible: bool,
+    opacity: f32,
+}
+
+impl UpdateLayerActionPayload {
+    #[must_use]
+    pub fn new(payload: &LayerDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id: payload.id,
+            name: payload.name.clone(),
+            order_index: payload.order_index,
+            is_visible: payload.is_visible,
+            opacity: payload.opacity,
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::ReorderLayers`].
+#[serde(rename_all = "camelCase")]
+pub struct ReorderLayersActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    /// The ids of all layers of the map from the bottom to the top.
+    layer_ids: Vec<i32>,
+}
+
+impl ReorderLayersActionPayload {
+    #[must_use]
+    pub fn new(payload: &[LayerDto], user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            layer_ids: payload.iter().map(|layer| layer.id).collect(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::ForkLayer`].
+#[serde(rename_all = "camelCase")]
+pub struct ForkLayerActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    /// The layer that was forked.
+    source_id: i32,
+    layer: LayerDto,
+}
+
+impl ForkLayerActionPayload {
+    #[must_use]
+    pub fn new(source_id: i32, payload: &LayerDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            source_id,
+            layer: payload.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::PromoteLayer`].
+#[serde(rename_all = "camelCase")]
+pub struct PromoteLayerActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: i32,
+    /// All layers of the map after the promotion.
+    layers: Vec<LayerDto>,
+}
+
+impl PromoteLayerActionPayload {
+    #[must_use]
+    pub fn new(id: i32, payload: &[LayerDto], user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id,
+            layers: payload.to_vec(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::DeleteLayer`].
+#[serde(rename_all = "camelCase")]
+pub struct DeleteLayerActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: i32,
+}
+
+impl DeleteLayerActionPayload {
+    #[must_use]
+    pub fn new(id: i32, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id,
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::RestoreLayer`].
+#[serde(rename_all = "camelCase")]
+pub struct RestoreLayerActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    layer: LayerDto,
+}
+
+impl RestoreLayerActionPayload {
+    #[must_use]
+    pub fn new(payload: &LayerDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            layer: payload.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::CreateDrawing`].
+/// This struct should always match [`DrawingDto`].
+#[serde(rename_all = "camelCase")]
+pub struct CreateDrawingActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: Uuid,
+    layer_id: i32,
+    shape: ShapeDto,
+    style: ShapeStyleDto,
+}
+
+impl CreateDrawingActionPayload {
+    #[must_use]
+    pub fn new(payload: &DrawingDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id: payload.id,
+            layer_id: payload.layer_id,
+            shape: payload.shape.clone(),
+            style: payload.style.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::UpdateDrawing`].
+/// This struct should always match [`DrawingDto`].
+#[serde(rename_all = "camelCase")]
+pub struct UpdateDrawingActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: Uuid,
+    layer_id: i32,
+    shape: ShapeDto,
+    style: ShapeStyleDto,
+}
+
+impl UpdateDrawingActionPayload {
+    #[must_use]
+    pub fn new(payload: &DrawingDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id: payload.id,
+            layer_id: payload.layer_id,
+            shape: payload.shape.clone(),
+            style: payload.style.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::DeleteDrawing`].
+#[serde(rename_all = "camelCase")]
+pub struct DeleteDrawingActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: Uuid,
+}
+
+impl DeleteDrawingActionPayload {
+    #[must_use]
+    pub fn new(id: Uuid, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id,
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::CreateSoilSample`].
+#[serde(rename_all = "camelCase")]
+pub struct CreateSoilSampleActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    sample: SoilSampleDto,
+}
+
+impl CreateSoilSampleActionPayload {
+    #[must_use]
+    pub fn new(payload: &SoilSampleDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            sample: payload.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::UpdateSoilSample`].
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSoilSampleActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    sample: SoilSampleDto,
+}
+
+impl UpdateSoilSampleActionPayload {
+    #[must_use]
+    pub fn new(payload: &SoilSampleDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            sample: payload.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::DeleteSoilSample`].
+#[serde(rename_all = "camelCase")]
+pub struct DeleteSoilSampleActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: Uuid,
+}
+
+impl DeleteSoilSampleActionPayload {
+    #[must_use]
+    pub fn new(id: Uuid, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id,
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::CreateShadeObstacle`].
+#[serde(rename_all = "camelCase")]
+pub struct CreateShadeObstacleActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    obstacle: ShadeObstacleDto,
+}
+
+impl CreateShadeObstacleActionPayload {
+    #[must_use]
+    pub fn new(payload: &ShadeObstacleDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            obstacle: payload.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::UpdateShadeObstacle`].
+#[serde(rename_all = "camelCase")]
+pub struct UpdateShadeObstacleActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    obstacle: ShadeObstacleDto,
+}
+
+impl UpdateShadeObstacleActionPayload {
+    #[must_use]
+    pub fn new(payload: &ShadeObstacleDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            obstacle: payload.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::DeleteShadeObstacle`].
+#[serde(rename_all = "camelCase")]
+pub struct DeleteShadeObstacleActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: Uuid,
+}
+
+impl DeleteShadeObstacleActionPayload {
+    #[must_use]
+    pub fn new(id: Uuid, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id,
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::CreateTodo`].
+#[serde(rename_all = "camelCase")]
+pub struct CreateTodoActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    todo: TodoDto,
+}
+
+impl CreateTodoActionPayload {
+    #[must_use]
+    pub fn new(payload: &TodoDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            todo: payload.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::UpdateTodo`].
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTodoActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    todo: TodoDto,
+}
+
+impl UpdateTodoActionPayload {
+    #[must_use]
+    pub fn new(payload: &TodoDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            todo: payload.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::DeleteTodo`].
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTodoActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: Uuid,
+}
+
+impl DeleteTodoActionPayload {
+    #[must_use]
+    pub fn new(id: Uuid, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id,
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::CreatePhoto`].
+#[serde(rename_all = "camelCase")]
+pub struct CreatePhotoActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    photo: PhotoDto,
+}
+
+impl CreatePhotoActionPayload {
+    #[must_use]
+    pub fn new(payload: &PhotoDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            photo: payload.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::UpdatePhoto`].
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePhotoActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    photo: PhotoDto,
+}
+
+impl UpdatePhotoActionPayload {
+    #[must_use]
+    pub fn new(payload: &PhotoDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            photo: payload.clone(),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::DeletePhoto`].
+#[serde(rename_all = "camelCase")]
+pub struct DeletePhotoActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    id: Uuid,
+}
+
+impl DeletePhotoActionPayload {
+    #[must_use]
+    pub fn new(id: Uuid, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            id,
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, Clone)]
+/// The payload of the [`Action::UpdateWindRose`].
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWindRoseActionPayload {
+    user_id: Uuid,
+    action_id: Uuid,
+    wind_rose: WindRoseDto,
+}
+
+impl UpdateWindRoseActionPayload {
+    #[must_use]
+    pub fn new(payload: &WindRoseDto, user_id: Uuid, action_id: Uuid) -> Self {
+        Self {
+            user_id,
+            action_id,
+            wind_rose: payload.clone(),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Contains the implementation of [`CalendarDto`] and [`CalendarPlantingDto`].
+
+use chrono::{Days, NaiveDate, Utc};
+
+use crate::model::entity::{plantings::Planting, Plants};
+
+use super::{CalendarDto, CalendarPlantingDto};
+
+/// The maximum length of a line in an iCalendar file in octets.
+const ICS_LINE_LENGTH: usize = 75;
+
+impl From<(Planting, Plants)> for CalendarPlantingDto {
+    fn from((planting, plant): (Planting, Plants)) -> Self {
+        Self {
+            planting_id: planting.id,
+            plant_id: plant.id,
+            unique_name: plant.unique_name,
+            common_name_en: plant.common_name_en,
+            add_date: planting.add_date,
+            remove_date: planting.remove_date,
+        }
+    }
+}
+
+impl CalendarPlantingDto {
+    /// The name shown for the planting, which is the first English common name if there is one.
+    #[must_use]
+    pub fn display_name(&self) -> &str {
+        self.common_name_en
+            .iter()
+            .flatten()
+            .flatten()
+            .next()
+            .map_or(&self.unique_name, String::as_str)
+    }
+}
+
+impl CalendarDto {
+    /// Export the calendar as iCalendar (RFC 5545).
+    ///
+    /// Sowing and harvesting are all-day events spanning the half month,
+    /// adding and removing plantings are all-day events on their date.
+    #[must_use]
+    pub fn to_ics(&self, map_name: &str) -> String {
+        let dtstamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_owned(),
+            "VERSION:2.0".to_owned(),
+            "PRODID:-//PermaplanT//Map Calendar//EN".to_owned(),
+            "CALSCALE:GREGORIAN".to_owned(),
+            format!("X-WR-CALNAME:{}", ics_escape(map_name)),
+        ];
+
+        for entry in &self.entries {
+            for (kind, verb, plantings) in [
+                ("sow", "Sow", &entry.sow),
+                ("harvest", "Harvest", &entry.harvest),
+            ] {
+                if plantings.is_empty() {
+                    continue;
+                }
+                let mut names = Vec::new();
+                for name in plantings.iter().map(CalendarPlantingDto::display_name) {
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+                let uid = format!(
+                    "map-{}-{}-{}-{kind}@permaplant",
+                    self.map_id, self.year, entry.half_month
+                );
+                let summary = format!("{verb}: {}", names.join(", "));
+                push_event(&mut lines, &uid, &dtstamp, entry.start, entry.end, &summary);
+            }
+
+            for (kind, verb, plantings) in [
+                ("add", "Add", &entry.add),
+                ("remove", "Remove", &entry.remove),
+            ] {
+                for planting in plantings {
+                    let date = if kind == "add" {
+                        planting.add_date
+                    } else {
+                        planting.remove_date
+                    };
+                    let Some(date) = date else {
+                        continue;
+                    };
+                    let uid = format!("planting-{}-{kind}@permaplant", planting.planting_id);
+                    let summary = format!("{verb} {}", planting.display_name());
+                    push_event(&mut lines, &uid, &dtstamp, date, date, &summary);
+                }
+            }
+        }
+
+        lines.push("END:VCALENDAR".to_owned());
+        lines.iter().map(|line| ics_fold(line)).collect()
+    }
+}
+
+/// Append an all-day event from `first` to `last` (inclusive) to the lines of an iCalendar file.
+fn push_event(
+    lines: &mut Vec<String>,
+    uid: &str,
+    dtstamp: &str,
+    first: NaiveDate,
+    last: NaiveDate,
+    summary: &str,
+) {
+    let end = last.checked_add_days(Days::new(1)).unwrap_or(last);
+    lines.extend([
+        "BEGIN:VEVENT".to_owned(),
+        format!("UID:{uid}"),
+        format!("DTSTAMP:{dtstamp}"),
+        format!("DTSTART;VALUE=DATE:{}", first.format("%Y%m%d")),
+        format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")),
+        format!("SUMMARY:{}", ics_escape(summary)),
+        "END:VEVENT".to_owned(),
+    ]);
+}
+
+/// Escape text for use in an iCalendar property value.
+fn ics_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line into lines of at most [`ICS_LINE_LENGTH`] octets, each terminated by CRLF.
+fn ics_fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut length = 0_usize;
+    for character in line.chars() {
+        if length.saturating_add(character.len_utf8()) > ICS_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(character);
+        length = length.saturating_add(character.len_utf8());
+    }
+    folded.push_str("\r\n");
+    folded
+}
@@ -0,0 +1,188 @@
+//! All DTOs associated with [`DrawingDto`].
+
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// A vector shape on a drawing, paths, label or zones layer.
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawingDto {
+    /// The id of the drawing.
+    pub id: Uuid,
+    /// The layer the drawing is on.
+    pub layer_id: i32,
+    /// The geometry of the drawing.
+    pub shape: ShapeDto,
+    /// The appearance of the drawing.
+    pub style: ShapeStyleDto,
+}
+
+/// The geometry of a drawing, all coordinates and sizes are in cm.
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", content = "content")]
+pub enum ShapeDto {
+    /// A straight line between two points.
+    Line(LineShapeDto),
+    /// An open line through several points.
+    Polyline(PolylineShapeDto),
+    /// A closed area through several points.
+    Polygon(PolygonShapeDto),
+    /// A rectangle that can be rotated around its first corner.
+    Rectangle(RectangleShapeDto),
+    /// An ellipse that can be rotated around its center.
+    Ellipse(EllipseShapeDto),
+    /// A text label.
+    Text(TextShapeDto),
+}
+
+/// A point of a shape.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ShapePointDto {
+    /// The x coordinate of the point.
+    pub x: f64,
+    /// The y coordinate of the point.
+    pub y: f64,
+}
+
+/// A straight line between two points.
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LineShapeDto {
+    /// The point the line starts at.
+    pub start: ShapePointDto,
+    /// The point the line ends at.
+    pub end: ShapePointDto,
+}
+
+/// An open line through several points.
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PolylineShapeDto {
+    /// The points of the line, at least two.
+    pub points: Vec<ShapePointDto>,
+}
+
+/// A closed area through several points.
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PolygonShapeDto {
+    /// The corners of the area, at least three.
+    /// The first corner must not be repeated at the end.
+    pub points: Vec<ShapePointDto>,
+}
+
+/// A rectangle that can be rotated around its first corner.
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RectangleShapeDto {
+    /// The x coordinate of the top left corner.
+    pub x: f64,
+    /// The y coordinate of the top left corner.
+    pub y: f64,
+    /// The width of the rectangle.
+    pub width: f64,
+    /// The height of the rectangle.
+    pub height: f64,
+    /// The rotation in degrees clockwise around the top left corner.
+    pub rotation: f32,
+}
+
+/// An ellipse that can be rotated around its center.
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EllipseShapeDto {
+    /// The center of the ellipse.
+    pub center: ShapePointDto,
+    /// The horizontal radius of the ellipse.
+    pub radius_x: f32,
+    /// The vertical radius of the ellipse.
+    pub radius_y: f32,
+    /// The rotation in degrees clockwise around the center.
+    pub rotation: f32,
+}
+
+/// A text label.
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TextShapeDto {
+    /// The top left corner of the text.
+    pub position: ShapePointDto,
+    /// The content of the text.
+    pub text: String,
+    /// The font size of the text.
+    pub font_size: f32,
+    /// The rotation in degrees clockwise around the top left corner.
+    pub rotation: f32,
+}
+
+/// The appearance of a drawing.
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ShapeStyleDto {
+    /// The color of the outline, e.g. `#00ff00`.
+    pub stroke_color: String,
+    /// The width of the outline.
+    pub stroke_width: f32,
+    /// The color of the area, if it is filled.
+    pub fill_color: Option<String>,
+}
+
+/// Query parameters for searching drawings.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DrawingSearchParameters {
+    /// The id of the layer the drawings are on.
+    pub layer_id: i32,
+}
+
+/// Used to create a new drawing.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NewDrawingDto {
+    /// The id of the drawing.
+    pub id: Option<Uuid>,
+    /// The layer the drawing is on.
+    pub layer_id: i32,
+    /// The geometry of the drawing.
+    pub shape: ShapeDto,
+    /// The appearance of the drawing.
+    pub style: ShapeStyleDto,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to change the geometry or appearance of a drawing.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateDrawingDto {
+    /// The new geometry of the drawing, the kind of shape may change.
+    pub shape: Option<ShapeDto>,
+    /// The new appearance of the drawing.
+    pub style: Option<ShapeStyleDto>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to delete a drawing.
+/// The id of the drawing is passed in the path.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteDrawingDto {
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
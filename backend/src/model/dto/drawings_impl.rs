@@ -0,0 +1,200 @@
+//! Contains the implementation of [`DrawingDto`].
+
+use postgis_diesel::types::{LineString, Point, Polygon};
+
+use crate::db::geometry::ShapeGeometry;
+use crate::model::entity::drawings::Drawing;
+use crate::model::r#enum::shape_type::ShapeType;
+
+use super::drawings::{
+    DrawingDto, EllipseShapeDto, LineShapeDto, PolygonShapeDto, PolylineShapeDto,
+    RectangleShapeDto, ShapeDto, ShapePointDto, ShapeStyleDto, TextShapeDto,
+};
+
+/// `PostGIS` identifier of the coordinate system of drawings.
+const COORDINATE_SYSTEM: u32 = 4326;
+
+impl From<Point> for ShapePointDto {
+    fn from(point: Point) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
+impl From<ShapePointDto> for Point {
+    fn from(point: ShapePointDto) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            srid: Some(COORDINATE_SYSTEM),
+        }
+    }
+}
+
+impl From<&ShapeDto> for ShapeType {
+    fn from(shape: &ShapeDto) -> Self {
+        match shape {
+            ShapeDto::Line(_) => Self::Line,
+            ShapeDto::Polyline(_) => Self::Polyline,
+            ShapeDto::Polygon(_) => Self::Polygon,
+            ShapeDto::Rectangle(_) => Self::Rectangle,
+            ShapeDto::Ellipse(_) => Self::Ellipse,
+            ShapeDto::Text(_) => Self::Text,
+        }
+    }
+}
+
+impl From<&ShapeDto> for ShapeGeometry {
+    fn from(shape: &ShapeDto) -> Self {
+        match shape {
+            ShapeDto::Line(line) => line_string(&[line.start, line.end]),
+            ShapeDto::Polyline(polyline) => line_string(&polyline.points),
+            ShapeDto::Polygon(polygon) => closed_polygon(&polygon.points),
+            ShapeDto::Rectangle(rectangle) => closed_polygon(&rectangle_corners(rectangle)),
+            ShapeDto::Ellipse(ellipse) => Self::Point(ellipse.center.into()),
+            ShapeDto::Text(text) => Self::Point(text.position.into()),
+        }
+    }
+}
+
+impl From<DrawingDto> for Drawing {
+    fn from(dto: DrawingDto) -> Self {
+        let drawing = Self {
+            id: dto.id,
+            layer_id: dto.layer_id,
+            shape_type: ShapeType::from(&dto.shape),
+            geometry: ShapeGeometry::from(&dto.shape),
+            rotation: 0.0,
+            radius_x: None,
+            radius_y: None,
+            text: None,
+            font_size: None,
+            stroke_color: dto.style.stroke_color,
+            stroke_width: dto.style.stroke_width,
+            fill_color: dto.style.fill_color,
+        };
+        match dto.shape {
+            ShapeDto::Line(_) | ShapeDto::Polyline(_) | ShapeDto::Polygon(_) => drawing,
+            ShapeDto::Rectangle(rectangle) => Self {
+                rotation: rectangle.rotation,
+                ..drawing
+            },
+            ShapeDto::Ellipse(ellipse) => Self {
+                rotation: ellipse.rotation,
+                radius_x: Some(ellipse.radius_x),
+                radius_y: Some(ellipse.radius_y),
+                ..drawing
+            },
+            ShapeDto::Text(text) => Self {
+                rotation: text.rotation,
+                text: Some(text.text),
+                font_size: Some(text.font_size),
+                ..drawing
+            },
+        }
+    }
+}
+
+impl From<Drawing> for DrawingDto {
+    fn from(drawing: Drawing) -> Self {
+        let mut points = drawing
+            .geometry
+            .points()
+            .into_iter()
+            .map(ShapePointDto::from)
+            .collect::<Vec<_>>();
+        let first = points.first().copied().unwrap_or_default();
+        let shape = match drawing.shape_type {
+            ShapeType::Line => ShapeDto::Line(LineShapeDto {
+                start: first,
+                end: points.last().copied().unwrap_or_default(),
+            }),
+            ShapeType::Polyline => ShapeDto::Polyline(PolylineShapeDto { points }),
+            ShapeType::Polygon => {
+                // The ring of the polygon repeats the first corner at the end.
+                points.pop();
+                ShapeDto::Polygon(PolygonShapeDto { points })
+            }
+            ShapeType::Rectangle => {
+                let side = |index: usize| {
+                    points
+                        .get(index)
+                        .map_or(0.0, |corner| (corner.x - first.x).hypot(corner.y - first.y))
+                };
+                ShapeDto::Rectangle(RectangleShapeDto {
+                    x: first.x,
+                    y: first.y,
+                    width: side(1),
+                    height: side(3),
+                    rotation: drawing.rotation,
+                })
+            }
+            ShapeType::Ellipse => ShapeDto::Ellipse(EllipseShapeDto {
+                center: first,
+                radius_x: drawing.radius_x.unwrap_or_default(),
+                radius_y: drawing.radius_y.unwrap_or_default(),
+                rotation: drawing.rotation,
+            }),
+            ShapeType::Text => ShapeDto::Text(TextShapeDto {
+                position: first,
+                text: drawing.text.unwrap_or_default(),
+                font_size: drawing.font_size.unwrap_or_default(),
+                rotation: drawing.rotation,
+            }),
+        };
+        Self {
+            id: drawing.id,
+            layer_id: drawing.layer_id,
+            shape,
+            style: ShapeStyleDto {
+                stroke_color: drawing.stroke_color,
+                stroke_width: drawing.stroke_width,
+                fill_color: drawing.fill_color,
+            },
+        }
+    }
+}
+
+/// A line string through the points.
+fn line_string(points: &[ShapePointDto]) -> ShapeGeometry {
+    ShapeGeometry::LineString(LineString {
+        points: points.iter().copied().map(Into::into).collect(),
+        srid: Some(COORDINATE_SYSTEM),
+    })
+}
+
+/// A polygon with the corners, the first corner is repeated to close the ring.
+fn closed_polygon(corners: &[ShapePointDto]) -> ShapeGeometry {
+    let mut ring = corners.iter().copied().map(Point::from).collect::<Vec<_>>();
+    if let Some(first) = ring.first().copied() {
+        ring.push(first);
+    }
+    ShapeGeometry::Polygon(Polygon {
+        rings: vec![ring],
+        srid: Some(COORDINATE_SYSTEM),
+    })
+}
+
+/// The corners of a rectangle clockwise starting at the top left corner.
+fn rectangle_corners(rectangle: &RectangleShapeDto) -> [ShapePointDto; 4] {
+    let (sin, cos) = f64::from(rectangle.rotation).to_radians().sin_cos();
+    let top_left = ShapePointDto {
+        x: rectangle.x,
+        y: rectangle.y,
+    };
+    let top_right = ShapePointDto {
+        x: rectangle.width.mul_add(cos, rectangle.x),
+        y: rectangle.width.mul_add(sin, rectangle.y),
+    };
+    let bottom_left = ShapePointDto {
+        x: (-rectangle.height).mul_add(sin, rectangle.x),
+        y: rectangle.height.mul_add(cos, rectangle.y),
+    };
+    let bottom_right = ShapePointDto {
+        x: top_right.x + bottom_left.x - top_left.x,
+        y: top_right.y + bottom_left.y - top_left.y,
+    };
+    [top_left, top_right, bottom_right, bottom_left]
+}
@@ -0,0 +1,34 @@
+//! Contains the implementation of [`FacetDto`].
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{FacetDto, FacetValueDto};
+
+impl FacetDto {
+    /// Create a facet out of values and how often they occur.
+    ///
+    /// The values are named the same way they are serialized.
+    /// `NULL` values are not part of the facet.
+    #[must_use]
+    pub fn new<T: Serialize>(attribute: &str, counted_values: Vec<(Option<T>, i64)>) -> Self {
+        let values = counted_values
+            .into_iter()
+            .filter_map(|(value, count)| {
+                let value = match serde_json::to_value(value?).ok()? {
+                    Value::String(value) => value,
+                    value => value.to_string(),
+                };
+                Some(FacetValueDto {
+                    value,
+                    count: i32::try_from(count).unwrap_or(i32::MAX),
+                })
+            })
+            .collect();
+
+        Self {
+            attribute: attribute.to_owned(),
+            values,
+        }
+    }
+}
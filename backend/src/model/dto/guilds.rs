@@ -0,0 +1,177 @@
+//! All DTOs associated with [`GuildDto`].
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::plantings::PlantingDto;
+
+/// A group of plantings that are moved, rotated and duplicated together,
+/// e.g. a fruit tree with its companion plants.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GuildDto {
+    /// The id of the guild.
+    pub id: Uuid,
+    /// The plant layer the guild is on.
+    pub layer_id: i32,
+    /// The name of the guild.
+    pub name: String,
+    /// The plantings of the guild.
+    pub plantings: Vec<PlantingDto>,
+}
+
+/// Query parameters for searching guilds.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct GuildSearchParameters {
+    /// The id of the plant layer the guilds are on.
+    pub layer_id: i32,
+}
+
+/// Used to group existing plantings into a new guild.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NewGuildDto {
+    /// The id of the guild.
+    pub id: Option<Uuid>,
+    /// The plant layer the guild is on.
+    pub layer_id: i32,
+    /// The name of the guild.
+    pub name: String,
+    /// The plantings of the guild, they have to be on the same layer.
+    pub planting_ids: Vec<Uuid>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to differentiate between different update operations on guilds.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", content = "content")]
+pub enum UpdateGuildDto {
+    /// Move all plantings of a guild.
+    Move(MoveGuildDto),
+    /// Rotate all plantings of a guild around its center.
+    Rotate(RotateGuildDto),
+}
+
+/// Used to move all plantings of a guild.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveGuildDto {
+    /// The distance in x direction the plantings are moved by.
+    pub dx: i32,
+    /// The distance in y direction the plantings are moved by.
+    pub dy: i32,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to rotate all plantings of a guild around its center.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RotateGuildDto {
+    /// The angle in degrees the plantings are rotated by clockwise.
+    pub rotation: f32,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to duplicate a guild with all of its plantings.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGuildDto {
+    /// The id of the new guild.
+    pub id: Option<Uuid>,
+    /// The distance in x direction of the copies to the original plantings.
+    pub dx: i32,
+    /// The distance in y direction of the copies to the original plantings.
+    pub dy: i32,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to ungroup a guild.
+/// The id of the guild is passed in the path.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteGuildDto {
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// A guild saved to be placed on other maps.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GuildTemplateDto {
+    /// The id of the template.
+    pub id: i32,
+    /// The name of the template.
+    pub name: String,
+    /// The plants of the template.
+    pub members: Vec<GuildTemplateMemberDto>,
+}
+
+/// A plant of a [`GuildTemplateDto`].
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GuildTemplateMemberDto {
+    /// The plant that is planted.
+    pub plant_id: i32,
+    /// The x coordinate of the position relative to the center of the guild.
+    pub x: i32,
+    /// The y coordinate of the position relative to the center of the guild.
+    pub y: i32,
+    /// The width of the plant on the map.
+    pub width: i32,
+    /// The height of the plant on the map.
+    pub height: i32,
+    /// The rotation in degrees (0-360) of the plant on the map.
+    pub rotation: f32,
+    /// The x scale of the plant on the map.
+    pub scale_x: f32,
+    /// The y scale of the plant on the map.
+    pub scale_y: f32,
+}
+
+/// Used to save a guild as a template.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NewGuildTemplateDto {
+    /// The name of the template.
+    pub name: String,
+}
+
+/// Used to place a new guild from a template.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyGuildTemplateDto {
+    /// The id of the new guild.
+    pub id: Option<Uuid>,
+    /// The template the guild is created from.
+    pub template_id: i32,
+    /// The plant layer the guild is placed on.
+    pub layer_id: i32,
+    /// The x coordinate of the center of the guild on the map.
+    pub x: i32,
+    /// The y coordinate of the center of the guild on the map.
+    pub y: i32,
+    /// The date the plantings are added to the map.
+    /// If None, the plantings always existed.
+    pub add_date: Option<NaiveDate>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
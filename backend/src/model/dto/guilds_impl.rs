@@ -0,0 +1,60 @@
+//! Contains the implementations related to [`GuildDto`].
+
+use crate::model::entity::guilds::{
+    Guild, GuildTemplate, GuildTemplateMember, NewGuildTemplateMember,
+};
+
+use super::guilds::{GuildDto, GuildTemplateDto, GuildTemplateMemberDto};
+use super::plantings::PlantingDto;
+
+impl From<(Guild, Vec<PlantingDto>)> for GuildDto {
+    fn from((guild, plantings): (Guild, Vec<PlantingDto>)) -> Self {
+        Self {
+            id: guild.id,
+            layer_id: guild.layer_id,
+            name: guild.name,
+            plantings,
+        }
+    }
+}
+
+impl From<(GuildTemplate, Vec<GuildTemplateMemberDto>)> for GuildTemplateDto {
+    fn from((template, members): (GuildTemplate, Vec<GuildTemplateMemberDto>)) -> Self {
+        Self {
+            id: template.id,
+            name: template.name,
+            members,
+        }
+    }
+}
+
+impl From<GuildTemplateMember> for GuildTemplateMemberDto {
+    fn from(member: GuildTemplateMember) -> Self {
+        Self {
+            plant_id: member.plant_id,
+            x: member.x,
+            y: member.y,
+            width: member.width,
+            height: member.height,
+            rotation: member.rotation,
+            scale_x: member.scale_x,
+            scale_y: member.scale_y,
+        }
+    }
+}
+
+impl From<(i32, GuildTemplateMemberDto)> for NewGuildTemplateMember {
+    fn from((template_id, member): (i32, GuildTemplateMemberDto)) -> Self {
+        Self {
+            template_id,
+            plant_id: member.plant_id,
+            x: member.x,
+            y: member.y,
+            width: member.width,
+            height: member.height,
+            rotation: member.rotation,
+            scale_x: member.scale_x,
+            scale_y: member.scale_y,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! Contains the implementations related to [`HarvestDto`].
+
+use uuid::Uuid;
+
+use crate::model::entity::{Harvest, NewHarvest};
+
+use super::{HarvestDto, NewHarvestDto};
+
+impl From<Harvest> for HarvestDto {
+    fn from(harvest: Harvest) -> Self {
+        Self {
+            id: harvest.id,
+            planting_id: harvest.planting_id,
+            harvest_date: harvest.harvest_date,
+            quantity: harvest.quantity,
+            unit: harvest.unit,
+            quality: harvest.quality,
+            notes: harvest.notes,
+            created_by: harvest.created_by,
+        }
+    }
+}
+
+impl From<(Uuid, NewHarvestDto, Uuid)> for NewHarvest {
+    fn from((planting_id, new_harvest, created_by): (Uuid, NewHarvestDto, Uuid)) -> Self {
+        Self {
+            planting_id,
+            harvest_date: new_harvest.harvest_date,
+            quantity: new_harvest.quantity,
+            unit: new_harvest.unit,
+            quality: new_harvest.quality,
+            notes: new_harvest.notes,
+            created_by,
+        }
+    }
+}
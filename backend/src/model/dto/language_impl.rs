@@ -0,0 +1,25 @@
+//! Contains the implementation of [`Language`].
+
+use actix_web::http::header::{AcceptLanguage, Preference};
+
+use super::Language;
+
+impl Language {
+    /// Choose the most preferred supported language of an `Accept-Language` header.
+    ///
+    /// Returns [`None`] if none of the accepted languages is supported.
+    #[must_use]
+    pub fn from_accept_language(header: &AcceptLanguage) -> Option<Self> {
+        header
+            .ranked()
+            .iter()
+            .find_map(|preference| match preference {
+                Preference::Specific(tag) => match tag.primary_language() {
+                    "en" => Some(Self::En),
+                    "de" => Some(Self::De),
+                    _ => None,
+                },
+                Preference::Any => None,
+            })
+    }
+}
@@ -1,8 +1,8 @@
 //! Contains the implementation of [`LayerDto`].
 
-use crate::model::entity::Layer;
+use crate::model::entity::{Layer, UpdateLayer};
 
-use super::LayerDto;
+use super::{LayerDto, UpdateLayerDto};
 
 impl From<Layer> for LayerDto {
     fn from(layer: Layer) -> Self {
@@ -12,6 +12,21 @@ impl From<Layer> for LayerDto {
             type_: layer.type_,
             name: layer.name,
             is_alternative: layer.is_alternative,
+            order_index: layer.order_index,
+            is_visible: layer.is_visible,
+            opacity: layer.opacity,
+            deletion_date: layer.deletion_date,
+        }
+    }
+}
+
+impl From<UpdateLayerDto> for UpdateLayer {
+    fn from(dto: UpdateLayerDto) -> Self {
+        Self {
+            name: dto.name,
+            order_index: dto.order_index,
+            is_visible: dto.is_visible,
+            opacity: dto.opacity,
         }
     }
 }
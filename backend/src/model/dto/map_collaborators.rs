@@ -0,0 +1,26 @@
+//! All DTOs associated with [`MapCollaboratorDto`].
+
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A user collaborating on a map of another user.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MapCollaboratorDto {
+    /// The map the user collaborates on.
+    pub map_id: i32,
+    /// The id of the collaborating user.
+    pub user_id: Uuid,
+}
+
+/// Used to add a collaborator to a map.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NewMapCollaboratorDto {
+    /// The id of the user to add.
+    pub user_id: Uuid,
+}
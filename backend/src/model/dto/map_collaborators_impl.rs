@@ -0,0 +1,14 @@
+//! Contains the implementation of [`MapCollaboratorDto`].
+
+use crate::model::entity::map_collaborators::MapCollaborator;
+
+use super::map_collaborators::MapCollaboratorDto;
+
+impl From<MapCollaborator> for MapCollaboratorDto {
+    fn from(collaborator: MapCollaborator) -> Self {
+        Self {
+            map_id: collaborator.map_id,
+            user_id: collaborator.user_id,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Contains the implementation of [`NewCustomPlantDto`].
+
+use uuid::Uuid;
+
+use crate::model::entity::{NewPlants, Plants};
+
+use super::NewCustomPlantDto;
+
+impl From<(NewCustomPlantDto, Plants, Uuid)> for NewPlants {
+    fn from((new_plant, base, owner_id): (NewCustomPlantDto, Plants, Uuid)) -> Self {
+        Self {
+            unique_name: new_plant.unique_name,
+            common_name_en: new_plant.common_name_en.or(base.common_name_en),
+            common_name_de: new_plant.common_name_de.or(base.common_name_de),
+            family: base.family,
+            edible_uses_en: base.edible_uses_en,
+            functions: base.functions,
+            heat_zone: base.heat_zone,
+            shade: base.shade,
+            soil_ph: base.soil_ph,
+            soil_texture: base.soil_texture,
+            ecosystem_niche: base.ecosystem_niche,
+            deciduous_or_evergreen: base.deciduous_or_evergreen,
+            herbaceous_or_woody: base.herbaceous_or_woody,
+            life_cycle: base.life_cycle,
+            growth_rate: new_plant.growth_rate.or(base.growth_rate),
+            height: new_plant.height.or(base.height),
+            fertility: base.fertility,
+            has_drought_tolerance: base.has_drought_tolerance,
+            tolerates_wind: base.tolerates_wind,
+            preferable_permaculture_zone: base.preferable_permaculture_zone,
+            hardiness_zone: base.hardiness_zone,
+            light_requirement: base.light_requirement,
+            water_requirement: base.water_requirement,
+            propagation_method: base.propagation_method,
+            alternate_name: base.alternate_name,
+            edible: base.edible,
+            edible_parts: base.edible_parts,
+            spread: new_plant.spread.or(base.spread),
+            warning: new_plant.warning.or(base.warning),
+            version: None,
+            external_source: None,
+            sowing_outdoors: new_plant.sowing_outdoors.or(base.sowing_outdoors),
+            harvest_time: new_plant.harvest_time.or(base.harvest_time),
+            seed_weight_1000: base.seed_weight_1000,
+            owner_id: Some(owner_id),
+            base_plant_id: Some(base.id),
+            nutrition_demand: base.nutrition_demand,
+        }
+    }
+}
@@ -11,6 +11,7 @@ impl From<NewLayerDto> for NewLayer {
             type_: new_layer.type_,
             name: new_layer.name,
             is_alternative: new_layer.is_alternative,
+            order_index: 0,
         }
     }
 }
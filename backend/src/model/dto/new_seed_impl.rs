@@ -23,6 +23,7 @@ impl From<(NewSeedDto, Uuid)> for NewSeed {
             price: new_seed.price,
             notes: new_seed.notes,
             owner_id,
+            parent_seed_id: new_seed.parent_seed_id,
         }
     }
 }
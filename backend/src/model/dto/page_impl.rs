@@ -1,6 +1,9 @@
 //! Contains the implementation of [`Page`].
 
-use crate::model::dto::Page;
+use std::cmp::max;
+
+use crate::db::pagination::{DEFAULT_PER_PAGE, MIN_PAGE, MIN_PER_PAGE};
+use crate::model::dto::{FacetDto, FacetedPage, Page, PageParameters};
 
 impl<T> Page<T> {
     /// Used to convert from a page of entities to a page of dto.
@@ -18,4 +21,59 @@ impl<T> Page<T> {
             total_pages: value.total_pages,
         }
     }
+
+    /// Used to select one page of results that were loaded and ordered completely.
+    ///
+    /// Page parameters are handled the same way as in [`crate::db::pagination::Paginate`].
+    #[must_use]
+    pub fn from_results(results: Vec<T>, page_parameters: &PageParameters) -> Self {
+        let page = max(page_parameters.page.unwrap_or(MIN_PAGE), MIN_PAGE);
+        let per_page = max(
+            page_parameters.per_page.unwrap_or(DEFAULT_PER_PAGE),
+            MIN_PER_PAGE,
+        );
+        let total = i32::try_from(results.len()).unwrap_or(i32::MAX);
+        #[allow(clippy::integer_division)]
+        let total_pages = total.saturating_add(per_page - 1) / per_page;
+        let offset = usize::try_from((page - 1).saturating_mul(per_page)).unwrap_or(usize::MAX);
+        let per_page_len = usize::try_from(per_page).unwrap_or(usize::MAX);
+
+        Self {
+            results: results
+                .into_iter()
+                .skip(offset)
+                .take(per_page_len)
+                .collect(),
+            page,
+            per_page,
+            total_pages,
+        }
+    }
+
+    /// Used to convert the results of a page using a function.
+    pub fn map<U, F>(self, f: F) -> Page<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        Page {
+            results: self.results.into_iter().map(f).collect(),
+            page: self.page,
+            per_page: self.per_page,
+            total_pages: self.total_pages,
+        }
+    }
+}
+
+impl<T> FacetedPage<T> {
+    /// Used to combine a page of results with the facets of the search.
+    #[must_use]
+    pub fn from_page(page: Page<T>, facets: Vec<FacetDto>) -> Self {
+        Self {
+            results: page.results,
+            page: page.page,
+            per_page: page.per_page,
+            total_pages: page.total_pages,
+            facets,
+        }
+    }
 }
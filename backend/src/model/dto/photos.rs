@@ -0,0 +1,114 @@
+//! All DTOs associated with [`PhotoDto`].
+
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// A photo pinned to a position on the map, documenting the garden at the time it was taken.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoDto {
+    /// The id of the photo.
+    pub id: Uuid,
+    /// The photo layer the photo is on.
+    pub layer_id: i32,
+    /// The x coordinate of the position the photo is pinned to.
+    pub x: i32,
+    /// The y coordinate of the position the photo is pinned to.
+    pub y: i32,
+    /// The planting the photo shows.
+    pub planting_id: Option<Uuid>,
+    /// The path of the image in Nextcloud.
+    pub path: String,
+    /// A description of the photo.
+    pub description: Option<String>,
+    /// The local time the photo was taken at.
+    pub captured_at: Option<NaiveDateTime>,
+    /// The id of the user who added the photo.
+    pub created_by: Uuid,
+}
+
+/// Query parameters for searching photos.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PhotoSearchParameters {
+    /// The id of the photo layer the photos are on.
+    pub layer_id: i32,
+    /// Only photos taken on or after this date.
+    pub from: Option<NaiveDate>,
+    /// Only photos taken on or before this date.
+    pub to: Option<NaiveDate>,
+}
+
+/// Used to pin a new photo to the map.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NewPhotoDto {
+    /// The id of the photo.
+    pub id: Option<Uuid>,
+    /// The photo layer the photo is on.
+    pub layer_id: i32,
+    /// The x coordinate of the position the photo is pinned to.
+    pub x: i32,
+    /// The y coordinate of the position the photo is pinned to.
+    pub y: i32,
+    /// The planting the photo shows.
+    pub planting_id: Option<Uuid>,
+    /// The path of the image in Nextcloud.
+    pub path: String,
+    /// A description of the photo.
+    pub description: Option<String>,
+    /// The local time the photo was taken at.
+    pub captured_at: Option<NaiveDateTime>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to replace the position and details of a photo.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePhotoDto {
+    /// The x coordinate of the position the photo is pinned to.
+    pub x: i32,
+    /// The y coordinate of the position the photo is pinned to.
+    pub y: i32,
+    /// The planting the photo shows.
+    pub planting_id: Option<Uuid>,
+    /// A description of the photo.
+    pub description: Option<String>,
+    /// The local time the photo was taken at.
+    pub captured_at: Option<NaiveDateTime>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to delete a photo.
+/// The id of the photo is passed in the path.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletePhotoDto {
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// The metadata read from the EXIF data of an image,
+/// used to prefill a [`NewPhotoDto`].
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoMetadataDto {
+    /// The local time the photo was taken at.
+    pub captured_at: Option<NaiveDateTime>,
+    /// The x coordinate of the GPS position of the photo on the map.
+    /// `None` if the image has no GPS position or the map has no location.
+    pub x: Option<i32>,
+    /// The y coordinate of the GPS position of the photo on the map.
+    /// `None` if the image has no GPS position or the map has no location.
+    pub y: Option<i32>,
+}
@@ -0,0 +1,51 @@
+//! Contains the implementation of [`PhotoDto`].
+
+use uuid::Uuid;
+
+use crate::model::entity::photos::{Photo, UpdatePhoto};
+
+use super::photos::{NewPhotoDto, PhotoDto, UpdatePhotoDto};
+
+impl From<Photo> for PhotoDto {
+    fn from(photo: Photo) -> Self {
+        Self {
+            id: photo.id,
+            layer_id: photo.layer_id,
+            x: photo.x,
+            y: photo.y,
+            planting_id: photo.planting_id,
+            path: photo.path,
+            description: photo.description,
+            captured_at: photo.captured_at,
+            created_by: photo.created_by,
+        }
+    }
+}
+
+impl From<(NewPhotoDto, Uuid)> for Photo {
+    fn from((new_photo, user_id): (NewPhotoDto, Uuid)) -> Self {
+        Self {
+            id: new_photo.id.unwrap_or_else(Uuid::new_v4),
+            layer_id: new_photo.layer_id,
+            x: new_photo.x,
+            y: new_photo.y,
+            planting_id: new_photo.planting_id,
+            path: new_photo.path,
+            description: new_photo.description,
+            captured_at: new_photo.captured_at,
+            created_by: user_id,
+        }
+    }
+}
+
+impl From<UpdatePhotoDto> for UpdatePhoto {
+    fn from(update_photo: UpdatePhotoDto) -> Self {
+        Self {
+            x: update_photo.x,
+            y: update_photo.y,
+            planting_id: update_photo.planting_id,
+            description: update_photo.description,
+            captured_at: update_photo.captured_at,
+        }
+    }
+}
@@ -6,10 +6,12 @@ use typeshare::typeshare;
 use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+use crate::model::r#enum::planting_status::PlantingStatus;
+
 /// Represents plant planted on a map.
 /// E.g. a user drags a plant from the search results and drops it on the map.
 #[typeshare]
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PlantingDto {
     /// The id of the planting.
@@ -38,6 +40,18 @@ pub struct PlantingDto {
     /// The date the planting was removed from the map.
     /// If None, the planting is still on the map.
     pub remove_date: Option<NaiveDate>,
+    /// Notes about the planting.
+    pub notes: Option<String>,
+    /// Where the planting is in its lifecycle.
+    pub status: PlantingStatus,
+    /// The seed the plant was grown from.
+    pub seed_id: Option<i32>,
+    /// Paths to the photos of the planting in Nextcloud.
+    pub photos: Vec<String>,
+    /// The guild the planting belongs to.
+    pub guild_id: Option<Uuid>,
+    /// The planting this planting was copied from when its layer was forked into an alternative.
+    pub origin_id: Option<Uuid>,
 }
 
 /// Used to create a new planting.
@@ -77,7 +91,7 @@ pub struct NewPlantingDto {
 /// Ordering of enum variants is important.
 /// Serde will try to deserialize starting from the top.
 #[typeshare]
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(tag = "type", content = "content")]
 pub enum UpdatePlantingDto {
     /// Transform a plantings.
@@ -88,6 +102,14 @@ pub enum UpdatePlantingDto {
     UpdateAddDate(UpdateAddDatePlantingDto),
     /// Change the `remove_date` of a planting.
     UpdateRemoveDate(UpdateRemoveDatePlantingDto),
+    /// Change the notes of a planting.
+    UpdateNotes(UpdateNotesPlantingDto),
+    /// Change the lifecycle status of a planting.
+    UpdateStatus(UpdateStatusPlantingDto),
+    /// Change the seed a planting was grown from.
+    UpdateSeed(UpdateSeedPlantingDto),
+    /// Replace the photos of a planting.
+    UpdatePhotos(UpdatePhotosPlantingDto),
 }
 
 /// Used to transform an existing planting.
@@ -146,6 +168,51 @@ pub struct UpdateRemoveDatePlantingDto {
     pub action_id: Uuid,
 }
 
+/// Used to change the notes of a planting.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateNotesPlantingDto {
+    /// Notes about the planting.
+    pub notes: Option<String>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to change the lifecycle status of a planting.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateStatusPlantingDto {
+    /// Where the planting is in its lifecycle.
+    pub status: PlantingStatus,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to change the seed a planting was grown from.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSeedPlantingDto {
+    /// The seed the plant was grown from.
+    /// If None, the planting is no longer linked to a seed.
+    pub seed_id: Option<i32>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to replace the photos of a planting.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePhotosPlantingDto {
+    /// Paths to the photos of the planting in Nextcloud.
+    pub photos: Vec<String>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
 /// Used to delete a planting.
 /// The id of the planting is passed in the path.
 #[typeshare]
@@ -166,4 +233,23 @@ pub struct PlantingSearchParameters {
     pub layer_id: Option<i32>,
     /// Plantings that exist around this date are returned.
     pub relative_to_date: NaiveDate,
+    /// The lowest x coordinate of the bounding box plantings are searched in.
+    /// The bounding box is only used if all of its coordinates are set.
+    pub x_min: Option<i32>,
+    /// The lowest y coordinate of the bounding box plantings are searched in.
+    pub y_min: Option<i32>,
+    /// The highest x coordinate of the bounding box plantings are searched in.
+    pub x_max: Option<i32>,
+    /// The highest y coordinate of the bounding box plantings are searched in.
+    pub y_max: Option<i32>,
+    /// The x coordinate of the center of the circle plantings are searched in.
+    /// The circle is only used if its center and radius are set.
+    pub center_x: Option<i32>,
+    /// The y coordinate of the center of the circle plantings are searched in.
+    pub center_y: Option<i32>,
+    /// The radius of the circle plantings are searched in.
+    pub radius: Option<i32>,
+    /// The corners of the polygon plantings are searched in as comma separated coordinates,
+    /// e.g. `0,0,100,0,100,100` for a triangle.
+    pub polygon: Option<String>,
 }
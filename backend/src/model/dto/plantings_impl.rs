@@ -4,6 +4,7 @@
 use uuid::Uuid;
 
 use crate::model::entity::plantings::{Planting, UpdatePlanting};
+use crate::model::r#enum::planting_status::PlantingStatus;
 
 use super::plantings::{NewPlantingDto, PlantingDto, UpdatePlantingDto};
 
@@ -22,6 +23,12 @@ impl From<Planting> for PlantingDto {
             scale_y: entity.scale_y,
             add_date: entity.add_date,
             remove_date: entity.remove_date,
+            notes: entity.notes,
+            status: entity.status,
+            seed_id: entity.seed_id,
+            photos: entity.photos.into_iter().flatten().collect(),
+            guild_id: entity.guild_id,
+            origin_id: entity.origin_id,
         }
     }
 }
@@ -41,6 +48,12 @@ impl From<NewPlantingDto> for Planting {
             scale_y: dto.scale_y,
             add_date: dto.add_date,
             remove_date: None,
+            notes: None,
+            status: PlantingStatus::default(),
+            seed_id: None,
+            photos: Vec::new(),
+            guild_id: None,
+            origin_id: None,
             //create_date: Utc::now().date_naive(),
             //delete_date: None,
         }
@@ -71,6 +84,22 @@ impl From<UpdatePlantingDto> for UpdatePlanting {
                 remove_date: Some(dto.remove_date),
                 ..Default::default()
             },
+            UpdatePlantingDto::UpdateNotes(dto) => Self {
+                notes: Some(dto.notes),
+                ..Default::default()
+            },
+            UpdatePlantingDto::UpdateStatus(dto) => Self {
+                status: Some(dto.status),
+                ..Default::default()
+            },
+            UpdatePlantingDto::UpdateSeed(dto) => Self {
+                seed_id: Some(dto.seed_id),
+                ..Default::default()
+            },
+            UpdatePlantingDto::UpdatePhotos(dto) => Self {
+                photos: Some(dto.photos.into_iter().map(Some).collect()),
+                ..Default::default()
+            },
         }
     }
 }
@@ -0,0 +1,173 @@
+//! Contains the implementation of [`PlantsDatasetDto`] and [`ImportPlantDto`].
+
+use serde_json::{Map, Value};
+
+use crate::model::{
+    entity::{NewPlants, Plants},
+    r#enum::external_source::ExternalSource,
+};
+
+use super::{ImportPlantDto, PlantsDatasetDto};
+
+/// Columns of a CSV dataset containing comma separated lists.
+const LIST_COLUMNS: [&str; 11] = [
+    "common_name_en",
+    "common_name_de",
+    "soil_ph",
+    "soil_texture",
+    "life_cycle",
+    "growth_rate",
+    "fertility",
+    "light_requirement",
+    "water_requirement",
+    "propagation_method",
+    "edible_parts",
+];
+
+/// Columns of a CSV dataset containing text that must not be parsed as JSON.
+const TEXT_COLUMNS: [&str; 8] = [
+    "unique_name",
+    "family",
+    "edible_uses_en",
+    "functions",
+    "ecosystem_niche",
+    "hardiness_zone",
+    "alternate_name",
+    "warning",
+];
+
+impl PlantsDatasetDto {
+    /// Read a dataset from CSV containing one plant per row.
+    ///
+    /// The header has to use the field names of [`ImportPlantDto`], other columns are ignored.
+    /// Lists are separated by commas, numbers, booleans and time periods (e.g. `[8,9]`) are parsed as JSON.
+    /// A CSV dataset never contains relations.
+    ///
+    /// # Errors
+    /// * If the CSV is malformed or a value does not match the type of its column.
+    pub fn from_csv(data: &[u8]) -> Result<Self, String> {
+        let mut reader = csv::Reader::from_reader(data);
+        let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+
+        let mut plants = Vec::new();
+        for record in reader.records() {
+            let record = record.map_err(|e| e.to_string())?;
+            let row = headers
+                .iter()
+                .zip(record.iter())
+                .map(|(column, cell)| (column.trim().to_owned(), csv_value(column.trim(), cell)))
+                .collect::<Map<_, _>>();
+            let line = record.position().map_or(0, csv::Position::line);
+            let plant = serde_json::from_value(Value::Object(row))
+                .map_err(|e| format!("Invalid plant in line {line}: {e}"))?;
+            plants.push(plant);
+        }
+
+        Ok(Self {
+            plants,
+            relations: Vec::new(),
+        })
+    }
+}
+
+/// Convert a cell of a CSV dataset to the JSON value of its column.
+fn csv_value(column: &str, cell: &str) -> Value {
+    let cell = cell.trim();
+    if cell.is_empty() {
+        Value::Null
+    } else if LIST_COLUMNS.contains(&column) {
+        Value::Array(
+            cell.split(',')
+                .map(|item| Value::String(item.trim().to_owned()))
+                .collect(),
+        )
+    } else if TEXT_COLUMNS.contains(&column) {
+        Value::String(cell.to_owned())
+    } else {
+        serde_json::from_str(cell).unwrap_or_else(|_| Value::String(cell.to_owned()))
+    }
+}
+
+impl From<Plants> for ImportPlantDto {
+    fn from(plants: Plants) -> Self {
+        Self {
+            unique_name: plants.unique_name,
+            common_name_en: plants.common_name_en,
+            common_name_de: plants.common_name_de,
+            family: plants.family,
+            edible_uses_en: plants.edible_uses_en,
+            functions: plants.functions,
+            heat_zone: plants.heat_zone,
+            shade: plants.shade,
+            soil_ph: plants.soil_ph,
+            soil_texture: plants.soil_texture,
+            ecosystem_niche: plants.ecosystem_niche,
+            deciduous_or_evergreen: plants.deciduous_or_evergreen,
+            herbaceous_or_woody: plants.herbaceous_or_woody,
+            life_cycle: plants.life_cycle,
+            growth_rate: plants.growth_rate,
+            height: plants.height,
+            fertility: plants.fertility,
+            has_drought_tolerance: plants.has_drought_tolerance,
+            tolerates_wind: plants.tolerates_wind,
+            preferable_permaculture_zone: plants.preferable_permaculture_zone,
+            hardiness_zone: plants.hardiness_zone,
+            light_requirement: plants.light_requirement,
+            water_requirement: plants.water_requirement,
+            propagation_method: plants.propagation_method,
+            alternate_name: plants.alternate_name,
+            edible: plants.edible,
+            edible_parts: plants.edible_parts,
+            spread: plants.spread,
+            warning: plants.warning,
+            sowing_outdoors: plants.sowing_outdoors,
+            harvest_time: plants.harvest_time,
+            seed_weight_1000: plants.seed_weight_1000,
+            nutrition_demand: plants.nutrition_demand,
+        }
+    }
+}
+
+impl From<(ImportPlantDto, ExternalSource, i16)> for NewPlants {
+    fn from((plant, external_source, version): (ImportPlantDto, ExternalSource, i16)) -> Self {
+        Self {
+            unique_name: plant.unique_name,
+            common_name_en: plant.common_name_en,
+            common_name_de: plant.common_name_de,
+            family: plant.family,
+            edible_uses_en: plant.edible_uses_en,
+            functions: plant.functions,
+            heat_zone: plant.heat_zone,
+            shade: plant.shade,
+            soil_ph: plant.soil_ph,
+            soil_texture: plant.soil_texture,
+            ecosystem_niche: plant.ecosystem_niche,
+            deciduous_or_evergreen: plant.deciduous_or_evergreen,
+            herbaceous_or_woody: plant.herbaceous_or_woody,
+            life_cycle: plant.life_cycle,
+            growth_rate: plant.growth_rate,
+            height: plant.height,
+            fertility: plant.fertility,
+            has_drought_tolerance: plant.has_drought_tolerance,
+            tolerates_wind: plant.tolerates_wind,
+            preferable_permaculture_zone: plant.preferable_permaculture_zone,
+            hardiness_zone: plant.hardiness_zone,
+            light_requirement: plant.light_requirement,
+            water_requirement: plant.water_requirement,
+            propagation_method: plant.propagation_method,
+            alternate_name: plant.alternate_name,
+            edible: plant.edible,
+            edible_parts: plant.edible_parts,
+            spread: plant.spread,
+            warning: plant.warning,
+            version: Some(version),
+            external_source: Some(external_source),
+            sowing_outdoors: plant.sowing_outdoors,
+            harvest_time: plant.harvest_time,
+            seed_weight_1000: plant.seed_weight_1000,
+            owner_id: None,
+            base_plant_id: None,
+            nutrition_demand: plant.nutrition_demand,
+        }
+    }
+}
@@ -1,22 +1,97 @@
-//! Contains the implementation of [`PlantsSummaryDto`].
+//! Contains the implementation of [`PlantsSummaryDto`] and [`PlantDetailDto`].
 
-use crate::model::entity::Plants;
+use crate::model::entity::{PlantDiversity, Plants};
 
-use super::PlantsSummaryDto;
+use super::{Language, PlantDetailDto, PlantsSummaryDto};
 
-impl From<Plants> for PlantsSummaryDto {
-    fn from(plants: Plants) -> Self {
+impl PlantsSummaryDto {
+    /// Create the summary of a plant with its common names in the requested `language`.
+    #[must_use]
+    pub fn localized(plants: Plants, language: Language) -> Self {
+        let common_name = match language {
+            Language::De if plants.common_name_de.is_some() => plants.common_name_de,
+            Language::En | Language::De => plants.common_name_en.clone(),
+        };
         Self {
             id: plants.id,
             unique_name: plants.unique_name,
             common_name_en: plants.common_name_en,
+            common_name,
             spread: plants.spread,
         }
     }
 }
 
-impl<T> From<(T, Plants)> for PlantsSummaryDto {
-    fn from((_, plants): (T, Plants)) -> Self {
-        Self::from(plants)
+impl From<Plants> for PlantsSummaryDto {
+    fn from(plants: Plants) -> Self {
+        Self::localized(plants, Language::default())
+    }
+}
+
+impl From<PlantDiversity> for PlantsSummaryDto {
+    fn from(plant: PlantDiversity) -> Self {
+        Self {
+            id: plant.id,
+            unique_name: plant.unique_name,
+            common_name: plant.common_name_en.clone(),
+            common_name_en: plant.common_name_en,
+            spread: plant.spread,
+        }
+    }
+}
+
+impl From<Plants> for PlantDetailDto {
+    fn from(plants: Plants) -> Self {
+        Self {
+            id: plants.id,
+            unique_name: plants.unique_name,
+            common_name_en: plants.common_name_en,
+            common_name_de: plants.common_name_de,
+            family: plants.family,
+            genus: plants.genus,
+            edible_uses_en: plants.edible_uses_en,
+            functions: plants.functions,
+            material_uses_and_functions: plants.material_uses_and_functions,
+            botanic: plants.botanic,
+            heat_zone: plants.heat_zone,
+            shade: plants.shade,
+            soil_ph: plants.soil_ph,
+            soil_texture: plants.soil_texture,
+            soil_water_retention: plants.soil_water_retention,
+            ecosystem_niche: plants.ecosystem_niche,
+            deciduous_or_evergreen: plants.deciduous_or_evergreen,
+            herbaceous_or_woody: plants.herbaceous_or_woody,
+            is_tree: plants.is_tree,
+            life_cycle: plants.life_cycle,
+            growth_rate: plants.growth_rate,
+            height: plants.height,
+            fertility: plants.fertility,
+            flower_type: plants.flower_type,
+            has_drought_tolerance: plants.has_drought_tolerance,
+            tolerates_wind: plants.tolerates_wind,
+            preferable_permaculture_zone: plants.preferable_permaculture_zone,
+            hardiness_zone: plants.hardiness_zone,
+            light_requirement: plants.light_requirement,
+            water_requirement: plants.water_requirement,
+            propagation_method: plants.propagation_method,
+            alternate_name: plants.alternate_name,
+            edible: plants.edible,
+            edible_parts: plants.edible_parts,
+            spread: plants.spread,
+            warning: plants.warning,
+            sowing_outdoors: plants.sowing_outdoors,
+            harvest_time: plants.harvest_time,
+            seed_weight_1000: plants.seed_weight_1000,
+            nutrition_demand: plants.nutrition_demand,
+            external_source: plants.external_source,
+            plant_references: plants.plant_references,
+            article_last_modified_at: plants.article_last_modified_at,
+            version: plants.version,
+            created_at: plants.created_at,
+            updated_at: plants.updated_at,
+            owner_id: plants.owner_id,
+            base_plant_id: plants.base_plant_id,
+            submitted_for_review: plants.submitted_for_review,
+        }
     }
 }
@@ -22,6 +22,7 @@ impl From<Seed> for SeedDto {
             price: seed.price,
             notes: seed.notes,
             owner_id: seed.owner_id,
+            parent_seed_id: seed.parent_seed_id,
         }
     }
 }
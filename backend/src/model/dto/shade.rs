@@ -0,0 +1,132 @@
+//! All DTOs associated with the shade layer.
+
+use chrono::NaiveDate;
+use postgis_diesel::types::{Point, Polygon};
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::model::r#enum::shade::Shade;
+
+/// An obstacle casting shade, e.g. a house, a wall or an existing tree.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ShadeObstacleDto {
+    /// The id of the obstacle.
+    pub id: Uuid,
+    /// The shade layer the obstacle is on.
+    pub layer_id: i32,
+    /// The name of the obstacle.
+    pub name: String,
+    /// The footprint of the obstacle.
+    ///
+    /// E.g. `{"rings": [[{"x": 0.0,"y": 0.0},{"x": 500.0,"y": 0.0},{"x": 500.0,"y": 300.0},{"x": 0.0,"y": 300.0},{"x": 0.0,"y": 0.0}]],"srid": 4326}`
+    #[typeshare(serialized_as = "object")]
+    #[schema(value_type = Object)]
+    pub geometry: Polygon<Point>,
+    /// The height of the obstacle in cm.
+    pub height: i32,
+}
+
+/// Query parameters for searching shade obstacles.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ShadeObstacleSearchParameters {
+    /// The id of the shade layer the obstacles are on.
+    pub layer_id: i32,
+}
+
+/// Used to create a new shade obstacle.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NewShadeObstacleDto {
+    /// The id of the obstacle.
+    pub id: Option<Uuid>,
+    /// The shade layer the obstacle is on.
+    pub layer_id: i32,
+    /// The name of the obstacle.
+    pub name: String,
+    /// The footprint of the obstacle.
+    #[typeshare(serialized_as = "object")]
+    #[schema(value_type = Object)]
+    pub geometry: Polygon<Point>,
+    /// The height of the obstacle in cm.
+    pub height: i32,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to partially update a shade obstacle.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateShadeObstacleDto {
+    /// The name of the obstacle.
+    pub name: Option<String>,
+    /// The footprint of the obstacle.
+    #[typeshare(serialized_as = "Option<object>")]
+    #[schema(value_type = Option<Object>)]
+    pub geometry: Option<Polygon<Point>>,
+    /// The height of the obstacle in cm.
+    pub height: Option<i32>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to delete a shade obstacle.
+/// The id of the obstacle is passed in the path.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteShadeObstacleDto {
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Query parameters for computing the shade of a shade layer.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ShadeRasterParameters {
+    /// The id of the shade layer with the obstacles.
+    pub layer_id: i32,
+    /// The day to follow the sun on.
+    /// Defaults to today.
+    pub date: Option<NaiveDate>,
+    /// The size of a cell of the raster in cm.
+    /// Defaults to 100 cm.
+    pub resolution: Option<i32>,
+}
+
+/// The hours of direct sunlight across the map on a day.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ShadeRasterDto {
+    /// The x coordinate of the left edge of the raster.
+    pub x_min: i32,
+    /// The y coordinate of the top edge of the raster.
+    pub y_min: i32,
+    /// The size of a cell in cm.
+    pub resolution: i32,
+    /// The day the sun was followed on.
+    pub date: NaiveDate,
+    /// The rows of cells from top to bottom, each from left to right.
+    /// Cells outside of the map geometry are `None`.
+    pub cells: Vec<Vec<Option<ShadeCellDto>>>,
+}
+
+/// The light conditions of a cell of a [`ShadeRasterDto`].
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ShadeCellDto {
+    /// The hours the cell gets direct sunlight.
+    pub sun_hours: f32,
+    /// The hours the sun is up but the cell is shaded.
+    pub shade_hours: f32,
+    /// The kind of shade the cell is in, as used for plant requirements.
+    pub shade: Shade,
+}
@@ -0,0 +1,41 @@
+//! Contains the implementation of [`ShadeObstacleDto`].
+
+use uuid::Uuid;
+
+use crate::model::entity::shade_obstacles::{ShadeObstacle, UpdateShadeObstacle};
+
+use super::shade::{NewShadeObstacleDto, ShadeObstacleDto, UpdateShadeObstacleDto};
+
+impl From<ShadeObstacle> for ShadeObstacleDto {
+    fn from(obstacle: ShadeObstacle) -> Self {
+        Self {
+            id: obstacle.id,
+            layer_id: obstacle.layer_id,
+            name: obstacle.name,
+            geometry: obstacle.geometry,
+            height: obstacle.height,
+        }
+    }
+}
+
+impl From<NewShadeObstacleDto> for ShadeObstacle {
+    fn from(new_obstacle: NewShadeObstacleDto) -> Self {
+        Self {
+            id: new_obstacle.id.unwrap_or_else(Uuid::new_v4),
+            layer_id: new_obstacle.layer_id,
+            name: new_obstacle.name,
+            geometry: new_obstacle.geometry,
+            height: new_obstacle.height,
+        }
+    }
+}
+
+impl From<UpdateShadeObstacleDto> for UpdateShadeObstacle {
+    fn from(update_obstacle: UpdateShadeObstacleDto) -> Self {
+        Self {
+            name: update_obstacle.name,
+            geometry: update_obstacle.geometry,
+            height: update_obstacle.height,
+        }
+    }
+}
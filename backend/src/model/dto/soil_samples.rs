@@ -0,0 +1,150 @@
+//! All DTOs associated with [`SoilSampleDto`].
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::model::r#enum::{
+    soil_ph::SoilPh, soil_texture::SoilTexture, soil_water_retention::SoilWaterRetention,
+};
+
+/// A soil sample taken at a position of the map.
+/// All measurements are optional, as not every sample is analysed completely.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SoilSampleDto {
+    /// The id of the sample.
+    pub id: Uuid,
+    /// The soil layer the sample is on.
+    pub layer_id: i32,
+    /// The x coordinate of the position the sample was taken at.
+    pub x: i32,
+    /// The y coordinate of the position the sample was taken at.
+    pub y: i32,
+    /// The measured pH value (0-14).
+    pub ph: Option<f32>,
+    /// The texture of the soil.
+    pub soil_texture: Option<SoilTexture>,
+    /// How well the soil holds water.
+    pub water_retention: Option<SoilWaterRetention>,
+    /// The percentage (0-100) of organic matter in the soil.
+    pub organic_matter: Option<f32>,
+    /// The date the sample was taken.
+    pub sample_date: Option<NaiveDate>,
+}
+
+/// Query parameters for searching soil samples.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SoilSampleSearchParameters {
+    /// The id of the soil layer the samples are on.
+    pub layer_id: i32,
+}
+
+/// Used to create a new soil sample.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NewSoilSampleDto {
+    /// The id of the sample.
+    pub id: Option<Uuid>,
+    /// The soil layer the sample is on.
+    pub layer_id: i32,
+    /// The x coordinate of the position the sample was taken at.
+    pub x: i32,
+    /// The y coordinate of the position the sample was taken at.
+    pub y: i32,
+    /// The measured pH value (0-14).
+    pub ph: Option<f32>,
+    /// The texture of the soil.
+    pub soil_texture: Option<SoilTexture>,
+    /// How well the soil holds water.
+    pub water_retention: Option<SoilWaterRetention>,
+    /// The percentage (0-100) of organic matter in the soil.
+    pub organic_matter: Option<f32>,
+    /// The date the sample was taken.
+    pub sample_date: Option<NaiveDate>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to replace the position and measurements of a soil sample.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSoilSampleDto {
+    /// The x coordinate of the position the sample was taken at.
+    pub x: i32,
+    /// The y coordinate of the position the sample was taken at.
+    pub y: i32,
+    /// The measured pH value (0-14).
+    pub ph: Option<f32>,
+    /// The texture of the soil.
+    pub soil_texture: Option<SoilTexture>,
+    /// How well the soil holds water.
+    pub water_retention: Option<SoilWaterRetention>,
+    /// The percentage (0-100) of organic matter in the soil.
+    pub organic_matter: Option<f32>,
+    /// The date the sample was taken.
+    pub sample_date: Option<NaiveDate>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to delete a soil sample.
+/// The id of the sample is passed in the path.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteSoilSampleDto {
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Query parameters for interpolating the samples of a soil layer.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SoilRasterParameters {
+    /// The id of the soil layer the samples are on.
+    pub layer_id: i32,
+    /// The size of a cell of the raster in cm.
+    /// Defaults to 100 cm.
+    pub resolution: Option<i32>,
+}
+
+/// The soil properties interpolated from the samples across the map.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SoilRasterDto {
+    /// The x coordinate of the left edge of the raster.
+    pub x_min: i32,
+    /// The y coordinate of the top edge of the raster.
+    pub y_min: i32,
+    /// The size of a cell in cm.
+    pub resolution: i32,
+    /// The rows of cells from top to bottom, each from left to right.
+    /// Cells outside of the map geometry are `None`.
+    pub cells: Vec<Vec<Option<SoilCellDto>>>,
+}
+
+/// The interpolated soil properties of a cell of a [`SoilRasterDto`].
+/// A property is `None` if no sample measured it.
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SoilCellDto {
+    /// The pH value.
+    pub ph: Option<f32>,
+    /// The pH range the pH value falls into, as used for plant requirements.
+    pub soil_ph: Option<SoilPh>,
+    /// The most likely texture of the soil.
+    pub soil_texture: Option<SoilTexture>,
+    /// The most likely water retention of the soil.
+    pub water_retention: Option<SoilWaterRetention>,
+    /// The percentage of organic matter.
+    pub organic_matter: Option<f32>,
+}
@@ -0,0 +1,53 @@
+//! Contains the implementation of [`SoilSampleDto`].
+
+use uuid::Uuid;
+
+use crate::model::entity::soil_samples::{SoilSample, UpdateSoilSample};
+
+use super::soil_samples::{NewSoilSampleDto, SoilSampleDto, UpdateSoilSampleDto};
+
+impl From<SoilSample> for SoilSampleDto {
+    fn from(sample: SoilSample) -> Self {
+        Self {
+            id: sample.id,
+            layer_id: sample.layer_id,
+            x: sample.x,
+            y: sample.y,
+            ph: sample.ph,
+            soil_texture: sample.soil_texture,
+            water_retention: sample.water_retention,
+            organic_matter: sample.organic_matter,
+            sample_date: sample.sample_date,
+        }
+    }
+}
+
+impl From<NewSoilSampleDto> for SoilSample {
+    fn from(new_sample: NewSoilSampleDto) -> Self {
+        Self {
+            id: new_sample.id.unwrap_or_else(Uuid::new_v4),
+            layer_id: new_sample.layer_id,
+            x: new_sample.x,
+            y: new_sample.y,
+            ph: new_sample.ph,
+            soil_texture: new_sample.soil_texture,
+            water_retention: new_sample.water_retention,
+            organic_matter: new_sample.organic_matter,
+            sample_date: new_sample.sample_date,
+        }
+    }
+}
+
+impl From<UpdateSoilSampleDto> for UpdateSoilSample {
+    fn from(update_sample: UpdateSoilSampleDto) -> Self {
+        Self {
+            x: update_sample.x,
+            y: update_sample.y,
+            ph: update_sample.ph,
+            soil_texture: update_sample.soil_texture,
+            water_retention: update_sample.water_retention,
+            organic_matter: update_sample.organic_matter,
+            sample_date: update_sample.sample_date,
+        }
+    }
+}
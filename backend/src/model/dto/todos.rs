@@ -0,0 +1,131 @@
+//! All DTOs associated with [`TodoDto`].
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::model::r#enum::todo_status::TodoStatus;
+
+/// A task pinned to a position on the map or to a planting.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoDto {
+    /// The id of the todo.
+    pub id: Uuid,
+    /// The todo layer the todo is on.
+    pub layer_id: i32,
+    /// The x coordinate of the position the todo is pinned to.
+    pub x: Option<i32>,
+    /// The y coordinate of the position the todo is pinned to.
+    pub y: Option<i32>,
+    /// The planting the todo is pinned to.
+    pub planting_id: Option<Uuid>,
+    /// The title of the todo.
+    pub title: String,
+    /// The description of the todo.
+    pub description: Option<String>,
+    /// The date the todo should be done by.
+    pub due_date: Option<NaiveDate>,
+    /// The id of the collaborator who should do the todo.
+    pub assignee_id: Option<Uuid>,
+    /// The progress of the todo.
+    pub status: TodoStatus,
+    /// The number of days after which the todo is due again once it is done,
+    /// e.g. 3 for watering every 3 days.
+    pub recurrence_days: Option<i32>,
+    /// The id of the user who created the todo.
+    pub created_by: Uuid,
+}
+
+/// A todo assigned to the user together with the map it is on.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignedTodoDto {
+    /// The map the todo is on.
+    pub map_id: i32,
+    /// The todo.
+    pub todo: TodoDto,
+}
+
+/// Query parameters for searching todos.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TodoSearchParameters {
+    /// The id of the todo layer the todos are on.
+    pub layer_id: i32,
+    /// Only return todos with this status.
+    pub status: Option<TodoStatus>,
+}
+
+/// Used to create a new todo.
+/// A todo is pinned either to the position `x`,`y` or to a planting.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NewTodoDto {
+    /// The id of the todo.
+    pub id: Option<Uuid>,
+    /// The todo layer the todo is on.
+    pub layer_id: i32,
+    /// The x coordinate of the position the todo is pinned to.
+    pub x: Option<i32>,
+    /// The y coordinate of the position the todo is pinned to.
+    pub y: Option<i32>,
+    /// The planting the todo is pinned to.
+    pub planting_id: Option<Uuid>,
+    /// The title of the todo.
+    pub title: String,
+    /// The description of the todo.
+    pub description: Option<String>,
+    /// The date the todo should be done by.
+    pub due_date: Option<NaiveDate>,
+    /// The id of the collaborator who should do the todo.
+    pub assignee_id: Option<Uuid>,
+    /// The number of days after which the todo is due again once it is done.
+    pub recurrence_days: Option<i32>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to replace the content of a todo.
+///
+/// Setting a recurring todo to done reopens it, due after its recurrence.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTodoDto {
+    /// The x coordinate of the position the todo is pinned to.
+    pub x: Option<i32>,
+    /// The y coordinate of the position the todo is pinned to.
+    pub y: Option<i32>,
+    /// The planting the todo is pinned to.
+    pub planting_id: Option<Uuid>,
+    /// The title of the todo.
+    pub title: String,
+    /// The description of the todo.
+    pub description: Option<String>,
+    /// The date the todo should be done by.
+    pub due_date: Option<NaiveDate>,
+    /// The id of the collaborator who should do the todo.
+    pub assignee_id: Option<Uuid>,
+    /// The progress of the todo.
+    pub status: TodoStatus,
+    /// The number of days after which the todo is due again once it is done.
+    pub recurrence_days: Option<i32>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Used to delete a todo.
+/// The id of the todo is passed in the path.
+#[typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTodoDto {
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
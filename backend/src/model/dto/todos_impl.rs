@@ -0,0 +1,62 @@
+//! Contains the implementation of [`TodoDto`].
+
+use uuid::Uuid;
+
+use crate::model::entity::todos::{Todo, UpdateTodo};
+use crate::model::r#enum::todo_status::TodoStatus;
+
+use super::todos::{NewTodoDto, TodoDto, UpdateTodoDto};
+
+impl From<Todo> for TodoDto {
+    fn from(todo: Todo) -> Self {
+        Self {
+            id: todo.id,
+            layer_id: todo.layer_id,
+            x: todo.x,
+            y: todo.y,
+            planting_id: todo.planting_id,
+            title: todo.title,
+            description: todo.description,
+            due_date: todo.due_date,
+            assignee_id: todo.assignee_id,
+            status: todo.status,
+            recurrence_days: todo.recurrence_days,
+            created_by: todo.created_by,
+        }
+    }
+}
+
+impl From<(NewTodoDto, Uuid)> for Todo {
+    fn from((new_todo, user_id): (NewTodoDto, Uuid)) -> Self {
+        Self {
+            id: new_todo.id.unwrap_or_else(Uuid::new_v4),
+            layer_id: new_todo.layer_id,
+            x: new_todo.x,
+            y: new_todo.y,
+            planting_id: new_todo.planting_id,
+            title: new_todo.title,
+            description: new_todo.description,
+            due_date: new_todo.due_date,
+            assignee_id: new_todo.assignee_id,
+            status: TodoStatus::Open,
+            recurrence_days: new_todo.recurrence_days,
+            created_by: user_id,
+        }
+    }
+}
+
+impl From<UpdateTodoDto> for UpdateTodo {
+    fn from(update_todo: UpdateTodoDto) -> Self {
+        Self {
+            x: update_todo.x,
+            y: update_todo.y,
+            planting_id: update_todo.planting_id,
+            title: update_todo.title,
+            description: update_todo.description,
+            due_date: update_todo.due_date,
+            assignee_id: update_todo.assignee_id,
+            status: update_todo.status,
+            recurrence_days: update_todo.recurrence_days,
+        }
+    }
+}
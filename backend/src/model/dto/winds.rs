@@ -0,0 +1,97 @@
+//! All DTOs associated with [`WindRoseDto`].
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// The prevailing winds of a map, as share of the time and mean speed per direction.
+/// The time not covered by any direction is calm.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WindRoseDto {
+    /// The winds layer the wind rose is on.
+    pub layer_id: i32,
+    /// The directions, ordered clockwise from north.
+    pub entries: Vec<WindRoseEntryDto>,
+}
+
+/// How often and how strong the wind comes from a direction.
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WindRoseEntryDto {
+    /// The compass direction the wind comes from in degrees (0-359), clockwise from north.
+    pub direction: i32,
+    /// The share of the time in percent (0-100) the wind comes from the direction.
+    pub frequency: f32,
+    /// The mean speed in m/s of the wind from the direction.
+    pub speed: f32,
+}
+
+/// Query parameters for getting the wind rose of a winds layer.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct WindRoseSearchParameters {
+    /// The id of the winds layer.
+    pub layer_id: i32,
+}
+
+/// Used to replace the wind rose of a winds layer.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWindRoseDto {
+    /// The winds layer the wind rose is on.
+    pub layer_id: i32,
+    /// The directions, each direction at most once.
+    pub entries: Vec<WindRoseEntryDto>,
+    /// Id of the action (for identifying the action in the frontend).
+    pub action_id: Uuid,
+}
+
+/// Query parameters for computing the wind exposure of the map.
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct WindRasterParameters {
+    /// The id of the winds layer with the wind rose.
+    pub layer_id: i32,
+    /// The day the plantings acting as windbreaks exist on.
+    /// Defaults to today.
+    pub date: Option<NaiveDate>,
+    /// The size of a cell of the raster in cm.
+    /// Defaults to 100 cm.
+    pub resolution: Option<i32>,
+}
+
+/// The wind exposure across the map, reduced by plantings acting as windbreaks.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WindRasterDto {
+    /// The x coordinate of the left edge of the raster.
+    pub x_min: i32,
+    /// The y coordinate of the top edge of the raster.
+    pub y_min: i32,
+    /// The size of a cell in cm.
+    pub resolution: i32,
+    /// The day the windbreaks were taken from.
+    pub date: NaiveDate,
+    /// The rows of cells from top to bottom, each from left to right.
+    /// Cells outside of the map geometry are `None`.
+    pub cells: Vec<Vec<Option<WindCellDto>>>,
+}
+
+/// The wind at a cell of a [`WindRasterDto`].
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WindCellDto {
+    /// The mean wind speed in m/s, including calm periods.
+    pub speed: f32,
+    /// The mean wind speed relative to open ground (0-1),
+    /// where 0 is fully sheltered and 1 is not sheltered at all.
+    pub exposure: f32,
+}
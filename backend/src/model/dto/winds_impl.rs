@@ -0,0 +1,26 @@
+//! Contains the implementation of [`WindRoseDto`].
+
+use crate::model::entity::wind_rose_entries::WindRoseEntry;
+
+use super::winds::WindRoseEntryDto;
+
+impl From<WindRoseEntry> for WindRoseEntryDto {
+    fn from(entry: WindRoseEntry) -> Self {
+        Self {
+            direction: entry.direction,
+            frequency: entry.frequency,
+            speed: entry.speed,
+        }
+    }
+}
+
+impl From<(i32, WindRoseEntryDto)> for WindRoseEntry {
+    fn from((layer_id, entry): (i32, WindRoseEntryDto)) -> Self {
+        Self {
+            layer_id,
+            direction: entry.direction,
+            frequency: entry.frequency,
+            speed: entry.speed,
+        }
+    }
+}
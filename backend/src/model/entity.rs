@@ -2,43 +2,63 @@
 
 pub mod base_layer_images_impl;
 pub mod blossoms_impl;
+pub mod drawings;
+pub mod drawings_impl;
 pub mod guided_tours_impl;
+pub mod guilds;
+pub mod guilds_impl;
+pub mod harvests_impl;
 pub mod layer_impl;
+pub mod map_collaborators;
+pub mod map_collaborators_impl;
 pub mod map_impl;
+pub mod photos;
+pub mod photos_impl;
 pub mod plant_layer;
 pub mod plantings;
 pub mod plantings_impl;
 pub mod plants_impl;
 pub mod seed_impl;
+pub mod shade_obstacles;
+pub mod shade_obstacles_impl;
+pub mod soil_samples;
+pub mod soil_samples_impl;
+pub mod todos;
+pub mod todos_impl;
 pub mod users_impl;
+pub mod wind_rose_entries;
+pub mod wind_rose_entries_impl;
 
 use chrono::NaiveDate;
 use chrono::NaiveDateTime;
 
 use diesel::AsChangeset;
 use diesel::QueryableByName;
-use diesel::{Identifiable, Insertable, Queryable};
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
 use postgis_diesel::types::Point;
 use postgis_diesel::types::Polygon;
 use uuid::Uuid;
 
 use crate::schema::{
-    base_layer_images, blossoms, gained_blossoms, guided_tours, layers, maps, plants, seeds, users,
+    base_layer_images, blossoms, gained_blossoms, guided_tours, harvests, layers, maps, plants,
+    seeds, users,
 };
 
 use super::r#enum::experience::Experience;
+use super::r#enum::harvest_quality::HarvestQuality;
+use super::r#enum::harvest_unit::HarvestUnit;
 use super::r#enum::membership::Membership;
 use super::r#enum::privacy_option::PrivacyOption;
 use super::r#enum::salutation::Salutation;
 use super::r#enum::track::Track;
 use super::r#enum::{
     deciduous_or_evergreen::DeciduousOrEvergreen, external_source::ExternalSource,
-    fertility::Fertility, /*flower_type::FlowerType, */ growth_rate::GrowthRate,
+    fertility::Fertility, flower_type::FlowerType, growth_rate::GrowthRate,
     herbaceous_or_woody::HerbaceousOrWoody, layer_type::LayerType, life_cycle::LifeCycle,
-    light_requirement::LightRequirement, /*nutrition_demand::NutritionDemand,*/
+    light_requirement::LightRequirement, nutrition_demand::NutritionDemand,
     plant_height::PlantHeight, plant_spread::PlantSpread, propagation_method::PropagationMethod,
     quality::Quality, quantity::Quantity, shade::Shade, soil_ph::SoilPh, soil_texture::SoilTexture,
-    /*soil_water_retention::SoilWaterRetention, */ water_requirement::WaterRequirement,
+    soil_water_retention::SoilWaterRetention, water_requirement::WaterRequirement,
 };
 
 /// The `Plants` entity builds up an hierarchical structure, see `/doc/database/hierarchy.md`:
@@ -74,17 +94,6 @@ pub struct Plants {
     /// - *Fill ratio:* 88%
     pub family: Option<String>,
 
-    /*
-    /// - Genus of the plant.
-    /// - Is also first word of unique_name, see above (`/doc/database/hierarchy.md`)
-    /// - *See also* `/doc/architecture/glossary.md`.
-    /// - *Used* to build up hierarchy.
-    /// - Genus is determined by botanical nomenclature. Sometimes gets changed due to modern insights through genetical research.
-    /// - *Fetched from* PracticalPlants and Permapeople.
-    /// - *TODO:* copy from first word of unique name.
-    /// - *Fill ratio:* 63%
-    pub genus: Option<String>,
-    */
     /// - The edible use of the plant, answering: Which food type can be produced from this plant, e.g. oil?
     /// - Interesting for search functionality.
     /// - *Fetched from* Permapeople as `edible_uses` and merged with Reinsaat.
@@ -96,16 +105,6 @@ pub struct Plants {
     /// - *Fetched from* PracticalPlants as `medicinal_uses` and merged with Permapeople.
     /// - *Fill ratio:* 1%
     //pub medicinal_uses: Option<String>,
-
-    /// - Only for references.
-    /// - *Fetched from* PracticalPlants)
-    /// - *Fill ratio:* 34%
-    pub material_uses_and_functions: Option<String>,
-
-    /// - Only for references.
-    /// - *Fetched from* PracticalPlants)
-    /// - *Fill ratio:* 63%
-    pub botanic: Option<String>,
     */
 
     /*
@@ -156,15 +155,6 @@ pub struct Plants {
     /// - *Fill ratio:* 88%
     pub soil_texture: Option<Vec<Option<SoilTexture>>>,
 
-    /*
-    /// - *Used* in hydrology layer.
-    /// - *Fetched from* PracticalPlants
-    /// - wet = drowned, (often) flooded or in general very moist, e.g. swamp
-    /// - moist = humid, can hold some water, e.g. flat bed with humus
-    /// - well drained = dry, low capacity to hold water, e.g. sandhill.
-    /// - *Fill ratio:* 37%
-    pub soil_water_retention: Option<Vec<Option<SoilWaterRetention>>>,
-    */
     /*
     /// - Only informational.
     /// - *Fetched from* PracticalPlants
@@ -238,13 +228,7 @@ pub struct Plants {
     /// - *Fetched from* PracticalPlants
     /// - *Fill ratio:* 0.5%
     //pub flower_colour: Option<String>,
-
-    /// - Only informational.
-    /// - *Fetched from* PracticalPlants
-    /// - a plant can contain flowers of two different sexes, male or female (monoecious), a plant can contain only flowers of one specific sex and therefore needs at least another plant of the other sex to reproduce (dioecious) or can contain flowers that have both the sexes within the same flower (hermaphrodite).
-    /// - *Fill ratio:* 62%
-    pub flower_type: Option<FlowerType>,
-    */
+     */
     /// - The creation date of the entry.
     /// - Only for administration.
     /// - *Fill ratio:* 100%
@@ -265,38 +249,10 @@ pub struct Plants {
     /// - *Fill ratio:* 10%
     pub tolerates_wind: Option<bool>,
 
-    /*
-    /// - The list of the references of the plant.
-    /// - `references` items link to these items.
-    /// - Only informational.
-    /// - *Fill ratio:* 58%
-    pub plant_references: Option<Vec<Option<String>>>,
-    */
-
-    /*
-    /// - Boolean value indicating whether the plant is a tree.
-    /// - Plants with `is_tree == true` can be used in the tree layer.
-    /// - In plants layer all plants can be used.
-    /// - *Initial value* is to `True` if  herbaceous_or_woody (woody) and life_cycle (perennial)
-    /// - *Fill ratio:* 0.1%
-    pub is_tree: Option<bool>,
-
-    /// - Only informational.
-    /// - *Initial value* is to `light feeder` if "Nutritionally poor soil" in `environmental_tolerances` is present.
-    /// - *Fill ratio:* 0.04%
-    pub nutrition_demand: Option<NutritionDemand>,
-    */
     /// - Not used.
     /// - Number value between -1..6 (-1 should be printed as 00)
     /// - *Fill ratio:* 0%
     pub preferable_permaculture_zone: Option<i16>,
-    /*
-    /// - When article was modified last time.
-    /// - Only for administration.
-    /// - Date value fetched from PracticalPlants page showing the last modification date of the plant.
-    /// - *Fill ratio:* 63%
-    pub article_last_modified_at: Option<NaiveDateTime>,
-    */
     /// - USDA Hardiness Zone (without subranges).
     /// - Important information.
     /// - Fetched from PracticalPlants and Permapeople (merged with usda_hardiness_zone of Permapeople).
@@ -681,6 +637,142 @@ pub struct Plants {
     /// - *Fill ratio:* 6%
     //pub edible_uses_de: Option<String>,
     */
+    /// - Genus of the plant.
+    /// - Is also first word of `unique_name`, see above (`/doc/database/hierarchy.md`)
+    /// - *See also* `/doc/architecture/glossary.md`.
+    /// - *Used* to build up hierarchy.
+    /// - Genus is determined by botanical nomenclature. Sometimes gets changed due to modern insights through genetical research.
+    /// - *Fetched from* PracticalPlants and Permapeople.
+    /// - *TODO:* copy from first word of unique name.
+    /// - *Fill ratio:* 63%
+    pub genus: Option<String>,
+
+    /// - Only for references.
+    /// - *Fetched from* PracticalPlants)
+    /// - *Fill ratio:* 34%
+    pub material_uses_and_functions: Option<String>,
+
+    /// - Only for references.
+    /// - *Fetched from* PracticalPlants)
+    /// - *Fill ratio:* 63%
+    pub botanic: Option<String>,
+
+    /// - *Used* in hydrology layer.
+    /// - *Fetched from* PracticalPlants
+    /// - wet = drowned, (often) flooded or in general very moist, e.g. swamp
+    /// - moist = humid, can hold some water, e.g. flat bed with humus
+    /// - well drained = dry, low capacity to hold water, e.g. sandhill.
+    /// - *Fill ratio:* 37%
+    pub soil_water_retention: Option<Vec<Option<SoilWaterRetention>>>,
+
+    /// - Only informational.
+    /// - *Fetched from* PracticalPlants
+    /// - a plant can contain flowers of two different sexes, male or female (monoecious), a plant can contain only flowers of one specific sex and therefore needs at least another plant of the other sex to reproduce (dioecious) or can contain flowers that have both the sexes within the same flower (hermaphrodite).
+    /// - *Fill ratio:* 62%
+    pub flower_type: Option<FlowerType>,
+
+    /// - The list of the references of the plant.
+    /// - `references` items link to these items.
+    /// - Only informational.
+    /// - *Fill ratio:* 58%
+    pub plant_references: Option<Vec<Option<String>>>,
+
+    /// - Boolean value indicating whether the plant is a tree.
+    /// - Plants with `is_tree == true` can be used in the tree layer.
+    /// - In plants layer all plants can be used.
+    /// - *Initial value* is to `True` if  `herbaceous_or_woody` (woody) and `life_cycle` (perennial)
+    /// - *Fill ratio:* 0.1%
+    pub is_tree: Option<bool>,
+
+    /// - When article was modified last time.
+    /// - Only for administration.
+    /// - Date value fetched from PracticalPlants page showing the last modification date of the plant.
+    /// - *Fill ratio:* 63%
+    pub article_last_modified_at: Option<NaiveDateTime>,
+
+    /// - The user owning this custom plant or variety.
+    /// - Custom plants are only visible to their owner.
+    /// - `None` for plants of the shared catalogue.
+    pub owner_id: Option<Uuid>,
+
+    /// - The plant this custom plant or variety inherited its attributes from.
+    pub base_plant_id: Option<i32>,
+
+    /// - Whether the owner submitted this custom plant for review to be added to the shared catalogue.
+    pub submitted_for_review: bool,
+
+    /// - Used for crop rotation, heavy feeders should not follow each other.
+    /// - *Initial value* is to `light feeder` if "Nutritionally poor soil" in `environmental_tolerances` is present.
+    /// - *Fill ratio:* 0.04%
+    pub nutrition_demand: Option<NutritionDemand>,
+}
+
+/// The `NewPlants` entity.
+///
+/// Used to create custom plants or varieties of users and to import plants into the catalogue.
+#[allow(clippy::missing_docs_in_private_items)] // Same fields as `Plants`.
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = plants, treat_none_as_null = true)]
+pub struct NewPlants {
+    pub unique_name: String,
+    pub common_name_en: Option<Vec<Option<String>>>,
+    pub common_name_de: Option<Vec<Option<String>>>,
+    pub family: Option<String>,
+    pub edible_uses_en: Option<String>,
+    pub functions: Option<String>,
+    pub heat_zone: Option<i16>,
+    pub shade: Option<Shade>,
+    pub soil_ph: Option<Vec<Option<SoilPh>>>,
+    pub soil_texture: Option<Vec<Option<SoilTexture>>>,
+    pub ecosystem_niche: Option<String>,
+    pub deciduous_or_evergreen: Option<DeciduousOrEvergreen>,
+    pub herbaceous_or_woody: Option<HerbaceousOrWoody>,
+    pub life_cycle: Option<Vec<Option<LifeCycle>>>,
+    pub growth_rate: Option<Vec<Option<GrowthRate>>>,
+    pub height: Option<PlantHeight>,
+    pub fertility: Option<Vec<Option<Fertility>>>,
+    pub has_drought_tolerance: Option<bool>,
+    pub tolerates_wind: Option<bool>,
+    pub preferable_permaculture_zone: Option<i16>,
+    pub hardiness_zone: Option<String>,
+    pub light_requirement: Option<Vec<Option<LightRequirement>>>,
+    pub water_requirement: Option<Vec<Option<WaterRequirement>>>,
+    pub propagation_method: Option<Vec<Option<PropagationMethod>>>,
+    pub alternate_name: Option<String>,
+    pub edible: Option<bool>,
+    pub edible_parts: Option<Vec<Option<String>>>,
+    pub spread: Option<PlantSpread>,
+    pub warning: Option<String>,
+    pub version: Option<i16>,
+    pub external_source: Option<ExternalSource>,
+    pub sowing_outdoors: Option<Vec<Option<i16>>>,
+    pub harvest_time: Option<Vec<Option<i16>>>,
+    pub seed_weight_1000: Option<f64>,
+    pub owner_id: Option<Uuid>,
+    pub base_plant_id: Option<i32>,
+    pub nutrition_demand: Option<NutritionDemand>,
+}
+
+/// The attributes of a plant that describe its contribution to the diversity of a planting.
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = plants)]
+pub struct PlantDiversity {
+    /// The id of the plant.
+    pub id: i32,
+    /// The unique name of the plant.
+    pub unique_name: String,
+    /// The English common names of the plant.
+    pub common_name_en: Option<Vec<Option<String>>>,
+    /// How far the plant spreads.
+    pub spread: Option<PlantSpread>,
+    /// The family of the plant.
+    pub family: Option<String>,
+    /// Comma separated ecological functions of the plant, e.g. nitrogen fixer.
+    pub functions: Option<String>,
+    /// Comma separated vertical layers the plant inhabits, e.g. canopy.
+    pub ecosystem_niche: Option<String>,
+    /// Whether the plant is herbaceous or woody.
+    pub herbaceous_or_woody: Option<HerbaceousOrWoody>,
 }
 /// The `Seed` entity.
 #[derive(Identifiable, Queryable)]
@@ -716,6 +808,8 @@ pub struct Seed {
     pub plant_id: Option<i32>,
     /// The id of the owner of the seed.
     pub owner_id: Uuid,
+    /// The id of the seed this seed was harvested from.
+    pub parent_seed_id: Option<i32>,
 }
 
 /// The `NewSeed` entity.
@@ -737,6 +831,7 @@ pub struct NewSeed {
     pub notes: Option<String>,
     pub variety: Option<String>,
     pub owner_id: Uuid,
+    pub parent_seed_id: Option<i32>,
 }
 
 /// The `Map` entity.
@@ -837,6 +932,14 @@ pub struct Layer {
     pub name: String,
     /// A flag indicating if this layer is an user created alternative.
     pub is_alternative: bool,
+    /// The position of the layer in the layer stack of the map, higher layers are drawn on top.
+    pub order_index: i32,
+    /// Whether the layer is shown on the map.
+    pub is_visible: bool,
+    /// The opacity of the layer from 0 (transparent) to 1 (opaque).
+    pub opacity: f32,
+    /// The date the layer was deleted, it can be restored until it is cleaned up.
+    pub deletion_date: Option<NaiveDate>,
 }
 
 /// The `NewLayer` entity.
@@ -851,6 +954,22 @@ pub struct NewLayer {
     pub name: String,
     /// A flag indicating if this layer is an user created alternative.
     pub is_alternative: bool,
+    /// The position of the layer in the layer stack of the map, higher layers are drawn on top.
+    pub order_index: i32,
+}
+
+/// The `UpdateLayer` entity.
+#[derive(AsChangeset)]
+#[diesel(table_name = layers)]
+pub struct UpdateLayer {
+    /// The name of the layer.
+    pub name: Option<String>,
+    /// The position of the layer in the layer stack of the map, higher layers are drawn on top.
+    pub order_index: Option<i32>,
+    /// Whether the layer is shown on the map.
+    pub is_visible: Option<bool>,
+    /// The opacity of the layer from 0 (transparent) to 1 (opaque).
+    pub opacity: Option<f32>,
 }
 
 /// The `BaseLayerImages` entity.
@@ -946,3 +1065,45 @@ pub struct GainedBlossoms {
     /// The date on which the user gained this Blossom.
     pub gained_date: NaiveDate,
 }
+
+/// The `Harvest` entity.
+#[derive(Identifiable, Queryable, Selectable)]
+#[diesel(table_name = harvests)]
+pub struct Harvest {
+    /// The id of the harvest.
+    pub id: i32,
+    /// The planting that was harvested.
+    pub planting_id: Uuid,
+    /// The date of the harvest.
+    pub harvest_date: NaiveDate,
+    /// The harvested quantity in `unit`.
+    pub quantity: f32,
+    /// The unit of the quantity.
+    pub unit: HarvestUnit,
+    /// How good the harvest was.
+    pub quality: Option<HarvestQuality>,
+    /// Notes about the harvest.
+    pub notes: Option<String>,
+    /// The id of the user who logged the harvest.
+    pub created_by: Uuid,
+}
+
+/// The `NewHarvest` entity.
+#[derive(Insertable)]
+#[diesel(table_name = harvests)]
+pub struct NewHarvest {
+    /// The planting that was harvested.
+    pub planting_id: Uuid,
+    /// The date of the harvest.
+    pub harvest_date: NaiveDate,
+    /// The harvested quantity in `unit`.
+    pub quantity: f32,
+    /// The unit of the quantity.
+    pub unit: HarvestUnit,
+    /// How good the harvest was.
+    pub quality: Option<HarvestQuality>,
+    /// Notes about the harvest.
+    pub notes: Option<String>,
+    /// The id of the user who logged the harvest.
+    pub created_by: Uuid,
+}
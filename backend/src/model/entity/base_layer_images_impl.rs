@@ -8,11 +8,12 @@ use uuid::Uuid;
 
 use crate::model::dto::{BaseLayerImageDto, UpdateBaseLayerImageDto};
 use crate::schema::base_layer_images::{self, all_columns, layer_id};
+use crate::schema::layers;
 
 use super::BaseLayerImages;
 
 impl BaseLayerImages {
-    /// Get all `BaseLayerImages` for the layer.
+    /// Get all `BaseLayerImages` for the layer, images of deleted layers are not found.
     ///
     /// # Errors
     /// * Unknown, diesel doesn't say why it might error.
@@ -22,7 +23,14 @@ impl BaseLayerImages {
     ) -> QueryResult<Vec<BaseLayerImageDto>> {
         let query = base_layer_images::table
             .select(all_columns)
-            .filter(layer_id.eq(layer_id_param));
+            .filter(layer_id.eq(layer_id_param))
+            .filter(
+                layer_id.eq_any(
+                    layers::table
+                        .select(layers::id)
+                        .filter(layers::deletion_date.is_null()),
+                ),
+            );
 
         debug!("{}", debug_query::<Pg, _>(&query));
         Ok(query
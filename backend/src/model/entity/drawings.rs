@@ -0,0 +1,38 @@
+//! All entities associated with [`Drawing`].
+
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable, Selectable};
+use uuid::Uuid;
+
+use crate::db::geometry::ShapeGeometry;
+use crate::model::r#enum::shape_type::ShapeType;
+use crate::schema::drawings;
+
+/// The `Drawing` entity.
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable, Insertable, AsChangeset)]
+#[diesel(table_name = drawings, treat_none_as_null = true)]
+pub struct Drawing {
+    /// The id of the drawing.
+    pub id: Uuid,
+    /// The layer the drawing is on.
+    pub layer_id: i32,
+    /// The kind of shape.
+    pub shape_type: ShapeType,
+    /// The points of the shape in cm.
+    pub geometry: ShapeGeometry,
+    /// The rotation in degrees of rectangles, ellipses and texts.
+    pub rotation: f32,
+    /// The horizontal radius of ellipses.
+    pub radius_x: Option<f32>,
+    /// The vertical radius of ellipses.
+    pub radius_y: Option<f32>,
+    /// The content of texts.
+    pub text: Option<String>,
+    /// The font size of texts.
+    pub font_size: Option<f32>,
+    /// The color of the outline.
+    pub stroke_color: String,
+    /// The width of the outline.
+    pub stroke_width: f32,
+    /// The color of the area, if it is filled.
+    pub fill_color: Option<String>,
+}
@@ -0,0 +1,101 @@
+//! Contains the implementation of [`Drawing`].
+
+use diesel::pg::Pg;
+use diesel::{debug_query, ExpressionMethods, QueryDsl, QueryResult, SelectableHelper};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use log::debug;
+use uuid::Uuid;
+
+use crate::model::dto::drawings::DrawingDto;
+use crate::model::entity::drawings::Drawing;
+use crate::schema::{drawings, layers};
+
+impl Drawing {
+    /// Get all drawings of a layer on a map.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_layer(
+        map_id: i32,
+        layer_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<DrawingDto>> {
+        let query = drawings::table
+            .inner_join(layers::table)
+            .select(Self::as_select())
+            .filter(drawings::layer_id.eq(layer_id))
+            .filter(layers::map_id.eq(map_id))
+            .filter(layers::deletion_date.is_null())
+            .order(drawings::id);
+        debug!("{}", debug_query::<Pg, _>(&query));
+        Ok(query
+            .load::<Self>(conn)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// Get a drawing on a map.
+    ///
+    /// # Errors
+    /// * If the drawing does not exist on the map.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_id_on_map(
+        id: Uuid,
+        map_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<DrawingDto> {
+        let query = drawings::table
+            .inner_join(layers::table)
+            .select(Self::as_select())
+            .filter(drawings::id.eq(id))
+            .filter(layers::map_id.eq(map_id))
+            .filter(layers::deletion_date.is_null());
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.first::<Self>(conn).await.map(Into::into)
+    }
+
+    /// Create a new drawing in the database.
+    ///
+    /// # Errors
+    /// * If the `layer_id` references a layer that doesn't exist.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn create(drawing: Self, conn: &mut AsyncPgConnection) -> QueryResult<DrawingDto> {
+        let query = diesel::insert_into(drawings::table).values(&drawing);
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.get_result::<Self>(conn).await.map(Into::into)
+    }
+
+    /// Replace the shape and style of a drawing in the database.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn update(drawing: Self, conn: &mut AsyncPgConnection) -> QueryResult<DrawingDto> {
+        let query = diesel::update(drawings::table.find(drawing.id)).set(&drawing);
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.get_result::<Self>(conn).await.map(Into::into)
+    }
+
+    /// Delete a drawing on a map.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn delete_by_id(
+        id: Uuid,
+        map_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<usize> {
+        let query = diesel::delete(
+            drawings::table.filter(drawings::id.eq(id)).filter(
+                drawings::layer_id.eq_any(
+                    layers::table
+                        .select(layers::id)
+                        .filter(layers::map_id.eq(map_id)),
+                ),
+            ),
+        );
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.execute(conn).await
+    }
+}
@@ -0,0 +1,92 @@
+//! All entities associated with [`Guild`].
+
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
+use uuid::Uuid;
+
+use crate::schema::{guild_template_members, guild_templates, guilds};
+
+/// The `Guild` entity.
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable, Insertable)]
+#[diesel(table_name = guilds)]
+pub struct Guild {
+    /// The id of the guild.
+    pub id: Uuid,
+    /// The plant layer the guild is on.
+    pub layer_id: i32,
+    /// The name of the guild.
+    pub name: String,
+    /// The id of the user who created the guild.
+    pub created_by: Uuid,
+}
+
+/// The `GuildTemplate` entity.
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable)]
+#[diesel(table_name = guild_templates)]
+pub struct GuildTemplate {
+    /// The id of the template.
+    pub id: i32,
+    /// The name of the template.
+    pub name: String,
+    /// The id of the user who saved the template.
+    pub owner_id: Uuid,
+}
+
+/// The `NewGuildTemplate` entity.
+#[derive(Insertable)]
+#[diesel(table_name = guild_templates)]
+pub struct NewGuildTemplate {
+    /// The name of the template.
+    pub name: String,
+    /// The id of the user who saved the template.
+    pub owner_id: Uuid,
+}
+
+/// The `GuildTemplateMember` entity.
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable)]
+#[diesel(table_name = guild_template_members)]
+pub struct GuildTemplateMember {
+    /// The id of the member.
+    pub id: i32,
+    /// The template the member belongs to.
+    pub template_id: i32,
+    /// The plant that is planted.
+    pub plant_id: i32,
+    /// The x coordinate of the position relative to the center of the guild.
+    pub x: i32,
+    /// The y coordinate of the position relative to the center of the guild.
+    pub y: i32,
+    /// The width of the plant on the map.
+    pub width: i32,
+    /// The height of the plant on the map.
+    pub height: i32,
+    /// The rotation in degrees (0-360) of the plant on the map.
+    pub rotation: f32,
+    /// The x scale of the plant on the map.
+    pub scale_x: f32,
+    /// The y scale of the plant on the map.
+    pub scale_y: f32,
+}
+
+/// The `NewGuildTemplateMember` entity.
+#[derive(Insertable)]
+#[diesel(table_name = guild_template_members)]
+pub struct NewGuildTemplateMember {
+    /// The template the member belongs to.
+    pub template_id: i32,
+    /// The plant that is planted.
+    pub plant_id: i32,
+    /// The x coordinate of the position relative to the center of the guild.
+    pub x: i32,
+    /// The y coordinate of the position relative to the center of the guild.
+    pub y: i32,
+    /// The width of the plant on the map.
+    pub width: i32,
+    /// The height of the plant on the map.
+    pub height: i32,
+    /// The rotation in degrees (0-360) of the plant on the map.
+    pub rotation: f32,
+    /// The x scale of the plant on the map.
+    pub scale_x: f32,
+    /// The y scale of the plant on the map.
+    pub scale_y: f32,
+}
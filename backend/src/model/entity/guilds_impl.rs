@@ -0,0 +1,334 @@
+//! Contains the implementation of [`Guild`] and [`GuildTemplate`].
+
+use std::collections::HashSet;
+
+use diesel::pg::Pg;
+use diesel::{debug_query, ExpressionMethods, QueryDsl, QueryResult, SelectableHelper};
+use diesel_async::{
+    scoped_futures::ScopedFutureExt, AsyncConnection, AsyncPgConnection, RunQueryDsl,
+};
+use log::debug;
+use uuid::Uuid;
+
+use crate::model::dto::guilds::{GuildDto, GuildTemplateDto, GuildTemplateMemberDto, NewGuildDto};
+use crate::model::entity::guilds::{
+    Guild, GuildTemplate, GuildTemplateMember, NewGuildTemplate, NewGuildTemplateMember,
+};
+use crate::model::entity::plantings::{Planting, UpdatePlanting};
+use crate::schema::{guild_template_members, guild_templates, guilds, layers, plantings};
+
+impl Guild {
+    /// Get all guilds of a layer with their plantings.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_layer(
+        layer_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<GuildDto>> {
+        let query = guilds::table
+            .filter(guilds::layer_id.eq(layer_id))
+            .order((guilds::name, guilds::id));
+        debug!("{}", debug_query::<Pg, _>(&query));
+        let guilds = query.load::<Self>(conn).await?;
+        Self::with_plantings(guilds, conn).await
+    }
+
+    /// Get a guild on a map with its plantings.
+    ///
+    /// # Errors
+    /// * If the guild does not exist on the map.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_id_on_map(
+        id: Uuid,
+        map_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<GuildDto> {
+        let query = guilds::table
+            .inner_join(layers::table)
+            .select(Self::as_select())
+            .filter(guilds::id.eq(id))
+            .filter(layers::map_id.eq(map_id))
+            .filter(layers::deletion_date.is_null());
+        debug!("{}", debug_query::<Pg, _>(&query));
+        let guild = query.first::<Self>(conn).await?;
+        let mut guilds = Self::with_plantings(vec![guild], conn).await?;
+        guilds.pop().ok_or(diesel::result::Error::NotFound)
+    }
+
+    /// Group existing plantings of the layer into a new guild.
+    ///
+    /// # Errors
+    /// * If any of the plantings does not exist on the layer of the guild or already belongs to a guild.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn create(
+        new_guild: NewGuildDto,
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<GuildDto> {
+        conn.transaction(|conn| {
+            async move {
+                let guild = Self {
+                    id: new_guild.id.unwrap_or_else(Uuid::new_v4),
+                    layer_id: new_guild.layer_id,
+                    name: new_guild.name,
+                    created_by: user_id,
+                };
+                let guild_query = diesel::insert_into(guilds::table).values(&guild);
+                debug!("{}", debug_query::<Pg, _>(&guild_query));
+                guild_query.execute(conn).await?;
+
+                let planting_ids = new_guild.planting_ids.into_iter().collect::<HashSet<_>>();
+                let plantings_query = diesel::update(
+                    plantings::table
+                        .filter(plantings::id.eq_any(&planting_ids))
+                        .filter(plantings::layer_id.eq(guild.layer_id))
+                        .filter(plantings::guild_id.is_null()),
+                )
+                .set(plantings::guild_id.eq(guild.id));
+                debug!("{}", debug_query::<Pg, _>(&plantings_query));
+                if plantings_query.execute(conn).await? != planting_ids.len() {
+                    return Err(diesel::result::Error::NotFound);
+                }
+
+                let mut guilds = Self::with_plantings(vec![guild], conn).await?;
+                guilds.pop().ok_or(diesel::result::Error::NotFound)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Create a guild together with its new plantings.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn create_with_plantings(
+        guild: Self,
+        new_plantings: Vec<Planting>,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<GuildDto> {
+        conn.transaction(|conn| {
+            async move {
+                let guild_query = diesel::insert_into(guilds::table).values(&guild);
+                debug!("{}", debug_query::<Pg, _>(&guild_query));
+                guild_query.execute(conn).await?;
+
+                let plantings_query = diesel::insert_into(plantings::table).values(&new_plantings);
+                debug!("{}", debug_query::<Pg, _>(&plantings_query));
+                plantings_query.execute(conn).await?;
+
+                let mut guilds = Self::with_plantings(vec![guild], conn).await?;
+                guilds.pop().ok_or(diesel::result::Error::NotFound)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Partially update the plantings of a guild.
+    ///
+    /// # Errors
+    /// * If any of the plantings does not belong to the guild.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn update_plantings(
+        id: Uuid,
+        updates: Vec<(Uuid, UpdatePlanting)>,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<GuildDto> {
+        conn.transaction(|conn| {
+            async move {
+                for (planting_id, update) in updates {
+                    let planting_query = diesel::update(
+                        plantings::table
+                            .filter(plantings::id.eq(planting_id))
+                            .filter(plantings::guild_id.eq(id)),
+                    )
+                    .set(&update);
+                    debug!("{}", debug_query::<Pg, _>(&planting_query));
+                    if planting_query.execute(conn).await? == 0 {
+                        return Err(diesel::result::Error::NotFound);
+                    }
+                }
+
+                let guild_query = guilds::table.find(id);
+                debug!("{}", debug_query::<Pg, _>(&guild_query));
+                let guild = guild_query.first::<Self>(conn).await?;
+                let mut guilds = Self::with_plantings(vec![guild], conn).await?;
+                guilds.pop().ok_or(diesel::result::Error::NotFound)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Ungroup a guild on a map, its plantings stay on the map.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn delete_by_id(
+        id: Uuid,
+        map_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<usize> {
+        let query = diesel::delete(
+            guilds::table.filter(guilds::id.eq(id)).filter(
+                guilds::layer_id.eq_any(
+                    layers::table
+                        .select(layers::id)
+                        .filter(layers::map_id.eq(map_id)),
+                ),
+            ),
+        );
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.execute(conn).await
+    }
+
+    /// Load the plantings of the guilds.
+    async fn with_plantings(
+        guilds: Vec<Self>,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<GuildDto>> {
+        let ids = guilds.iter().map(|guild| guild.id).collect::<Vec<_>>();
+        let query = plantings::table
+            .select(plantings::all_columns)
+            .filter(plantings::guild_id.eq_any(ids))
+            .order(plantings::id);
+        debug!("{}", debug_query::<Pg, _>(&query));
+        let members = query.load::<Planting>(conn).await?;
+
+        Ok(guilds
+            .into_iter()
+            .map(|guild| {
+                let plantings = members
+                    .iter()
+                    .filter(|planting| planting.guild_id == Some(guild.id))
+                    .cloned()
+                    .map(Into::into)
+                    .collect();
+                GuildDto::from((guild, plantings))
+            })
+            .collect())
+    }
+}
+
+impl GuildTemplate {
+    /// Get all guild templates of the user.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find(
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<GuildTemplateDto>> {
+        let query = guild_templates::table
+            .filter(guild_templates::owner_id.eq(user_id))
+            .order((guild_templates::name, guild_templates::id));
+        debug!("{}", debug_query::<Pg, _>(&query));
+        let templates = query.load::<Self>(conn).await?;
+        Self::with_members(templates, conn).await
+    }
+
+    /// Get a guild template of the user.
+    ///
+    /// # Errors
+    /// * If the template does not exist or belongs to another user.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_id(
+        id: i32,
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<GuildTemplateDto> {
+        let query = guild_templates::table
+            .filter(guild_templates::id.eq(id))
+            .filter(guild_templates::owner_id.eq(user_id));
+        debug!("{}", debug_query::<Pg, _>(&query));
+        let template = query.first::<Self>(conn).await?;
+        let mut templates = Self::with_members(vec![template], conn).await?;
+        templates.pop().ok_or(diesel::result::Error::NotFound)
+    }
+
+    /// Save a guild template of the user.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn create(
+        name: String,
+        members: Vec<GuildTemplateMemberDto>,
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<GuildTemplateDto> {
+        conn.transaction(|conn| {
+            async move {
+                let template_query =
+                    diesel::insert_into(guild_templates::table).values(NewGuildTemplate {
+                        name,
+                        owner_id: user_id,
+                    });
+                debug!("{}", debug_query::<Pg, _>(&template_query));
+                let template = template_query.get_result::<Self>(conn).await?;
+
+                let new_members = members
+                    .into_iter()
+                    .map(|member| NewGuildTemplateMember::from((template.id, member)))
+                    .collect::<Vec<_>>();
+                let members_query =
+                    diesel::insert_into(guild_template_members::table).values(new_members);
+                debug!("{}", debug_query::<Pg, _>(&members_query));
+                members_query.execute(conn).await?;
+
+                let mut templates = Self::with_members(vec![template], conn).await?;
+                templates.pop().ok_or(diesel::result::Error::NotFound)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Delete a guild template of the user.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn delete_by_id(
+        id: i32,
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<usize> {
+        let query = diesel::delete(
+            guild_templates::table
+                .filter(guild_templates::id.eq(id))
+                .filter(guild_templates::owner_id.eq(user_id)),
+        );
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.execute(conn).await
+    }
+
+    /// Load the members of the templates.
+    async fn with_members(
+        templates: Vec<Self>,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<GuildTemplateDto>> {
+        let ids = templates
+            .iter()
+            .map(|template| template.id)
+            .collect::<Vec<_>>();
+        let query = guild_template_members::table
+            .filter(guild_template_members::template_id.eq_any(ids))
+            .order(guild_template_members::id);
+        debug!("{}", debug_query::<Pg, _>(&query));
+        let members = query.load::<GuildTemplateMember>(conn).await?;
+
+        Ok(templates
+            .into_iter()
+            .map(|template| {
+                let template_members = members
+                    .iter()
+                    .filter(|member| member.template_id == template.id)
+                    .cloned()
+                    .map(Into::into)
+                    .collect();
+                GuildTemplateDto::from((template, template_members))
+            })
+            .collect())
+    }
+}
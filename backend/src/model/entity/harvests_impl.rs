@@ -0,0 +1,130 @@
+//! Contains the implementation of [`Harvest`].
+
+use chrono::NaiveDate;
+use diesel::pg::Pg;
+use diesel::{debug_query, ExpressionMethods, QueryDsl, QueryResult, SelectableHelper};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use log::debug;
+use uuid::Uuid;
+
+use crate::model::dto::{HarvestDto, NewHarvestDto};
+use crate::model::entity::{Harvest, NewHarvest};
+use crate::model::r#enum::layer_type::LayerType;
+use crate::schema::{harvests, layers, plantings, plants};
+
+/// Arguments for the database layer find harvests of a map function.
+pub struct FindHarvestsParameters {
+    /// Only find harvests of plantings on this layer.
+    pub layer_id: Option<i32>,
+    /// Also find harvests of plantings on alternative layers.
+    pub include_alternatives: bool,
+    /// Only find harvests of this plant.
+    pub plant_id: Option<i32>,
+    /// First date of the time frame harvests are searched for.
+    pub from: Option<NaiveDate>,
+    /// Last date of the time frame harvests are searched for.
+    pub to: Option<NaiveDate>,
+}
+
+impl Harvest {
+    /// Get all harvests of a planting, the latest first.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_planting(
+        planting_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<HarvestDto>> {
+        let query = harvests::table
+            .filter(harvests::planting_id.eq(planting_id))
+            .order((harvests::harvest_date.desc(), harvests::id.desc()));
+        debug!("{}", debug_query::<Pg, _>(&query));
+        Ok(query
+            .load::<Self>(conn)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// Get all harvests of plantings on plant layers of the map
+    /// together with the layer, the plant and the unique name of the plant of their planting.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_map(
+        map_id: i32,
+        search_parameters: FindHarvestsParameters,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<(Self, i32, i32, String)>> {
+        let mut query = harvests::table
+            .inner_join(
+                plantings::table
+                    .inner_join(layers::table)
+                    .inner_join(plants::table),
+            )
+            .select((
+                Self::as_select(),
+                plantings::layer_id,
+                plantings::plant_id,
+                plants::unique_name,
+            ))
+            .filter(layers::map_id.eq(map_id))
+            .filter(layers::type_.eq(LayerType::Plants))
+            .filter(layers::deletion_date.is_null())
+            .into_boxed();
+
+        if let Some(id) = search_parameters.layer_id {
+            query = query.filter(plantings::layer_id.eq(id));
+        } else if !search_parameters.include_alternatives {
+            query = query.filter(layers::is_alternative.eq(false));
+        }
+        if let Some(id) = search_parameters.plant_id {
+            query = query.filter(plantings::plant_id.eq(id));
+        }
+        if let Some(from) = search_parameters.from {
+            query = query.filter(harvests::harvest_date.ge(from));
+        }
+        if let Some(to) = search_parameters.to {
+            query = query.filter(harvests::harvest_date.le(to));
+        }
+        query = query.order((harvests::harvest_date, harvests::id));
+
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.load::<(Self, i32, i32, String)>(conn).await
+    }
+
+    /// Log a new harvest of a planting in the database.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn create(
+        planting_id: Uuid,
+        new_harvest: NewHarvestDto,
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<HarvestDto> {
+        let new_harvest = NewHarvest::from((planting_id, new_harvest, user_id));
+        let query = diesel::insert_into(harvests::table).values(&new_harvest);
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.get_result::<Self>(conn).await.map(Into::into)
+    }
+
+    /// Delete a harvest of a planting from the database.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn delete_by_id(
+        planting_id: Uuid,
+        id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<usize> {
+        let query = diesel::delete(
+            harvests::table
+                .filter(harvests::planting_id.eq(planting_id))
+                .filter(harvests::id.eq(id)),
+        );
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.execute(conn).await
+    }
+}
@@ -1,21 +1,33 @@
 //! Contains the implementation of [`Layer`].
 
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
 use diesel::pg::Pg;
 use diesel::{debug_query, ExpressionMethods, QueryDsl, QueryResult};
-use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use diesel_async::{
+    scoped_futures::ScopedFutureExt, AsyncConnection, AsyncPgConnection, RunQueryDsl,
+};
 use log::debug;
+use uuid::Uuid;
 
 use crate::model::dto::LayerSearchParameters;
+use crate::model::entity::guilds::Guild;
+use crate::model::entity::plantings::Planting;
+use crate::schema::{guilds, plantings};
 use crate::{
     model::dto::{LayerDto, NewLayerDto},
-    schema::layers::{self, all_columns, is_alternative, map_id, type_},
+    schema::layers::{
+        self, all_columns, deletion_date, is_alternative, map_id, order_index, type_,
+    },
 };
 
-use super::{Layer, NewLayer};
+use super::{Layer, NewLayer, UpdateLayer};
 
 impl Layer {
-    /// Get a page of layers.
+    /// Get a page of layers ordered from the bottom to the top of the layer stack.
     /// Can be filtered by its active status if one is provided in `search_parameters`.
+    /// Deleted layers are only returned if they are searched for explicitly.
     ///
     /// # Errors
     /// * Unknown, diesel doesn't say why it might error.
@@ -23,7 +35,10 @@ impl Layer {
         search_parameters: LayerSearchParameters,
         conn: &mut AsyncPgConnection,
     ) -> QueryResult<Vec<LayerDto>> {
-        let mut query = layers::table.select(all_columns).into_boxed();
+        let mut query = layers::table
+            .select(all_columns)
+            .order((order_index, layers::id))
+            .into_boxed();
 
         if let Some(map_id_search) = search_parameters.map_id {
             query = query.filter(map_id.eq(map_id_search));
@@ -34,6 +49,11 @@ impl Layer {
         if let Some(is_alternative_search) = search_parameters.is_alternative {
             query = query.filter(is_alternative.eq(is_alternative_search));
         }
+        if search_parameters.is_deleted.unwrap_or(false) {
+            query = query.filter(deletion_date.is_not_null());
+        } else {
+            query = query.filter(deletion_date.is_null());
+        }
 
         debug!("{}", debug_query::<Pg, _>(&query));
         Ok(query
@@ -44,17 +64,40 @@ impl Layer {
             .collect())
     }
 
-    /// Fetch layer by id from the database.
+    /// Fetch layer by id from the database, deleted layers are not found.
     ///
     /// # Errors
     /// * Unknown, diesel doesn't say why it might error.
     pub async fn find_by_id(id: i32, conn: &mut AsyncPgConnection) -> QueryResult<LayerDto> {
-        let query = layers::table.find(id);
+        let query = layers::table.find(id).filter(deletion_date.is_null());
         debug!("{}", debug_query::<Pg, _>(&query));
         query.first::<Self>(conn).await.map(Into::into)
     }
 
+    /// Fetch all layers of a map that are not deleted and lock them until the end of the transaction.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_map_for_update(
+        layer_map_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<LayerDto>> {
+        let query = layers::table
+            .filter(map_id.eq(layer_map_id))
+            .filter(deletion_date.is_null())
+            .order(layers::id)
+            .for_update();
+        debug!("{}", debug_query::<Pg, _>(&query));
+        Ok(query
+            .load::<Self>(conn)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
     /// Create a new layer in the database.
+    /// The layer is placed on top of all other layers of the map.
     ///
     /// # Errors
     /// * Unknown, diesel doesn't say why it might error.
@@ -62,19 +105,255 @@ impl Layer {
         new_layer: NewLayerDto,
         conn: &mut AsyncPgConnection,
     ) -> QueryResult<LayerDto> {
-        let new_layer = NewLayer::from(new_layer);
-        let query = diesel::insert_into(layers::table).values(&new_layer);
+        conn.transaction(|conn| {
+            async move {
+                let mut new_layer = NewLayer::from(new_layer);
+
+                let top_query = layers::table
+                    .select(diesel::dsl::max(order_index))
+                    .filter(map_id.eq(new_layer.map_id));
+                debug!("{}", debug_query::<Pg, _>(&top_query));
+                let top = top_query.get_result::<Option<i32>>(conn).await?;
+                new_layer.order_index = top.map_or(0, |top| top + 1);
+
+                let query = diesel::insert_into(layers::table).values(&new_layer);
+                debug!("{}", debug_query::<Pg, _>(&query));
+                query.get_result::<Self>(conn).await.map(Into::into)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Partially update a layer of a map.
+    ///
+    /// # Errors
+    /// * If the layer does not exist on the map.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn update(
+        layer_id: i32,
+        layer_map_id: i32,
+        update: UpdateLayer,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<LayerDto> {
+        let query = diesel::update(
+            layers::table
+                .filter(layers::id.eq(layer_id))
+                .filter(map_id.eq(layer_map_id))
+                .filter(deletion_date.is_null()),
+        )
+        .set(&update);
         debug!("{}", debug_query::<Pg, _>(&query));
         query.get_result::<Self>(conn).await.map(Into::into)
     }
 
-    /// Delete the layer from the database.
+    /// Rewrite the order of all layers of a map.
+    /// The position of a layer in `layer_ids` becomes its order index.
+    /// The layers of the map are locked while the order is rewritten,
+    /// returns `None` if `layer_ids` does not contain every layer of the map exactly once.
     ///
     /// # Errors
     /// * Unknown, diesel doesn't say why it might error.
-    pub async fn delete_by_id(id: i32, conn: &mut AsyncPgConnection) -> QueryResult<usize> {
-        let query = diesel::delete(layers::table.find(id));
+    pub async fn reorder(
+        layer_map_id: i32,
+        layer_ids: Vec<i32>,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Option<Vec<LayerDto>>> {
+        conn.transaction(|conn| {
+            async move {
+                let existing_ids = Self::find_by_map_for_update(layer_map_id, conn)
+                    .await?
+                    .into_iter()
+                    .map(|layer| layer.id)
+                    .collect::<Vec<_>>();
+                let mut requested_ids = layer_ids.clone();
+                requested_ids.sort_unstable();
+                if existing_ids != requested_ids {
+                    return Ok(None);
+                }
+
+                for (index, layer_id) in (0..).zip(layer_ids) {
+                    let query =
+                        diesel::update(layers::table.find(layer_id)).set(order_index.eq(index));
+                    debug!("{}", debug_query::<Pg, _>(&query));
+                    query.execute(conn).await?;
+                }
+
+                let search_parameters = LayerSearchParameters {
+                    map_id: Some(layer_map_id),
+                    type_: None,
+                    is_alternative: None,
+                    is_deleted: None,
+                };
+                Self::find(search_parameters, conn).await.map(Some)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Fork a layer into a new alternative layer on top of all other layers of the map.
+    /// The guilds and plantings of the layer are copied, each copy remembers its original planting.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn fork(
+        source: LayerDto,
+        name: String,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<LayerDto> {
+        conn.transaction(|conn| {
+            async move {
+                let new_layer = NewLayerDto {
+                    map_id: source.map_id,
+                    type_: source.type_,
+                    name,
+                    is_alternative: true,
+                };
+                let layer = Self::create(new_layer, conn).await?;
+
+                let guilds_query = guilds::table.filter(guilds::layer_id.eq(source.id));
+                debug!("{}", debug_query::<Pg, _>(&guilds_query));
+                let guild_copies = guilds_query
+                    .load::<Guild>(conn)
+                    .await?
+                    .into_iter()
+                    .map(|guild| {
+                        let copy = Guild {
+                            id: Uuid::new_v4(),
+                            layer_id: layer.id,
+                            ..guild.clone()
+                        };
+                        (guild.id, copy)
+                    })
+                    .collect::<HashMap<_, _>>();
+                if !guild_copies.is_empty() {
+                    let new_guilds = guild_copies.values().collect::<Vec<_>>();
+                    let query = diesel::insert_into(guilds::table).values(new_guilds);
+                    debug!("{}", debug_query::<Pg, _>(&query));
+                    query.execute(conn).await?;
+                }
+
+                let plantings_query = plantings::table.filter(plantings::layer_id.eq(source.id));
+                debug!("{}", debug_query::<Pg, _>(&plantings_query));
+                let planting_copies = plantings_query
+                    .load::<Planting>(conn)
+                    .await?
+                    .into_iter()
+                    .map(|planting| Planting {
+                        id: Uuid::new_v4(),
+                        layer_id: layer.id,
+                        guild_id: planting
+                            .guild_id
+                            .and_then(|guild_id| guild_copies.get(&guild_id))
+                            .map(|guild| guild.id),
+                        origin_id: Some(planting.origin_id.unwrap_or(planting.id)),
+                        ..planting
+                    })
+                    .collect::<Vec<_>>();
+                if !planting_copies.is_empty() {
+                    let query = diesel::insert_into(plantings::table).values(&planting_copies);
+                    debug!("{}", debug_query::<Pg, _>(&query));
+                    query.execute(conn).await?;
+                }
+
+                Ok(layer)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Promote a layer to be the main layer of its type on the map.
+    /// The previous main layers of the type become alternatives.
+    ///
+    /// # Errors
+    /// * If the layer does not exist on the map.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn promote(
+        layer_id: i32,
+        layer_map_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<LayerDto>> {
+        conn.transaction(|conn| {
+            async move {
+                let promote_query = diesel::update(
+                    layers::table
+                        .filter(layers::id.eq(layer_id))
+                        .filter(map_id.eq(layer_map_id))
+                        .filter(deletion_date.is_null()),
+                )
+                .set(is_alternative.eq(false));
+                debug!("{}", debug_query::<Pg, _>(&promote_query));
+                let promoted = promote_query.get_result::<Self>(conn).await?;
+
+                let demote_query = diesel::update(
+                    layers::table
+                        .filter(layers::id.ne(layer_id))
+                        .filter(map_id.eq(layer_map_id))
+                        .filter(type_.eq(promoted.type_))
+                        .filter(is_alternative.eq(false))
+                        .filter(deletion_date.is_null()),
+                )
+                .set(is_alternative.eq(true));
+                debug!("{}", debug_query::<Pg, _>(&demote_query));
+                demote_query.execute(conn).await?;
+
+                let search_parameters = LayerSearchParameters {
+                    map_id: Some(layer_map_id),
+                    type_: None,
+                    is_alternative: None,
+                    is_deleted: None,
+                };
+                Self::find(search_parameters, conn).await
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Mark a layer of a map as deleted.
+    /// Its plantings and images stay in the database until the layer is cleaned up.
+    ///
+    /// # Errors
+    /// * If the layer does not exist on the map or is already deleted.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn delete_by_id(
+        id: i32,
+        layer_map_id: i32,
+        date: NaiveDate,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<LayerDto> {
+        let query = diesel::update(
+            layers::table
+                .filter(layers::id.eq(id))
+                .filter(map_id.eq(layer_map_id))
+                .filter(deletion_date.is_null()),
+        )
+        .set(deletion_date.eq(date));
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.get_result::<Self>(conn).await.map(Into::into)
+    }
+
+    /// Restore a layer of a map that was deleted on or after `deleted_since`.
+    ///
+    /// # Errors
+    /// * If the layer does not exist on the map, is not deleted or was deleted before `deleted_since`.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn restore(
+        id: i32,
+        layer_map_id: i32,
+        deleted_since: NaiveDate,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<LayerDto> {
+        let query = diesel::update(
+            layers::table
+                .filter(layers::id.eq(id))
+                .filter(map_id.eq(layer_map_id))
+                .filter(deletion_date.ge(deleted_since)),
+        )
+        .set(deletion_date.eq(None::<NaiveDate>));
         debug!("{}", debug_query::<Pg, _>(&query));
-        query.execute(conn).await
+        query.get_result::<Self>(conn).await.map(Into::into)
     }
 }
@@ -0,0 +1,18 @@
+//! All entities associated with [`MapCollaborator`].
+
+use diesel::{Insertable, Queryable, Selectable};
+use uuid::Uuid;
+
+use crate::schema::map_collaborators;
+
+/// The `MapCollaborator` entity.
+///
+/// The owner of a map is always a collaborator and has no entry.
+#[derive(Debug, Clone, Copy, Queryable, Selectable, Insertable)]
+#[diesel(table_name = map_collaborators)]
+pub struct MapCollaborator {
+    /// The map the user collaborates on.
+    pub map_id: i32,
+    /// The id of the collaborating user.
+    pub user_id: Uuid,
+}
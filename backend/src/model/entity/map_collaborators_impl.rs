@@ -0,0 +1,86 @@
+//! Contains the implementation of [`MapCollaborator`].
+
+use diesel::pg::Pg;
+use diesel::{debug_query, dsl::exists, ExpressionMethods, QueryDsl, QueryResult};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use log::debug;
+use uuid::Uuid;
+
+use crate::model::dto::map_collaborators::MapCollaboratorDto;
+use crate::model::entity::map_collaborators::MapCollaborator;
+use crate::schema::map_collaborators;
+
+impl MapCollaborator {
+    /// Get all collaborators of a map, not including its owner.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_map(
+        map_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<MapCollaboratorDto>> {
+        let query = map_collaborators::table
+            .filter(map_collaborators::map_id.eq(map_id))
+            .order(map_collaborators::user_id);
+        debug!("{}", debug_query::<Pg, _>(&query));
+        Ok(query
+            .load::<Self>(conn)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// Whether the user was added as a collaborator of the map.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn exists(
+        map_id: i32,
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<bool> {
+        let query = diesel::select(exists(
+            map_collaborators::table
+                .filter(map_collaborators::map_id.eq(map_id))
+                .filter(map_collaborators::user_id.eq(user_id)),
+        ));
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.get_result::<bool>(conn).await
+    }
+
+    /// Add a collaborator to a map, adding an existing collaborator again has no effect.
+    ///
+    /// # Errors
+    /// * If the map does not exist.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn create(
+        collaborator: Self,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<MapCollaboratorDto> {
+        let query = diesel::insert_into(map_collaborators::table)
+            .values(&collaborator)
+            .on_conflict_do_nothing();
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.execute(conn).await?;
+        Ok(collaborator.into())
+    }
+
+    /// Remove a collaborator from a map.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn delete(
+        map_id: i32,
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<usize> {
+        let query = diesel::delete(
+            map_collaborators::table
+                .filter(map_collaborators::map_id.eq(map_id))
+                .filter(map_collaborators::user_id.eq(user_id)),
+        );
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.execute(conn).await
+    }
+}
@@ -0,0 +1,47 @@
+//! All entities associated with [`Photo`].
+
+use chrono::NaiveDateTime;
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable, Selectable};
+use uuid::Uuid;
+
+use crate::schema::photos;
+
+/// The `Photo` entity.
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable, Insertable)]
+#[diesel(table_name = photos)]
+pub struct Photo {
+    /// The id of the photo.
+    pub id: Uuid,
+    /// The photo layer the photo is on.
+    pub layer_id: i32,
+    /// The x coordinate of the position the photo is pinned to.
+    pub x: i32,
+    /// The y coordinate of the position the photo is pinned to.
+    pub y: i32,
+    /// The planting the photo shows.
+    pub planting_id: Option<Uuid>,
+    /// The path of the image in Nextcloud.
+    pub path: String,
+    /// A description of the photo.
+    pub description: Option<String>,
+    /// The local time the photo was taken at.
+    pub captured_at: Option<NaiveDateTime>,
+    /// The id of the user who added the photo.
+    pub created_by: Uuid,
+}
+
+/// The `UpdatePhoto` entity.
+#[derive(Debug, Clone, AsChangeset)]
+#[diesel(table_name = photos, treat_none_as_null = true)]
+pub struct UpdatePhoto {
+    /// The x coordinate of the position the photo is pinned to.
+    pub x: i32,
+    /// The y coordinate of the position the photo is pinned to.
+    pub y: i32,
+    /// The planting the photo shows.
+    pub planting_id: Option<Uuid>,
+    /// A description of the photo.
+    pub description: Option<String>,
+    /// The local time the photo was taken at.
+    pub captured_at: Option<NaiveDateTime>,
+}
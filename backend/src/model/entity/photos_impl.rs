@@ -0,0 +1,107 @@
+//! Contains the implementation of [`Photo`].
+
+use chrono::NaiveDateTime;
+use diesel::pg::Pg;
+use diesel::{
+    debug_query, ExpressionMethods, PgSortExpressionMethods, QueryDsl, QueryResult,
+    SelectableHelper,
+};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use log::debug;
+use uuid::Uuid;
+
+use crate::model::dto::photos::PhotoDto;
+use crate::model::entity::photos::{Photo, UpdatePhoto};
+use crate::schema::{layers, photos};
+
+impl Photo {
+    /// Get the photos of a photo layer, optionally only those taken in the given time range.
+    /// Photos taken first come first.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_layer(
+        layer_id: i32,
+        captured_from: Option<NaiveDateTime>,
+        captured_before: Option<NaiveDateTime>,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<PhotoDto>> {
+        let mut query = photos::table
+            .filter(photos::layer_id.eq(layer_id))
+            .order((photos::captured_at.asc().nulls_last(), photos::id))
+            .into_boxed();
+        if let Some(captured_from) = captured_from {
+            query = query.filter(photos::captured_at.ge(captured_from));
+        }
+        if let Some(captured_before) = captured_before {
+            query = query.filter(photos::captured_at.lt(captured_before));
+        }
+        debug!("{}", debug_query::<Pg, _>(&query));
+        Ok(query
+            .load::<Self>(conn)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// Create a new photo in the database.
+    ///
+    /// # Errors
+    /// * If the `layer_id` references a layer that doesn't exist.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn create(photo: Self, conn: &mut AsyncPgConnection) -> QueryResult<PhotoDto> {
+        let query = diesel::insert_into(photos::table).values(&photo);
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.get_result::<Self>(conn).await.map(Into::into)
+    }
+
+    /// Replace the position and details of a photo on a map.
+    ///
+    /// # Errors
+    /// * If the photo does not exist on the map.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn update(
+        id: Uuid,
+        map_id: i32,
+        photo: UpdatePhoto,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<PhotoDto> {
+        let query = diesel::update(
+            photos::table.filter(photos::id.eq(id)).filter(
+                photos::layer_id.eq_any(
+                    layers::table
+                        .select(layers::id)
+                        .filter(layers::map_id.eq(map_id))
+                        .filter(layers::deletion_date.is_null()),
+                ),
+            ),
+        )
+        .set(&photo)
+        .returning(Self::as_returning());
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.get_result::<Self>(conn).await.map(Into::into)
+    }
+
+    /// Delete a photo on a map.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn delete_by_id(
+        id: Uuid,
+        map_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<usize> {
+        let query = diesel::delete(
+            photos::table.filter(photos::id.eq(id)).filter(
+                photos::layer_id.eq_any(
+                    layers::table
+                        .select(layers::id)
+                        .filter(layers::map_id.eq(map_id)),
+                ),
+            ),
+        );
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.execute(conn).await
+    }
+}
@@ -12,9 +12,12 @@ use log::debug;
 use crate::{
     model::{
         dto::{RelationDto, RelationSearchParameters, RelationsDto},
-        r#enum::relation_type::RelationType,
+        r#enum::{
+            light_requirement::LightRequirement, relation_type::RelationType, soil_ph::SoilPh,
+            soil_texture::SoilTexture,
+        },
     },
-    schema::relations,
+    schema::{plants, relations},
 };
 
 /// The resolution of the generated heatmap in cm.
@@ -22,19 +25,19 @@ pub const GRANULARITY: i32 = 10;
 
 /// A bounding box around the maps geometry.
 #[derive(Debug, Clone, QueryableByName)]
-struct BoundingBox {
+pub struct BoundingBox {
     /// The lowest x value in the geometry.
     #[diesel(sql_type = Integer)]
-    x_min: i32,
+    pub x_min: i32,
     /// The lowest y value in the geometry.
     #[diesel(sql_type = Integer)]
-    y_min: i32,
+    pub y_min: i32,
     /// The highest x value in the geometry.
     #[diesel(sql_type = Integer)]
-    x_max: i32,
+    pub x_max: i32,
     /// The highest y value in the geometry.
     #[diesel(sql_type = Integer)]
-    y_max: i32,
+    pub y_max: i32,
 }
 
 /// Stores the score of a x,y coordinate on the heatmap.
@@ -51,8 +54,21 @@ struct HeatMapElement {
     y: i32,
 }
 
+/// Fetch the bounding box x and y values of the maps coordinates.
+///
+/// # Errors
+/// * If no map with id `map_id` exists.
+pub async fn bounding_box(map_id: i32, conn: &mut AsyncPgConnection) -> QueryResult<BoundingBox> {
+    let query = diesel::sql_query("SELECT * FROM calculate_bbox($1)").bind::<Integer, _>(map_id);
+    debug!("{}", debug_query::<Pg, _>(&query));
+    query.get_result::<BoundingBox>(conn).await
+}
+
 /// Generates a heatmap signaling ideal locations for planting the plant.
 ///
+/// The cell at `[y][x]` of the heatmap covers the area starting at
+/// `x_min + x * GRANULARITY`,`y_min + y * GRANULARITY` of the bounding box.
+///
 /// # Errors
 /// * If no map with id `map_id` exists.
 /// * If no layer with id `layer_id` exists, if the layer is not a plant layer or if the layer is not part of the map.
@@ -66,14 +82,9 @@ pub async fn heatmap(
     map_id: i32,
     layer_id: i32,
     plant_id: i32,
+    bounding_box: &BoundingBox,
     conn: &mut AsyncPgConnection,
 ) -> QueryResult<Vec<Vec<f32>>> {
-    // Fetch the bounding box x and y values of the maps coordinates
-    let bounding_box_query =
-        diesel::sql_query("SELECT * FROM calculate_bbox($1)").bind::<Integer, _>(map_id);
-    debug!("{}", debug_query::<Pg, _>(&bounding_box_query));
-    let bounding_box = bounding_box_query.get_result::<BoundingBox>(conn).await?;
-
     // Fetch the heatmap
     let query = diesel::sql_query("SELECT * FROM calculate_score($1, $2, $3, $4, $5, $6, $7, $8)")
         .bind::<Integer, _>(map_id)
@@ -100,6 +111,61 @@ pub async fn heatmap(
     Ok(heatmap)
 }
 
+/// Get the soil pH ranges and textures the plant tolerates.
+///
+/// # Errors
+/// * If no plant with id `plant_id` exists.
+pub async fn find_soil_requirements(
+    plant_id: i32,
+    conn: &mut AsyncPgConnection,
+) -> QueryResult<(Vec<SoilPh>, Vec<SoilTexture>)> {
+    let query = plants::table
+        .select((plants::soil_ph, plants::soil_texture))
+        .find(plant_id);
+    debug!("{}", debug_query::<Pg, _>(&query));
+    let (soil_ph, soil_texture) = query
+        .first::<(
+            Option<Vec<Option<SoilPh>>>,
+            Option<Vec<Option<SoilTexture>>>,
+        )>(conn)
+        .await?;
+    Ok((
+        soil_ph.into_iter().flatten().flatten().collect(),
+        soil_texture.into_iter().flatten().flatten().collect(),
+    ))
+}
+
+/// Get the light requirements of the plant.
+///
+/// # Errors
+/// * If no plant with id `plant_id` exists.
+pub async fn find_light_requirements(
+    plant_id: i32,
+    conn: &mut AsyncPgConnection,
+) -> QueryResult<Vec<LightRequirement>> {
+    let query = plants::table
+        .select(plants::light_requirement)
+        .find(plant_id);
+    debug!("{}", debug_query::<Pg, _>(&query));
+    let light_requirement = query
+        .first::<Option<Vec<Option<LightRequirement>>>>(conn)
+        .await?;
+    Ok(light_requirement.into_iter().flatten().flatten().collect())
+}
+
+/// Get whether the plant tolerates wind, `None` if unknown.
+///
+/// # Errors
+/// * If no plant with id `plant_id` exists.
+pub async fn find_wind_tolerance(
+    plant_id: i32,
+    conn: &mut AsyncPgConnection,
+) -> QueryResult<Option<bool>> {
+    let query = plants::table.select(plants::tolerates_wind).find(plant_id);
+    debug!("{}", debug_query::<Pg, _>(&query));
+    query.first::<Option<bool>>(conn).await
+}
+
 /// Get all relations of a certain plant.
 ///
 /// # Errors
@@ -128,3 +194,28 @@ pub async fn find_relations(
         relations,
     })
 }
+
+/// Get all companion and antagonist relations of the given plants.
+///
+/// As relations apply in both directions, they are returned as `(plant, related plant, relation)`
+/// where `plant` is one of `plant_ids`.
+///
+/// # Errors
+/// * If the SQL query fails.
+pub async fn find_relations_of_plants(
+    plant_ids: &[i32],
+    conn: &mut AsyncPgConnection,
+) -> QueryResult<Vec<(i32, i32, RelationType)>> {
+    let query = relations::table
+        .select((relations::plant1, relations::plant2, relations::relation))
+        .filter(relations::plant1.eq_any(plant_ids))
+        .filter(relations::relation.ne(RelationType::Neutral))
+        .union(
+            relations::table
+                .select((relations::plant2, relations::plant1, relations::relation))
+                .filter(relations::plant2.eq_any(plant_ids))
+                .filter(relations::relation.ne(RelationType::Neutral)),
+        );
+    debug!("{}", debug_query::<Pg, _>(&query));
+    query.load::<(i32, i32, RelationType)>(conn).await
+}
@@ -4,6 +4,7 @@ use chrono::NaiveDate;
 use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
 use uuid::Uuid;
 
+use crate::model::r#enum::planting_status::PlantingStatus;
 use crate::schema::plantings;
 
 /// The `Planting` entity.
@@ -36,6 +37,18 @@ pub struct Planting {
     /// The date the planting was removed from the map.
     /// If None, the planting is still on the map.
     pub remove_date: Option<NaiveDate>,
+    /// Notes about the planting.
+    pub notes: Option<String>,
+    /// Where the planting is in its lifecycle.
+    pub status: PlantingStatus,
+    /// The seed the plant was grown from.
+    pub seed_id: Option<i32>,
+    /// Paths to the photos of the planting in Nextcloud.
+    pub photos: Vec<Option<String>>,
+    /// The guild the planting belongs to.
+    pub guild_id: Option<Uuid>,
+    /// The planting this planting was copied from when its layer was forked into an alternative.
+    pub origin_id: Option<Uuid>,
     /*
     /// The date the planting was created.
     //pub create_date: NaiveDate,
@@ -68,4 +81,12 @@ pub struct UpdatePlanting {
     pub add_date: Option<Option<NaiveDate>>,
     /// The date the planting was removed from the map.
     pub remove_date: Option<Option<NaiveDate>>,
+    /// Notes about the planting.
+    pub notes: Option<Option<String>>,
+    /// Where the planting is in its lifecycle.
+    pub status: Option<PlantingStatus>,
+    /// The seed the plant was grown from.
+    pub seed_id: Option<Option<i32>>,
+    /// Paths to the photos of the planting in Nextcloud.
+    pub photos: Option<Vec<Option<String>>>,
 }
@@ -2,14 +2,23 @@
 
 use chrono::NaiveDate;
 use diesel::pg::Pg;
-use diesel::{debug_query, BoolExpressionMethods, ExpressionMethods, QueryDsl, QueryResult};
+use diesel::{
+    debug_query, BoolExpressionMethods, ExpressionMethods, QueryDsl, QueryResult, SelectableHelper,
+};
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use log::debug;
+use postgis_diesel::functions::st_covered_by;
+use postgis_diesel::types::{Point, Polygon};
 use uuid::Uuid;
 
+use crate::db::function::{st_d_within, st_make_envelope, st_make_point};
+
 use crate::model::dto::plantings::{NewPlantingDto, PlantingDto, UpdatePlantingDto};
 use crate::model::entity::plantings::{Planting, UpdatePlanting};
+use crate::model::entity::{PlantDiversity, Plants};
+use crate::model::r#enum::layer_type::LayerType;
 use crate::schema::plantings::{self, all_columns, layer_id, plant_id};
+use crate::schema::{layers, plants};
 
 /// Arguments for the database layer find plantings function.
 pub struct FindPlantingsParameters {
@@ -21,6 +30,35 @@ pub struct FindPlantingsParameters {
     pub from: NaiveDate,
     /// Last date in the time frame plantings are searched for.
     pub to: NaiveDate,
+    /// Areas of the map the positions of the plantings have to be in.
+    pub areas: Vec<PlantingArea>,
+}
+
+/// An area of the map plantings are searched in.
+/// Positions on the border of the area are inside.
+pub enum PlantingArea {
+    /// A rectangle aligned to the axes.
+    BoundingBox {
+        /// The lowest x coordinate.
+        x_min: i32,
+        /// The lowest y coordinate.
+        y_min: i32,
+        /// The highest x coordinate.
+        x_max: i32,
+        /// The highest y coordinate.
+        y_max: i32,
+    },
+    /// A circle.
+    Circle {
+        /// The x coordinate of the center.
+        x: i32,
+        /// The y coordinate of the center.
+        y: i32,
+        /// The radius.
+        radius: i32,
+    },
+    /// A polygon.
+    Polygon(Polygon<Point>),
 }
 
 impl Planting {
@@ -32,7 +70,16 @@ impl Planting {
         search_parameters: FindPlantingsParameters,
         conn: &mut AsyncPgConnection,
     ) -> QueryResult<Vec<PlantingDto>> {
-        let mut query = plantings::table.select(all_columns).into_boxed();
+        let mut query = plantings::table
+            .select(all_columns)
+            .filter(
+                layer_id.eq_any(
+                    layers::table
+                        .select(layers::id)
+                        .filter(layers::deletion_date.is_null()),
+                ),
+            )
+            .into_boxed();
 
         if let Some(id) = search_parameters.plant_id {
             query = query.filter(plant_id.eq(id));
@@ -52,8 +99,149 @@ impl Planting {
 
         query = query.filter(plantings_added_before_date.and(plantings_removed_after_date));
 
+        for area in search_parameters.areas {
+            let position = st_make_point(plantings::x, plantings::y);
+            query = match area {
+                PlantingArea::BoundingBox {
+                    x_min,
+                    y_min,
+                    x_max,
+                    y_max,
+                } => query.filter(st_covered_by(
+                    position,
+                    st_make_envelope(
+                        f64::from(x_min),
+                        f64::from(y_min),
+                        f64::from(x_max),
+                        f64::from(y_max),
+                    ),
+                )),
+                PlantingArea::Circle { x, y, radius } => query.filter(st_d_within(
+                    position,
+                    Point::new(f64::from(x), f64::from(y), None),
+                    f64::from(radius),
+                )),
+                PlantingArea::Polygon(polygon) => query.filter(st_covered_by(position, polygon)),
+            };
+        }
+
+        debug!("{}", debug_query::<Pg, _>(&query));
+
+        Ok(query
+            .load::<Self>(conn)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// Get all plantings with their plants on plant layers of the map that exist between `from` and `to`.
+    /// Plantings on alternative layers are ignored.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_map_with_plants(
+        map_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<(Self, Plants)>> {
+        let query = plantings::table
+            .inner_join(layers::table)
+            .inner_join(plants::table)
+            .select((all_columns, plants::all_columns))
+            .filter(layers::map_id.eq(map_id))
+            .filter(layers::type_.eq(LayerType::Plants))
+            .filter(layers::is_alternative.eq(false))
+            .filter(layers::deletion_date.is_null())
+            .filter(plantings::add_date.is_null().or(plantings::add_date.le(to)))
+            .filter(
+                plantings::remove_date
+                    .is_null()
+                    .or(plantings::remove_date.ge(from)),
+            )
+            .order((plants::unique_name, plantings::id));
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.load::<(Self, Plants)>(conn).await
+    }
+
+    /// Get all plantings with their plants on the layer that exist between `from` and `to`.
+    /// Plantings of a deleted layer are ignored.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_layer_with_plants(
+        layer: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<(Self, Plants)>> {
+        let query = plantings::table
+            .inner_join(layers::table)
+            .inner_join(plants::table)
+            .select((all_columns, plants::all_columns))
+            .filter(layer_id.eq(layer))
+            .filter(layers::deletion_date.is_null())
+            .filter(plantings::add_date.is_null().or(plantings::add_date.le(to)))
+            .filter(
+                plantings::remove_date
+                    .is_null()
+                    .or(plantings::remove_date.ge(from)),
+            )
+            .order((plantings::add_date, plantings::id));
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.load::<(Self, Plants)>(conn).await
+    }
+
+    /// Get the ids of all plantings on plant layers of the map that exist at `date` together with the diversity attributes of their plants.
+    /// If `layer_id` is set, only plantings on this layer are used, otherwise plantings on alternative layers are ignored.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_active_plants(
+        map_id: i32,
+        layer: Option<i32>,
+        date: NaiveDate,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<(Uuid, PlantDiversity)>> {
+        let mut query = plantings::table
+            .inner_join(layers::table)
+            .inner_join(plants::table)
+            .select((plantings::id, PlantDiversity::as_select()))
+            .filter(layers::map_id.eq(map_id))
+            .filter(layers::type_.eq(LayerType::Plants))
+            .filter(layers::deletion_date.is_null())
+            .filter(
+                plantings::add_date
+                    .is_null()
+                    .or(plantings::add_date.le(date)),
+            )
+            .filter(
+                plantings::remove_date
+                    .is_null()
+                    .or(plantings::remove_date.gt(date)),
+            )
+            .into_boxed();
+        query = match layer {
+            Some(id) => query.filter(layer_id.eq(id)),
+            None => query.filter(layers::is_alternative.eq(false)),
+        };
         debug!("{}", debug_query::<Pg, _>(&query));
+        query.load::<(Uuid, PlantDiversity)>(conn).await
+    }
 
+    /// Get all plantings of a layer regardless of when they exist.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_layer(
+        planting_layer_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<PlantingDto>> {
+        let query = plantings::table
+            .filter(layer_id.eq(planting_layer_id))
+            .order(plantings::id);
+        debug!("{}", debug_query::<Pg, _>(&query));
         Ok(query
             .load::<Self>(conn)
             .await?
@@ -62,6 +250,25 @@ impl Planting {
             .collect())
     }
 
+    /// Get the planting with the given id if it is on a plant layer of the map.
+    ///
+    /// # Errors
+    /// * If the planting does not exist on the map.
+    pub async fn find_by_id_on_map(
+        id: Uuid,
+        map_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Self> {
+        let query = plantings::table
+            .inner_join(layers::table)
+            .select(all_columns)
+            .filter(plantings::id.eq(id))
+            .filter(layers::map_id.eq(map_id))
+            .filter(layers::deletion_date.is_null());
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.first::<Self>(conn).await
+    }
+
     /// Create a new planting in the database.
     ///
     /// # Errors
@@ -1,47 +1,70 @@
 //! Contains the implementation of [`Plants`].
 
+use std::collections::{HashMap, HashSet};
+
 use diesel::{
     debug_query,
-    dsl::sql,
+    dsl::{exists, not, now, sql},
+    pg::upsert::excluded,
     pg::Pg,
-    sql_types::{Bool, Float, Integer},
-    BoolExpressionMethods, ExpressionMethods, QueryDsl, QueryResult,
+    sql_types::{Bool, Float, Integer, Nullable},
+    BoolExpressionMethods, BoxableExpression, ExpressionMethods, IntoSql, PgArrayExpressionMethods,
+    QueryDsl, QueryResult, SelectableHelper,
+};
+use diesel_async::{
+    methods::LoadQuery, scoped_futures::ScopedFutureExt, AsyncConnection, AsyncPgConnection,
+    RunQueryDsl,
 };
-use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use log::debug;
+use serde::Serialize;
 use uuid::Uuid;
 
 use crate::{
     db::{
-        function::{array_to_string, greatest, similarity, similarity_nullable},
+        facet::{CountValues, CountedValuesQuery},
+        function::{array_to_string, greatest, similarity, similarity_nullable, unnest},
         pagination::Paginate,
     },
     model::{
-        dto::{Page, PageParameters, PlantsSummaryDto},
-        r#enum::quantity::Quantity,
+        dto::{
+            FacetDto, ImportPlantDto, ImportRelationDto, NewCustomPlantDto, Page, PageParameters,
+            PlantDetailDto, PlantsDatasetDto, PlantsImportReportDto, PlantsSearchParameters,
+            PlantsSummaryDto,
+        },
+        r#enum::{
+            external_source::ExternalSource, fertility::Fertility, growth_rate::GrowthRate,
+            life_cycle::LifeCycle, light_requirement::LightRequirement, plant_height::PlantHeight,
+            plant_spread::PlantSpread, quantity::Quantity, water_requirement::WaterRequirement,
+        },
     },
     schema::{
         plants::{
-            self, all_columns, common_name_de, common_name_en, edible_uses_en, sowing_outdoors,
-            unique_name,
+            self, all_columns, alternate_name, common_name_de, common_name_en, ecosystem_niche,
+            edible, edible_uses_en, external_source, family, fertility, functions, growth_rate,
+            height, herbaceous_or_woody, life_cycle, light_requirement, owner_id, sowing_outdoors,
+            spread, submitted_for_review, unique_name, updated_at, water_requirement,
         },
-        seeds,
+        relations, seeds,
     },
 };
 
-use super::Plants;
+use super::{NewPlants, PlantDiversity, Plants};
 
 impl Plants {
     /// Get the top plants matching the search query.
     ///
-    /// Uses `pg_trgm` to find matches in `unique_name`, `common_name_de`, `common_name_en` and `edible_uses_en`.
+    /// Uses `pg_trgm` to find matches in `unique_name`, `common_name_de`, `common_name_en`, `alternate_name` and `edible_uses_en`.
     /// Ranks them using the `pg_trgm` function `similarity()`.
+    /// Only plants visible to the user and matching all attribute filters of the `search_parameters` are returned.
+    /// The common names of the results are in the language requested by the `search_parameters`.
     ///
     /// # Errors
     /// * Unknown, diesel doesn't say why it might error.
     pub async fn search(
         search_query: &str,
+        search_parameters: &PlantsSearchParameters,
         page_parameters: PageParameters,
+        user_id: Uuid,
         conn: &mut AsyncPgConnection,
     ) -> QueryResult<Page<PlantsSummaryDto>> {
         let query = plants::table
@@ -50,57 +73,388 @@ impl Plants {
                     similarity(unique_name, search_query),
                     similarity(array_to_string(common_name_de, " "), search_query),
                     similarity(array_to_string(common_name_en, " "), search_query),
+                    similarity_nullable(alternate_name, search_query),
                     similarity_nullable(edible_uses_en, search_query),
                 ),
                 plants::all_columns,
             ))
-            .filter(
-                similarity(unique_name, search_query)
-                    .gt(0.1)
-                    .or(similarity(array_to_string(common_name_de, " "), search_query).gt(0.1))
-                    .or(similarity(array_to_string(common_name_en, " "), search_query).gt(0.1))
-                    .or(similarity_nullable(edible_uses_en, search_query).gt(0.1)),
-            )
+            .filter(similar_to(search_query))
+            .into_boxed();
+        let query = filter_attributes(query, search_parameters, user_id)
             .order(sql::<Float>("1").desc())
             .paginate(page_parameters.page)
             .per_page(page_parameters.per_page);
         debug!("{}", debug_query::<Pg, _>(&query));
-        query
-            .load_page::<(f32, Self)>(conn)
-            .await
-            .map(Page::from_entity)
+        let language = search_parameters.lang.unwrap_or_default();
+        let page = query.load_page::<(f32, Self)>(conn).await?;
+        Ok(page.map(|(_, plant)| PlantsSummaryDto::localized(plant, language)))
     }
 
-    /// Get a page of some plants.
+    /// Get a page of some plants visible to the user and matching the attribute filters of the `search_parameters`.
+    /// The common names of the results are in the language requested by the `search_parameters`.
     ///
     /// # Errors
     /// * Unknown, diesel doesn't say why it might error.
     pub async fn find_any(
+        search_parameters: &PlantsSearchParameters,
         page_parameters: PageParameters,
+        user_id: Uuid,
         conn: &mut AsyncPgConnection,
     ) -> QueryResult<Page<PlantsSummaryDto>> {
-        let query = plants::table
-            .select(all_columns)
-            .into_boxed()
+        let query = plants::table.select(all_columns).into_boxed();
+        let query = filter_attributes(query, search_parameters, user_id)
             .paginate(page_parameters.page)
             .per_page(page_parameters.per_page);
         debug!("{}", debug_query::<Pg, _>(&query));
-        query.load_page::<Self>(conn).await.map(Page::from_entity)
+        let language = search_parameters.lang.unwrap_or_default();
+        let page = query.load_page::<Self>(conn).await?;
+        Ok(page.map(|plant| PlantsSummaryDto::localized(plant, language)))
     }
 
-    /// Fetch plant by id from the database.
+    /// Count how many plants visible to the user and matching the `search_parameters` have each value of the filterable attributes.
+    ///
+    /// The hardiness zone is not part of the facets as it is stored as a range.
     ///
     /// # Errors
     /// * Unknown, diesel doesn't say why it might error.
+    pub async fn facets(
+        search_parameters: &PlantsSearchParameters,
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<FacetDto>> {
+        Ok(vec![
+            facet::<_, LifeCycle>(
+                "life_cycle",
+                plants::table.select(unnest(life_cycle)).into_boxed(),
+                search_parameters,
+                user_id,
+                conn,
+            )
+            .await?,
+            facet::<_, GrowthRate>(
+                "growth_rate",
+                plants::table.select(unnest(growth_rate)).into_boxed(),
+                search_parameters,
+                user_id,
+                conn,
+            )
+            .await?,
+            facet::<_, LightRequirement>(
+                "light_requirement",
+                plants::table.select(unnest(light_requirement)).into_boxed(),
+                search_parameters,
+                user_id,
+                conn,
+            )
+            .await?,
+            facet::<_, WaterRequirement>(
+                "water_requirement",
+                plants::table.select(unnest(water_requirement)).into_boxed(),
+                search_parameters,
+                user_id,
+                conn,
+            )
+            .await?,
+            facet::<_, Fertility>(
+                "fertility",
+                plants::table.select(unnest(fertility)).into_boxed(),
+                search_parameters,
+                user_id,
+                conn,
+            )
+            .await?,
+            facet::<_, bool>(
+                "edible",
+                plants::table.select(edible).into_boxed(),
+                search_parameters,
+                user_id,
+                conn,
+            )
+            .await?,
+            facet::<_, PlantHeight>(
+                "height",
+                plants::table.select(height).into_boxed(),
+                search_parameters,
+                user_id,
+                conn,
+            )
+            .await?,
+            facet::<_, PlantSpread>(
+                "spread",
+                plants::table.select(spread).into_boxed(),
+                search_parameters,
+                user_id,
+                conn,
+            )
+            .await?,
+        ])
+    }
+
+    /// Fetch plant by id from the database.
+    ///
+    /// # Errors
+    /// * If the plant does not exist or is not visible to the user.
     pub async fn find_by_id(
         id: i32,
+        user_id: Uuid,
         conn: &mut AsyncPgConnection,
     ) -> QueryResult<PlantsSummaryDto> {
-        let query = plants::table.find(id);
+        let query = plants::table.find(id).filter(visible_to(user_id));
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.first::<Self>(conn).await.map(Into::into)
+    }
+
+    /// Fetch plant by id including all of its details from the database.
+    ///
+    /// # Errors
+    /// * If the plant does not exist or is not visible to the user.
+    pub async fn find_details_by_id(
+        id: i32,
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<PlantDetailDto> {
+        let query = plants::table.find(id).filter(visible_to(user_id));
         debug!("{}", debug_query::<Pg, _>(&query));
         query.first::<Self>(conn).await.map(Into::into)
     }
 
+    /// Whether the plant exists and is visible to the user.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn is_visible(
+        id: i32,
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<bool> {
+        let query = diesel::select(exists(plants::table.find(id).filter(visible_to(user_id))));
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.get_result::<bool>(conn).await
+    }
+
+    /// Create a custom plant or variety of the user.
+    ///
+    /// All attributes not given in `new_plant` as well as the relations to other plants
+    /// are copied from the base plant.
+    ///
+    /// # Errors
+    /// * If the base plant does not exist or is not visible to the user.
+    /// * If a plant with the same `unique_name` already exists.
+    pub async fn create_custom(
+        new_plant: NewCustomPlantDto,
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<PlantDetailDto> {
+        conn.transaction(|conn| {
+            async move {
+                let base_query = plants::table
+                    .find(new_plant.base_plant_id)
+                    .filter(visible_to(user_id));
+                debug!("{}", debug_query::<Pg, _>(&base_query));
+                let base = base_query.first::<Self>(conn).await?;
+                let base_id = base.id;
+
+                let new_plant = NewPlants::from((new_plant, base, user_id));
+                let query = diesel::insert_into(plants::table).values(&new_plant);
+                debug!("{}", debug_query::<Pg, _>(&query));
+                let plant = query.get_result::<Self>(conn).await?;
+
+                let outgoing_query = diesel::insert_into(relations::table)
+                    .values(
+                        relations::table
+                            .select((
+                                plant.id.into_sql::<Integer>(),
+                                relations::plant2,
+                                relations::relation,
+                                relations::note,
+                            ))
+                            .filter(relations::plant1.eq(base_id)),
+                    )
+                    .into_columns((
+                        relations::plant1,
+                        relations::plant2,
+                        relations::relation,
+                        relations::note,
+                    ));
+                debug!("{}", debug_query::<Pg, _>(&outgoing_query));
+                outgoing_query.execute(conn).await?;
+
+                let incoming_query = diesel::insert_into(relations::table)
+                    .values(
+                        relations::table
+                            .select((
+                                relations::plant1,
+                                plant.id.into_sql::<Integer>(),
+                                relations::relation,
+                                relations::note,
+                            ))
+                            .filter(relations::plant2.eq(base_id)),
+                    )
+                    .into_columns((
+                        relations::plant1,
+                        relations::plant2,
+                        relations::relation,
+                        relations::note,
+                    ));
+                debug!("{}", debug_query::<Pg, _>(&incoming_query));
+                incoming_query.execute(conn).await?;
+
+                Ok(plant.into())
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Submit a custom plant of the user for review to be added to the shared catalogue.
+    ///
+    /// # Errors
+    /// * If the plant does not exist or is not owned by the user.
+    pub async fn submit_for_review(
+        id: i32,
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<PlantDetailDto> {
+        let query = diesel::update(plants::table.find(id).filter(owner_id.eq(user_id)))
+            .set(submitted_for_review.eq(true));
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.get_result::<Self>(conn).await.map(Into::into)
+    }
+
+    /// Import a dataset of the `source` into the plant catalogue.
+    ///
+    /// Plants are matched by their `unique_name`.
+    /// New plants are added, plants whose attributes differ from the dataset are updated and their `version` is incremented.
+    /// Plants belonging to another source are never changed, custom plants of users are not considered.
+    /// Plants of the `source` missing in the dataset are only reported, as they might still be in use.
+    /// Relations between plants unknown to the catalogue are ignored.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn import(
+        dataset: PlantsDatasetDto,
+        source: ExternalSource,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<PlantsImportReportDto> {
+        conn.transaction(|conn| {
+            async move {
+                let mut report = PlantsImportReportDto::default();
+                let names = dataset
+                    .plants
+                    .iter()
+                    .map(|plant| plant.unique_name.clone())
+                    .collect::<Vec<_>>();
+
+                let existing_query = plants::table
+                    .filter(unique_name.eq_any(&names))
+                    .filter(owner_id.is_null());
+                debug!("{}", debug_query::<Pg, _>(&existing_query));
+                let mut existing = existing_query
+                    .load::<Self>(conn)
+                    .await?
+                    .into_iter()
+                    .map(|plant| (plant.unique_name.clone(), plant))
+                    .collect::<HashMap<_, _>>();
+
+                let mut imported = HashSet::new();
+                for plant in dataset.plants {
+                    if !imported.insert(plant.unique_name.clone()) {
+                        report.skipped.push(plant.unique_name);
+                        continue;
+                    }
+                    match existing.remove(&plant.unique_name) {
+                        None => {
+                            report.added.push(plant.unique_name.clone());
+                            let new_plant = NewPlants::from((plant, source, 1));
+                            let query = diesel::insert_into(plants::table).values(&new_plant);
+                            debug!("{}", debug_query::<Pg, _>(&query));
+                            query.execute(conn).await?;
+                        }
+                        Some(current) if current.external_source != Some(source) => {
+                            report.skipped.push(plant.unique_name);
+                        }
+                        Some(current) => {
+                            let plant_id = current.id;
+                            let version = current.version.unwrap_or(0).saturating_add(1);
+                            if ImportPlantDto::from(current) == plant {
+                                continue;
+                            }
+                            report.changed.push(plant.unique_name.clone());
+                            let changed_plant = NewPlants::from((plant, source, version));
+                            let query = diesel::update(plants::table.find(plant_id))
+                                .set((&changed_plant, updated_at.eq(now)));
+                            debug!("{}", debug_query::<Pg, _>(&query));
+                            query.execute(conn).await?;
+                        }
+                    }
+                }
+
+                let removed_query = plants::table
+                    .select(unique_name)
+                    .filter(external_source.eq(source))
+                    .filter(owner_id.is_null())
+                    .filter(not(unique_name.eq_any(&names)))
+                    .order(unique_name);
+                debug!("{}", debug_query::<Pg, _>(&removed_query));
+                report.removed = removed_query.load::<String>(conn).await?;
+
+                report.relations = Self::import_relations(dataset.relations, conn).await?;
+
+                Ok(report)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Upsert the `relations` of an imported dataset and return how many were stored.
+    async fn import_relations(
+        dataset_relations: Vec<ImportRelationDto>,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<i32> {
+        let relation_names = dataset_relations
+            .iter()
+            .flat_map(|relation| [relation.plant1.clone(), relation.plant2.clone()])
+            .collect::<Vec<_>>();
+        let ids_query = plants::table
+            .select((unique_name, plants::id))
+            .filter(unique_name.eq_any(&relation_names))
+            .filter(owner_id.is_null());
+        debug!("{}", debug_query::<Pg, _>(&ids_query));
+        let ids = ids_query
+            .load::<(String, i32)>(conn)
+            .await?
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+        let mut count = 0_i32;
+        for relation in dataset_relations {
+            let (Some(plant1), Some(plant2)) =
+                (ids.get(&relation.plant1), ids.get(&relation.plant2))
+            else {
+                continue;
+            };
+            let query = diesel::insert_into(relations::table)
+                .values((
+                    relations::plant1.eq(plant1),
+                    relations::plant2.eq(plant2),
+                    relations::relation.eq(relation.relation),
+                    relations::note.eq(relation.note),
+                ))
+                .on_conflict((relations::plant1, relations::plant2))
+                .do_update()
+                .set((
+                    relations::relation.eq(excluded(relations::relation)),
+                    relations::note.eq(excluded(relations::note)),
+                ));
+            debug!("{}", debug_query::<Pg, _>(&query));
+            query.execute(conn).await?;
+            count = count.saturating_add(1);
+        }
+
+        Ok(count)
+    }
+
     /// Fetch available and seasonal plants.
     /// - A plant is available if for the given `user_id` there is a `Seed` with `quantity` not `Nothing`.
     /// - A plant is seasonal if the given `half_of_month` is included in a `Plant`'s `sowing_outdoors`
@@ -141,4 +495,169 @@ impl Plants {
 
         query.load_page::<Self>(conn).await.map(Page::from_entity)
     }
+
+    /// Fetch the plants with the given ids that are seasonal and visible to the user.
+    /// - A plant is seasonal if the given `half_of_month` is included in a `Plant`'s `sowing_outdoors`
+    ///   or the `sowing_outdoors` is empty.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_seasonal_by_ids(
+        ids: &[i32],
+        half_month_bucket: i32,
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<Self>> {
+        let query = plants::table
+            .select(all_columns)
+            .filter(plants::id.eq_any(ids))
+            .filter(visible_to(user_id))
+            .filter(
+                sql::<Bool>("")
+                    .bind::<Integer, _>(half_month_bucket)
+                    .sql(" = ANY(")
+                    .bind(sowing_outdoors)
+                    .sql(")")
+                    .or(sowing_outdoors.is_null()),
+            );
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.load::<Self>(conn).await
+    }
+}
+
+impl PlantDiversity {
+    /// Fetch the diversity attributes of all seasonal plants visible to the user.
+    /// - A plant is seasonal if the given `half_of_month` is included in a `Plant`'s `sowing_outdoors`
+    ///   or the `sowing_outdoors` is empty.
+    /// - Plants without any diversity attributes are ignored.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_seasonal(
+        half_month_bucket: i32,
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<Self>> {
+        let query = plants::table
+            .select(Self::as_select())
+            .filter(visible_to(user_id))
+            .filter(
+                sql::<Bool>("")
+                    .bind::<Integer, _>(half_month_bucket)
+                    .sql(" = ANY(")
+                    .bind(sowing_outdoors)
+                    .sql(")")
+                    .or(sowing_outdoors.is_null()),
+            )
+            .filter(
+                family
+                    .is_not_null()
+                    .or(functions.is_not_null())
+                    .or(ecosystem_niche.is_not_null())
+                    .or(herbaceous_or_woody.is_not_null()),
+            )
+            .order(unique_name);
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.load::<Self>(conn).await
+    }
+}
+
+/// Condition matching plants whose names or edible uses are similar to the `search_query`.
+fn similar_to(
+    search_query: &str,
+) -> Box<dyn BoxableExpression<plants::table, Pg, SqlType = Bool> + '_> {
+    Box::new(
+        similarity(unique_name, search_query)
+            .gt(0.1)
+            .or(similarity(array_to_string(common_name_de, " "), search_query).gt(0.1))
+            .or(similarity(array_to_string(common_name_en, " "), search_query).gt(0.1))
+            .or(similarity_nullable(alternate_name, search_query).gt(0.1))
+            .or(similarity_nullable(edible_uses_en, search_query).gt(0.1)),
+    )
+}
+
+/// Condition matching plants of the shared catalogue and custom plants of the user.
+fn visible_to(
+    user_id: Uuid,
+) -> Box<dyn BoxableExpression<plants::table, Pg, SqlType = Nullable<Bool>>> {
+    Box::new(owner_id.is_null().or(owner_id.eq(user_id)))
+}
+
+/// Restrict the query to plants visible to the user and matching the attribute filters of the `search_parameters`.
+///
+/// The `name` of the `search_parameters` is not used, as searching by name also changes the ranking.
+fn filter_attributes<'a, ST: 'a>(
+    mut query: plants::BoxedQuery<'a, Pg, ST>,
+    search_parameters: &PlantsSearchParameters,
+    user_id: Uuid,
+) -> plants::BoxedQuery<'a, Pg, ST> {
+    query = query.filter(visible_to(user_id));
+    if let Some(value) = search_parameters.life_cycle {
+        query = query.filter(life_cycle.contains(vec![Some(value)]));
+    }
+    if let Some(value) = search_parameters.growth_rate {
+        query = query.filter(growth_rate.contains(vec![Some(value)]));
+    }
+    if let Some(value) = search_parameters.light_requirement {
+        query = query.filter(light_requirement.contains(vec![Some(value)]));
+    }
+    if let Some(value) = search_parameters.water_requirement {
+        query = query.filter(water_requirement.contains(vec![Some(value)]));
+    }
+    if let Some(value) = search_parameters.fertility {
+        query = query.filter(fertility.contains(vec![Some(value)]));
+    }
+    if let Some(value) = search_parameters.edible {
+        query = query.filter(edible.eq(value));
+    }
+    if let Some(zone) = search_parameters.hardiness_zone {
+        // The hardiness zone is stored as a range (e.g. `[4,9)`) where both bounds are inclusive.
+        query = query.filter(
+            sql::<Bool>(r"substring(plants.hardiness_zone from '^\[(\d+),')::integer <= ")
+                .bind::<Integer, _>(zone)
+                .sql(r" AND substring(plants.hardiness_zone from ',(\d+)\)$')::integer >= ")
+                .bind::<Integer, _>(zone),
+        );
+    }
+    if let Some(value) = search_parameters.min_height {
+        query = query.filter(height.ge(value));
+    }
+    if let Some(value) = search_parameters.max_height {
+        query = query.filter(height.le(value));
+    }
+    if let Some(value) = search_parameters.min_spread {
+        query = query.filter(spread.ge(value));
+    }
+    if let Some(value) = search_parameters.max_spread {
+        query = query.filter(spread.le(value));
+    }
+    query
+}
+
+/// Count the values selected by `query` for all plants visible to the user and matching the `search_parameters`.
+async fn facet<'a, ST, T>(
+    attribute: &str,
+    query: plants::BoxedQuery<'a, Pg, ST>,
+    search_parameters: &'a PlantsSearchParameters,
+    user_id: Uuid,
+    conn: &mut AsyncPgConnection,
+) -> QueryResult<FacetDto>
+where
+    ST: 'a,
+    T: Serialize + Send,
+    CountedValuesQuery<plants::BoxedQuery<'a, Pg, ST>>:
+        LoadQuery<'a, AsyncPgConnection, (Option<T>, i64)>,
+{
+    let mut query = filter_attributes(query, search_parameters, user_id);
+    if let Some(search_query) = search_parameters
+        .name
+        .as_deref()
+        .filter(|name| !name.is_empty())
+    {
+        query = query.filter(similar_to(search_query));
+    }
+    let query = query.count_values();
+    debug!("{}", debug_query::<Pg, _>(&query));
+    let values = query.load::<(Option<T>, i64)>(conn).await?;
+    Ok(FacetDto::new(attribute, values))
 }
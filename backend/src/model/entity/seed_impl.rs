@@ -1,19 +1,23 @@
 //! Contains the implementation of [`Seed`].
 
+use std::collections::HashMap;
+
 use diesel::pg::Pg;
 use diesel::{
-    debug_query, BoolExpressionMethods, ExpressionMethods, PgTextExpressionMethods, QueryDsl,
-    QueryResult,
+    debug_query, BoolExpressionMethods, ExpressionMethods, OptionalExtension,
+    PgTextExpressionMethods, QueryDsl, QueryResult,
 };
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use log::debug;
 use uuid::Uuid;
 
 use crate::db::pagination::Paginate;
-use crate::model::dto::{Page, PageParameters, SeedSearchParameters};
+use crate::model::dto::{
+    Page, PageParameters, SeedLineageDto, SeedLineageNodeDto, SeedSearchParameters,
+};
 use crate::{
     model::dto::{NewSeedDto, SeedDto},
-    schema::seeds::{self, all_columns, harvest_year, name, owner_id},
+    schema::seeds::{self, all_columns, generation, harvest_year, name, owner_id, parent_seed_id},
 };
 
 use super::{NewSeed, Seed};
@@ -66,16 +70,86 @@ impl Seed {
         query.first::<Self>(conn).await.map(Into::into)
     }
 
+    /// Fetch the ancestors and descendants of a seed from the database.
+    ///
+    /// # Errors
+    /// * If the seed does not exist or does not belong to the user.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_lineage(
+        id: i32,
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<SeedLineageDto> {
+        let seed_query = seeds::table.filter(owner_id.eq(user_id).and(seeds::id.eq(id)));
+        debug!("{}", debug_query::<Pg, _>(&seed_query));
+        let seed = seed_query.first::<Self>(conn).await?;
+
+        // Follow the parents until the root of the lineage is reached.
+        let mut ancestors = Vec::new();
+        let mut next_parent_id = seed.parent_seed_id;
+        while let Some(parent_id) = next_parent_id {
+            let parent_query =
+                seeds::table.filter(owner_id.eq(user_id).and(seeds::id.eq(parent_id)));
+            debug!("{}", debug_query::<Pg, _>(&parent_query));
+            let Some(parent) = parent_query.first::<Self>(conn).await.optional()? else {
+                break;
+            };
+            next_parent_id = parent.parent_seed_id;
+            ancestors.push(parent.into());
+        }
+
+        // Load the descendants one generation at a time.
+        let mut children_by_parent: HashMap<i32, Vec<Self>> = HashMap::new();
+        let mut generation_ids = vec![seed.id];
+        while !generation_ids.is_empty() {
+            let query = seeds::table
+                .filter(
+                    owner_id
+                        .eq(user_id)
+                        .and(parent_seed_id.eq_any(&generation_ids)),
+                )
+                .order(seeds::id);
+            debug!("{}", debug_query::<Pg, _>(&query));
+            let children = query.load::<Self>(conn).await?;
+
+            generation_ids = children.iter().map(|child| child.id).collect();
+            for child in children {
+                if let Some(parent_id) = child.parent_seed_id {
+                    children_by_parent.entry(parent_id).or_default().push(child);
+                }
+            }
+        }
+
+        let descendants = lineage_nodes(seed.id, &mut children_by_parent);
+        Ok(SeedLineageDto {
+            seed: seed.into(),
+            ancestors,
+            descendants,
+        })
+    }
+
     /// Create a new seed in the database.
     ///
+    /// If the seed was harvested from another seed, its generation is derived from the parent.
+    ///
     /// # Errors
+    /// * If the parent seed does not exist or does not belong to the user.
     /// * Unknown, diesel doesn't say why it might error.
     pub async fn create(
         new_seed: NewSeedDto,
         user_id: Uuid,
         conn: &mut AsyncPgConnection,
     ) -> QueryResult<SeedDto> {
-        let new_seed = NewSeed::from((new_seed, user_id));
+        let mut new_seed = NewSeed::from((new_seed, user_id));
+        if let Some(parent_id) = new_seed.parent_seed_id {
+            let parent_query = seeds::table
+                .select(generation)
+                .filter(owner_id.eq(user_id).and(seeds::id.eq(parent_id)));
+            debug!("{}", debug_query::<Pg, _>(&parent_query));
+            let parent_generation = parent_query.first::<Option<i16>>(conn).await?;
+            new_seed.generation = Some(parent_generation.unwrap_or(0).saturating_add(1));
+        }
+
         let query = diesel::insert_into(seeds::table).values(&new_seed);
         debug!("{}", debug_query::<Pg, _>(&query));
         query.get_result::<Self>(conn).await.map(Into::into)
@@ -98,3 +172,19 @@ impl Seed {
         query.execute(conn).await
     }
 }
+
+/// Build the lineage tree below the seed with id `parent_id`.
+fn lineage_nodes(
+    parent_id: i32,
+    children_by_parent: &mut HashMap<i32, Vec<Seed>>,
+) -> Vec<SeedLineageNodeDto> {
+    children_by_parent
+        .remove(&parent_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|child| SeedLineageNodeDto {
+            children: lineage_nodes(child.id, children_by_parent),
+            seed: child.into(),
+        })
+        .collect()
+}
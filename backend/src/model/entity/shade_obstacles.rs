@@ -0,0 +1,35 @@
+//! All entities associated with [`ShadeObstacle`].
+
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable, Selectable};
+use postgis_diesel::types::{Point, Polygon};
+use uuid::Uuid;
+
+use crate::schema::shade_obstacles;
+
+/// The `ShadeObstacle` entity.
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable, Insertable)]
+#[diesel(table_name = shade_obstacles)]
+pub struct ShadeObstacle {
+    /// The id of the obstacle.
+    pub id: Uuid,
+    /// The shade layer the obstacle is on.
+    pub layer_id: i32,
+    /// The name of the obstacle, e.g. `House`.
+    pub name: String,
+    /// The footprint of the obstacle.
+    pub geometry: Polygon<Point>,
+    /// The height of the obstacle in cm.
+    pub height: i32,
+}
+
+/// The `UpdateShadeObstacle` entity.
+#[derive(Debug, Clone, AsChangeset)]
+#[diesel(table_name = shade_obstacles)]
+pub struct UpdateShadeObstacle {
+    /// The name of the obstacle.
+    pub name: Option<String>,
+    /// The footprint of the obstacle.
+    pub geometry: Option<Polygon<Point>>,
+    /// The height of the obstacle in cm.
+    pub height: Option<i32>,
+}
@@ -0,0 +1,100 @@
+//! Contains the implementation of [`ShadeObstacle`].
+
+use diesel::pg::Pg;
+use diesel::{debug_query, ExpressionMethods, QueryDsl, QueryResult, SelectableHelper};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use log::debug;
+use uuid::Uuid;
+
+use crate::model::dto::shade::ShadeObstacleDto;
+use crate::model::entity::shade_obstacles::{ShadeObstacle, UpdateShadeObstacle};
+use crate::schema::{layers, shade_obstacles};
+
+impl ShadeObstacle {
+    /// Get all obstacles of a shade layer.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_layer(
+        layer_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<ShadeObstacleDto>> {
+        let query = shade_obstacles::table
+            .filter(shade_obstacles::layer_id.eq(layer_id))
+            .order((shade_obstacles::name, shade_obstacles::id));
+        debug!("{}", debug_query::<Pg, _>(&query));
+        Ok(query
+            .load::<Self>(conn)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// Create a new obstacle in the database.
+    ///
+    /// # Errors
+    /// * If the `layer_id` references a layer that doesn't exist.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn create(
+        obstacle: Self,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<ShadeObstacleDto> {
+        let query = diesel::insert_into(shade_obstacles::table).values(&obstacle);
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.get_result::<Self>(conn).await.map(Into::into)
+    }
+
+    /// Partially update an obstacle on a map.
+    ///
+    /// # Errors
+    /// * If the obstacle does not exist on the map.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn update(
+        id: Uuid,
+        map_id: i32,
+        obstacle: UpdateShadeObstacle,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<ShadeObstacleDto> {
+        let query = diesel::update(
+            shade_obstacles::table
+                .filter(shade_obstacles::id.eq(id))
+                .filter(
+                    shade_obstacles::layer_id.eq_any(
+                        layers::table
+                            .select(layers::id)
+                            .filter(layers::map_id.eq(map_id))
+                            .filter(layers::deletion_date.is_null()),
+                    ),
+                ),
+        )
+        .set(&obstacle)
+        .returning(Self::as_returning());
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.get_result::<Self>(conn).await.map(Into::into)
+    }
+
+    /// Delete an obstacle on a map.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn delete_by_id(
+        id: Uuid,
+        map_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<usize> {
+        let query = diesel::delete(
+            shade_obstacles::table
+                .filter(shade_obstacles::id.eq(id))
+                .filter(
+                    shade_obstacles::layer_id.eq_any(
+                        layers::table
+                            .select(layers::id)
+                            .filter(layers::map_id.eq(map_id)),
+                    ),
+                ),
+        );
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.execute(conn).await
+    }
+}
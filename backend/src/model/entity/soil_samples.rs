@@ -0,0 +1,52 @@
+//! All entities associated with [`SoilSample`].
+
+use chrono::NaiveDate;
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable, Selectable};
+use uuid::Uuid;
+
+use crate::model::r#enum::{soil_texture::SoilTexture, soil_water_retention::SoilWaterRetention};
+use crate::schema::soil_samples;
+
+/// The `SoilSample` entity.
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable, Insertable)]
+#[diesel(table_name = soil_samples)]
+pub struct SoilSample {
+    /// The id of the sample.
+    pub id: Uuid,
+    /// The soil layer the sample is on.
+    pub layer_id: i32,
+    /// The x coordinate of the position the sample was taken at.
+    pub x: i32,
+    /// The y coordinate of the position the sample was taken at.
+    pub y: i32,
+    /// The measured pH value.
+    pub ph: Option<f32>,
+    /// The texture of the soil.
+    pub soil_texture: Option<SoilTexture>,
+    /// How well the soil holds water.
+    pub water_retention: Option<SoilWaterRetention>,
+    /// The percentage of organic matter in the soil.
+    pub organic_matter: Option<f32>,
+    /// The date the sample was taken.
+    pub sample_date: Option<NaiveDate>,
+}
+
+/// The `UpdateSoilSample` entity.
+#[derive(Debug, Clone, AsChangeset)]
+#[diesel(table_name = soil_samples, treat_none_as_null = true)]
+pub struct UpdateSoilSample {
+    /// The x coordinate of the position the sample was taken at.
+    pub x: i32,
+    /// The y coordinate of the position the sample was taken at.
+    pub y: i32,
+    /// The measured pH value.
+    pub ph: Option<f32>,
+    /// The texture of the soil.
+    pub soil_texture: Option<SoilTexture>,
+    /// How well the soil holds water.
+    pub water_retention: Option<SoilWaterRetention>,
+    /// The percentage of organic matter in the soil.
+    pub organic_matter: Option<f32>,
+    /// The date the sample was taken.
+    pub sample_date: Option<NaiveDate>,
+}
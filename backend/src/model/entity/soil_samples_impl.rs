@@ -0,0 +1,93 @@
+//! Contains the implementation of [`SoilSample`].
+
+use diesel::pg::Pg;
+use diesel::{debug_query, ExpressionMethods, QueryDsl, QueryResult, SelectableHelper};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use log::debug;
+use uuid::Uuid;
+
+use crate::model::dto::soil_samples::SoilSampleDto;
+use crate::model::entity::soil_samples::{SoilSample, UpdateSoilSample};
+use crate::schema::{layers, soil_samples};
+
+impl SoilSample {
+    /// Get all samples of a soil layer.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_layer(
+        layer_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<SoilSampleDto>> {
+        let query = soil_samples::table
+            .filter(soil_samples::layer_id.eq(layer_id))
+            .order(soil_samples::id);
+        debug!("{}", debug_query::<Pg, _>(&query));
+        Ok(query
+            .load::<Self>(conn)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// Create a new sample in the database.
+    ///
+    /// # Errors
+    /// * If the `layer_id` references a layer that doesn't exist.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn create(sample: Self, conn: &mut AsyncPgConnection) -> QueryResult<SoilSampleDto> {
+        let query = diesel::insert_into(soil_samples::table).values(&sample);
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.get_result::<Self>(conn).await.map(Into::into)
+    }
+
+    /// Replace the position and measurements of a sample on a map.
+    ///
+    /// # Errors
+    /// * If the sample does not exist on the map.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn update(
+        id: Uuid,
+        map_id: i32,
+        sample: UpdateSoilSample,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<SoilSampleDto> {
+        let query = diesel::update(
+            soil_samples::table.filter(soil_samples::id.eq(id)).filter(
+                soil_samples::layer_id.eq_any(
+                    layers::table
+                        .select(layers::id)
+                        .filter(layers::map_id.eq(map_id))
+                        .filter(layers::deletion_date.is_null()),
+                ),
+            ),
+        )
+        .set(&sample)
+        .returning(Self::as_returning());
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.get_result::<Self>(conn).await.map(Into::into)
+    }
+
+    /// Delete a sample on a map.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn delete_by_id(
+        id: Uuid,
+        map_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<usize> {
+        let query = diesel::delete(
+            soil_samples::table.filter(soil_samples::id.eq(id)).filter(
+                soil_samples::layer_id.eq_any(
+                    layers::table
+                        .select(layers::id)
+                        .filter(layers::map_id.eq(map_id)),
+                ),
+            ),
+        );
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.execute(conn).await
+    }
+}
@@ -0,0 +1,62 @@
+//! All entities associated with [`Todo`].
+
+use chrono::NaiveDate;
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable, Selectable};
+use uuid::Uuid;
+
+use crate::model::r#enum::todo_status::TodoStatus;
+use crate::schema::todos;
+
+/// The `Todo` entity.
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable, Insertable)]
+#[diesel(table_name = todos)]
+pub struct Todo {
+    /// The id of the todo.
+    pub id: Uuid,
+    /// The todo layer the todo is on.
+    pub layer_id: i32,
+    /// The x coordinate of the position the todo is pinned to.
+    pub x: Option<i32>,
+    /// The y coordinate of the position the todo is pinned to.
+    pub y: Option<i32>,
+    /// The planting the todo is pinned to.
+    pub planting_id: Option<Uuid>,
+    /// The title of the todo.
+    pub title: String,
+    /// The description of the todo.
+    pub description: Option<String>,
+    /// The date the todo should be done by.
+    pub due_date: Option<NaiveDate>,
+    /// The id of the collaborator who should do the todo.
+    pub assignee_id: Option<Uuid>,
+    /// The progress of the todo.
+    pub status: TodoStatus,
+    /// The number of days after which the todo is due again once it is done.
+    pub recurrence_days: Option<i32>,
+    /// The id of the user who created the todo.
+    pub created_by: Uuid,
+}
+
+/// The `UpdateTodo` entity.
+#[derive(Debug, Clone, AsChangeset)]
+#[diesel(table_name = todos, treat_none_as_null = true)]
+pub struct UpdateTodo {
+    /// The x coordinate of the position the todo is pinned to.
+    pub x: Option<i32>,
+    /// The y coordinate of the position the todo is pinned to.
+    pub y: Option<i32>,
+    /// The planting the todo is pinned to.
+    pub planting_id: Option<Uuid>,
+    /// The title of the todo.
+    pub title: String,
+    /// The description of the todo.
+    pub description: Option<String>,
+    /// The date the todo should be done by.
+    pub due_date: Option<NaiveDate>,
+    /// The id of the collaborator who should do the todo.
+    pub assignee_id: Option<Uuid>,
+    /// The progress of the todo.
+    pub status: TodoStatus,
+    /// The number of days after which the todo is due again once it is done.
+    pub recurrence_days: Option<i32>,
+}
@@ -0,0 +1,131 @@
+//! Contains the implementation of [`Todo`].
+
+use diesel::pg::Pg;
+use diesel::{
+    debug_query, ExpressionMethods, PgSortExpressionMethods, QueryDsl, QueryResult,
+    SelectableHelper,
+};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use log::debug;
+use uuid::Uuid;
+
+use crate::model::dto::todos::{AssignedTodoDto, TodoDto};
+use crate::model::entity::todos::{Todo, UpdateTodo};
+use crate::model::r#enum::todo_status::TodoStatus;
+use crate::schema::{layers, todos};
+
+impl Todo {
+    /// Get all todos of a todo layer, optionally only those with the given status.
+    /// Todos due first come first.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_layer(
+        layer_id: i32,
+        status: Option<TodoStatus>,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<TodoDto>> {
+        let mut query = todos::table
+            .filter(todos::layer_id.eq(layer_id))
+            .order((todos::due_date.asc().nulls_last(), todos::title, todos::id))
+            .into_boxed();
+        if let Some(status) = status {
+            query = query.filter(todos::status.eq(status));
+        }
+        debug!("{}", debug_query::<Pg, _>(&query));
+        Ok(query
+            .load::<Self>(conn)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// Get the open todos assigned to the user on all maps.
+    /// Todos due first come first.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_open_by_assignee(
+        user_id: Uuid,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<AssignedTodoDto>> {
+        let query = todos::table
+            .inner_join(layers::table)
+            .select((Self::as_select(), layers::map_id))
+            .filter(todos::assignee_id.eq(user_id))
+            .filter(todos::status.eq(TodoStatus::Open))
+            .filter(layers::deletion_date.is_null())
+            .order((todos::due_date.asc().nulls_last(), todos::title, todos::id));
+        debug!("{}", debug_query::<Pg, _>(&query));
+        Ok(query
+            .load::<(Self, i32)>(conn)
+            .await?
+            .into_iter()
+            .map(|(todo, map_id)| AssignedTodoDto {
+                map_id,
+                todo: todo.into(),
+            })
+            .collect())
+    }
+
+    /// Create a new todo in the database.
+    ///
+    /// # Errors
+    /// * If the `layer_id` references a layer that doesn't exist.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn create(todo: Self, conn: &mut AsyncPgConnection) -> QueryResult<TodoDto> {
+        let query = diesel::insert_into(todos::table).values(&todo);
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.get_result::<Self>(conn).await.map(Into::into)
+    }
+
+    /// Replace the content of a todo on a map.
+    ///
+    /// # Errors
+    /// * If the todo does not exist on the map.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn update(
+        id: Uuid,
+        map_id: i32,
+        todo: UpdateTodo,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<TodoDto> {
+        let query = diesel::update(
+            todos::table.filter(todos::id.eq(id)).filter(
+                todos::layer_id.eq_any(
+                    layers::table
+                        .select(layers::id)
+                        .filter(layers::map_id.eq(map_id))
+                        .filter(layers::deletion_date.is_null()),
+                ),
+            ),
+        )
+        .set(&todo)
+        .returning(Self::as_returning());
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.get_result::<Self>(conn).await.map(Into::into)
+    }
+
+    /// Delete a todo on a map.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn delete_by_id(
+        id: Uuid,
+        map_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<usize> {
+        let query = diesel::delete(
+            todos::table.filter(todos::id.eq(id)).filter(
+                todos::layer_id.eq_any(
+                    layers::table
+                        .select(layers::id)
+                        .filter(layers::map_id.eq(map_id)),
+                ),
+            ),
+        );
+        debug!("{}", debug_query::<Pg, _>(&query));
+        query.execute(conn).await
+    }
+}
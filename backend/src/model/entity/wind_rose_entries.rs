@@ -0,0 +1,19 @@
+//! All entities associated with [`WindRoseEntry`].
+
+use diesel::{Insertable, Queryable, Selectable};
+
+use crate::schema::wind_rose_entries;
+
+/// The `WindRoseEntry` entity.
+#[derive(Debug, Clone, Copy, Queryable, Selectable, Insertable)]
+#[diesel(table_name = wind_rose_entries)]
+pub struct WindRoseEntry {
+    /// The winds layer the entry is on.
+    pub layer_id: i32,
+    /// The compass direction the wind comes from in degrees, clockwise from north.
+    pub direction: i32,
+    /// The share of the time in percent the wind comes from the direction.
+    pub frequency: f32,
+    /// The mean speed in m/s of the wind from the direction.
+    pub speed: f32,
+}
@@ -0,0 +1,64 @@
+//! Contains the implementation of [`WindRoseEntry`].
+
+use diesel::pg::Pg;
+use diesel::{debug_query, ExpressionMethods, QueryDsl, QueryResult};
+use diesel_async::{
+    scoped_futures::ScopedFutureExt, AsyncConnection, AsyncPgConnection, RunQueryDsl,
+};
+use log::debug;
+
+use crate::model::dto::winds::WindRoseEntryDto;
+use crate::model::entity::wind_rose_entries::WindRoseEntry;
+use crate::schema::wind_rose_entries;
+
+impl WindRoseEntry {
+    /// Get the wind rose of a winds layer, ordered clockwise from north.
+    ///
+    /// # Errors
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn find_by_layer(
+        layer_id: i32,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<WindRoseEntryDto>> {
+        let query = wind_rose_entries::table
+            .filter(wind_rose_entries::layer_id.eq(layer_id))
+            .order(wind_rose_entries::direction);
+        debug!("{}", debug_query::<Pg, _>(&query));
+        Ok(query
+            .load::<Self>(conn)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// Replace the wind rose of a winds layer.
+    ///
+    /// # Errors
+    /// * If the `layer_id` references a layer that doesn't exist.
+    /// * If two entries have the same direction.
+    /// * Unknown, diesel doesn't say why it might error.
+    pub async fn replace(
+        layer_id: i32,
+        entries: Vec<Self>,
+        conn: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<WindRoseEntryDto>> {
+        conn.transaction(|conn| {
+            async move {
+                let delete_query = diesel::delete(
+                    wind_rose_entries::table.filter(wind_rose_entries::layer_id.eq(layer_id)),
+                );
+                debug!("{}", debug_query::<Pg, _>(&delete_query));
+                delete_query.execute(conn).await?;
+
+                let insert_query = diesel::insert_into(wind_rose_entries::table).values(&entries);
+                debug!("{}", debug_query::<Pg, _>(&insert_query));
+                insert_query.execute(conn).await?;
+
+                Self::find_by_layer(layer_id, conn).await
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+}
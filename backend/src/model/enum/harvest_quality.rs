@@ -0,0 +1,29 @@
+//! [`HarvestQuality`] enum.
+
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+/// How good a harvest was.
+#[typeshare]
+#[derive(Serialize, Deserialize, DbEnum, Debug, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[ExistingTypePath = "crate::schema::sql_types::HarvestQuality"]
+pub enum HarvestQuality {
+    /// Better than expected.
+    #[serde(rename = "excellent")]
+    #[db_rename = "excellent"]
+    Excellent,
+    /// As expected.
+    #[serde(rename = "good")]
+    #[db_rename = "good"]
+    Good,
+    /// Usable with some defects.
+    #[serde(rename = "fair")]
+    #[db_rename = "fair"]
+    Fair,
+    /// Mostly unusable, e.g. because of pests or diseases.
+    #[serde(rename = "poor")]
+    #[db_rename = "poor"]
+    Poor,
+}
@@ -0,0 +1,35 @@
+//! [`HarvestUnit`] enum.
+
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+/// The unit the quantity of a harvest is measured in.
+#[typeshare]
+#[derive(
+    Serialize, Deserialize, DbEnum, Debug, ToSchema, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[ExistingTypePath = "crate::schema::sql_types::HarvestUnit"]
+pub enum HarvestUnit {
+    /// Single fruits or vegetables, e.g. pumpkins.
+    #[serde(rename = "pieces")]
+    #[db_rename = "pieces"]
+    Pieces,
+    /// Bunches, e.g. of herbs or radishes.
+    #[serde(rename = "bunches")]
+    #[db_rename = "bunches"]
+    Bunches,
+    /// Weight in grams.
+    #[serde(rename = "grams")]
+    #[db_rename = "grams"]
+    Grams,
+    /// Weight in kilograms.
+    #[serde(rename = "kilograms")]
+    #[db_rename = "kilograms"]
+    Kilograms,
+    /// Volume in liters, e.g. of berries.
+    #[serde(rename = "liters")]
+    #[db_rename = "liters"]
+    Liters,
+}
@@ -4,16 +4,19 @@ pub mod deciduous_or_evergreen;
 pub mod experience;
 pub mod external_source;
 pub mod fertility;
-//pub mod flower_type;
+pub mod flower_type;
 pub mod growth_rate;
+pub mod harvest_quality;
+pub mod harvest_unit;
 pub mod herbaceous_or_woody;
 pub mod layer_type;
 pub mod life_cycle;
 pub mod light_requirement;
 pub mod membership;
-//pub mod nutrition_demand;
+pub mod nutrition_demand;
 pub mod plant_height;
 pub mod plant_spread;
+pub mod planting_status;
 pub mod privacy_option;
 pub mod propagation_method;
 pub mod quality;
@@ -21,8 +24,10 @@ pub mod quantity;
 pub mod relation_type;
 pub mod salutation;
 pub mod shade;
+pub mod shape_type;
 pub mod soil_ph;
 pub mod soil_texture;
-//pub mod soil_water_retention;
+pub mod soil_water_retention;
+pub mod todo_status;
 pub mod track;
 pub mod water_requirement;
@@ -7,7 +7,7 @@ use utoipa::ToSchema;
 
 #[allow(clippy::missing_docs_in_private_items)] // TODO: See #97.
 #[typeshare]
-#[derive(Serialize, Deserialize, DbEnum, Debug, ToSchema, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, DbEnum, Debug, ToSchema, Clone, Copy, Eq, PartialEq)]
 #[ExistingTypePath = "crate::schema::sql_types::PlantSpread"]
 pub enum PlantSpread {
     #[serde(rename = "narrow")]
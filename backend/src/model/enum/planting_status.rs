@@ -0,0 +1,34 @@
+//! [`PlantingStatus`] enum.
+
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+/// Where a planting is in its lifecycle.
+#[typeshare]
+#[derive(Serialize, Deserialize, DbEnum, Debug, ToSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[ExistingTypePath = "crate::schema::sql_types::PlantingStatus"]
+pub enum PlantingStatus {
+    /// The planting is only planned.
+    #[default]
+    #[serde(rename = "planned")]
+    #[db_rename = "planned"]
+    Planned,
+    /// The seeds were sown.
+    #[serde(rename = "sown")]
+    #[db_rename = "sown"]
+    Sown,
+    /// The plant has established itself.
+    #[serde(rename = "established")]
+    #[db_rename = "established"]
+    Established,
+    /// The seeds did not germinate or the plant did not take root.
+    #[serde(rename = "failed")]
+    #[db_rename = "failed"]
+    Failed,
+    /// The plant died after it was established.
+    #[serde(rename = "dead")]
+    #[db_rename = "dead"]
+    Dead,
+}
@@ -0,0 +1,37 @@
+//! [`ShapeType`] enum.
+
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+/// The kind of a vector shape on a drawing layer.
+#[typeshare]
+#[derive(Serialize, Deserialize, DbEnum, Debug, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[ExistingTypePath = "crate::schema::sql_types::ShapeType"]
+pub enum ShapeType {
+    /// A straight line between two points.
+    #[serde(rename = "line")]
+    #[db_rename = "line"]
+    Line,
+    /// An open line through several points.
+    #[serde(rename = "polyline")]
+    #[db_rename = "polyline"]
+    Polyline,
+    /// A closed area through several points.
+    #[serde(rename = "polygon")]
+    #[db_rename = "polygon"]
+    Polygon,
+    /// A rectangle that can be rotated around its first corner.
+    #[serde(rename = "rectangle")]
+    #[db_rename = "rectangle"]
+    Rectangle,
+    /// An ellipse that can be rotated around its center.
+    #[serde(rename = "ellipse")]
+    #[db_rename = "ellipse"]
+    Ellipse,
+    /// A text label.
+    #[serde(rename = "text")]
+    #[db_rename = "text"]
+    Text,
+}
@@ -0,0 +1,25 @@
+//! [`TodoStatus`] enum.
+
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+/// The progress of a todo on a todo layer.
+#[typeshare]
+#[derive(Serialize, Deserialize, DbEnum, Debug, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[ExistingTypePath = "crate::schema::sql_types::TodoStatus"]
+pub enum TodoStatus {
+    /// The todo still needs to be done.
+    #[serde(rename = "open")]
+    #[db_rename = "open"]
+    Open,
+    /// The todo was done.
+    #[serde(rename = "done")]
+    #[db_rename = "done"]
+    Done,
+    /// The todo is no longer relevant, but kept for reference.
+    #[serde(rename = "archived")]
+    #[db_rename = "archived"]
+    Archived,
+}
@@ -8,7 +8,7 @@ use utoipa::ToSchema;
 /// *Used* in hydrology layer.
 /// *Fetched from* PracticalPlants and Permapeople (merged with `water` of PracticalPlants).
 #[typeshare]
-#[derive(Serialize, Deserialize, DbEnum, Debug, ToSchema)]
+#[derive(Serialize, Deserialize, DbEnum, Debug, ToSchema, Clone, Copy, PartialEq, Eq)]
 #[ExistingTypePath = "crate::schema::sql_types::WaterRequirement"]
 pub enum WaterRequirement {
     /// well drained, little water input
@@ -0,0 +1,147 @@
+//! Service layer for the calendar of maps.
+
+use actix_http::StatusCode;
+use actix_web::web::Data;
+use chrono::{Datelike, NaiveDate};
+use diesel_async::AsyncPgConnection;
+
+use crate::config::data::AppDataInner;
+use crate::error::ServiceError;
+use crate::model::dto::{CalendarDto, CalendarEntryDto, CalendarPlantingDto};
+use crate::model::entity::plantings::Planting;
+use crate::model::entity::Map;
+
+use super::util::{half_month_bucket_start, HalfMonthBucket};
+
+/// The number of half months in a year.
+const HALF_MONTHS: i32 = 24;
+
+/// Get the sowing and harvest calendar of a map for the given `year`.
+///
+/// # Errors
+/// * If the map does not exist.
+/// * If the year is out of range.
+/// * If the connection to the database could not be established.
+pub async fn find(
+    map_id: i32,
+    year: i32,
+    app_data: &Data<AppDataInner>,
+) -> Result<CalendarDto, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    Map::find_by_id(map_id, &mut conn).await?;
+    calendar(map_id, year, &mut conn).await
+}
+
+/// Get the sowing and harvest calendar of a map for the given `year` as iCalendar file.
+///
+/// # Errors
+/// * If the map does not exist.
+/// * If the year is out of range.
+/// * If the connection to the database could not be established.
+pub async fn find_ics(
+    map_id: i32,
+    year: i32,
+    app_data: &Data<AppDataInner>,
+) -> Result<String, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    let map = Map::find_by_id(map_id, &mut conn).await?;
+    let calendar = calendar(map_id, year, &mut conn).await?;
+    Ok(calendar.to_ics(&map.name))
+}
+
+/// Aggregate the plantings of the map into one entry per half month.
+///
+/// A planting is listed for sowing and harvesting only in the half months it exists on the map.
+async fn calendar(
+    map_id: i32,
+    year: i32,
+    conn: &mut AsyncPgConnection,
+) -> Result<CalendarDto, ServiceError> {
+    let invalid_year =
+        || ServiceError::new(StatusCode::BAD_REQUEST, format!("Invalid year {year}"));
+
+    let mut entries = (0..HALF_MONTHS)
+        .map(|half_month| {
+            let start = half_month_bucket_start(year, half_month)?;
+            let end = match half_month_bucket_start(year, half_month + 1) {
+                Some(next_start) => next_start.pred_opt()?,
+                None => NaiveDate::from_ymd_opt(year, 12, 31)?,
+            };
+            Some(CalendarEntryDto {
+                half_month,
+                start,
+                end,
+                sow: Vec::new(),
+                harvest: Vec::new(),
+                add: Vec::new(),
+                remove: Vec::new(),
+            })
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(invalid_year)?;
+    let (Some(first), Some(last)) = (entries.first(), entries.last()) else {
+        return Err(invalid_year());
+    };
+
+    let plantings = Planting::find_by_map_with_plants(map_id, first.start, last.end, conn).await?;
+    for (planting, plant) in plantings {
+        let sowing_outdoors = bucket_list(plant.sowing_outdoors.as_deref());
+        let harvest_time = bucket_list(plant.harvest_time.as_deref());
+        let dto = CalendarPlantingDto::from((planting, plant));
+
+        for entry in &mut entries {
+            let exists = dto.add_date.iter().all(|date| *date <= entry.end)
+                && dto.remove_date.iter().all(|date| *date >= entry.start);
+            if !exists {
+                continue;
+            }
+            if sowing_outdoors.contains(&entry.half_month) {
+                entry.sow.push(dto.clone());
+            }
+            if harvest_time.contains(&entry.half_month) {
+                entry.harvest.push(dto.clone());
+            }
+        }
+
+        if let Some(entry) = dto
+            .add_date
+            .and_then(|date| entry_of(&mut entries, year, date))
+        {
+            entry.add.push(dto.clone());
+        }
+        if let Some(entry) = dto
+            .remove_date
+            .and_then(|date| entry_of(&mut entries, year, date))
+        {
+            entry.remove.push(dto);
+        }
+    }
+
+    Ok(CalendarDto {
+        map_id,
+        year,
+        entries,
+    })
+}
+
+/// Convert the half month buckets stored for a plant.
+fn bucket_list(buckets: Option<&[Option<i16>]>) -> Vec<i32> {
+    buckets
+        .unwrap_or_default()
+        .iter()
+        .flatten()
+        .map(|bucket| i32::from(*bucket))
+        .collect()
+}
+
+/// Find the entry of the half month containing `date`, if it lies in `year`.
+fn entry_of(
+    entries: &mut [CalendarEntryDto],
+    year: i32,
+    date: NaiveDate,
+) -> Option<&mut CalendarEntryDto> {
+    if date.year() != year {
+        return None;
+    }
+    entries.get_mut(usize::try_from(date.half_month_bucket()).ok()?)
+}
@@ -0,0 +1,191 @@
+//! Service layer for drawings.
+
+use actix_http::StatusCode;
+use actix_web::web::Data;
+use uuid::Uuid;
+
+use crate::config::data::AppDataInner;
+use crate::error::ServiceError;
+use crate::model::dto::drawings::{
+    DrawingDto, NewDrawingDto, ShapeDto, ShapePointDto, ShapeStyleDto, UpdateDrawingDto,
+};
+use crate::model::entity::drawings::Drawing;
+use crate::model::r#enum::layer_type::LayerType;
+
+use super::layer::check_layer;
+
+/// The layer types that can contain drawings.
+const DRAWING_LAYER_TYPES: [LayerType; 4] = [
+    LayerType::Drawing,
+    LayerType::Paths,
+    LayerType::Label,
+    LayerType::Zones,
+];
+
+/// Get all drawings of a layer on the map.
+///
+/// # Errors
+/// * If the layer cannot contain drawings.
+/// * If the connection to the database could not be established.
+pub async fn find(
+    map_id: i32,
+    layer_id: i32,
+    app_data: &Data<AppDataInner>,
+) -> Result<Vec<DrawingDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, layer_id, &DRAWING_LAYER_TYPES, &mut conn).await?;
+    let result = Drawing::find_by_layer(map_id, layer_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Create a new drawing on a layer of the map.
+///
+/// # Errors
+/// * If the shape or style is invalid.
+/// * If the layer cannot contain drawings.
+/// * If the connection to the database could not be established.
+pub async fn create(
+    map_id: i32,
+    new_drawing: NewDrawingDto,
+    app_data: &Data<AppDataInner>,
+) -> Result<DrawingDto, ServiceError> {
+    check_shape(&new_drawing.shape)?;
+    check_style(&new_drawing.style)?;
+    let mut conn = app_data.pool.get().await?;
+    check_layer(
+        map_id,
+        new_drawing.layer_id,
+        &DRAWING_LAYER_TYPES,
+        &mut conn,
+    )
+    .await?;
+    let drawing = DrawingDto {
+        id: new_drawing.id.unwrap_or_else(Uuid::new_v4),
+        layer_id: new_drawing.layer_id,
+        shape: new_drawing.shape,
+        style: new_drawing.style,
+    };
+    let result = Drawing::create(drawing.into(), &mut conn).await?;
+    Ok(result)
+}
+
+/// Replace the shape or style of a drawing on the map.
+///
+/// # Errors
+/// * If the shape or style is invalid.
+/// * If the drawing does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn update(
+    map_id: i32,
+    drawing_id: Uuid,
+    update_drawing: UpdateDrawingDto,
+    app_data: &Data<AppDataInner>,
+) -> Result<DrawingDto, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    let mut drawing = Drawing::find_by_id_on_map(drawing_id, map_id, &mut conn).await?;
+    if let Some(shape) = update_drawing.shape {
+        check_shape(&shape)?;
+        drawing.shape = shape;
+    }
+    if let Some(style) = update_drawing.style {
+        check_style(&style)?;
+        drawing.style = style;
+    }
+    let result = Drawing::update(drawing.into(), &mut conn).await?;
+    Ok(result)
+}
+
+/// Delete a drawing on the map.
+///
+/// # Errors
+/// * If the drawing does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn delete_by_id(
+    map_id: i32,
+    drawing_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<(), ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    if Drawing::delete_by_id(drawing_id, map_id, &mut conn).await? == 0 {
+        return Err(ServiceError::new(
+            StatusCode::NOT_FOUND,
+            StatusCode::NOT_FOUND.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Check that the shape has enough points and positive sizes.
+fn check_shape(shape: &ShapeDto) -> Result<(), ServiceError> {
+    let error = match shape {
+        ShapeDto::Line(line) if !all_finite(&[line.start, line.end]) => {
+            Some("The points of a line must be finite")
+        }
+        ShapeDto::Polyline(polyline)
+            if polyline.points.len() < 2 || !all_finite(&polyline.points) =>
+        {
+            Some("A polyline needs at least two finite points")
+        }
+        ShapeDto::Polygon(polygon) if polygon.points.len() < 3 || !all_finite(&polygon.points) => {
+            Some("A polygon needs at least three finite points")
+        }
+        ShapeDto::Rectangle(rectangle)
+            if !(rectangle.x.is_finite()
+                && rectangle.y.is_finite()
+                && rectangle.width > 0.0
+                && rectangle.height > 0.0
+                && rectangle.width.is_finite()
+                && rectangle.height.is_finite()) =>
+        {
+            Some("A rectangle needs a finite position and a positive size")
+        }
+        ShapeDto::Ellipse(ellipse)
+            if !(all_finite(&[ellipse.center])
+                && ellipse.radius_x > 0.0
+                && ellipse.radius_y > 0.0
+                && ellipse.radius_x.is_finite()
+                && ellipse.radius_y.is_finite()) =>
+        {
+            Some("An ellipse needs a finite center and positive radii")
+        }
+        ShapeDto::Text(text) if text.text.trim().is_empty() => Some("A text must not be empty"),
+        ShapeDto::Text(text)
+            if !(all_finite(&[text.position])
+                && text.font_size > 0.0
+                && text.font_size.is_finite()) =>
+        {
+            Some("A text needs a finite position and a positive font size")
+        }
+        _ => None,
+    };
+    error.map_or(Ok(()), |message| {
+        Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            message.to_owned(),
+        ))
+    })
+}
+
+/// Check that the style has a color and a valid stroke width.
+fn check_style(style: &ShapeStyleDto) -> Result<(), ServiceError> {
+    if style.stroke_color.trim().is_empty() {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The stroke color must not be empty".to_owned(),
+        ));
+    }
+    if !(style.stroke_width >= 0.0 && style.stroke_width.is_finite()) {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The stroke width must not be negative".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Whether all coordinates of the points are finite.
+fn all_finite(points: &[ShapePointDto]) -> bool {
+    points
+        .iter()
+        .all(|point| point.x.is_finite() && point.y.is_finite())
+}
@@ -0,0 +1,417 @@
+//! Service layer for guilds.
+
+use actix_http::StatusCode;
+use actix_web::web::Data;
+use uuid::Uuid;
+
+use crate::config::data::AppDataInner;
+use crate::error::ServiceError;
+use crate::model::dto::guilds::{
+    ApplyGuildTemplateDto, DuplicateGuildDto, GuildDto, GuildTemplateDto, GuildTemplateMemberDto,
+    NewGuildDto, NewGuildTemplateDto, UpdateGuildDto,
+};
+use crate::model::dto::plantings::PlantingDto;
+use crate::model::entity::guilds::{Guild, GuildTemplate};
+use crate::model::entity::plantings::{Planting, UpdatePlanting};
+use crate::model::r#enum::layer_type::LayerType;
+use crate::model::r#enum::planting_status::PlantingStatus;
+
+use super::layer::check_layer;
+
+/// Get all guilds of a plant layer on the map.
+///
+/// # Errors
+/// * If the layer is not a plant layer of the map.
+/// * If the connection to the database could not be established.
+pub async fn find(
+    map_id: i32,
+    layer_id: i32,
+    app_data: &Data<AppDataInner>,
+) -> Result<Vec<GuildDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, layer_id, &[LayerType::Plants], &mut conn).await?;
+    let result = Guild::find_by_layer(layer_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Group existing plantings into a new guild.
+///
+/// # Errors
+/// * If the name or the plantings are missing.
+/// * If the layer is not a plant layer of the map.
+/// * If any of the plantings is not on the layer or already belongs to a guild.
+/// * If the connection to the database could not be established.
+pub async fn create(
+    map_id: i32,
+    new_guild: NewGuildDto,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<GuildDto, ServiceError> {
+    check_name(&new_guild.name)?;
+    if new_guild.planting_ids.is_empty() {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "A guild needs at least one planting".to_owned(),
+        ));
+    }
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, new_guild.layer_id, &[LayerType::Plants], &mut conn).await?;
+    let result = Guild::create(new_guild, user_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Move or rotate all plantings of a guild in one transaction.
+///
+/// # Errors
+/// * If the rotation is not a finite number.
+/// * If the guild does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn update(
+    map_id: i32,
+    id: Uuid,
+    dto: UpdateGuildDto,
+    app_data: &Data<AppDataInner>,
+) -> Result<GuildDto, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    let guild = Guild::find_by_id_on_map(id, map_id, &mut conn).await?;
+    let updates = match dto {
+        UpdateGuildDto::Move(move_dto) => guild
+            .plantings
+            .iter()
+            .map(|planting| {
+                let update = UpdatePlanting {
+                    x: Some(planting.x.saturating_add(move_dto.dx)),
+                    y: Some(planting.y.saturating_add(move_dto.dy)),
+                    ..Default::default()
+                };
+                (planting.id, update)
+            })
+            .collect(),
+        UpdateGuildDto::Rotate(rotate_dto) => {
+            if !rotate_dto.rotation.is_finite() {
+                return Err(ServiceError::new(
+                    StatusCode::BAD_REQUEST,
+                    "The rotation must be a finite number".to_owned(),
+                ));
+            }
+            rotate(&guild.plantings, rotate_dto.rotation)
+        }
+    };
+    let result = Guild::update_plantings(id, updates, &mut conn).await?;
+    Ok(result)
+}
+
+/// Duplicate a guild with copies of all of its plantings.
+///
+/// The copies are new plantings, their notes, photos, seeds and status are not copied.
+///
+/// # Errors
+/// * If the guild does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn duplicate(
+    map_id: i32,
+    id: Uuid,
+    dto: DuplicateGuildDto,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<GuildDto, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    let original = Guild::find_by_id_on_map(id, map_id, &mut conn).await?;
+    let guild = Guild {
+        id: dto.id.unwrap_or_else(Uuid::new_v4),
+        layer_id: original.layer_id,
+        name: original.name,
+        created_by: user_id,
+    };
+    let copies = original
+        .plantings
+        .iter()
+        .map(|planting| Planting {
+            x: planting.x.saturating_add(dto.dx),
+            y: planting.y.saturating_add(dto.dy),
+            ..copy_planting(planting, guild.id)
+        })
+        .collect();
+    let result = Guild::create_with_plantings(guild, copies, &mut conn).await?;
+    Ok(result)
+}
+
+/// Ungroup a guild, its plantings stay on the map.
+///
+/// # Errors
+/// * If the guild does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn delete_by_id(
+    map_id: i32,
+    id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<(), ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    if Guild::delete_by_id(id, map_id, &mut conn).await? == 0 {
+        return Err(ServiceError::new(
+            StatusCode::NOT_FOUND,
+            format!("Guild {id} not found"),
+        ));
+    }
+    Ok(())
+}
+
+/// Save a guild as a template of the user.
+///
+/// The positions of the plants are stored relative to the center of the guild.
+///
+/// # Errors
+/// * If the name is missing or the guild has no plantings.
+/// * If the guild does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn create_template(
+    map_id: i32,
+    id: Uuid,
+    new_template: NewGuildTemplateDto,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<GuildTemplateDto, ServiceError> {
+    check_name(&new_template.name)?;
+    let mut conn = app_data.pool.get().await?;
+    let guild = Guild::find_by_id_on_map(id, map_id, &mut conn).await?;
+    let Some((center_x, center_y)) = center(&guild.plantings) else {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The guild has no plantings".to_owned(),
+        ));
+    };
+    let members = guild
+        .plantings
+        .iter()
+        .map(|planting| GuildTemplateMemberDto {
+            plant_id: planting.plant_id,
+            x: round(f64::from(planting.x) - center_x),
+            y: round(f64::from(planting.y) - center_y),
+            width: planting.width,
+            height: planting.height,
+            rotation: planting.rotation,
+            scale_x: planting.scale_x,
+            scale_y: planting.scale_y,
+        })
+        .collect();
+    let result = GuildTemplate::create(new_template.name, members, user_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Get all guild templates of the user.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+pub async fn find_templates(
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<Vec<GuildTemplateDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    let result = GuildTemplate::find(user_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Delete a guild template of the user.
+///
+/// # Errors
+/// * If the template does not exist or belongs to another user.
+/// * If the connection to the database could not be established.
+pub async fn delete_template(
+    id: i32,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<(), ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    if GuildTemplate::delete_by_id(id, user_id, &mut conn).await? == 0 {
+        return Err(ServiceError::new(
+            StatusCode::NOT_FOUND,
+            format!("Guild template {id} not found"),
+        ));
+    }
+    Ok(())
+}
+
+/// Place a new guild from a template of the user on a plant layer of the map.
+///
+/// # Errors
+/// * If the template does not exist or belongs to another user.
+/// * If the layer is not a plant layer of the map.
+/// * If the connection to the database could not be established.
+pub async fn apply_template(
+    map_id: i32,
+    dto: ApplyGuildTemplateDto,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<GuildDto, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, dto.layer_id, &[LayerType::Plants], &mut conn).await?;
+    let template = GuildTemplate::find_by_id(dto.template_id, user_id, &mut conn).await?;
+    let guild = Guild {
+        id: dto.id.unwrap_or_else(Uuid::new_v4),
+        layer_id: dto.layer_id,
+        name: template.name,
+        created_by: user_id,
+    };
+    let plantings = template
+        .members
+        .iter()
+        .map(|member| Planting {
+            id: Uuid::new_v4(),
+            layer_id: dto.layer_id,
+            plant_id: member.plant_id,
+            x: dto.x.saturating_add(member.x),
+            y: dto.y.saturating_add(member.y),
+            width: member.width,
+            height: member.height,
+            rotation: member.rotation,
+            scale_x: member.scale_x,
+            scale_y: member.scale_y,
+            add_date: dto.add_date,
+            remove_date: None,
+            notes: None,
+            status: PlantingStatus::default(),
+            seed_id: None,
+            photos: Vec::new(),
+            guild_id: Some(guild.id),
+            origin_id: None,
+        })
+        .collect();
+    let result = Guild::create_with_plantings(guild, plantings, &mut conn).await?;
+    Ok(result)
+}
+
+/// Check that the name of a guild or template is not blank.
+fn check_name(name: &str) -> Result<(), ServiceError> {
+    if name.trim().is_empty() {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The name must not be empty".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// A new planting in the guild `guild_id` with the same plant, size and dates as `planting`.
+fn copy_planting(planting: &PlantingDto, guild_id: Uuid) -> Planting {
+    Planting {
+        id: Uuid::new_v4(),
+        layer_id: planting.layer_id,
+        plant_id: planting.plant_id,
+        x: planting.x,
+        y: planting.y,
+        width: planting.width,
+        height: planting.height,
+        rotation: planting.rotation,
+        scale_x: planting.scale_x,
+        scale_y: planting.scale_y,
+        add_date: planting.add_date,
+        remove_date: planting.remove_date,
+        notes: None,
+        status: PlantingStatus::default(),
+        seed_id: None,
+        photos: Vec::new(),
+        guild_id: Some(guild_id),
+        origin_id: None,
+    }
+}
+
+/// The center of the bounding box of the positions of the plantings.
+fn center(plantings: &[PlantingDto]) -> Option<(f64, f64)> {
+    let x_min = plantings.iter().map(|planting| planting.x).min()?;
+    let x_max = plantings.iter().map(|planting| planting.x).max()?;
+    let y_min = plantings.iter().map(|planting| planting.y).min()?;
+    let y_max = plantings.iter().map(|planting| planting.y).max()?;
+    Some((
+        f64::midpoint(f64::from(x_min), f64::from(x_max)),
+        f64::midpoint(f64::from(y_min), f64::from(y_max)),
+    ))
+}
+
+/// Rotate the plantings clockwise by `angle` degrees around their center.
+fn rotate(plantings: &[PlantingDto], angle: f32) -> Vec<(Uuid, UpdatePlanting)> {
+    let Some((center_x, center_y)) = center(plantings) else {
+        return Vec::new();
+    };
+    let (sin, cos) = f64::from(angle).to_radians().sin_cos();
+    plantings
+        .iter()
+        .map(|planting| {
+            let dx = f64::from(planting.x) - center_x;
+            let dy = f64::from(planting.y) - center_y;
+            let update = UpdatePlanting {
+                x: Some(round(dy.mul_add(-sin, dx.mul_add(cos, center_x)))),
+                y: Some(round(dy.mul_add(cos, dx.mul_add(sin, center_y)))),
+                rotation: Some((planting.rotation + angle).rem_euclid(360.0)),
+                ..Default::default()
+            };
+            (planting.id, update)
+        })
+        .collect()
+}
+
+/// Round a coordinate to the nearest integer.
+// The coordinates are clamped to the range of `i32`, so the cast can't truncate.
+#[allow(clippy::cast_possible_truncation)]
+fn round(value: f64) -> i32 {
+    value
+        .round()
+        .clamp(f64::from(i32::MIN), f64::from(i32::MAX)) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planting(x: i32, y: i32, rotation: f32) -> PlantingDto {
+        PlantingDto {
+            id: Uuid::new_v4(),
+            layer_id: -1,
+            plant_id: -1,
+            x,
+            y,
+            width: 0,
+            height: 0,
+            rotation,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            add_date: None,
+            remove_date: None,
+            notes: None,
+            status: PlantingStatus::default(),
+            seed_id: None,
+            photos: Vec::new(),
+            guild_id: None,
+            origin_id: None,
+        }
+    }
+
+    #[test]
+    fn test_center_is_center_of_bounding_box() {
+        assert_eq!(center(&[]), None);
+        assert_eq!(
+            center(&[
+                planting(0, 0, 0.0),
+                planting(100, 50, 0.0),
+                planting(10, 10, 0.0)
+            ]),
+            Some((50.0, 25.0))
+        );
+    }
+
+    #[test]
+    fn test_rotate_turns_positions_around_center() {
+        let plantings = [planting(0, 0, 0.0), planting(100, 0, 300.0)];
+        let updates = rotate(&plantings, 90.0);
+        let positions = updates
+            .iter()
+            .map(|(_, update)| (update.x, update.y, update.rotation))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![
+                (Some(50), Some(-50), Some(90.0)),
+                (Some(50), Some(50), Some(30.0)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Service layer for harvests.
+
+use std::collections::{BTreeMap, HashSet};
+
+use actix_http::StatusCode;
+use actix_web::web::Data;
+use chrono::{Datelike, NaiveDate};
+use uuid::Uuid;
+
+use crate::config::data::AppDataInner;
+use crate::error::ServiceError;
+use crate::model::dto::{
+    HarvestDto, HarvestGrouping, HarvestSummaryDto, HarvestSummaryParameters, NewHarvestDto,
+};
+use crate::model::entity::harvests_impl::FindHarvestsParameters;
+use crate::model::entity::plantings::Planting;
+use crate::model::entity::{Harvest, Map};
+use crate::model::r#enum::harvest_unit::HarvestUnit;
+
+/// Get all harvests of a planting on the map.
+///
+/// # Errors
+/// * If the planting does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn find(
+    map_id: i32,
+    planting_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<Vec<HarvestDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    Planting::find_by_id_on_map(planting_id, map_id, &mut conn).await?;
+    let result = Harvest::find_by_planting(planting_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Log a new harvest of a planting on the map.
+///
+/// # Errors
+/// * If the quantity is not positive.
+/// * If the planting does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn create(
+    map_id: i32,
+    planting_id: Uuid,
+    new_harvest: NewHarvestDto,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<HarvestDto, ServiceError> {
+    if !new_harvest.quantity.is_finite() || new_harvest.quantity <= 0.0 {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The quantity of a harvest must be positive".to_owned(),
+        ));
+    }
+    let mut conn = app_data.pool.get().await?;
+    Planting::find_by_id_on_map(planting_id, map_id, &mut conn).await?;
+    let result = Harvest::create(planting_id, new_harvest, user_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Delete a harvest of a planting on the map.
+///
+/// # Errors
+/// * If the harvest does not exist for the planting on the map.
+/// * If the connection to the database could not be established.
+pub async fn delete_by_id(
+    map_id: i32,
+    planting_id: Uuid,
+    id: i32,
+    app_data: &Data<AppDataInner>,
+) -> Result<(), ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    Planting::find_by_id_on_map(planting_id, map_id, &mut conn).await?;
+    if Harvest::delete_by_id(planting_id, id, &mut conn).await? == 0 {
+        return Err(ServiceError::new(
+            StatusCode::NOT_FOUND,
+            format!("Harvest {id} not found"),
+        ));
+    }
+    Ok(())
+}
+
+/// Total of the harvests of one group in one unit.
+#[derive(Default)]
+struct Total {
+    /// The unique name of the plant, if grouped by plant.
+    unique_name: Option<String>,
+    /// The total harvested quantity.
+    quantity: f64,
+    /// The number of harvests.
+    harvests: i32,
+    /// The harvested plantings.
+    plantings: HashSet<Uuid>,
+}
+
+/// Aggregate the harvests of the map.
+///
+/// # Errors
+/// * If the map does not exist.
+/// * If the season is out of range.
+/// * If the connection to the database could not be established.
+pub async fn summarize(
+    map_id: i32,
+    parameters: HarvestSummaryParameters,
+    app_data: &Data<AppDataInner>,
+) -> Result<Vec<HarvestSummaryDto>, ServiceError> {
+    let (from, to) = match parameters.season {
+        Some(season) => {
+            let (Some(from), Some(to)) = (
+                NaiveDate::from_ymd_opt(season, 1, 1),
+                NaiveDate::from_ymd_opt(season, 12, 31),
+            ) else {
+                return Err(ServiceError::new(
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid season {season}"),
+                ));
+            };
+            (Some(from), Some(to))
+        }
+        None => (None, None),
+    };
+
+    let mut conn = app_data.pool.get().await?;
+    Map::find_by_id(map_id, &mut conn).await?;
+    let harvests = Harvest::find_by_map(
+        map_id,
+        FindHarvestsParameters {
+            layer_id: parameters.layer_id,
+            include_alternatives: parameters.group_by == HarvestGrouping::Layer,
+            plant_id: parameters.plant_id,
+            from,
+            to,
+        },
+        &mut conn,
+    )
+    .await?;
+
+    let mut totals = BTreeMap::<(Option<i32>, Option<i32>, Option<i32>, HarvestUnit), Total>::new();
+    for (harvest, layer_id, plant_id, unique_name) in harvests {
+        let key = match parameters.group_by {
+            HarvestGrouping::Plant => (Some(plant_id), None, None, harvest.unit),
+            HarvestGrouping::Layer => (None, Some(layer_id), None, harvest.unit),
+            HarvestGrouping::Map => (None, None, None, harvest.unit),
+            HarvestGrouping::Season => {
+                (None, None, Some(harvest.harvest_date.year()), harvest.unit)
+            }
+        };
+        let total = totals.entry(key).or_default();
+        if parameters.group_by == HarvestGrouping::Plant {
+            total.unique_name = Some(unique_name);
+        }
+        total.quantity += f64::from(harvest.quantity);
+        total.harvests = total.harvests.saturating_add(1);
+        total.plantings.insert(harvest.planting_id);
+    }
+
+    let mut summaries = totals
+        .into_iter()
+        .map(
+            |((plant_id, layer_id, season, unit), total)| HarvestSummaryDto {
+                plant_id,
+                unique_name: total.unique_name,
+                layer_id,
+                season,
+                unit,
+                total_quantity: total.quantity,
+                harvests: total.harvests,
+                plantings: i32::try_from(total.plantings.len()).unwrap_or(i32::MAX),
+            },
+        )
+        .collect::<Vec<_>>();
+    summaries.sort_by(|a, b| {
+        a.unique_name
+            .cmp(&b.unique_name)
+            .then_with(|| a.plant_id.cmp(&b.plant_id))
+            .then_with(|| a.layer_id.cmp(&b.layer_id))
+            .then_with(|| a.season.cmp(&b.season))
+            .then_with(|| a.unit.cmp(&b.unit))
+    });
+    Ok(summaries)
+}
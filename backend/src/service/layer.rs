@@ -1,9 +1,22 @@
 //! Service layer for layers.
 
+use std::collections::HashMap;
+
+use actix_http::StatusCode;
 use actix_web::web::Data;
+use chrono::{Days, Utc};
+use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection, AsyncPgConnection};
+use uuid::Uuid;
 
 use crate::config::data::AppDataInner;
-use crate::model::dto::LayerSearchParameters;
+use crate::db::cronjobs::DELETED_LAYERS_RETENTION_DAYS;
+use crate::model::dto::plantings::PlantingDto;
+use crate::model::dto::{
+    LayerDiffDto, LayerOrderDto, LayerSearchParameters, MovedPlantingDto, NewAlternativeLayerDto,
+    UpdateLayerDto,
+};
+use crate::model::entity::plantings::Planting;
+use crate::model::r#enum::layer_type::LayerType;
 use crate::{
     error::ServiceError,
     model::{
@@ -48,12 +61,345 @@ pub async fn create(
     Ok(result)
 }
 
-/// Delete the layer in the database.
+/// Rename a layer or change its position, visibility or opacity.
 ///
 /// # Errors
-/// If the connection to the database could not be established.
-pub async fn delete_by_id(id: i32, app_data: &Data<AppDataInner>) -> Result<(), ServiceError> {
+/// * If nothing is changed.
+/// * If the name is empty or the opacity is not between 0 and 1.
+/// * If the layer does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn update(
+    map_id: i32,
+    id: i32,
+    update_layer: UpdateLayerDto,
+    app_data: &Data<AppDataInner>,
+) -> Result<LayerDto, ServiceError> {
+    if update_layer.name.is_none()
+        && update_layer.order_index.is_none()
+        && update_layer.is_visible.is_none()
+        && update_layer.opacity.is_none()
+    {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "At least one attribute of the layer has to be changed".to_owned(),
+        ));
+    }
+    if update_layer
+        .name
+        .as_ref()
+        .is_some_and(|name| name.trim().is_empty())
+    {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The name of a layer must not be empty".to_owned(),
+        ));
+    }
+    if update_layer
+        .opacity
+        .is_some_and(|opacity| !(0.0..=1.0).contains(&opacity))
+    {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The opacity of a layer must be between 0 and 1".to_owned(),
+        ));
+    }
+
+    let mut conn = app_data.pool.get().await?;
+    let result = Layer::update(id, map_id, update_layer.into(), &mut conn).await?;
+    Ok(result)
+}
+
+/// Rewrite the order of all layers of a map at once.
+///
+/// # Errors
+/// * If the ids are not exactly the layers of the map.
+/// * If the connection to the database could not be established.
+pub async fn reorder(
+    map_id: i32,
+    layer_order: LayerOrderDto,
+    app_data: &Data<AppDataInner>,
+) -> Result<Vec<LayerDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    Layer::reorder(map_id, layer_order.layer_ids, &mut conn)
+        .await?
+        .ok_or_else(|| {
+            ServiceError::new(
+                StatusCode::BAD_REQUEST,
+                "The order has to contain every layer of the map exactly once".to_owned(),
+            )
+        })
+}
+
+/// Fork a plant layer into an alternative with copies of all its plantings.
+///
+/// # Errors
+/// * If the layer is not a plant layer of the map.
+/// * If the connection to the database could not be established.
+pub async fn fork(
+    map_id: i32,
+    id: i32,
+    new_alternative: NewAlternativeLayerDto,
+    app_data: &Data<AppDataInner>,
+) -> Result<LayerDto, ServiceError> {
+    if new_alternative.name.trim().is_empty() {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The name of a layer must not be empty".to_owned(),
+        ));
+    }
+
+    let mut conn = app_data.pool.get().await?;
+    let source = check_layer(map_id, id, &[LayerType::Plants], &mut conn).await?;
+    let result = Layer::fork(source, new_alternative.name, &mut conn).await?;
+    Ok(result)
+}
+
+/// Compare the plantings of two plant layers of a map.
+///
+/// # Errors
+/// * If one of the layers is not a plant layer of the map.
+/// * If the connection to the database could not be established.
+pub async fn compare(
+    map_id: i32,
+    id: i32,
+    other_id: i32,
+    app_data: &Data<AppDataInner>,
+) -> Result<LayerDiffDto, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, id, &[LayerType::Plants], &mut conn).await?;
+    check_layer(map_id, other_id, &[LayerType::Plants], &mut conn).await?;
+    let plantings = Planting::find_by_layer(id, &mut conn).await?;
+    let other_plantings = Planting::find_by_layer(other_id, &mut conn).await?;
+    Ok(diff(plantings, other_plantings))
+}
+
+/// Promote a layer to be the main layer of its type, the previous main layer becomes an alternative.
+///
+/// # Errors
+/// * If the layer does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn promote(
+    map_id: i32,
+    id: i32,
+    app_data: &Data<AppDataInner>,
+) -> Result<Vec<LayerDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    let result = Layer::promote(id, map_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Delete a layer of a map, it can be restored until it is cleaned up after 30 days.
+///
+/// # Errors
+/// * If the layer does not exist on the map.
+/// * If the layer is the last main layer of its type on the map.
+/// * If the connection to the database could not be established.
+pub async fn delete_by_id(
+    map_id: i32,
+    id: i32,
+    app_data: &Data<AppDataInner>,
+) -> Result<LayerDto, ServiceError> {
     let mut conn = app_data.pool.get().await?;
-    let _ = Layer::delete_by_id(id, &mut conn).await?;
-    Ok(())
+    conn.transaction(|conn| {
+        async move {
+            // Concurrent deletions must not remove all main layers of a type.
+            let layers = Layer::find_by_map_for_update(map_id, conn).await?;
+            let Some(layer) = layers.iter().find(|layer| layer.id == id) else {
+                return Err(ServiceError::new(
+                    StatusCode::NOT_FOUND,
+                    format!("Layer {id} not found on map {map_id}"),
+                ));
+            };
+            let main_layers = layers
+                .iter()
+                .filter(|other| other.type_ == layer.type_ && !other.is_alternative)
+                .count();
+            if !layer.is_alternative && main_layers <= 1 {
+                return Err(ServiceError::new(
+                    StatusCode::CONFLICT,
+                    format!("The last {} layer of a map cannot be deleted", layer.type_),
+                ));
+            }
+
+            let result = Layer::delete_by_id(id, map_id, Utc::now().date_naive(), conn).await?;
+            Ok(result)
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+/// Restore a deleted layer of a map with its plantings and images.
+///
+/// # Errors
+/// * If the layer is not a deleted layer of the map.
+/// * If the layer was deleted more than [`DELETED_LAYERS_RETENTION_DAYS`] days ago.
+/// * If the connection to the database could not be established.
+pub async fn restore(
+    map_id: i32,
+    id: i32,
+    app_data: &Data<AppDataInner>,
+) -> Result<LayerDto, ServiceError> {
+    let Some(deleted_since) = Utc::now()
+        .date_naive()
+        .checked_sub_days(Days::new(DELETED_LAYERS_RETENTION_DAYS))
+    else {
+        return Err(ServiceError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to calculate the oldest date of restorable layers".to_owned(),
+        ));
+    };
+
+    let mut conn = app_data.pool.get().await?;
+    match Layer::restore(id, map_id, deleted_since, &mut conn).await {
+        Ok(layer) => Ok(layer),
+        Err(diesel::result::Error::NotFound) => Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Layer {id} is not a layer of map {map_id} deleted in the last {DELETED_LAYERS_RETENTION_DAYS} days"
+            ),
+        )),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Check that the layer is on the map and of one of the types.
+///
+/// # Errors
+/// * If the layer does not exist or is deleted.
+/// * If the layer is on another map or of another type.
+/// * If the connection to the database could not be established.
+pub async fn check_layer(
+    map_id: i32,
+    layer_id: i32,
+    types: &[LayerType],
+    conn: &mut AsyncPgConnection,
+) -> Result<LayerDto, ServiceError> {
+    let layer = Layer::find_by_id(layer_id, conn).await?;
+    if layer.map_id != map_id || !types.contains(&layer.type_) {
+        let types = types
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" or ");
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            format!("Layer {layer_id} is not a {types} layer of map {map_id}"),
+        ));
+    }
+    Ok(layer)
+}
+
+/// Check that the planting is on a layer of the map.
+///
+/// # Errors
+/// * If the planting does not exist or is not on the map.
+/// * If the connection to the database could not be established.
+pub async fn check_planting(
+    map_id: i32,
+    planting_id: Uuid,
+    conn: &mut AsyncPgConnection,
+) -> Result<(), ServiceError> {
+    match Planting::find_by_id_on_map(planting_id, map_id, conn).await {
+        Ok(_) => Ok(()),
+        Err(diesel::result::Error::NotFound) => Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            format!("Planting {planting_id} is not on map {map_id}"),
+        )),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Match the plantings of two layers by their original planting and collect the differences.
+fn diff(plantings: Vec<PlantingDto>, other_plantings: Vec<PlantingDto>) -> LayerDiffDto {
+    let origin = |planting: &PlantingDto| planting.origin_id.unwrap_or(planting.id);
+    let mut remaining = plantings
+        .into_iter()
+        .map(|planting| (origin(&planting), planting))
+        .collect::<HashMap<Uuid, _>>();
+
+    let mut added = Vec::new();
+    let mut moved = Vec::new();
+    for other in other_plantings {
+        match remaining.remove(&origin(&other)) {
+            Some(planting) if planting.x != other.x || planting.y != other.y => {
+                moved.push(MovedPlantingDto {
+                    from: planting,
+                    to: other,
+                });
+            }
+            Some(_) => {}
+            None => added.push(other),
+        }
+    }
+
+    let mut removed = remaining.into_values().collect::<Vec<_>>();
+    removed.sort_unstable_by_key(|planting| planting.id);
+    LayerDiffDto {
+        added,
+        removed,
+        moved,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::model::{dto::plantings::PlantingDto, r#enum::planting_status::PlantingStatus};
+
+    use super::diff;
+
+    fn planting(id: u128, origin: Option<u128>, x: i32) -> PlantingDto {
+        PlantingDto {
+            id: Uuid::from_u128(id),
+            plant_id: -1,
+            layer_id: -1,
+            x,
+            y: 0,
+            width: 50,
+            height: 50,
+            rotation: 0.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            add_date: None,
+            remove_date: None,
+            notes: None,
+            status: PlantingStatus::default(),
+            seed_id: None,
+            photos: Vec::new(),
+            guild_id: None,
+            origin_id: origin.map(Uuid::from_u128),
+        }
+    }
+
+    #[test]
+    fn test_diff_matches_copies_by_origin() {
+        let main = vec![
+            planting(1, None, 0),
+            planting(2, None, 0),
+            planting(3, None, 0),
+        ];
+        let alternative = vec![
+            planting(11, Some(1), 0),
+            planting(12, Some(2), 100),
+            planting(14, None, 0),
+        ];
+
+        let result = diff(main, alternative);
+
+        let ids = |plantings: &[PlantingDto]| {
+            plantings
+                .iter()
+                .map(|planting| planting.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&result.added), vec![Uuid::from_u128(14)]);
+        assert_eq!(ids(&result.removed), vec![Uuid::from_u128(3)]);
+        let [moved] = result.moved.as_slice() else {
+            panic!("expected exactly one moved planting");
+        };
+        assert_eq!(moved.from.id, Uuid::from_u128(2));
+        assert_eq!(moved.to.x, 100);
+    }
 }
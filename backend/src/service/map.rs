@@ -2,13 +2,19 @@
 
 use actix_http::StatusCode;
 use actix_web::web::Data;
+use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection, AsyncPgConnection};
 use uuid::Uuid;
 
 use crate::config::data::AppDataInner;
-use crate::model::dto::{BaseLayerImageDto, MapSearchParameters, Page, UpdateMapDto};
+use crate::model::dto::map_collaborators::{MapCollaboratorDto, NewMapCollaboratorDto};
+use crate::model::dto::{
+    BaseLayerImageDto, LayerSearchParameters, MapSearchParameters, Page, UpdateMapDto,
+};
 use crate::model::dto::{NewLayerDto, PageParameters};
-use crate::model::entity::{BaseLayerImages, Layer};
+use crate::model::entity::map_collaborators::MapCollaborator;
+use crate::model::entity::{BaseLayerImages, Layer, UpdateLayer};
 use crate::model::r#enum::layer_type::LayerType;
+use crate::model::r#enum::privacy_option::PrivacyOption;
 use crate::{
     error::ServiceError,
     model::{
@@ -44,46 +50,132 @@ pub async fn find_by_id(id: i32, app_data: &Data<AppDataInner>) -> Result<MapDto
     Ok(result)
 }
 
-/// Create a new map in the database.
+/// A layer a new map is initialized with.
+struct InitialLayer {
+    /// The type of the layer.
+    type_: LayerType,
+    /// The name of the layer.
+    name: String,
+    /// A flag indicating if this layer is an user created alternative.
+    is_alternative: bool,
+    /// Visibility and opacity cloned from a template map.
+    appearance: Option<UpdateLayer>,
+}
+
+/// Create a new map in the database together with its layers.
+/// Either all of it is created or nothing.
 ///
 /// # Errors
-/// If the connection to the database could not be established.
+/// * If both layer types and a template map are given.
+/// * If the template map does not exist or is private to another user.
+/// * If the connection to the database could not be established.
 pub async fn create(
     new_map: NewMapDto,
     user_id: Uuid,
     app_data: &Data<AppDataInner>,
 ) -> Result<MapDto, ServiceError> {
     let mut conn = app_data.pool.get().await?;
-    let result = Map::create(new_map, user_id, &mut conn).await?;
-    for layer_type in &LAYER_TYPES {
-        let new_layer = NewLayerDto {
-            map_id: result.id,
-            type_: *layer_type,
-            name: format!("{layer_type} Layer"),
-            is_alternative: false,
-        };
-        let layer = Layer::create(new_layer, &mut conn).await?;
+    let initial_layers = initial_layers(&new_map, user_id, &mut conn).await?;
+
+    conn.transaction(|conn| {
+        async move {
+            let result = Map::create(new_map, user_id, conn).await?;
+            for initial_layer in initial_layers {
+                let new_layer = NewLayerDto {
+                    map_id: result.id,
+                    type_: initial_layer.type_,
+                    name: initial_layer.name,
+                    is_alternative: initial_layer.is_alternative,
+                };
+                let layer = Layer::create(new_layer, conn).await?;
+                if let Some(appearance) = initial_layer.appearance {
+                    Layer::update(layer.id, result.id, appearance, conn).await?;
+                }
 
-        // Immediately initialize a base layer image,
-        // because the frontend would always have to create one
-        // anyway.
-        if layer.type_ == LayerType::Base {
-            BaseLayerImages::create(
-                BaseLayerImageDto {
-                    id: Uuid::new_v4(),
-                    layer_id: layer.id,
-                    path: String::new(),
-                    rotation: 0.0,
-                    scale: 100.0,
-                    action_id: Uuid::nil(),
-                },
-                &mut conn,
-            )
-            .await?;
+                // Immediately initialize a base layer image,
+                // because the frontend would always have to create one
+                // anyway.
+                if layer.type_ == LayerType::Base {
+                    BaseLayerImages::create(
+                        BaseLayerImageDto {
+                            id: Uuid::new_v4(),
+                            layer_id: layer.id,
+                            path: String::new(),
+                            rotation: 0.0,
+                            scale: 100.0,
+                            action_id: Uuid::nil(),
+                        },
+                        conn,
+                    )
+                    .await?;
+                }
+            }
+            Ok(result)
         }
-    }
+        .scope_boxed()
+    })
+    .await
+}
 
-    Ok(result)
+/// Decide which layers a new map is initialized with.
+async fn initial_layers(
+    new_map: &NewMapDto,
+    user_id: Uuid,
+    conn: &mut AsyncPgConnection,
+) -> Result<Vec<InitialLayer>, ServiceError> {
+    match (&new_map.layer_types, new_map.template_map_id) {
+        (Some(_), Some(_)) => Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "A map can either be initialized with layer types or from a template map".to_owned(),
+        )),
+        (None, Some(template_map_id)) => {
+            let template = Map::find_by_id(template_map_id, conn).await?;
+            if template.owner_id != user_id && !matches!(template.privacy, PrivacyOption::Public) {
+                return Err(ServiceError::new(
+                    StatusCode::FORBIDDEN,
+                    "No permission to use the map as a template".to_owned(),
+                ));
+            }
+            let search_parameters = LayerSearchParameters {
+                map_id: Some(template_map_id),
+                type_: None,
+                is_alternative: None,
+                is_deleted: None,
+            };
+            Ok(Layer::find(search_parameters, conn)
+                .await?
+                .into_iter()
+                .map(|layer| InitialLayer {
+                    type_: layer.type_,
+                    name: layer.name,
+                    is_alternative: layer.is_alternative,
+                    appearance: Some(UpdateLayer {
+                        name: None,
+                        order_index: None,
+                        is_visible: Some(layer.is_visible),
+                        opacity: Some(layer.opacity),
+                    }),
+                })
+                .collect())
+        }
+        (layer_types, None) => {
+            let mut types = Vec::new();
+            for layer_type in layer_types.as_deref().unwrap_or(&LAYER_TYPES) {
+                if !types.contains(layer_type) {
+                    types.push(*layer_type);
+                }
+            }
+            Ok(types
+                .into_iter()
+                .map(|layer_type| InitialLayer {
+                    type_: layer_type,
+                    name: format!("{layer_type} Layer"),
+                    is_alternative: false,
+                    appearance: None,
+                })
+                .collect())
+        }
+    }
 }
 
 /// Update a map in the database.
@@ -109,3 +201,102 @@ pub async fn update(
     let result = Map::update(map_update, id, &mut conn).await?;
     Ok(result)
 }
+
+/// Get the collaborators of a map, not including its owner.
+/// Only the owner and the collaborators of the map can see them.
+///
+/// # Errors
+/// * If the map does not exist.
+/// * If the requesting user is neither the owner nor a collaborator of the map.
+/// * If the connection to the database could not be established.
+pub async fn find_collaborators(
+    map_id: i32,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<Vec<MapCollaboratorDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    if !is_collaborator(map_id, user_id, &mut conn).await? {
+        return Err(ServiceError::new(
+            StatusCode::FORBIDDEN,
+            "Only the owner and collaborators of a map can see its collaborators".to_owned(),
+        ));
+    }
+    let result = MapCollaborator::find_by_map(map_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Add a collaborator to a map owned by the requesting user.
+///
+/// # Errors
+/// * If the map does not exist.
+/// * If the requesting user is not the owner of the map.
+/// * If the connection to the database could not be established.
+pub async fn add_collaborator(
+    map_id: i32,
+    new_collaborator: NewMapCollaboratorDto,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<MapCollaboratorDto, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    let map = Map::find_by_id(map_id, &mut conn).await?;
+    if map.owner_id != user_id {
+        return Err(ServiceError::new(
+            StatusCode::FORBIDDEN,
+            "Only the owner of a map can add collaborators".to_owned(),
+        ));
+    }
+    let result = MapCollaborator::create(
+        MapCollaborator {
+            map_id,
+            user_id: new_collaborator.user_id,
+        },
+        &mut conn,
+    )
+    .await?;
+    Ok(result)
+}
+
+/// Remove a collaborator from a map.
+/// The owner of the map can remove anyone, collaborators can leave the map.
+///
+/// # Errors
+/// * If the map does not exist.
+/// * If the requesting user is neither the owner of the map nor the collaborator to remove.
+/// * If the user is not a collaborator of the map.
+/// * If the connection to the database could not be established.
+pub async fn remove_collaborator(
+    map_id: i32,
+    collaborator_id: Uuid,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<(), ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    let map = Map::find_by_id(map_id, &mut conn).await?;
+    if map.owner_id != user_id && collaborator_id != user_id {
+        return Err(ServiceError::new(
+            StatusCode::FORBIDDEN,
+            "Only the owner of a map can remove other collaborators".to_owned(),
+        ));
+    }
+    if MapCollaborator::delete(map_id, collaborator_id, &mut conn).await? == 0 {
+        return Err(ServiceError::new(
+            StatusCode::NOT_FOUND,
+            StatusCode::NOT_FOUND.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Whether the user is the owner or a collaborator of the map.
+///
+/// # Errors
+/// * If the map does not exist.
+/// * If the connection to the database could not be established.
+pub async fn is_collaborator(
+    map_id: i32,
+    user_id: Uuid,
+    conn: &mut AsyncPgConnection,
+) -> Result<bool, ServiceError> {
+    let map = Map::find_by_id(map_id, conn).await?;
+    Ok(map.owner_id == user_id || MapCollaborator::exists(map_id, user_id, conn).await?)
+}
@@ -2,12 +2,24 @@
 
 pub mod base_layer_images;
 pub mod blossoms;
+pub mod calendar;
+pub mod drawings;
 pub mod guided_tours;
+pub mod guilds;
+pub mod harvests;
 pub mod layer;
 pub mod map;
+pub mod photo_layer;
 pub mod plant_layer;
+pub mod planting_suggestions;
 pub mod plantings;
 pub mod plants;
+pub mod rotation;
 pub mod seed;
+pub mod shade_layer;
+pub mod soil_layer;
+pub mod spacing;
+pub mod todo_layer;
 pub mod users;
 pub mod util;
+pub mod winds_layer;
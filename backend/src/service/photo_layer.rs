@@ -0,0 +1,355 @@
+//! Service layer for the photo layer.
+
+use std::io::Cursor;
+
+use actix_http::StatusCode;
+use actix_web::web::Data;
+use chrono::{NaiveDate, NaiveDateTime};
+use exif::{In, Reader, Tag, Value};
+use uuid::Uuid;
+
+use crate::config::data::AppDataInner;
+use crate::error::ServiceError;
+use crate::model::dto::photos::{
+    NewPhotoDto, PhotoDto, PhotoMetadataDto, PhotoSearchParameters, UpdatePhotoDto,
+};
+use crate::model::dto::Coordinates;
+use crate::model::entity::photos::Photo;
+use crate::model::entity::Map;
+use crate::model::r#enum::layer_type::LayerType;
+use crate::service::layer::{check_layer, check_planting};
+
+/// The mean radius of the earth in cm.
+const EARTH_RADIUS: f64 = 637_100_000.0;
+
+/// Get the photos of a photo layer on the map, optionally only those taken in a date range.
+///
+/// # Errors
+/// * If the layer is not a photo layer of the map.
+/// * If the connection to the database could not be established.
+pub async fn find(
+    map_id: i32,
+    search_parameters: PhotoSearchParameters,
+    app_data: &Data<AppDataInner>,
+) -> Result<Vec<PhotoDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    check_layer(
+        map_id,
+        search_parameters.layer_id,
+        &[LayerType::Photo],
+        &mut conn,
+    )
+    .await?;
+    let captured_from = search_parameters.from.and_then(start_of_day);
+    let captured_before = search_parameters
+        .to
+        .as_ref()
+        .and_then(NaiveDate::succ_opt)
+        .and_then(start_of_day);
+    let result = Photo::find_by_layer(
+        search_parameters.layer_id,
+        captured_from,
+        captured_before,
+        &mut conn,
+    )
+    .await?;
+    Ok(result)
+}
+
+/// Pin a new photo to a photo layer of the map.
+///
+/// # Errors
+/// * If the path is blank.
+/// * If the planting is not on the map.
+/// * If the layer is not a photo layer of the map.
+/// * If the connection to the database could not be established.
+pub async fn create(
+    map_id: i32,
+    new_photo: NewPhotoDto,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<PhotoDto, ServiceError> {
+    if new_photo.path.trim().is_empty() {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The path of the photo must not be blank".to_owned(),
+        ));
+    }
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, new_photo.layer_id, &[LayerType::Photo], &mut conn).await?;
+    if let Some(planting_id) = new_photo.planting_id {
+        check_planting(map_id, planting_id, &mut conn).await?;
+    }
+    let result = Photo::create((new_photo, user_id).into(), &mut conn).await?;
+    Ok(result)
+}
+
+/// Replace the position and details of a photo on the map.
+///
+/// # Errors
+/// * If the planting is not on the map.
+/// * If the photo does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn update(
+    map_id: i32,
+    photo_id: Uuid,
+    update_photo: UpdatePhotoDto,
+    app_data: &Data<AppDataInner>,
+) -> Result<PhotoDto, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    if let Some(planting_id) = update_photo.planting_id {
+        check_planting(map_id, planting_id, &mut conn).await?;
+    }
+    let result = Photo::update(photo_id, map_id, update_photo.into(), &mut conn).await?;
+    Ok(result)
+}
+
+/// Delete a photo on the map.
+///
+/// # Errors
+/// * If the photo does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn delete_by_id(
+    map_id: i32,
+    photo_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<(), ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    if Photo::delete_by_id(photo_id, map_id, &mut conn).await? == 0 {
+        return Err(ServiceError::new(
+            StatusCode::NOT_FOUND,
+            StatusCode::NOT_FOUND.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Read the capture time and GPS position of an image from its EXIF data.
+/// The GPS position is converted to coordinates of the map, taking the location of the map as origin.
+///
+/// # Errors
+/// * If the image is neither a JPEG nor a TIFF image.
+/// * If the map does not exist.
+/// * If the connection to the database could not be established.
+pub async fn read_metadata(
+    map_id: i32,
+    image: &[u8],
+    app_data: &Data<AppDataInner>,
+) -> Result<PhotoMetadataDto, ServiceError> {
+    let exif = read_exif(image)?;
+    let mut conn = app_data.pool.get().await?;
+    let map = Map::find_by_id(map_id, &mut conn).await?;
+    let position = exif
+        .position
+        .zip(map.location)
+        .and_then(|((latitude, longitude), origin)| {
+            to_map_coordinates(&origin, latitude, longitude)
+        });
+    Ok(PhotoMetadataDto {
+        captured_at: exif.captured_at,
+        x: position.map(|(x, _)| x),
+        y: position.map(|(_, y)| y),
+    })
+}
+
+/// The first moment of the day.
+fn start_of_day(date: NaiveDate) -> Option<NaiveDateTime> {
+    date.and_hms_opt(0, 0, 0)
+}
+
+/// Convert a GPS position to coordinates of the map in cm.
+/// The x axis of the map points east and the y axis points south.
+///
+/// Uses an equirectangular projection around the origin, which is precise enough at the scale of a garden.
+/// Returns `None` if the position is too far away to be represented on the map.
+fn to_map_coordinates(origin: &Coordinates, latitude: f64, longitude: f64) -> Option<(i32, i32)> {
+    let x = (longitude - origin.longitude).to_radians()
+        * origin.latitude.to_radians().cos()
+        * EARTH_RADIUS;
+    let y = -(latitude - origin.latitude).to_radians() * EARTH_RADIUS;
+    Some((to_coordinate(x)?, to_coordinate(y)?))
+}
+
+/// Round a distance in cm to a coordinate of the map.
+#[allow(clippy::cast_possible_truncation)] // ok, because the range of i32 is checked before
+fn to_coordinate(value: f64) -> Option<i32> {
+    let rounded = value.round();
+    (rounded >= f64::from(i32::MIN) && rounded <= f64::from(i32::MAX)).then_some(rounded as i32)
+}
+
+/// The metadata found in the EXIF data of an image.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Exif {
+    /// The local time the photo was taken at.
+    captured_at: Option<NaiveDateTime>,
+    /// The GPS position the photo was taken at as latitude and longitude.
+    position: Option<(f64, f64)>,
+}
+
+/// Read the EXIF data of a JPEG or TIFF image.
+/// Missing or malformed EXIF data results in empty metadata, as many images don't have any.
+///
+/// # Errors
+/// * If the image is neither a JPEG nor a TIFF image.
+fn read_exif(image: &[u8]) -> Result<Exif, ServiceError> {
+    let is_jpeg = image.starts_with(&[0xFF, 0xD8]);
+    let is_tiff = image.starts_with(b"II*\0") || image.starts_with(b"MM\0*");
+    if !is_jpeg && !is_tiff {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "Only JPEG and TIFF images are supported".to_owned(),
+        ));
+    }
+    let Ok(exif) = Reader::new().read_from_container(&mut Cursor::new(image)) else {
+        return Ok(Exif::default());
+    };
+
+    let captured_at =
+        date_time(&exif, Tag::DateTimeOriginal).or_else(|| date_time(&exif, Tag::DateTime));
+    let position = degrees(&exif, Tag::GPSLatitude, Tag::GPSLatitudeRef, "S").zip(degrees(
+        &exif,
+        Tag::GPSLongitude,
+        Tag::GPSLongitudeRef,
+        "W",
+    ));
+    Ok(Exif {
+        captured_at,
+        position,
+    })
+}
+
+/// Read the string with the tag.
+fn ascii(exif: &exif::Exif, tag: Tag) -> Option<&str> {
+    match &exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Ascii(strings) => std::str::from_utf8(strings.first()?).ok(),
+        _ => None,
+    }
+}
+
+/// Read the time with the tag, which is formatted like `2023:07:01 12:30:00`.
+fn date_time(exif: &exif::Exif, tag: Tag) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(ascii(exif, tag)?, "%Y:%m:%d %H:%M:%S").ok()
+}
+
+/// Read the GPS coordinate with the tag, which is stored as degrees, minutes and seconds.
+/// The coordinate is negative if the hemisphere with the reference tag is `negative_ref`.
+fn degrees(exif: &exif::Exif, tag: Tag, ref_tag: Tag, negative_ref: &str) -> Option<f64> {
+    let Value::Rational(values) = &exif.get_field(tag, In::PRIMARY)?.value else {
+        return None;
+    };
+    let [degrees, minutes, seconds] = values.as_slice() else {
+        return None;
+    };
+    if [degrees, minutes, seconds]
+        .iter()
+        .any(|value| value.denom == 0)
+    {
+        return None;
+    }
+    let degrees = degrees.to_f64() + minutes.to_f64() / 60.0 + seconds.to_f64() / 3600.0;
+    Some(if ascii(exif, ref_tag)? == negative_ref {
+        -degrees
+    } else {
+        degrees
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The data type of strings.
+    const ASCII: u16 = 2;
+    /// The data type of 32 bit unsigned integers.
+    const LONG: u16 = 4;
+    /// The data type of fractions of two 32 bit unsigned integers.
+    const RATIONAL: u16 = 5;
+
+    /// Build a little endian JPEG containing only EXIF data with the capture time and GPS position.
+    fn jpeg_with_exif(captured_at: &str, latitude: [u32; 3], longitude: [u32; 3]) -> Vec<u8> {
+        let entry = |tag: Tag, format: u16, count: u32, value: u32| {
+            [
+                &tag.number().to_le_bytes()[..],
+                &format.to_le_bytes(),
+                &count.to_le_bytes(),
+                &value.to_le_bytes(),
+            ]
+            .concat()
+        };
+        // IFD0 at 8 with 2 entries (30 bytes), the EXIF IFD at 38 with 1 entry (18 bytes),
+        // the GPS IFD at 56 with 4 entries (54 bytes), followed by the values at 110.
+        let date_offset = 110;
+        let latitude_offset = date_offset + 20;
+        let longitude_offset = latitude_offset + 24;
+        let mut tiff = b"II*\0".to_vec();
+        tiff.extend(8_u32.to_le_bytes());
+        tiff.extend(2_u16.to_le_bytes());
+        tiff.extend(entry(Tag::ExifIFDPointer, LONG, 1, 38));
+        tiff.extend(entry(Tag::GPSInfoIFDPointer, LONG, 1, 56));
+        tiff.extend(0_u32.to_le_bytes());
+        tiff.extend(1_u16.to_le_bytes());
+        tiff.extend(entry(Tag::DateTimeOriginal, ASCII, 20, date_offset));
+        tiff.extend(0_u32.to_le_bytes());
+        tiff.extend(4_u16.to_le_bytes());
+        tiff.extend(entry(Tag::GPSLatitudeRef, ASCII, 2, u32::from(b'N')));
+        tiff.extend(entry(Tag::GPSLatitude, RATIONAL, 3, latitude_offset));
+        tiff.extend(entry(Tag::GPSLongitudeRef, ASCII, 2, u32::from(b'W')));
+        tiff.extend(entry(Tag::GPSLongitude, RATIONAL, 3, longitude_offset));
+        tiff.extend(0_u32.to_le_bytes());
+        tiff.extend(captured_at.as_bytes());
+        tiff.push(0);
+        for value in latitude.into_iter().chain(longitude) {
+            tiff.extend(value.to_le_bytes());
+            tiff.extend(1_u32.to_le_bytes());
+        }
+
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xE1];
+        jpeg.extend(
+            u16::try_from(tiff.len() + 8)
+                .unwrap_or_default()
+                .to_be_bytes(),
+        );
+        jpeg.extend(b"Exif\0\0");
+        jpeg.extend(tiff);
+        jpeg.extend([0xFF, 0xDA]);
+        jpeg
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[test]
+    fn test_read_exif_of_jpeg() {
+        let jpeg = jpeg_with_exif("2023:07:01 12:30:00", [48, 12, 36], [16, 22, 12]);
+
+        let exif = read_exif(&jpeg).unwrap();
+
+        assert_eq!(
+            exif.captured_at,
+            NaiveDate::from_ymd_opt(2023, 7, 1).and_then(|date| date.and_hms_opt(12, 30, 0))
+        );
+        let (latitude, longitude) = exif.position.unwrap();
+        assert!((latitude - 48.21).abs() < 1e-9);
+        assert!((longitude + 16.37).abs() < 1e-9);
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[test]
+    fn test_read_exif_without_metadata() {
+        let exif = read_exif(&[0xFF, 0xD8, 0xFF, 0xDA]).unwrap();
+        assert_eq!(exif, Exif::default());
+
+        assert!(read_exif(b"\x89PNG").is_err());
+    }
+
+    #[test]
+    fn test_to_map_coordinates() {
+        let origin = Coordinates {
+            latitude: 48.0,
+            longitude: 16.0,
+        };
+
+        // one thousandth of a degree to the north and to the east
+        let position = to_map_coordinates(&origin, 48.001, 16.001);
+
+        assert_eq!(position, Some((7_440, -11_119)));
+    }
+}
@@ -3,7 +3,8 @@
 use std::io::Cursor;
 
 use actix_http::StatusCode;
-use actix_web::web::Data;
+use actix_web::web::{self, Data};
+use chrono::Utc;
 use image::{ImageBuffer, Rgb};
 
 use crate::{
@@ -13,16 +14,28 @@ use crate::{
         dto::{HeatMapQueryParams, RelationSearchParameters, RelationsDto},
         entity::plant_layer,
     },
+    service::{
+        shade_layer,
+        soil_layer::{self, SoilRequirements},
+        winds_layer,
+    },
 };
 
 /// Generates a heatmap signaling ideal locations for planting the plant.
 /// The return values are raw bytes of an PNG image.
 ///
+/// Locations where the main soil layer doesn't meet the plant's soil requirements score lower.
+/// So do locations getting less sunlight than the plant needs on the given date,
+/// if the map has a location to compute the path of the sun for.
+/// Plants not tolerating wind score lower where the wind rose of the main winds layer
+/// blows without being slowed down by windbreaks.
+///
 /// # Errors
 /// * If the connection to the database could not be established.
 /// * If no map with id `map_id` exists.
 /// * If no layer with id `layer_id` exists, if the layer is not a plant layer or if the layer is not part of the map.
 /// * If no plant with id `plant_id` exists.
+/// * If the soil suitability or the shade or wind raster could not be computed.
 /// * If the image could not be parsed to bytes.
 pub async fn heatmap(
     map_id: i32,
@@ -30,14 +43,68 @@ pub async fn heatmap(
     app_data: &Data<AppDataInner>,
 ) -> Result<Vec<u8>, ServiceError> {
     let mut conn = app_data.pool.get().await?;
-    let result = plant_layer::heatmap(
+    let bounding_box = plant_layer::bounding_box(map_id, &mut conn).await?;
+    let mut result = plant_layer::heatmap(
         map_id,
         query_params.layer_id,
         query_params.plant_id,
+        &bounding_box,
         &mut conn,
     )
     .await?;
 
+    let soil_samples = soil_layer::find_main_layer_samples(map_id, &mut conn).await?;
+    if !soil_samples.is_empty() {
+        let (soil_ph, soil_texture) =
+            plant_layer::find_soil_requirements(query_params.plant_id, &mut conn).await?;
+        let origin = (bounding_box.x_min, bounding_box.y_min);
+        let requirements = SoilRequirements {
+            soil_ph,
+            soil_texture,
+        };
+        // Interpolating the samples for every cell is too slow for the worker thread.
+        result = web::block(move || {
+            soil_layer::apply_soil_suitability(
+                &mut result,
+                origin,
+                plant_layer::GRANULARITY,
+                &soil_samples,
+                &requirements,
+            );
+            result
+        })
+        .await?;
+    }
+
+    let date = query_params.date.unwrap_or_else(|| Utc::now().date_naive());
+    if let Some(shade) =
+        shade_layer::main_layer_raster(map_id, date, &app_data.shade_rasters, &mut conn).await?
+    {
+        let light_requirements =
+            plant_layer::find_light_requirements(query_params.plant_id, &mut conn).await?;
+        shade_layer::apply_light_suitability(
+            &mut result,
+            (bounding_box.x_min, bounding_box.y_min),
+            plant_layer::GRANULARITY,
+            &shade,
+            &light_requirements,
+        );
+    }
+
+    if let Some(winds) =
+        winds_layer::main_layer_raster(map_id, date, &app_data.wind_rasters, &mut conn).await?
+    {
+        let tolerates_wind =
+            plant_layer::find_wind_tolerance(query_params.plant_id, &mut conn).await?;
+        winds_layer::apply_wind_suitability(
+            &mut result,
+            (bounding_box.x_min, bounding_box.y_min),
+            plant_layer::GRANULARITY,
+            &winds,
+            tolerates_wind,
+        );
+    }
+
     let buffer = matrix_to_image(&result)?;
 
     Ok(buffer)
@@ -0,0 +1,291 @@
+//! Service layer for planting suggestions.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use actix_web::web::Data;
+use uuid::Uuid;
+
+use crate::config::data::AppDataInner;
+use crate::error::ServiceError;
+use crate::model::dto::{
+    CompanionSuggestionDto, DiversityCriterion, DiversityGapDto, DiversitySuggestionDto, Page,
+    PageParameters, PlantSuggestionsSearchParameters, PlantsSummaryDto,
+};
+use crate::model::entity::plantings::Planting;
+use crate::model::entity::{plant_layer, PlantDiversity, Plants};
+use crate::model::r#enum::herbaceous_or_woody::HerbaceousOrWoody;
+use crate::model::r#enum::relation_type::RelationType;
+
+use super::util::HalfMonthBucket;
+
+/// All criteria considered for diversity suggestions.
+const DIVERSITY_CRITERIA: [DiversityCriterion; 4] = [
+    DiversityCriterion::Family,
+    DiversityCriterion::Function,
+    DiversityCriterion::EcosystemNiche,
+    DiversityCriterion::HerbaceousOrWoody,
+];
+
+/// Score of a value that no planting on the layer has.
+const MISSING_SCORE: i32 = 2;
+/// Score of a value that is less common on the layer than the average value of its criterion.
+const UNDER_REPRESENTED_SCORE: i32 = 1;
+
+/// Find seasonal plants that fill gaps in the diversity of the plantings on the map.
+///
+/// For every criterion the values of the plantings existing at `relative_to_date` are counted.
+/// A plant fills a gap if one of its values is missing or under-represented.
+/// Plants are ranked by the gaps they fill, counting the best gap of each criterion.
+///
+/// # Errors
+/// If the connection to the database could not be established.
+pub async fn find_diversity(
+    map_id: i32,
+    search_parameters: PlantSuggestionsSearchParameters,
+    page_parameters: PageParameters,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<Page<DiversitySuggestionDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+
+    let date = search_parameters.relative_to_date;
+    let planted = Planting::find_active_plants(map_id, search_parameters.layer_id, date, &mut conn)
+        .await?
+        .into_iter()
+        .map(|(_, plant)| plant)
+        .collect::<Vec<_>>();
+    let candidates =
+        PlantDiversity::find_seasonal(date.half_month_bucket(), user_id, &mut conn).await?;
+
+    let planted_ids = planted.iter().map(|plant| plant.id).collect::<HashSet<_>>();
+    let representation = Representation::of(&planted);
+
+    let mut suggestions = candidates
+        .into_iter()
+        .filter(|plant| !planted_ids.contains(&plant.id))
+        .filter_map(|plant| representation.suggest(plant))
+        .collect::<Vec<_>>();
+    suggestions.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.plant.unique_name.cmp(&b.plant.unique_name))
+    });
+
+    Ok(Page::from_results(suggestions, &page_parameters))
+}
+
+/// Find seasonal plants related to the plantings on the map.
+///
+/// Each companion planting existing at `relative_to_date` increases the score of a plant, each antagonist planting decreases it.
+/// Only plants with a positive score that are not planted yet are suggested, the best first.
+///
+/// # Errors
+/// If the connection to the database could not be established.
+pub async fn find_companions(
+    map_id: i32,
+    search_parameters: PlantSuggestionsSearchParameters,
+    page_parameters: PageParameters,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<Page<CompanionSuggestionDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+
+    let date = search_parameters.relative_to_date;
+    let mut plantings_by_plant = HashMap::<_, Vec<_>>::new();
+    for (planting_id, plant) in
+        Planting::find_active_plants(map_id, search_parameters.layer_id, date, &mut conn).await?
+    {
+        plantings_by_plant
+            .entry(plant.id)
+            .or_default()
+            .push(planting_id);
+    }
+    let planted_ids = plantings_by_plant.keys().copied().collect::<Vec<_>>();
+
+    let mut related = HashMap::<_, (Vec<_>, Vec<_>)>::new();
+    for (plant_id, related_id, relation) in
+        plant_layer::find_relations_of_plants(&planted_ids, &mut conn).await?
+    {
+        if plantings_by_plant.contains_key(&related_id) {
+            continue;
+        }
+        let (companions, antagonists) = related.entry(related_id).or_default();
+        let plantings = plantings_by_plant.get(&plant_id).into_iter().flatten();
+        match relation {
+            RelationType::Companion => companions.extend(plantings),
+            RelationType::Antagonist => antagonists.extend(plantings),
+            RelationType::Neutral => {}
+        }
+    }
+
+    let related_ids = related.keys().copied().collect::<Vec<_>>();
+    let plants =
+        Plants::find_seasonal_by_ids(&related_ids, date.half_month_bucket(), user_id, &mut conn)
+            .await?;
+
+    let mut suggestions = plants
+        .into_iter()
+        .filter_map(|plant| {
+            let (mut companion_plantings, mut antagonist_plantings) = related.remove(&plant.id)?;
+            companion_plantings.sort();
+            antagonist_plantings.sort();
+            let score = i32::try_from(companion_plantings.len())
+                .unwrap_or(i32::MAX)
+                .saturating_sub(i32::try_from(antagonist_plantings.len()).unwrap_or(i32::MAX));
+            (score > 0).then(|| CompanionSuggestionDto {
+                plant: PlantsSummaryDto::from(plant),
+                score,
+                companion_plantings,
+                antagonist_plantings,
+            })
+        })
+        .collect::<Vec<_>>();
+    suggestions.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.plant.unique_name.cmp(&b.plant.unique_name))
+    });
+
+    Ok(Page::from_results(suggestions, &page_parameters))
+}
+
+/// How often each value of each criterion occurs in the plantings.
+struct Representation(HashMap<DiversityCriterion, HashMap<String, usize>>);
+
+impl Representation {
+    /// Count the values of the planted plants.
+    fn of(planted: &[PlantDiversity]) -> Self {
+        let mut counts = HashMap::<_, HashMap<_, _>>::new();
+        for plant in planted {
+            for criterion in DIVERSITY_CRITERIA {
+                for value in values(plant, criterion) {
+                    let count = counts
+                        .entry(criterion)
+                        .or_default()
+                        .entry(value)
+                        .or_insert(0_usize);
+                    *count = count.saturating_add(1);
+                }
+            }
+        }
+        Self(counts)
+    }
+
+    /// Find the gap a value of a criterion fills.
+    fn gap(&self, criterion: DiversityCriterion, value: String) -> Option<DiversityGapDto> {
+        let counts = self.0.get(&criterion);
+        let count = counts
+            .and_then(|counts| counts.get(&value))
+            .copied()
+            .unwrap_or(0);
+        let total = counts.map_or(0, |counts| counts.values().sum::<usize>());
+        let distinct = counts.map_or(0, HashMap::len);
+
+        if count == 0 {
+            Some(DiversityGapDto {
+                criterion,
+                value,
+                missing: true,
+            })
+        } else if count.saturating_mul(distinct) < total {
+            Some(DiversityGapDto {
+                criterion,
+                value,
+                missing: false,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Suggest the plant if it fills any gaps.
+    fn suggest(&self, plant: PlantDiversity) -> Option<DiversitySuggestionDto> {
+        let mut gaps = Vec::new();
+        let mut score = 0;
+        for criterion in DIVERSITY_CRITERIA {
+            let criterion_gaps = values(&plant, criterion)
+                .into_iter()
+                .filter_map(|value| self.gap(criterion, value))
+                .collect::<Vec<_>>();
+            score += criterion_gaps
+                .iter()
+                .map(|gap| {
+                    if gap.missing {
+                        MISSING_SCORE
+                    } else {
+                        UNDER_REPRESENTED_SCORE
+                    }
+                })
+                .max()
+                .unwrap_or(0);
+            gaps.extend(criterion_gaps);
+        }
+
+        if gaps.is_empty() {
+            return None;
+        }
+
+        Some(DiversitySuggestionDto {
+            explanation: explain(&gaps),
+            plant: PlantsSummaryDto::from(plant),
+            score,
+            gaps,
+        })
+    }
+}
+
+/// The values of a plant for a criterion.
+///
+/// Functions and ecosystem niches are stored as comma separated lists and are compared case-insensitively.
+fn values(plant: &PlantDiversity, criterion: DiversityCriterion) -> Vec<String> {
+    let list = |text: &Option<String>| {
+        text.iter()
+            .flat_map(|text| text.split([',', ';']))
+            .map(|value| value.trim().to_lowercase())
+            .filter(|value| !value.is_empty())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    };
+
+    match criterion {
+        DiversityCriterion::Family => plant
+            .family
+            .iter()
+            .map(|family| family.trim().to_owned())
+            .filter(|family| !family.is_empty())
+            .collect(),
+        DiversityCriterion::Function => list(&plant.functions),
+        DiversityCriterion::EcosystemNiche => list(&plant.ecosystem_niche),
+        DiversityCriterion::HerbaceousOrWoody => plant
+            .herbaceous_or_woody
+            .iter()
+            .map(|value| match value {
+                HerbaceousOrWoody::Herbaceous => "herbaceous".to_owned(),
+                HerbaceousOrWoody::Woody => "woody".to_owned(),
+            })
+            .collect(),
+    }
+}
+
+/// Describe the gaps filled by a plant, e.g. "Fills gaps on the layer: missing family Fabaceae, under-represented function nitrogen fixer."
+fn explain(gaps: &[DiversityGapDto]) -> String {
+    let descriptions = gaps
+        .iter()
+        .map(|gap| {
+            let representation = if gap.missing {
+                "missing"
+            } else {
+                "under-represented"
+            };
+            let criterion = match gap.criterion {
+                DiversityCriterion::Family => "family",
+                DiversityCriterion::Function => "function",
+                DiversityCriterion::EcosystemNiche => "ecosystem niche",
+                DiversityCriterion::HerbaceousOrWoody => "growth habit",
+            };
+            format!("{representation} {criterion} {}", gap.value)
+        })
+        .collect::<Vec<_>>();
+    format!("Fills gaps on the layer: {}.", descriptions.join(", "))
+}
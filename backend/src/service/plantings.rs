@@ -2,17 +2,22 @@
 
 use actix_http::StatusCode;
 use actix_web::web::Data;
-use chrono::Days;
+use chrono::{Days, Utc};
+use postgis_diesel::types::{Point, Polygon};
 use uuid::Uuid;
 
 use crate::config::data::AppDataInner;
 use crate::error::ServiceError;
 use crate::model::dto::plantings::{
-    NewPlantingDto, PlantingDto, PlantingSearchParameters, UpdatePlantingDto,
+    NewPlantingDto, PlantingDto, PlantingSearchParameters, UpdatePlantingDto, UpdateSeedPlantingDto,
 };
-use crate::model::dto::TimelinePage;
+use crate::model::dto::{SpacingMode, TimelinePage};
 use crate::model::entity::plantings::Planting;
-use crate::model::entity::plantings_impl::FindPlantingsParameters;
+use crate::model::entity::plantings_impl::{FindPlantingsParameters, PlantingArea};
+use crate::model::entity::Seed;
+
+use super::plants;
+use super::spacing::{self, Candidate};
 
 /// Time offset in days for loading plantings in the timeline.
 pub const TIME_LINE_LOADING_OFFSET_DAYS: u64 = 356;
@@ -20,11 +25,13 @@ pub const TIME_LINE_LOADING_OFFSET_DAYS: u64 = 356;
 /// Search plantings from the database.
 ///
 /// # Errors
-/// If the connection to the database could not be established.
+/// * If the bounding box, circle or polygon is incomplete or invalid.
+/// * If the connection to the database could not be established.
 pub async fn find(
     search_parameters: PlantingSearchParameters,
     app_data: &Data<AppDataInner>,
 ) -> Result<TimelinePage<PlantingDto>, ServiceError> {
+    let areas = areas(&search_parameters)?;
     let mut conn = app_data.pool.get().await?;
 
     let from = search_parameters
@@ -52,6 +59,7 @@ pub async fn find(
         plant_id: search_parameters.plant_id,
         from,
         to,
+        areas,
     };
     let result = Planting::find(search_parameters, &mut conn).await?;
 
@@ -65,12 +73,32 @@ pub async fn find(
 /// Create a new planting in the database.
 ///
 /// # Errors
-/// If the connection to the database could not be established.
+/// * If the plant is not visible to the user.
+/// * If the spacing mode is strict and the planting has spacing problems.
+/// * If the connection to the database could not be established.
 pub async fn create(
+    map_id: i32,
     dto: NewPlantingDto,
+    spacing_mode: SpacingMode,
+    user_id: Uuid,
     app_data: &Data<AppDataInner>,
 ) -> Result<PlantingDto, ServiceError> {
     let mut conn = app_data.pool.get().await?;
+    plants::check_visible(dto.plant_id, user_id, &mut conn).await?;
+    if spacing_mode == SpacingMode::Strict {
+        let candidate = Candidate {
+            layer_id: dto.layer_id,
+            plant_id: dto.plant_id,
+            x: dto.x,
+            y: dto.y,
+            width: f64::from(dto.width) * f64::from(dto.scale_x),
+            height: f64::from(dto.height) * f64::from(dto.scale_y),
+            date: dto.add_date.unwrap_or_else(|| Utc::now().date_naive()),
+            planting_id: None,
+        };
+        let warnings = spacing::warnings(map_id, &candidate, user_id, &mut conn).await?;
+        spacing::enforce(spacing_mode, warnings)?;
+    }
     let result = Planting::create(dto, &mut conn).await?;
     Ok(result)
 }
@@ -78,13 +106,68 @@ pub async fn create(
 /// Update the planting in the database.
 ///
 /// # Errors
-/// If the connection to the database could not be established.
+/// * If the spacing mode is strict and the moved or transformed planting has spacing problems.
+/// * If the linked seed does not belong to the user or is of a different plant.
+/// * If the connection to the database could not be established.
 pub async fn update(
+    map_id: i32,
     id: Uuid,
     dto: UpdatePlantingDto,
+    spacing_mode: SpacingMode,
+    user_id: Uuid,
     app_data: &Data<AppDataInner>,
 ) -> Result<PlantingDto, ServiceError> {
     let mut conn = app_data.pool.get().await?;
+    if let UpdatePlantingDto::UpdateSeed(UpdateSeedPlantingDto {
+        seed_id: Some(seed_id),
+        ..
+    }) = dto
+    {
+        let planting = Planting::find_by_id_on_map(id, map_id, &mut conn).await?;
+        let seed = Seed::find_by_id(seed_id, user_id, &mut conn).await?;
+        if seed
+            .plant_id
+            .iter()
+            .any(|plant_id| *plant_id != planting.plant_id)
+        {
+            return Err(ServiceError::new(
+                StatusCode::BAD_REQUEST,
+                "The seed is of a different plant than the planting".to_owned(),
+            ));
+        }
+    }
+    if spacing_mode == SpacingMode::Strict {
+        let position = match &dto {
+            UpdatePlantingDto::Transform(transform) => Some((
+                transform.x,
+                transform.y,
+                Some((transform.scale_x, transform.scale_y)),
+            )),
+            UpdatePlantingDto::Move(move_dto) => Some((move_dto.x, move_dto.y, None)),
+            UpdatePlantingDto::UpdateAddDate(_)
+            | UpdatePlantingDto::UpdateRemoveDate(_)
+            | UpdatePlantingDto::UpdateNotes(_)
+            | UpdatePlantingDto::UpdateStatus(_)
+            | UpdatePlantingDto::UpdateSeed(_)
+            | UpdatePlantingDto::UpdatePhotos(_) => None,
+        };
+        if let Some((x, y, scale)) = position {
+            let planting = Planting::find_by_id_on_map(id, map_id, &mut conn).await?;
+            let (scale_x, scale_y) = scale.unwrap_or((planting.scale_x, planting.scale_y));
+            let candidate = Candidate {
+                layer_id: planting.layer_id,
+                plant_id: planting.plant_id,
+                x,
+                y,
+                width: f64::from(planting.width) * f64::from(scale_x),
+                height: f64::from(planting.height) * f64::from(scale_y),
+                date: planting.add_date.unwrap_or_else(|| Utc::now().date_naive()),
+                planting_id: Some(id),
+            };
+            let warnings = spacing::warnings(map_id, &candidate, user_id, &mut conn).await?;
+            spacing::enforce(spacing_mode, warnings)?;
+        }
+    }
     let result = Planting::update(id, dto, &mut conn).await?;
     Ok(result)
 }
@@ -98,3 +181,126 @@ pub async fn delete_by_id(id: Uuid, app_data: &Data<AppDataInner>) -> Result<(),
     let _ = Planting::delete_by_id(id, &mut conn).await?;
     Ok(())
 }
+
+/// Collect the areas of the map plantings are searched in.
+fn areas(search_parameters: &PlantingSearchParameters) -> Result<Vec<PlantingArea>, ServiceError> {
+    let bad_request =
+        |message: &str| ServiceError::new(StatusCode::BAD_REQUEST, message.to_owned());
+    let mut areas = Vec::new();
+
+    match (
+        search_parameters.x_min,
+        search_parameters.y_min,
+        search_parameters.x_max,
+        search_parameters.y_max,
+    ) {
+        (Some(x_min), Some(y_min), Some(x_max), Some(y_max)) => {
+            if x_min > x_max || y_min > y_max {
+                return Err(bad_request(
+                    "The minimum of the bounding box must not be greater than its maximum",
+                ));
+            }
+            areas.push(PlantingArea::BoundingBox {
+                x_min,
+                y_min,
+                x_max,
+                y_max,
+            });
+        }
+        (None, None, None, None) => {}
+        _ => return Err(bad_request("The bounding box needs all four coordinates")),
+    }
+
+    match (
+        search_parameters.center_x,
+        search_parameters.center_y,
+        search_parameters.radius,
+    ) {
+        (Some(x), Some(y), Some(radius)) => {
+            if radius < 0 {
+                return Err(bad_request("The radius must not be negative"));
+            }
+            areas.push(PlantingArea::Circle { x, y, radius });
+        }
+        (None, None, None) => {}
+        _ => return Err(bad_request("The circle needs a center and a radius")),
+    }
+
+    if let Some(polygon) = &search_parameters.polygon {
+        let polygon = parse_polygon(polygon).ok_or_else(|| {
+            bad_request(
+                "The polygon needs at least three corners given as comma separated coordinates",
+            )
+        })?;
+        areas.push(PlantingArea::Polygon(polygon));
+    }
+
+    Ok(areas)
+}
+
+/// Parse a polygon from comma separated coordinates of its corners, e.g. `0,0,100,0,100,100`.
+/// The ring is closed if the last corner differs from the first.
+fn parse_polygon(coordinates: &str) -> Option<Polygon<Point>> {
+    let values = coordinates
+        .split(',')
+        .map(|value| {
+            value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let mut corners = values
+        .chunks(2)
+        .map(|chunk| match *chunk {
+            [x, y] => Some(Point::new(x, y, None)),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if let (Some(first), Some(last)) = (corners.first().copied(), corners.last()) {
+        if first != *last {
+            corners.push(first);
+        }
+    }
+    // A closed triangle has four points.
+    if corners.len() < 4 {
+        return None;
+    }
+
+    let mut polygon = Polygon::new(None);
+    polygon.add_points(corners);
+    Some(polygon)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::unwrap_used)]
+    #[test]
+    fn test_parse_polygon_closes_ring() {
+        let polygon = parse_polygon("0,0, 100,0, 100,100").unwrap();
+        assert_eq!(
+            polygon.rings,
+            vec![vec![
+                Point::new(0.0, 0.0, None),
+                Point::new(100.0, 0.0, None),
+                Point::new(100.0, 100.0, None),
+                Point::new(0.0, 0.0, None),
+            ]]
+        );
+        assert_eq!(
+            parse_polygon("0,0,100,0,100,100,0,0").unwrap().rings,
+            polygon.rings
+        );
+    }
+
+    #[test]
+    fn test_parse_polygon_rejects_invalid_coordinates() {
+        assert!(parse_polygon("0,0,100,0").is_none());
+        assert!(parse_polygon("0,0,100,0,100").is_none());
+        assert!(parse_polygon("0,0,100,0,100,a").is_none());
+        assert!(parse_polygon("").is_none());
+    }
+}
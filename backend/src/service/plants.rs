@@ -1,40 +1,57 @@
 //! Service layer for plants.
 
-use actix_web::web::Data;
+use actix_web::{http::StatusCode, web::Data};
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use diesel_async::AsyncPgConnection;
 use uuid::Uuid;
 
 use super::util::HalfMonthBucket;
 use crate::config::data::AppDataInner;
-use crate::model::dto::Page;
 use crate::model::dto::PageParameters;
 use crate::model::dto::PlantSuggestionsSearchParameters;
+use crate::model::dto::{FacetedPage, Page};
 use crate::{
     error::ServiceError,
     model::{
-        dto::{PlantsSearchParameters, PlantsSummaryDto},
+        dto::{
+            DatasetFormat, NewCustomPlantDto, PlantDetailDto, PlantsDatasetDto,
+            PlantsImportParameters, PlantsImportReportDto, PlantsSearchParameters,
+            PlantsSummaryDto,
+        },
         entity::Plants,
     },
 };
 
 /// Search plants from in the database.
+/// The page also contains the facets of all plants matching the search.
+/// Only plants of the shared catalogue and custom plants of the user are found.
 ///
 /// # Errors
 /// If the connection to the database could not be established.
 pub async fn find(
     search_parameters: PlantsSearchParameters,
     page_parameters: PageParameters,
+    user_id: Uuid,
     app_data: &Data<AppDataInner>,
-) -> Result<Page<PlantsSummaryDto>, ServiceError> {
+) -> Result<FacetedPage<PlantsSummaryDto>, ServiceError> {
     let mut conn = app_data.pool.get().await?;
     let result = match &search_parameters.name {
         // Empty search queries should be treated like nonexistent queries.
         Some(query) if !query.is_empty() => {
-            Plants::search(query, page_parameters, &mut conn).await?
+            Plants::search(
+                query,
+                &search_parameters,
+                page_parameters,
+                user_id,
+                &mut conn,
+            )
+            .await?
         }
-        _ => Plants::find_any(page_parameters, &mut conn).await?,
+        _ => Plants::find_any(&search_parameters, page_parameters, user_id, &mut conn).await?,
     };
+    let facets = Plants::facets(&search_parameters, user_id, &mut conn).await?;
 
-    Ok(result)
+    Ok(FacetedPage::from_page(result, facets))
 }
 
 /// Find the plant by id from the database.
@@ -43,10 +60,85 @@ pub async fn find(
 /// If the connection to the database could not be established.
 pub async fn find_by_id(
     id: i32,
+    user_id: Uuid,
     app_data: &Data<AppDataInner>,
 ) -> Result<PlantsSummaryDto, ServiceError> {
     let mut conn = app_data.pool.get().await?;
-    let result = Plants::find_by_id(id, &mut conn).await?;
+    let result = Plants::find_by_id(id, user_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Find the plant by id including all of its details from the database.
+///
+/// # Errors
+/// If the connection to the database could not be established.
+pub async fn find_details_by_id(
+    id: i32,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<PlantDetailDto, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    let result = Plants::find_details_by_id(id, user_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Create a custom plant or variety of the user.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+/// * If the base plant does not exist.
+/// * If a plant with the same unique name already exists.
+pub async fn create_custom(
+    new_plant: NewCustomPlantDto,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<PlantDetailDto, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    let result = Plants::create_custom(new_plant, user_id, &mut conn)
+        .await
+        .map_err(|err| match err {
+            DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => ServiceError::new(
+                StatusCode::CONFLICT,
+                "A plant with this unique name already exists".to_owned(),
+            ),
+            _ => err.into(),
+        })?;
+    Ok(result)
+}
+
+/// Submit a custom plant of the user for review to be added to the shared catalogue.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+/// * If the plant does not exist or is not owned by the user.
+pub async fn submit_for_review(
+    id: i32,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<PlantDetailDto, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    let result = Plants::submit_for_review(id, user_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Import a plant dataset into the plant catalogue.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+/// * If the dataset could not be parsed.
+pub async fn import(
+    parameters: PlantsImportParameters,
+    data: &[u8],
+    app_data: &Data<AppDataInner>,
+) -> Result<PlantsImportReportDto, ServiceError> {
+    let dataset = match parameters.format {
+        DatasetFormat::Json => serde_json::from_slice(data).map_err(|e| e.to_string()),
+        DatasetFormat::Csv => PlantsDatasetDto::from_csv(data),
+    }
+    .map_err(|e| ServiceError::new(StatusCode::BAD_REQUEST, e))?;
+
+    let mut conn = app_data.pool.get().await?;
+    let result = Plants::import(dataset, parameters.external_source, &mut conn).await?;
     Ok(result)
 }
 
@@ -70,3 +162,22 @@ pub async fn find_available_seasonal(
 
     Ok(result)
 }
+
+/// Check that the plant exists and is visible to the user.
+///
+/// # Errors
+/// * If the plant does not exist or is a custom plant of another user.
+/// * If the connection to the database could not be established.
+pub async fn check_visible(
+    plant_id: i32,
+    user_id: Uuid,
+    conn: &mut AsyncPgConnection,
+) -> Result<(), ServiceError> {
+    if !Plants::is_visible(plant_id, user_id, conn).await? {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            format!("Plant {plant_id} does not exist"),
+        ));
+    }
+    Ok(())
+}
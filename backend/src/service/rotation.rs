@@ -0,0 +1,277 @@
+//! Service layer for crop rotation.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use actix_http::StatusCode;
+use actix_web::web::Data;
+use chrono::{Datelike, Months, NaiveDate};
+use uuid::Uuid;
+
+use crate::config::data::AppDataInner;
+use crate::error::ServiceError;
+use crate::model::dto::{
+    RotationCheckParameters, RotationConflictDto, RotationConflictKind, RotationGroup,
+    RotationHistoryDto, RotationScheduleDto, RotationScheduleParameters, RotationYearDto,
+};
+use crate::model::entity::plantings::Planting;
+use crate::model::entity::Plants;
+use crate::model::r#enum::layer_type::LayerType;
+use crate::model::r#enum::nutrition_demand::NutritionDemand;
+
+use super::layer::check_layer;
+use super::util::{planting_area, Rectangle};
+
+/// Years that should pass before a plant of the same family is grown at the same spot again.
+pub const FAMILY_ROTATION_YEARS: i32 = 3;
+/// Years that should pass before a heavy feeder is grown at the same spot again.
+pub const HEAVY_FEEDER_ROTATION_YEARS: i32 = 1;
+/// The default number of years of a rotation schedule.
+const DEFAULT_SCHEDULE_YEARS: i32 = 4;
+/// The maximum number of years of a rotation schedule.
+const MAX_SCHEDULE_YEARS: i32 = 20;
+/// The family of legumes, which improve the soil by fixing nitrogen.
+const SOIL_IMPROVER_FAMILY: &str = "Fabaceae";
+
+/// Check a new or moved planting against the earlier plantings at the same spot of the layer.
+///
+/// # Errors
+/// * If the layer is not a plant layer of the map.
+/// * If the plant does not exist.
+/// * If the `add_date` is out of range.
+/// * If the connection to the database could not be established.
+pub async fn check(
+    map_id: i32,
+    parameters: RotationCheckParameters,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<Vec<RotationConflictDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, parameters.layer_id, &[LayerType::Plants], &mut conn).await?;
+
+    let plant = Plants::find_details_by_id(parameters.plant_id, user_id, &mut conn).await?;
+    let family_from = years_before(parameters.add_date, FAMILY_ROTATION_YEARS)?;
+    let heavy_feeder_from = years_before(parameters.add_date, HEAVY_FEEDER_ROTATION_YEARS)?;
+    let earlier_plantings = Planting::find_by_layer_with_plants(
+        parameters.layer_id,
+        family_from,
+        parameters.add_date,
+        &mut conn,
+    )
+    .await?;
+
+    let area = Rectangle::around(
+        parameters.x,
+        parameters.y,
+        f64::from(parameters.width) * f64::from(parameters.scale_x.unwrap_or(1.0)),
+        f64::from(parameters.height) * f64::from(parameters.scale_y.unwrap_or(1.0)),
+    );
+    let is_heavy_feeder = plant.nutrition_demand == Some(NutritionDemand::HeavyFeeder);
+
+    let mut conflicts = Vec::new();
+    for (planting, earlier_plant) in earlier_plantings {
+        if Some(planting.id) == parameters.planting_id || !area.overlaps(&planting_area(&planting))
+        {
+            continue;
+        }
+        let until = planting.remove_date.map_or_else(
+            || "and is still there".to_owned(),
+            |date| format!("until {date}"),
+        );
+
+        if let (Some(family), Some(earlier_family)) = (&plant.family, &earlier_plant.family) {
+            if same_family(family, earlier_family) {
+                conflicts.push(conflict(
+                    RotationConflictKind::SameFamily,
+                    &planting,
+                    &earlier_plant,
+                    format!(
+                        "{} of the family {earlier_family} grew here {until}, plants of the same family should only return after {FAMILY_ROTATION_YEARS} years.",
+                        earlier_plant.unique_name
+                    ),
+                ));
+            }
+        }
+
+        let is_recent = planting
+            .remove_date
+            .iter()
+            .all(|date| *date >= heavy_feeder_from);
+        if is_heavy_feeder
+            && is_recent
+            && earlier_plant.nutrition_demand == Some(NutritionDemand::HeavyFeeder)
+        {
+            conflicts.push(conflict(
+                RotationConflictKind::HeavyFeeder,
+                &planting,
+                &earlier_plant,
+                format!(
+                    "The heavy feeder {} grew here {until}, heavy feeders should not follow each other.",
+                    earlier_plant.unique_name
+                ),
+            ));
+        }
+    }
+
+    Ok(conflicts)
+}
+
+/// Suggest a crop rotation schedule for a bed based on the plantings of the previous years.
+///
+/// The rotation cycles through heavy feeders, moderate feeders, light feeders and soil improvers,
+/// continuing after the most demanding group grown in the bed in the latest year.
+///
+/// # Errors
+/// * If the layer is not a plant layer of the map.
+/// * If the bed or the years are invalid.
+/// * If the connection to the database could not be established.
+pub async fn schedule(
+    map_id: i32,
+    parameters: RotationScheduleParameters,
+    app_data: &Data<AppDataInner>,
+) -> Result<RotationScheduleDto, ServiceError> {
+    let years = parameters
+        .years
+        .unwrap_or(DEFAULT_SCHEDULE_YEARS)
+        .clamp(1, MAX_SCHEDULE_YEARS);
+    if parameters.x_min >= parameters.x_max || parameters.y_min >= parameters.y_max {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The bed must have a positive width and height".to_owned(),
+        ));
+    }
+    let first_year = parameters.start_year.saturating_sub(FAMILY_ROTATION_YEARS);
+    let last_year = parameters.start_year.saturating_sub(1);
+    let (Some(from), Some(to)) = (
+        NaiveDate::from_ymd_opt(first_year, 1, 1),
+        NaiveDate::from_ymd_opt(last_year, 12, 31),
+    ) else {
+        return Err(invalid_date());
+    };
+
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, parameters.layer_id, &[LayerType::Plants], &mut conn).await?;
+    let plantings =
+        Planting::find_by_layer_with_plants(parameters.layer_id, from, to, &mut conn).await?;
+
+    let bed = Rectangle {
+        x_min: f64::from(parameters.x_min),
+        y_min: f64::from(parameters.y_min),
+        x_max: f64::from(parameters.x_max),
+        y_max: f64::from(parameters.y_max),
+    };
+    let mut history = (first_year..=last_year)
+        .map(|year| (year, (BTreeSet::new(), BTreeSet::new())))
+        .collect::<BTreeMap<_, _>>();
+    for (planting, plant) in plantings {
+        if !bed.overlaps(&planting_area(&planting)) {
+            continue;
+        }
+        let first = planting
+            .add_date
+            .map_or(first_year, |date| date.year().max(first_year));
+        let last = planting
+            .remove_date
+            .map_or(last_year, |date| date.year().min(last_year));
+        let group = rotation_group(plant.family.as_deref(), plant.nutrition_demand);
+        for (_, (families, groups)) in history.range_mut(first..=last) {
+            families.extend(plant.family.clone());
+            groups.extend(group);
+        }
+    }
+
+    let mut group = history
+        .values()
+        .rev()
+        .find_map(|(_, groups)| groups.first().copied())
+        .map_or(RotationGroup::HeavyFeeder, next_group);
+    let mut schedule = Vec::new();
+    for year in parameters.start_year..parameters.start_year.saturating_add(years) {
+        let avoid_families = history
+            .range(year.saturating_sub(FAMILY_ROTATION_YEARS)..year)
+            .flat_map(|(_, (families, _))| families.iter().cloned())
+            .collect::<BTreeSet<_>>();
+        schedule.push(RotationYearDto {
+            year,
+            group,
+            avoid_families: avoid_families.into_iter().collect(),
+        });
+        group = next_group(group);
+    }
+
+    Ok(RotationScheduleDto {
+        history: history
+            .into_iter()
+            .map(|(year, (families, groups))| RotationHistoryDto {
+                year,
+                families: families.into_iter().collect(),
+                groups: groups.into_iter().collect(),
+            })
+            .collect(),
+        schedule,
+    })
+}
+
+/// The rotation group of a plant, legumes are soil improvers regardless of their nutrition demand.
+fn rotation_group(
+    family: Option<&str>,
+    nutrition_demand: Option<NutritionDemand>,
+) -> Option<RotationGroup> {
+    if family
+        .iter()
+        .any(|family| same_family(family, SOIL_IMPROVER_FAMILY))
+    {
+        return Some(RotationGroup::SoilImprover);
+    }
+    nutrition_demand.map(|demand| match demand {
+        NutritionDemand::HeavyFeeder => RotationGroup::HeavyFeeder,
+        NutritionDemand::ModerateFeeder => RotationGroup::ModerateFeeder,
+        NutritionDemand::LightFeeder => RotationGroup::LightFeeder,
+    })
+}
+
+/// The group following `group` in the rotation.
+const fn next_group(group: RotationGroup) -> RotationGroup {
+    match group {
+        RotationGroup::HeavyFeeder => RotationGroup::ModerateFeeder,
+        RotationGroup::ModerateFeeder => RotationGroup::LightFeeder,
+        RotationGroup::LightFeeder => RotationGroup::SoilImprover,
+        RotationGroup::SoilImprover => RotationGroup::HeavyFeeder,
+    }
+}
+
+/// Whether two family names are the same, ignoring case and surrounding whitespace.
+fn same_family(family: &str, other: &str) -> bool {
+    family.trim().eq_ignore_ascii_case(other.trim())
+}
+
+/// Create a conflict with an earlier planting.
+fn conflict(
+    kind: RotationConflictKind,
+    planting: &Planting,
+    plant: &Plants,
+    message: String,
+) -> RotationConflictDto {
+    RotationConflictDto {
+        kind,
+        planting_id: planting.id,
+        plant_id: plant.id,
+        unique_name: plant.unique_name.clone(),
+        add_date: planting.add_date,
+        remove_date: planting.remove_date,
+        message,
+    }
+}
+
+/// The date the given number of years before `date`.
+fn years_before(date: NaiveDate, years: i32) -> Result<NaiveDate, ServiceError> {
+    u32::try_from(years)
+        .ok()
+        .and_then(|years| years.checked_mul(12))
+        .and_then(|months| date.checked_sub_months(Months::new(months)))
+        .ok_or_else(invalid_date)
+}
+
+/// Error for dates that are out of range.
+fn invalid_date() -> ServiceError {
+    ServiceError::new(StatusCode::BAD_REQUEST, "Date out of range".to_owned())
+}
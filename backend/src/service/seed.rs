@@ -3,9 +3,10 @@
 use actix_web::web::Data;
 use uuid::Uuid;
 
+use super::plants;
 use crate::config::data::AppDataInner;
 use crate::model::dto::PageParameters;
-use crate::model::dto::{Page, SeedSearchParameters};
+use crate::model::dto::{Page, SeedLineageDto, SeedSearchParameters};
 use crate::{
     error::ServiceError,
     model::{
@@ -43,16 +44,34 @@ pub async fn find_by_id(
     Ok(result)
 }
 
-/// Create a new seed in the database.
+/// Find the ancestors and descendants of the seed from the database.
 ///
 /// # Errors
 /// If the connection to the database could not be established.
+pub async fn find_lineage(
+    id: i32,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<SeedLineageDto, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    let result = Seed::find_lineage(id, user_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Create a new seed in the database.
+///
+/// # Errors
+/// * If the plant of the seed is not visible to the user.
+/// * If the connection to the database could not be established.
 pub async fn create(
     new_seed: NewSeedDto,
     user_id: Uuid,
     app_data: &Data<AppDataInner>,
 ) -> Result<SeedDto, ServiceError> {
     let mut conn = app_data.pool.get().await?;
+    if let Some(plant_id) = new_seed.plant_id {
+        plants::check_visible(plant_id, user_id, &mut conn).await?;
+    }
     let result = Seed::create(new_seed, user_id, &mut conn).await?;
     Ok(result)
 }
@@ -0,0 +1,703 @@
+//! Service layer for the shade layer.
+
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+use actix_http::StatusCode;
+use actix_web::web::{self, Data};
+use chrono::{Datelike, NaiveDate, Utc};
+use diesel_async::AsyncPgConnection;
+use postgis_diesel::types::{Point, Polygon};
+use uuid::Uuid;
+
+use crate::config::data::AppDataInner;
+use crate::error::ServiceError;
+use crate::model::dto::shade::{
+    NewShadeObstacleDto, ShadeCellDto, ShadeObstacleDto, ShadeRasterDto, ShadeRasterParameters,
+    UpdateShadeObstacleDto,
+};
+use crate::model::dto::LayerSearchParameters;
+use crate::model::entity::plantings::Planting;
+use crate::model::entity::shade_obstacles::ShadeObstacle;
+use crate::model::entity::{Layer, Map};
+use crate::model::r#enum::layer_type::LayerType;
+use crate::model::r#enum::light_requirement::LightRequirement;
+use crate::model::r#enum::shade::Shade;
+
+use super::layer::check_layer;
+use super::util::{
+    exceeds_max_cells, mature_height, planting_area, polygon_contains, raster_cell, scale_heatmap,
+    RasterCache, Rectangle, SUITABILITY_PENALTY_PER_STEP,
+};
+
+/// The default size of a cell of the shade raster in cm.
+pub const DEFAULT_RESOLUTION: i32 = 100;
+
+/// The maximum number of cells of a shade raster, as every cell follows the sun for a whole day.
+const MAX_RASTER_CELLS: f64 = 62_500.0;
+
+/// The interval in minutes the position of the sun is sampled at.
+const SAMPLE_MINUTES: f64 = 15.0;
+
+/// The minimum hours of direct sunlight for plants needing full sun.
+const FULL_SUN_HOURS: f32 = 6.0;
+
+/// The minimum hours of direct sunlight for plants needing partial sun.
+const PARTIAL_SUN_HOURS: f32 = 3.0;
+
+/// The minimum hours of direct sunlight of cells in permanent, but not deep shade.
+const PERMANENT_SHADE_HOURS: f32 = 1.0;
+
+/// The shade rasters of maps used for scoring plant locations.
+pub type ShadeRasterCache = RasterCache<ShadeRasterInputs, ShadeRasterDto>;
+
+/// Everything the shade raster of a map on a day is computed from.
+#[derive(Debug, PartialEq)]
+pub struct ShadeRasterInputs {
+    /// The geometry of the map.
+    geometry: Polygon<Point>,
+    /// The obstacles and plantings casting shade.
+    casters: Vec<ShadeCaster>,
+    /// The latitude and longitude of the map.
+    location: (f64, f64),
+    /// The size of a cell in cm.
+    resolution: i32,
+}
+
+/// Something casting shade, either an obstacle of the shade layer or a planting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadeCaster {
+    /// The footprint of the caster.
+    footprint: Polygon<Point>,
+    /// The height of the caster in cm.
+    height: f64,
+}
+
+/// Get all obstacles of a shade layer on the map.
+///
+/// # Errors
+/// * If the layer is not a shade layer of the map.
+/// * If the connection to the database could not be established.
+pub async fn find(
+    map_id: i32,
+    layer_id: i32,
+    app_data: &Data<AppDataInner>,
+) -> Result<Vec<ShadeObstacleDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, layer_id, &[LayerType::Shade], &mut conn).await?;
+    let result = ShadeObstacle::find_by_layer(layer_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Create a new obstacle on a shade layer of the map.
+///
+/// # Errors
+/// * If the name is blank, the height is not positive or the footprint is not an area.
+/// * If the layer is not a shade layer of the map.
+/// * If the connection to the database could not be established.
+pub async fn create(
+    map_id: i32,
+    new_obstacle: NewShadeObstacleDto,
+    app_data: &Data<AppDataInner>,
+) -> Result<ShadeObstacleDto, ServiceError> {
+    check_obstacle(
+        Some(&new_obstacle.name),
+        Some(&new_obstacle.geometry),
+        Some(new_obstacle.height),
+    )?;
+    let mut conn = app_data.pool.get().await?;
+    check_layer(
+        map_id,
+        new_obstacle.layer_id,
+        &[LayerType::Shade],
+        &mut conn,
+    )
+    .await?;
+    let result = ShadeObstacle::create(new_obstacle.into(), &mut conn).await?;
+    Ok(result)
+}
+
+/// Partially update an obstacle on the map.
+///
+/// # Errors
+/// * If the name is blank, the height is not positive or the footprint is not an area.
+/// * If the obstacle does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn update(
+    map_id: i32,
+    obstacle_id: Uuid,
+    update_obstacle: UpdateShadeObstacleDto,
+    app_data: &Data<AppDataInner>,
+) -> Result<ShadeObstacleDto, ServiceError> {
+    check_obstacle(
+        update_obstacle.name.as_ref(),
+        update_obstacle.geometry.as_ref(),
+        update_obstacle.height,
+    )?;
+    let mut conn = app_data.pool.get().await?;
+    let result =
+        ShadeObstacle::update(obstacle_id, map_id, update_obstacle.into(), &mut conn).await?;
+    Ok(result)
+}
+
+/// Delete an obstacle on the map.
+///
+/// # Errors
+/// * If the obstacle does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn delete_by_id(
+    map_id: i32,
+    obstacle_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<(), ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    if ShadeObstacle::delete_by_id(obstacle_id, map_id, &mut conn).await? == 0 {
+        return Err(ServiceError::new(
+            StatusCode::NOT_FOUND,
+            StatusCode::NOT_FOUND.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Compute the hours of direct sunlight across the geometry of the map on a day.
+///
+/// Shade is cast by the obstacles of the layer and by the plantings of the map at their mature height.
+///
+/// # Errors
+/// * If the resolution is not positive or results in too many cells.
+/// * If the layer is not a shade layer of the map.
+/// * If the map has no location.
+/// * If the connection to the database could not be established.
+/// * If the raster could not be computed.
+pub async fn raster(
+    map_id: i32,
+    parameters: ShadeRasterParameters,
+    app_data: &Data<AppDataInner>,
+) -> Result<ShadeRasterDto, ServiceError> {
+    let resolution = parameters.resolution.unwrap_or(DEFAULT_RESOLUTION);
+    if resolution <= 0 {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The resolution must be positive".to_owned(),
+        ));
+    }
+    let date = parameters.date.unwrap_or_else(|| Utc::now().date_naive());
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, parameters.layer_id, &[LayerType::Shade], &mut conn).await?;
+    let map = Map::find_by_id(map_id, &mut conn).await?;
+    let Some(location) = map.location else {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            format!("Map {map_id} has no location to compute the path of the sun for"),
+        ));
+    };
+    if exceeds_max_cells(&map.geometry, resolution, MAX_RASTER_CELLS) {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            format!("The resolution {resolution} results in too many cells for the map"),
+        ));
+    }
+
+    let obstacles = ShadeObstacle::find_by_layer(parameters.layer_id, &mut conn).await?;
+    let casters = find_casters(map_id, obstacles, date, &mut conn).await?;
+    let raster = web::block(move || {
+        compute_raster(
+            &map.geometry,
+            &casters,
+            (location.latitude, location.longitude),
+            date,
+            resolution,
+        )
+    })
+    .await?;
+    Ok(raster)
+}
+
+/// Compute the shade of the map on a day for scoring plant locations.
+///
+/// Uses the obstacles of the main shade layer and the plantings of the map.
+/// Returns `None` if the map has no location or nothing on the map casts shade.
+/// The raster is reused from `cache` as long as the map, its obstacles and plantings don't change.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+/// * If the raster could not be computed.
+pub async fn main_layer_raster(
+    map_id: i32,
+    date: NaiveDate,
+    cache: &ShadeRasterCache,
+    conn: &mut AsyncPgConnection,
+) -> Result<Option<Arc<ShadeRasterDto>>, ServiceError> {
+    let map = Map::find_by_id(map_id, conn).await?;
+    let Some(location) = map.location else {
+        return Ok(None);
+    };
+    let layers = Layer::find(
+        LayerSearchParameters {
+            map_id: Some(map_id),
+            type_: Some(LayerType::Shade),
+            is_alternative: Some(false),
+            is_deleted: None,
+        },
+        conn,
+    )
+    .await?;
+    let obstacles = match layers.first() {
+        Some(layer) => ShadeObstacle::find_by_layer(layer.id, conn).await?,
+        None => Vec::new(),
+    };
+    let casters = find_casters(map_id, obstacles, date, conn).await?;
+    if casters.is_empty() {
+        return Ok(None);
+    }
+
+    let mut resolution = DEFAULT_RESOLUTION;
+    while exceeds_max_cells(&map.geometry, resolution, MAX_RASTER_CELLS) {
+        resolution *= 2;
+    }
+    let inputs = ShadeRasterInputs {
+        geometry: map.geometry,
+        casters,
+        location: (location.latitude, location.longitude),
+        resolution,
+    };
+    let raster = cache
+        .get_or_compute(map_id, date, inputs, move |inputs| {
+            compute_raster(
+                &inputs.geometry,
+                &inputs.casters,
+                inputs.location,
+                date,
+                inputs.resolution,
+            )
+        })
+        .await?;
+    Ok(Some(raster))
+}
+
+/// Lower the scores of a heatmap where the cells get less sunlight than the plant needs.
+///
+/// The score is lowered by [`SUITABILITY_PENALTY_PER_STEP`] for each light requirement
+/// the cell is darker than needed, see [`scale_heatmap`] for the layout of the heatmap.
+/// Lighter spots than needed are not penalized, as plants grow better with better light conditions.
+pub fn apply_light_suitability(
+    heatmap: &mut [Vec<f32>],
+    origin: (i32, i32),
+    granularity: i32,
+    shade: &ShadeRasterDto,
+    light_requirements: &[LightRequirement],
+) {
+    let Some(needed) = light_requirements.iter().copied().map(light_rank).min() else {
+        return;
+    };
+    scale_heatmap(heatmap, origin, granularity, |x, y| {
+        raster_cell(
+            &shade.cells,
+            (shade.x_min, shade.y_min),
+            shade.resolution,
+            (x, y),
+        )
+        .map_or(1.0, |cell| {
+            #[allow(clippy::cast_precision_loss)]
+            // ok, because there are only a few light requirements
+            let steps = (needed - sun_rank(cell.sun_hours)).max(0) as f32;
+            steps.mul_add(-SUITABILITY_PENALTY_PER_STEP, 1.0).max(0.0)
+        })
+    });
+}
+
+/// Check the given properties of an obstacle.
+fn check_obstacle(
+    name: Option<&String>,
+    geometry: Option<&Polygon<Point>>,
+    height: Option<i32>,
+) -> Result<(), ServiceError> {
+    if name.is_some_and(|name| name.trim().is_empty()) {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The name of an obstacle must not be blank".to_owned(),
+        ));
+    }
+    if geometry.is_some_and(|geometry| geometry.rings.first().map_or(0, Vec::len) < 3) {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The footprint of an obstacle needs at least three points".to_owned(),
+        ));
+    }
+    if height.is_some_and(|height| height <= 0) {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The height of an obstacle must be positive".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Collect the obstacles and the plantings existing on the date as shade casters.
+///
+/// Plantings are assumed to be fully grown, plantings of plants with unknown height cast no shade.
+async fn find_casters(
+    map_id: i32,
+    obstacles: Vec<ShadeObstacleDto>,
+    date: NaiveDate,
+    conn: &mut AsyncPgConnection,
+) -> Result<Vec<ShadeCaster>, ServiceError> {
+    let plantings = Planting::find_by_map_with_plants(map_id, date, date, conn).await?;
+    let planting_casters = plantings.into_iter().filter_map(|(planting, plant)| {
+        let height = plant.height.and_then(mature_height)?;
+        let area = planting_area(&planting);
+        let mut footprint = Polygon::new(None);
+        footprint.add_points([
+            Point::new(area.x_min, area.y_min, None),
+            Point::new(area.x_max, area.y_min, None),
+            Point::new(area.x_max, area.y_max, None),
+            Point::new(area.x_min, area.y_max, None),
+            Point::new(area.x_min, area.y_min, None),
+        ]);
+        Some(ShadeCaster { footprint, height })
+    });
+
+    Ok(obstacles
+        .into_iter()
+        .map(|obstacle| ShadeCaster {
+            footprint: obstacle.geometry,
+            height: f64::from(obstacle.height),
+        })
+        .chain(planting_casters)
+        .collect())
+}
+
+/// Compute the sun and shade hours for each cell of a raster covering the geometry.
+///
+/// `location` is the latitude and longitude of the map in degrees.
+#[allow(
+    clippy::cast_possible_truncation, // ok, because the raster is limited to `MAX_RASTER_CELLS` and map coordinates are i32
+    clippy::cast_sign_loss,           // ok, because the number of cells is never negative
+    clippy::cast_precision_loss       // ok, because the raster is limited to `MAX_RASTER_CELLS`
+)]
+fn compute_raster(
+    geometry: &Polygon<Point>,
+    casters: &[ShadeCaster],
+    location: (f64, f64),
+    date: NaiveDate,
+    resolution: i32,
+) -> ShadeRasterDto {
+    let Some(bounds) = Rectangle::enclosing(geometry) else {
+        return ShadeRasterDto {
+            x_min: 0,
+            y_min: 0,
+            resolution,
+            date,
+            cells: Vec::new(),
+        };
+    };
+    let (x_min, y_min) = (bounds.x_min.floor(), bounds.y_min.floor());
+    let size = f64::from(resolution);
+    let (num_cols, num_rows) = (
+        ((bounds.x_max - x_min) / size).ceil() as usize,
+        ((bounds.y_max - y_min) / size).ceil() as usize,
+    );
+    let sun_path = sun_path(location, date);
+    let casters = casters
+        .iter()
+        .filter_map(|caster| Some((caster, Rectangle::enclosing(&caster.footprint)?)))
+        .collect::<Vec<_>>();
+    let step_hours = (SAMPLE_MINUTES / 60.0) as f32;
+
+    let cells = (0..num_rows)
+        .map(|row| {
+            let y = (row as f64 + 0.5).mul_add(size, y_min);
+            (0..num_cols)
+                .map(|col| {
+                    let x = (col as f64 + 0.5).mul_add(size, x_min);
+                    if !polygon_contains(geometry, x, y) {
+                        return None;
+                    }
+                    let sun_steps = sun_path
+                        .iter()
+                        .filter(|sun| {
+                            !casters.iter().any(|(caster, caster_bounds)| {
+                                shades(caster, caster_bounds, x, y, sun)
+                            })
+                        })
+                        .count();
+                    let sun_hours = sun_steps as f32 * step_hours;
+                    let shade_hours = (sun_path.len() - sun_steps) as f32 * step_hours;
+                    Some(ShadeCellDto {
+                        sun_hours,
+                        shade_hours,
+                        shade: classify(sun_hours, shade_hours),
+                    })
+                })
+                .collect()
+        })
+        .collect();
+    ShadeRasterDto {
+        x_min: x_min as i32,
+        y_min: y_min as i32,
+        resolution,
+        date,
+        cells,
+    }
+}
+
+/// The position of the sun in the sky.
+#[derive(Debug, Clone, Copy)]
+struct SunPosition {
+    /// The angle above the horizon in radians.
+    elevation: f64,
+    /// The compass direction in radians, clockwise from north.
+    azimuth: f64,
+}
+
+/// The positions of the sun above the horizon, sampled every [`SAMPLE_MINUTES`] over the day.
+fn sun_path((latitude, longitude): (f64, f64), date: NaiveDate) -> Vec<SunPosition> {
+    // The day is centered around local solar noon, which is at 720 - 4 * longitude minutes UTC.
+    let start = -4.0 * longitude;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    // ok, because a day has a small, positive number of samples
+    let samples = (24.0 * 60.0 / SAMPLE_MINUTES) as u32;
+    (0..samples)
+        .map(|sample| {
+            let minutes = (f64::from(sample) + 0.5).mul_add(SAMPLE_MINUTES, start);
+            sun_position(latitude, longitude, date, minutes)
+        })
+        .filter(|sun| sun.elevation > 0.0)
+        .collect()
+}
+
+/// The position of the sun at `minutes` after midnight UTC of the date, following the NOAA approximation.
+///
+/// See <https://gml.noaa.gov/grad/solcalc/solareqns.PDF>.
+fn sun_position(latitude: f64, longitude: f64, date: NaiveDate, minutes: f64) -> SunPosition {
+    let latitude = latitude.to_radians();
+    let day_of_year = f64::from(date.ordinal0()) + (minutes / 60.0 - 12.0) / 24.0;
+    let gamma = 2.0 * PI / 365.0 * day_of_year;
+
+    let equation_of_time = 229.18
+        * fourier_series(
+            gamma,
+            0.000_075,
+            &[(0.001_868, -0.032_077), (-0.014_615, -0.040_849)],
+        );
+    let declination = fourier_series(
+        gamma,
+        0.006_918,
+        &[
+            (-0.399_912, 0.070_257),
+            (-0.006_758, 0.000_907),
+            (-0.002_697, 0.001_48),
+        ],
+    );
+
+    let true_solar_time = 4.0_f64.mul_add(longitude, minutes + equation_of_time);
+    let hour_angle = (true_solar_time / 4.0 - 180.0).to_radians();
+
+    let cos_zenith = latitude
+        .sin()
+        .mul_add(
+            declination.sin(),
+            latitude.cos() * declination.cos() * hour_angle.cos(),
+        )
+        .clamp(-1.0, 1.0);
+    let azimuth = hour_angle.sin().atan2(
+        hour_angle
+            .cos()
+            .mul_add(latitude.sin(), -(declination.tan() * latitude.cos())),
+    ) + PI;
+    SunPosition {
+        elevation: PI / 2.0 - cos_zenith.acos(),
+        azimuth,
+    }
+}
+
+/// Evaluate `constant + sum(cos_k * cos(k * gamma) + sin_k * sin(k * gamma))` for the coefficients of `k = 1, 2, ...`.
+fn fourier_series(gamma: f64, constant: f64, coefficients: &[(f64, f64)]) -> f64 {
+    coefficients
+        .iter()
+        .zip(1..)
+        .fold(constant, |sum, ((cos_k, sin_k), k)| {
+            let angle = f64::from(k) * gamma;
+            cos_k.mul_add(angle.cos(), sin_k.mul_add(angle.sin(), sum))
+        })
+}
+
+/// Whether the caster shades the point `x`,`y` with the sun at the given position.
+///
+/// The x axis of the map points east and the y axis points south.
+fn shades(caster: &ShadeCaster, bounds: &Rectangle, x: f64, y: f64, sun: &SunPosition) -> bool {
+    let length = caster.height / sun.elevation.tan();
+    let distance = (bounds.x_min - x)
+        .max(x - bounds.x_max)
+        .max(0.0)
+        .hypot((bounds.y_min - y).max(y - bounds.y_max).max(0.0));
+    if distance > length {
+        return false;
+    }
+    if polygon_contains(&caster.footprint, x, y) {
+        return true;
+    }
+
+    let start = (x, y);
+    let end = (
+        sun.azimuth.sin().mul_add(length, x),
+        (-sun.azimuth.cos()).mul_add(length, y),
+    );
+    caster.footprint.rings.iter().any(|ring| {
+        ring.iter()
+            .zip(ring.iter().skip(1))
+            .any(|(from, to)| segments_intersect(start, end, (from.x, from.y), (to.x, to.y)))
+    })
+}
+
+/// Whether the segments `a`-`b` and `c`-`d` intersect.
+fn segments_intersect(a: (f64, f64), b: (f64, f64), c: (f64, f64), d: (f64, f64)) -> bool {
+    let orientation = |p: (f64, f64), q: (f64, f64), r: (f64, f64)| {
+        (q.0 - p.0).mul_add(r.1 - p.1, -((q.1 - p.1) * (r.0 - p.0)))
+    };
+    let (o1, o2) = (orientation(a, b, c), orientation(a, b, d));
+    let (o3, o4) = (orientation(c, d, a), orientation(c, d, b));
+    (o1 * o2 <= 0.0) && (o3 * o4 <= 0.0) && (o1 != 0.0 || o2 != 0.0)
+}
+
+/// The kind of shade of a cell with the given hours of sun and shade.
+fn classify(sun_hours: f32, shade_hours: f32) -> Shade {
+    if shade_hours <= 0.0 {
+        Shade::NoShade
+    } else if sun_hours >= FULL_SUN_HOURS {
+        Shade::LightShade
+    } else if sun_hours >= PARTIAL_SUN_HOURS {
+        Shade::PartialShade
+    } else if sun_hours >= PERMANENT_SHADE_HOURS {
+        Shade::PermanentShade
+    } else {
+        Shade::PermanentDeepShade
+    }
+}
+
+/// The rank of a light requirement, from darkest to lightest.
+const fn light_rank(light_requirement: LightRequirement) -> i32 {
+    match light_requirement {
+        LightRequirement::FullShade => 0,
+        LightRequirement::Partial => 1,
+        LightRequirement::Full => 2,
+    }
+}
+
+/// The rank of the light requirement met by the hours of direct sunlight.
+fn sun_rank(sun_hours: f32) -> i32 {
+    if sun_hours >= FULL_SUN_HOURS {
+        light_rank(LightRequirement::Full)
+    } else if sun_hours >= PARTIAL_SUN_HOURS {
+        light_rank(LightRequirement::Partial)
+    } else {
+        light_rank(LightRequirement::FullShade)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rectangle(x: f64, y: f64, width: f64, height: f64) -> Polygon<Point> {
+        let mut polygon = Polygon::new(None);
+        polygon.add_points([
+            Point::new(x, y, None),
+            Point::new(x + width, y, None),
+            Point::new(x + width, y + height, None),
+            Point::new(x, y + height, None),
+            Point::new(x, y, None),
+        ]);
+        polygon
+    }
+
+    #[allow(clippy::unwrap_used, clippy::cast_precision_loss)]
+    #[test]
+    fn test_sun_path_follows_the_seasons() {
+        let day_length = |month, day| {
+            let date = NaiveDate::from_ymd_opt(2023, month, day).unwrap();
+            sun_path((48.2, 16.4), date).len() as f64 * SAMPLE_MINUTES / 60.0
+        };
+        assert!((15.5..=16.5).contains(&day_length(6, 21)));
+        assert!((11.5..=12.5).contains(&day_length(3, 20)));
+        assert!((8.0..=9.0).contains(&day_length(12, 21)));
+
+        let noon = sun_path((48.2, 16.4), NaiveDate::from_ymd_opt(2023, 6, 21).unwrap())
+            .into_iter()
+            .max_by(|first, second| first.elevation.total_cmp(&second.elevation))
+            .unwrap();
+        assert!((noon.elevation.to_degrees() - 65.2).abs() < 1.0);
+        assert!((noon.azimuth.to_degrees() - 180.0).abs() < 5.0);
+    }
+
+    #[allow(clippy::unwrap_used, clippy::indexing_slicing)]
+    #[test]
+    fn test_obstacle_shades_cells_north_of_it() {
+        let map = rectangle(0.0, 0.0, 1000.0, 1000.0);
+        let wall = ShadeCaster {
+            footprint: rectangle(0.0, 500.0, 1000.0, 20.0),
+            height: 300.0,
+        };
+        let date = NaiveDate::from_ymd_opt(2023, 12, 21).unwrap();
+
+        let raster = compute_raster(&map, &[wall], (48.2, 16.4), date, 100);
+        assert_eq!(raster.cells.len(), 10);
+        let north_of_wall = raster.cells[4][5].unwrap();
+        let south_of_wall = raster.cells[6][5].unwrap();
+        let far_north = raster.cells[0][5].unwrap();
+
+        assert_eq!(south_of_wall.shade, Shade::NoShade);
+        assert!(north_of_wall.sun_hours < PERMANENT_SHADE_HOURS);
+        assert_eq!(north_of_wall.shade, Shade::PermanentDeepShade);
+        assert!(far_north.sun_hours > north_of_wall.sun_hours);
+        assert!(
+            (north_of_wall.sun_hours + north_of_wall.shade_hours
+                - south_of_wall.sun_hours
+                - south_of_wall.shade_hours)
+                .abs()
+                < f32::EPSILON
+        );
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[test]
+    fn test_apply_light_suitability_lowers_scores_of_too_dark_cells() {
+        let cell = |sun_hours| {
+            Some(ShadeCellDto {
+                sun_hours,
+                shade_hours: 8.0 - sun_hours,
+                shade: classify(sun_hours, 8.0 - sun_hours),
+            })
+        };
+        let shade = ShadeRasterDto {
+            x_min: 0,
+            y_min: 0,
+            resolution: 20,
+            date: NaiveDate::from_ymd_opt(2023, 6, 21).unwrap(),
+            cells: vec![vec![cell(8.0), cell(4.0), cell(0.0)]],
+        };
+        let mut heatmap = vec![vec![0.8; 6]; 2];
+
+        apply_light_suitability(&mut heatmap, (0, 0), 10, &shade, &[LightRequirement::Full]);
+        assert!(heatmap.iter().all(|row| {
+            let expected = [0.8, 0.8, 0.4, 0.4, 0.0, 0.0];
+            row.iter()
+                .zip(expected)
+                .all(|(score, expected)| (score - expected).abs() < f32::EPSILON)
+        }));
+
+        let mut tolerant_heatmap = vec![vec![0.8; 6]; 2];
+        apply_light_suitability(
+            &mut tolerant_heatmap,
+            (0, 0),
+            10,
+            &shade,
+            &[LightRequirement::FullShade, LightRequirement::Full],
+        );
+        assert!(tolerant_heatmap
+            .iter()
+            .flatten()
+            .all(|score| (score - 0.8).abs() < f32::EPSILON));
+    }
+}
@@ -0,0 +1,474 @@
+//! Service layer for the soil layer.
+
+use actix_http::StatusCode;
+use actix_web::web::{self, Data};
+use diesel_async::AsyncPgConnection;
+use postgis_diesel::types::{Point, Polygon};
+use uuid::Uuid;
+
+use crate::config::data::AppDataInner;
+use crate::error::ServiceError;
+use crate::model::dto::soil_samples::{
+    NewSoilSampleDto, SoilCellDto, SoilRasterDto, SoilRasterParameters, SoilSampleDto,
+    UpdateSoilSampleDto,
+};
+use crate::model::dto::LayerSearchParameters;
+use crate::model::entity::soil_samples::SoilSample;
+use crate::model::entity::{Layer, Map};
+use crate::model::r#enum::layer_type::LayerType;
+use crate::model::r#enum::soil_ph::SoilPh;
+use crate::model::r#enum::soil_texture::SoilTexture;
+
+use super::layer::check_layer;
+use super::util::{polygon_contains, scale_heatmap, Rectangle, SUITABILITY_PENALTY_PER_STEP};
+
+/// The default size of a cell of the soil raster in cm.
+const DEFAULT_RESOLUTION: i32 = 100;
+
+/// The maximum number of cells of a soil raster, so responses stay reasonably small.
+const MAX_RASTER_CELLS: f64 = 250_000.0;
+
+/// Samples closer to a cell than this distance in cm are weighted as if they were this far away,
+/// which avoids dividing by zero for cells right at a sample.
+const MIN_DISTANCE: f64 = 1.0;
+
+/// The soil a plant tolerates.
+#[derive(Debug, Clone, Default)]
+pub struct SoilRequirements {
+    /// The tolerated pH ranges, empty if unknown.
+    pub soil_ph: Vec<SoilPh>,
+    /// The tolerated textures, empty if unknown.
+    pub soil_texture: Vec<SoilTexture>,
+}
+
+/// Get all samples of a soil layer on the map.
+///
+/// # Errors
+/// * If the layer is not a soil layer of the map.
+/// * If the connection to the database could not be established.
+pub async fn find(
+    map_id: i32,
+    layer_id: i32,
+    app_data: &Data<AppDataInner>,
+) -> Result<Vec<SoilSampleDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, layer_id, &[LayerType::Soil], &mut conn).await?;
+    let result = SoilSample::find_by_layer(layer_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Create a new sample on a soil layer of the map.
+///
+/// # Errors
+/// * If a measurement is out of range.
+/// * If the layer is not a soil layer of the map.
+/// * If the connection to the database could not be established.
+pub async fn create(
+    map_id: i32,
+    new_sample: NewSoilSampleDto,
+    app_data: &Data<AppDataInner>,
+) -> Result<SoilSampleDto, ServiceError> {
+    check_measurements(new_sample.ph, new_sample.organic_matter)?;
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, new_sample.layer_id, &[LayerType::Soil], &mut conn).await?;
+    let result = SoilSample::create(new_sample.into(), &mut conn).await?;
+    Ok(result)
+}
+
+/// Replace the position and measurements of a sample on the map.
+///
+/// # Errors
+/// * If a measurement is out of range.
+/// * If the sample does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn update(
+    map_id: i32,
+    sample_id: Uuid,
+    update_sample: UpdateSoilSampleDto,
+    app_data: &Data<AppDataInner>,
+) -> Result<SoilSampleDto, ServiceError> {
+    check_measurements(update_sample.ph, update_sample.organic_matter)?;
+    let mut conn = app_data.pool.get().await?;
+    let result = SoilSample::update(sample_id, map_id, update_sample.into(), &mut conn).await?;
+    Ok(result)
+}
+
+/// Delete a sample on the map.
+///
+/// # Errors
+/// * If the sample does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn delete_by_id(
+    map_id: i32,
+    sample_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<(), ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    if SoilSample::delete_by_id(sample_id, map_id, &mut conn).await? == 0 {
+        return Err(ServiceError::new(
+            StatusCode::NOT_FOUND,
+            StatusCode::NOT_FOUND.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Interpolate the samples of a soil layer across the geometry of the map.
+///
+/// # Errors
+/// * If the resolution is not positive or results in too many cells.
+/// * If the layer is not a soil layer of the map.
+/// * If the connection to the database could not be established.
+/// * If the raster could not be computed.
+pub async fn raster(
+    map_id: i32,
+    parameters: SoilRasterParameters,
+    app_data: &Data<AppDataInner>,
+) -> Result<SoilRasterDto, ServiceError> {
+    let resolution = parameters.resolution.unwrap_or(DEFAULT_RESOLUTION);
+    if resolution <= 0 {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The resolution must be positive".to_owned(),
+        ));
+    }
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, parameters.layer_id, &[LayerType::Soil], &mut conn).await?;
+    let map = Map::find_by_id(map_id, &mut conn).await?;
+    let samples = SoilSample::find_by_layer(parameters.layer_id, &mut conn).await?;
+    web::block(move || interpolate_raster(&map.geometry, &samples, resolution)).await?
+}
+
+/// Get the samples of the main soil layer of the map, empty if the map has none.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+pub async fn find_main_layer_samples(
+    map_id: i32,
+    conn: &mut AsyncPgConnection,
+) -> Result<Vec<SoilSampleDto>, ServiceError> {
+    let layers = Layer::find(
+        LayerSearchParameters {
+            map_id: Some(map_id),
+            type_: Some(LayerType::Soil),
+            is_alternative: Some(false),
+            is_deleted: None,
+        },
+        conn,
+    )
+    .await?;
+    match layers.first() {
+        Some(layer) => Ok(SoilSample::find_by_layer(layer.id, conn).await?),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Lower the scores of a heatmap where the soil doesn't meet the requirements of the plant.
+///
+/// The score is lowered by [`SUITABILITY_PENALTY_PER_STEP`] for each pH range or texture
+/// the soil is away from the requirements, see [`scale_heatmap`] for the layout of the heatmap.
+pub fn apply_soil_suitability(
+    heatmap: &mut [Vec<f32>],
+    origin: (i32, i32),
+    granularity: i32,
+    samples: &[SoilSampleDto],
+    requirements: &SoilRequirements,
+) {
+    if samples.is_empty() {
+        return;
+    }
+    scale_heatmap(heatmap, origin, granularity, |x, y| {
+        suitability(&interpolate(samples, x, y), requirements)
+    });
+}
+
+/// Check that the measurements of a sample are within their ranges.
+fn check_measurements(ph: Option<f32>, organic_matter: Option<f32>) -> Result<(), ServiceError> {
+    if ph.is_some_and(|ph| !(0.0..=14.0).contains(&ph)) {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The pH value must be between 0 and 14".to_owned(),
+        ));
+    }
+    if organic_matter.is_some_and(|organic_matter| !(0.0..=100.0).contains(&organic_matter)) {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The organic matter must be between 0 and 100 percent".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Interpolate the samples for each cell of a raster covering the geometry.
+#[allow(
+    clippy::cast_possible_truncation, // ok, because the raster is limited to `MAX_RASTER_CELLS` and map coordinates are i32
+    clippy::cast_sign_loss,           // ok, because the number of cells is never negative
+    clippy::cast_precision_loss       // ok, because the raster is limited to `MAX_RASTER_CELLS`
+)]
+fn interpolate_raster(
+    geometry: &Polygon<Point>,
+    samples: &[SoilSampleDto],
+    resolution: i32,
+) -> Result<SoilRasterDto, ServiceError> {
+    let Some(bounds) = Rectangle::enclosing(geometry) else {
+        return Ok(SoilRasterDto {
+            x_min: 0,
+            y_min: 0,
+            resolution,
+            cells: Vec::new(),
+        });
+    };
+    let (x_min, y_min) = (bounds.x_min.floor(), bounds.y_min.floor());
+    let size = f64::from(resolution);
+    let (num_cols, num_rows) = (
+        ((bounds.x_max - x_min) / size).ceil(),
+        ((bounds.y_max - y_min) / size).ceil(),
+    );
+    if num_cols * num_rows > MAX_RASTER_CELLS {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            format!("The resolution {resolution} results in too many cells for the map"),
+        ));
+    }
+
+    let cells = (0..num_rows as usize)
+        .map(|row| {
+            let y = (row as f64 + 0.5).mul_add(size, y_min);
+            (0..num_cols as usize)
+                .map(|col| {
+                    let x = (col as f64 + 0.5).mul_add(size, x_min);
+                    polygon_contains(geometry, x, y).then(|| interpolate(samples, x, y))
+                })
+                .collect()
+        })
+        .collect();
+    Ok(SoilRasterDto {
+        x_min: x_min as i32,
+        y_min: y_min as i32,
+        resolution,
+        cells,
+    })
+}
+
+/// Interpolate the soil at `x`,`y` using inverse distance weighting of the samples.
+///
+/// Numeric measurements are averaged, for categories the one with the highest total weight wins.
+fn interpolate(samples: &[SoilSampleDto], x: f64, y: f64) -> SoilCellDto {
+    let weighted = samples
+        .iter()
+        .map(|sample| {
+            let distance = (f64::from(sample.x) - x)
+                .hypot(f64::from(sample.y) - y)
+                .max(MIN_DISTANCE);
+            (sample, distance.powi(-2))
+        })
+        .collect::<Vec<_>>();
+
+    let ph = weighted_mean(
+        weighted
+            .iter()
+            .filter_map(|(sample, weight)| sample.ph.map(|ph| (ph, *weight))),
+    );
+    SoilCellDto {
+        ph,
+        soil_ph: ph.map(soil_ph),
+        soil_texture: weighted_mode(
+            weighted.iter().filter_map(|(sample, weight)| {
+                sample.soil_texture.map(|texture| (texture, *weight))
+            }),
+        ),
+        water_retention: weighted_mode(weighted.iter().filter_map(|(sample, weight)| {
+            sample.water_retention.map(|retention| (retention, *weight))
+        })),
+        organic_matter: weighted_mean(weighted.iter().filter_map(|(sample, weight)| {
+            sample
+                .organic_matter
+                .map(|organic_matter| (organic_matter, *weight))
+        })),
+    }
+}
+
+/// The weighted mean of the values, `None` if there are none.
+#[allow(clippy::cast_possible_truncation)] // ok, because the mean lies between the f32 values
+fn weighted_mean(values: impl Iterator<Item = (f32, f64)>) -> Option<f32> {
+    let (sum, total_weight) = values.fold((0.0, 0.0), |(sum, total_weight), (value, weight)| {
+        (f64::from(value).mul_add(weight, sum), total_weight + weight)
+    });
+    (total_weight > 0.0).then(|| (sum / total_weight) as f32)
+}
+
+/// The value with the highest total weight, `None` if there are none.
+fn weighted_mode<T: Copy + PartialEq>(values: impl Iterator<Item = (T, f64)>) -> Option<T> {
+    let mut totals: Vec<(T, f64)> = Vec::new();
+    for (value, weight) in values {
+        match totals.iter_mut().find(|(existing, _)| *existing == value) {
+            Some((_, total_weight)) => *total_weight += weight,
+            None => totals.push((value, weight)),
+        }
+    }
+    totals
+        .into_iter()
+        .max_by(|(_, first), (_, second)| first.total_cmp(second))
+        .map(|(value, _)| value)
+}
+
+/// The pH range of a pH value, following the USDA classes of soil reaction.
+fn soil_ph(ph: f32) -> SoilPh {
+    if ph < 5.1 {
+        SoilPh::VeryAcidic
+    } else if ph < 6.6 {
+        SoilPh::Acidic
+    } else if ph < 7.4 {
+        SoilPh::Neutral
+    } else if ph < 8.5 {
+        SoilPh::Alkaline
+    } else {
+        SoilPh::VeryAlkaline
+    }
+}
+
+/// How well the soil of the cell meets the requirements, from 0 (not at all) to 1 (completely).
+///
+/// Unknown soil properties or requirements are assumed to be met.
+fn suitability(cell: &SoilCellDto, requirements: &SoilRequirements) -> f32 {
+    let ph_rank = |ph: &SoilPh| match ph {
+        SoilPh::VeryAcidic => 0,
+        SoilPh::Acidic => 1,
+        SoilPh::Neutral => 2,
+        SoilPh::Alkaline => 3,
+        SoilPh::VeryAlkaline => 4,
+    };
+    let texture_rank = |texture: &SoilTexture| match texture {
+        SoilTexture::Sandy => 0,
+        SoilTexture::Loamy => 1,
+        SoilTexture::Clay => 2,
+        SoilTexture::HeavyClay => 3,
+    };
+    let factor = |actual: Option<i32>, tolerated: Vec<i32>| {
+        let steps = actual.and_then(|actual| {
+            tolerated
+                .into_iter()
+                .map(|tolerated| (actual - tolerated).abs())
+                .min()
+        });
+        #[allow(clippy::cast_precision_loss)] // ok, because there are at most four steps
+        steps.map_or(1.0, |steps| {
+            (steps as f32)
+                .mul_add(-SUITABILITY_PENALTY_PER_STEP, 1.0)
+                .max(0.0)
+        })
+    };
+
+    factor(
+        cell.soil_ph.as_ref().map(ph_rank),
+        requirements.soil_ph.iter().map(ph_rank).collect(),
+    ) * factor(
+        cell.soil_texture.as_ref().map(texture_rank),
+        requirements.soil_texture.iter().map(texture_rank).collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(x: i32, y: i32, ph: Option<f32>, soil_texture: Option<SoilTexture>) -> SoilSampleDto {
+        SoilSampleDto {
+            id: Uuid::new_v4(),
+            layer_id: 1,
+            x,
+            y,
+            ph,
+            soil_texture,
+            water_retention: None,
+            organic_matter: None,
+            sample_date: None,
+        }
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[test]
+    fn test_interpolate_weights_closer_samples_higher() {
+        let samples = [
+            sample(0, 0, Some(5.0), Some(SoilTexture::Sandy)),
+            sample(100, 0, Some(8.0), Some(SoilTexture::Clay)),
+            sample(50, 50, None, None),
+        ];
+
+        let at_sample = interpolate(&samples, 0.0, 0.0);
+        assert!((at_sample.ph.unwrap() - 5.0).abs() < 0.01);
+        assert_eq!(at_sample.soil_ph, Some(SoilPh::VeryAcidic));
+        assert_eq!(at_sample.soil_texture, Some(SoilTexture::Sandy));
+        assert_eq!(at_sample.organic_matter, None);
+
+        let between = interpolate(&samples, 50.0, 0.0);
+        assert!((between.ph.unwrap() - 6.5).abs() < 0.001);
+
+        let closer_to_clay = interpolate(&samples, 70.0, 0.0);
+        assert!(closer_to_clay.ph.unwrap() > 6.5);
+        assert_eq!(closer_to_clay.soil_texture, Some(SoilTexture::Clay));
+    }
+
+    #[allow(clippy::unwrap_used, clippy::indexing_slicing)]
+    #[test]
+    fn test_interpolate_raster_covers_only_the_geometry() {
+        let mut triangle = Polygon::new(None);
+        triangle.add_points([
+            Point::new(0.0, 0.0, None),
+            Point::new(400.0, 0.0, None),
+            Point::new(0.0, 400.0, None),
+            Point::new(0.0, 0.0, None),
+        ]);
+        let samples = [sample(0, 0, Some(7.0), None)];
+
+        let raster = interpolate_raster(&triangle, &samples, 100).unwrap();
+        assert_eq!((raster.x_min, raster.y_min), (0, 0));
+        assert_eq!(raster.cells.len(), 4);
+        assert!(raster.cells.iter().all(|row| row.len() == 4));
+        assert_eq!(
+            raster.cells[0][0].and_then(|cell| cell.soil_ph),
+            Some(SoilPh::Neutral)
+        );
+        assert!(raster.cells[3][3].is_none());
+
+        let mut large_triangle = Polygon::new(None);
+        large_triangle.add_points([
+            Point::new(0.0, 0.0, None),
+            Point::new(1000.0, 0.0, None),
+            Point::new(0.0, 1000.0, None),
+            Point::new(0.0, 0.0, None),
+        ]);
+        assert!(interpolate_raster(&large_triangle, &samples, 1).is_err());
+    }
+
+    #[test]
+    fn test_apply_soil_suitability_lowers_scores_of_unsuitable_soil() {
+        let samples = [sample(0, 0, Some(4.5), Some(SoilTexture::Loamy))];
+        let requirements = SoilRequirements {
+            soil_ph: vec![SoilPh::Neutral],
+            soil_texture: vec![SoilTexture::Loamy, SoilTexture::Clay],
+        };
+        let mut heatmap = vec![vec![0.8; 2]; 2];
+
+        apply_soil_suitability(&mut heatmap, (0, 0), 10, &samples, &requirements);
+        assert!(heatmap.iter().flatten().all(|score| *score == 0.0));
+
+        let mut acid_heatmap = vec![vec![0.8; 2]; 2];
+        let acid_tolerant = SoilRequirements {
+            soil_ph: vec![SoilPh::Acidic],
+            soil_texture: Vec::new(),
+        };
+        apply_soil_suitability(&mut acid_heatmap, (0, 0), 10, &samples, &acid_tolerant);
+        assert!(acid_heatmap
+            .iter()
+            .flatten()
+            .all(|score| (score - 0.4).abs() < f32::EPSILON));
+
+        let mut unsampled_heatmap = vec![vec![0.8; 2]; 2];
+        apply_soil_suitability(&mut unsampled_heatmap, (0, 0), 10, &[], &requirements);
+        assert!(unsampled_heatmap
+            .iter()
+            .flatten()
+            .all(|score| (score - 0.8).abs() < f32::EPSILON));
+    }
+}
@@ -0,0 +1,197 @@
+//! Service layer for the spacing of plantings.
+
+use actix_http::StatusCode;
+use actix_web::web::Data;
+use chrono::NaiveDate;
+use diesel_async::AsyncPgConnection;
+use uuid::Uuid;
+
+use crate::config::data::AppDataInner;
+use crate::error::ServiceError;
+use crate::model::dto::{
+    SpacingCheckParameters, SpacingMode, SpacingWarningDto, SpacingWarningKind,
+};
+use crate::model::entity::plantings::Planting;
+use crate::model::entity::{Map, Plants};
+use crate::model::r#enum::layer_type::LayerType;
+use crate::model::r#enum::plant_spread::PlantSpread;
+
+use super::layer::check_layer;
+use super::util::polygon_contains;
+
+/// The mature diameter in cm of plants with a narrow spread.
+const NARROW_DIAMETER: f64 = 15.0;
+/// The mature diameter in cm of plants with a medium spread.
+const MEDIUM_DIAMETER: f64 = 60.0;
+/// The mature diameter in cm of plants with a wide spread.
+const WIDE_DIAMETER: f64 = 150.0;
+
+/// A new or moved planting whose spacing is checked.
+pub struct Candidate {
+    /// The plant layer the planting is placed on.
+    pub layer_id: i32,
+    /// The plant that is planted.
+    pub plant_id: i32,
+    /// The x coordinate of the position on the map.
+    pub x: i32,
+    /// The y coordinate of the position on the map.
+    pub y: i32,
+    /// The scaled width of the plant on the map.
+    pub width: f64,
+    /// The scaled height of the plant on the map.
+    pub height: f64,
+    /// Neighbours existing at this date are checked.
+    pub date: NaiveDate,
+    /// The id of the planting if an existing planting is moved.
+    pub planting_id: Option<Uuid>,
+}
+
+impl From<&SpacingCheckParameters> for Candidate {
+    fn from(parameters: &SpacingCheckParameters) -> Self {
+        Self {
+            layer_id: parameters.layer_id,
+            plant_id: parameters.plant_id,
+            x: parameters.x,
+            y: parameters.y,
+            width: f64::from(parameters.width) * f64::from(parameters.scale_x.unwrap_or(1.0)),
+            height: f64::from(parameters.height) * f64::from(parameters.scale_y.unwrap_or(1.0)),
+            date: parameters.date,
+            planting_id: parameters.planting_id,
+        }
+    }
+}
+
+/// Check the spacing of a new or moved planting.
+///
+/// # Errors
+/// * If the map or the plant does not exist.
+/// * If the layer is not a plant layer of the map.
+/// * If the mode is strict and there are any warnings.
+/// * If the connection to the database could not be established.
+pub async fn check(
+    map_id: i32,
+    parameters: SpacingCheckParameters,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<Vec<SpacingWarningDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    let warnings = warnings(map_id, &Candidate::from(&parameters), user_id, &mut conn).await?;
+    enforce(parameters.mode.unwrap_or_default(), warnings)
+}
+
+/// Find the spacing problems of a new or moved planting.
+///
+/// The planting is compared to the plantings on the same layer existing at `date`.
+/// The mature canopy of a plant is a circle with the diameter given by its spread.
+/// Plants without a spread use the size they are drawn with.
+///
+/// # Errors
+/// * If the map or the plant does not exist.
+/// * If the layer is not a plant layer of the map.
+/// * If the connection to the database could not be established.
+pub async fn warnings(
+    map_id: i32,
+    candidate: &Candidate,
+    user_id: Uuid,
+    conn: &mut AsyncPgConnection,
+) -> Result<Vec<SpacingWarningDto>, ServiceError> {
+    let map = Map::find_by_id(map_id, conn).await?;
+    check_layer(map_id, candidate.layer_id, &[LayerType::Plants], conn).await?;
+    let plant = Plants::find_by_id(candidate.plant_id, user_id, conn).await?;
+    let neighbours = Planting::find_by_layer_with_plants(
+        candidate.layer_id,
+        candidate.date,
+        candidate.date,
+        conn,
+    )
+    .await?;
+
+    let (x, y) = (f64::from(candidate.x), f64::from(candidate.y));
+    let mut warnings = Vec::new();
+    if !polygon_contains(&map.geometry, x, y) {
+        warnings.push(SpacingWarningDto {
+            kind: SpacingWarningKind::OutsideMap,
+            planting_id: None,
+            plant_id: None,
+            unique_name: None,
+            distance: None,
+            required_distance: None,
+            message: format!("The planting at {x}, {y} is outside of the map."),
+        });
+    }
+
+    let radius = mature_diameter(plant.spread, candidate.width.max(candidate.height)) / 2.0;
+    for (planting, neighbour) in neighbours {
+        let removed = planting
+            .remove_date
+            .iter()
+            .any(|date| *date <= candidate.date);
+        if Some(planting.id) == candidate.planting_id || removed {
+            continue;
+        }
+
+        let drawn_size = (f64::from(planting.width) * f64::from(planting.scale_x))
+            .max(f64::from(planting.height) * f64::from(planting.scale_y));
+        let required_distance = radius + mature_diameter(neighbour.spread, drawn_size) / 2.0;
+        let distance = (f64::from(planting.x) - x).hypot(f64::from(planting.y) - y);
+
+        let (kind, message) = if distance < required_distance / 2.0 {
+            (
+                SpacingWarningKind::Crowding,
+                format!(
+                    "{} is only {distance:.0} cm away, the plants should be {required_distance:.0} cm apart when mature.",
+                    neighbour.unique_name
+                ),
+            )
+        } else if distance < required_distance {
+            (
+                SpacingWarningKind::CanopyOverlap,
+                format!(
+                    "The mature canopy overlaps with {} {distance:.0} cm away, the plants should be {required_distance:.0} cm apart.",
+                    neighbour.unique_name
+                ),
+            )
+        } else {
+            continue;
+        };
+        warnings.push(SpacingWarningDto {
+            kind,
+            planting_id: Some(planting.id),
+            plant_id: Some(neighbour.id),
+            unique_name: Some(neighbour.unique_name),
+            distance: Some(distance),
+            required_distance: Some(required_distance),
+            message,
+        });
+    }
+
+    Ok(warnings)
+}
+
+/// Reject the planting in strict mode if there are any warnings.
+///
+/// # Errors
+/// * If the mode is strict and there are any warnings.
+pub fn enforce(
+    mode: SpacingMode,
+    warnings: Vec<SpacingWarningDto>,
+) -> Result<Vec<SpacingWarningDto>, ServiceError> {
+    if mode == SpacingMode::Strict && !warnings.is_empty() {
+        let messages = warnings
+            .iter()
+            .map(|warning| warning.message.as_str())
+            .collect::<Vec<_>>();
+        return Err(ServiceError::new(StatusCode::CONFLICT, messages.join(" ")));
+    }
+    Ok(warnings)
+}
+
+/// The mature diameter of a plant, `fallback` if its spread is unknown.
+const fn mature_diameter(spread: Option<PlantSpread>, fallback: f64) -> f64 {
+    match spread {
+        Some(PlantSpread::Narrow) => NARROW_DIAMETER,
+        Some(PlantSpread::Medium) => MEDIUM_DIAMETER,
+        Some(PlantSpread::Wide) => WIDE_DIAMETER,
+        Some(PlantSpread::Na) | None => fallback,
+    }
+}
@@ -0,0 +1,252 @@
+//! Service layer for the todo layer.
+
+use actix_http::StatusCode;
+use actix_web::web::Data;
+use chrono::{Days, NaiveDate, Utc};
+use diesel_async::AsyncPgConnection;
+use uuid::Uuid;
+
+use crate::config::data::AppDataInner;
+use crate::error::ServiceError;
+use crate::model::dto::todos::{
+    AssignedTodoDto, NewTodoDto, TodoDto, TodoSearchParameters, UpdateTodoDto,
+};
+use crate::model::entity::todos::{Todo, UpdateTodo};
+use crate::model::r#enum::layer_type::LayerType;
+use crate::model::r#enum::todo_status::TodoStatus;
+use crate::service::layer::{check_layer, check_planting};
+use crate::service::map::is_collaborator;
+
+/// The maximum number of characters of the title of a todo.
+const MAX_TITLE_LENGTH: usize = 255;
+
+/// Where a todo is pinned to and who should do it.
+struct TodoTarget {
+    /// The x coordinate of the position the todo is pinned to.
+    x: Option<i32>,
+    /// The y coordinate of the position the todo is pinned to.
+    y: Option<i32>,
+    /// The planting the todo is pinned to.
+    planting_id: Option<Uuid>,
+    /// The id of the collaborator who should do the todo.
+    assignee_id: Option<Uuid>,
+}
+
+/// Get the todos of a todo layer on the map.
+///
+/// # Errors
+/// * If the layer is not a todo layer of the map.
+/// * If the connection to the database could not be established.
+pub async fn find(
+    map_id: i32,
+    search_parameters: TodoSearchParameters,
+    app_data: &Data<AppDataInner>,
+) -> Result<Vec<TodoDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    check_layer(
+        map_id,
+        search_parameters.layer_id,
+        &[LayerType::Todo],
+        &mut conn,
+    )
+    .await?;
+    let result = Todo::find_by_layer(
+        search_parameters.layer_id,
+        search_parameters.status,
+        &mut conn,
+    )
+    .await?;
+    Ok(result)
+}
+
+/// Get the open todos assigned to the user on all maps.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+pub async fn find_assigned(
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<Vec<AssignedTodoDto>, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    let result = Todo::find_open_by_assignee(user_id, &mut conn).await?;
+    Ok(result)
+}
+
+/// Create a new todo on a todo layer of the map.
+///
+/// # Errors
+/// * If the title is blank or too long, or the recurrence is not positive.
+/// * If the todo is not pinned to either a position or a planting of the map.
+/// * If the assignee is not a collaborator of the map.
+/// * If the layer is not a todo layer of the map.
+/// * If the connection to the database could not be established.
+pub async fn create(
+    map_id: i32,
+    new_todo: NewTodoDto,
+    user_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<TodoDto, ServiceError> {
+    check_content(&new_todo.title, new_todo.recurrence_days)?;
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, new_todo.layer_id, &[LayerType::Todo], &mut conn).await?;
+    check_target(
+        map_id,
+        &TodoTarget {
+            x: new_todo.x,
+            y: new_todo.y,
+            planting_id: new_todo.planting_id,
+            assignee_id: new_todo.assignee_id,
+        },
+        &mut conn,
+    )
+    .await?;
+    let result = Todo::create((new_todo, user_id).into(), &mut conn).await?;
+    Ok(result)
+}
+
+/// Replace the content of a todo on the map.
+///
+/// A recurring todo that is set to done stays open and is due again after its recurrence.
+///
+/// # Errors
+/// * If the title is blank or too long, or the recurrence is not positive.
+/// * If the todo is not pinned to either a position or a planting of the map.
+/// * If the assignee is not a collaborator of the map.
+/// * If the todo does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn update(
+    map_id: i32,
+    todo_id: Uuid,
+    update_todo: UpdateTodoDto,
+    app_data: &Data<AppDataInner>,
+) -> Result<TodoDto, ServiceError> {
+    check_content(&update_todo.title, update_todo.recurrence_days)?;
+    let mut conn = app_data.pool.get().await?;
+    check_target(
+        map_id,
+        &TodoTarget {
+            x: update_todo.x,
+            y: update_todo.y,
+            planting_id: update_todo.planting_id,
+            assignee_id: update_todo.assignee_id,
+        },
+        &mut conn,
+    )
+    .await?;
+    let todo = reschedule(update_todo.into(), Utc::now().date_naive());
+    let result = Todo::update(todo_id, map_id, todo, &mut conn).await?;
+    Ok(result)
+}
+
+/// Delete a todo on the map.
+///
+/// # Errors
+/// * If the todo does not exist on the map.
+/// * If the connection to the database could not be established.
+pub async fn delete_by_id(
+    map_id: i32,
+    todo_id: Uuid,
+    app_data: &Data<AppDataInner>,
+) -> Result<(), ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    if Todo::delete_by_id(todo_id, map_id, &mut conn).await? == 0 {
+        return Err(ServiceError::new(
+            StatusCode::NOT_FOUND,
+            StatusCode::NOT_FOUND.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Check the title and recurrence of a todo.
+fn check_content(title: &str, recurrence_days: Option<i32>) -> Result<(), ServiceError> {
+    if title.trim().is_empty() || title.chars().count() > MAX_TITLE_LENGTH {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            format!("The title must have between 1 and {MAX_TITLE_LENGTH} characters"),
+        ));
+    }
+    if recurrence_days.is_some_and(|days| days <= 0) {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The recurrence must be a positive number of days".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Check that the todo is pinned to either a position or a planting of the map
+/// and that it is assigned to a collaborator of the map.
+async fn check_target(
+    map_id: i32,
+    target: &TodoTarget,
+    conn: &mut AsyncPgConnection,
+) -> Result<(), ServiceError> {
+    match (target.x, target.y, target.planting_id) {
+        (Some(_), Some(_), None) => {}
+        (None, None, Some(planting_id)) => check_planting(map_id, planting_id, conn).await?,
+        _ => {
+            return Err(ServiceError::new(
+                StatusCode::BAD_REQUEST,
+                "A todo must be pinned to either a position or a planting".to_owned(),
+            ))
+        }
+    }
+    if let Some(assignee_id) = target.assignee_id {
+        if !is_collaborator(map_id, assignee_id, conn).await? {
+            return Err(ServiceError::new(
+                StatusCode::BAD_REQUEST,
+                format!("User {assignee_id} is not a collaborator of map {map_id}"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Reopen a recurring todo that was done, due after its recurrence counted from `today`.
+fn reschedule(mut todo: UpdateTodo, today: NaiveDate) -> UpdateTodo {
+    if todo.status == TodoStatus::Done {
+        let next_due_date = todo
+            .recurrence_days
+            .and_then(|days| u64::try_from(days).ok())
+            .and_then(|days| today.checked_add_days(Days::new(days)));
+        if let Some(next_due_date) = next_due_date {
+            todo.status = TodoStatus::Open;
+            todo.due_date = Some(next_due_date);
+        }
+    }
+    todo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn done_todo(recurrence_days: Option<i32>) -> UpdateTodo {
+        UpdateTodo {
+            x: Some(0),
+            y: Some(0),
+            planting_id: None,
+            title: "Water the tomatoes".to_owned(),
+            description: None,
+            due_date: NaiveDate::from_ymd_opt(2023, 7, 1),
+            assignee_id: None,
+            status: TodoStatus::Done,
+            recurrence_days,
+        }
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[test]
+    fn test_reschedule_reopens_recurring_todos() {
+        let today = NaiveDate::from_ymd_opt(2023, 7, 2).unwrap();
+
+        let recurring = reschedule(done_todo(Some(3)), today);
+        assert_eq!(recurring.status, TodoStatus::Open);
+        assert_eq!(recurring.due_date, NaiveDate::from_ymd_opt(2023, 7, 5));
+
+        let once = reschedule(done_todo(None), today);
+        assert_eq!(once.status, TodoStatus::Done);
+        assert_eq!(once.due_date, NaiveDate::from_ymd_opt(2023, 7, 1));
+    }
+}
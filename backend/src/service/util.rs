@@ -1,8 +1,35 @@
 //! Utility traits and implementations.
 
+use std::collections::HashMap;
 use std::ops::Div;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
-use chrono::Datelike;
+use actix_web::web;
+use chrono::{Datelike, NaiveDate};
+use postgis_diesel::types::{Point, Polygon};
+
+use crate::error::ServiceError;
+use crate::model::entity::plantings::Planting;
+use crate::model::r#enum::plant_height::PlantHeight;
+
+/// The maximum number of rasters kept by a [`RasterCache`].
+const MAX_CACHED_RASTERS: usize = 64;
+
+/// The number of days in each month.
+const DAYS_PER_MONTH: [i32; 12] = [
+    31, // January
+    28, // February
+    31, // March
+    30, // April
+    31, // May
+    30, // June
+    31, // July
+    31, // August
+    30, // September
+    31, // October
+    30, // November
+    31, // December
+];
 
 /// Trait for getting the half month bucket of a `NaiveDate`.
 pub trait HalfMonthBucket {
@@ -23,22 +50,6 @@ where
         clippy::cast_sign_loss
     )]
     fn half_month_bucket(&self) -> i32 {
-        /// The number of days in each month.
-        const DAYS_PER_MONTH: [i32; 12] = [
-            31, // January
-            28, // February
-            31, // March
-            30, // April
-            31, // May
-            30, // June
-            31, // July
-            31, // August
-            30, // September
-            31, // October
-            30, // November
-            31, // December
-        ];
-
         let month = self.month0() as i32;
         let day = self.day() as i32;
 
@@ -53,10 +64,259 @@ where
     }
 }
 
+/// Returns the first day of the half month `bucket` (see [`HalfMonthBucket`]) in the given `year`.
+///
+/// Returns `None` if the bucket is not between 0 and 23 or the year is out of range.
+#[must_use]
+pub fn half_month_bucket_start(year: i32, bucket: i32) -> Option<NaiveDate> {
+    let month = usize::try_from(bucket.div_euclid(2)).ok()?;
+    let days_in_month = DAYS_PER_MONTH.get(month).copied()?;
+    let day = if bucket % 2 == 0 {
+        1
+    } else {
+        days_in_month.div(2) + 1
+    };
+
+    NaiveDate::from_ymd_opt(
+        year,
+        u32::try_from(month).ok()? + 1,
+        u32::try_from(day).ok()?,
+    )
+}
+
+/// An axis aligned rectangle on a map.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rectangle {
+    /// The lowest x coordinate.
+    pub x_min: f64,
+    /// The lowest y coordinate.
+    pub y_min: f64,
+    /// The highest x coordinate.
+    pub x_max: f64,
+    /// The highest y coordinate.
+    pub y_max: f64,
+}
+
+impl Rectangle {
+    /// The area covered by an element of the given size centered at `x`,`y`, like plantings are drawn.
+    #[must_use]
+    pub fn around(x: i32, y: i32, width: f64, height: f64) -> Self {
+        let (half_width, half_height) = (width.abs() / 2.0, height.abs() / 2.0);
+        Self {
+            x_min: f64::from(x) - half_width,
+            y_min: f64::from(y) - half_height,
+            x_max: f64::from(x) + half_width,
+            y_max: f64::from(y) + half_height,
+        }
+    }
+
+    /// The smallest rectangle containing the outer boundary of the polygon.
+    ///
+    /// Returns `None` if the polygon has no points.
+    #[must_use]
+    pub fn enclosing(polygon: &Polygon<Point>) -> Option<Self> {
+        let ring = polygon.rings.first().filter(|ring| !ring.is_empty())?;
+        Some(ring.iter().fold(
+            Self {
+                x_min: f64::INFINITY,
+                y_min: f64::INFINITY,
+                x_max: f64::NEG_INFINITY,
+                y_max: f64::NEG_INFINITY,
+            },
+            |rectangle, point| Self {
+                x_min: rectangle.x_min.min(point.x),
+                y_min: rectangle.y_min.min(point.y),
+                x_max: rectangle.x_max.max(point.x),
+                y_max: rectangle.y_max.max(point.y),
+            },
+        ))
+    }
+
+    /// Whether both rectangles share an area. Touching edges are not overlapping.
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.x_min < other.x_max
+            && other.x_min < self.x_max
+            && self.y_min < other.y_max
+            && other.y_min < self.y_max
+    }
+}
+
+/// The area covered by a planting.
+#[must_use]
+pub fn planting_area(planting: &Planting) -> Rectangle {
+    Rectangle::around(
+        planting.x,
+        planting.y,
+        f64::from(planting.width) * f64::from(planting.scale_x),
+        f64::from(planting.height) * f64::from(planting.scale_y),
+    )
+}
+
+/// A representative height in cm of fully grown plants of the height class.
+#[must_use]
+pub const fn mature_height(height: PlantHeight) -> Option<f64> {
+    match height {
+        PlantHeight::Low => Some(50.0),
+        PlantHeight::Medium => Some(200.0),
+        PlantHeight::High => Some(800.0),
+        PlantHeight::Na => None,
+    }
+}
+
+/// Whether a raster of the geometry with cells of the given size has more than `max_cells` cells.
+#[must_use]
+pub fn exceeds_max_cells(geometry: &Polygon<Point>, resolution: i32, max_cells: f64) -> bool {
+    Rectangle::enclosing(geometry).is_some_and(|bounds| {
+        let size = f64::from(resolution);
+        ((bounds.x_max - bounds.x_min.floor()) / size).ceil()
+            * ((bounds.y_max - bounds.y_min.floor()) / size).ceil()
+            > max_cells
+    })
+}
+
+/// The decrease of the suitability of a heatmap cell per step its conditions are worse than a plant needs.
+pub const SUITABILITY_PENALTY_PER_STEP: f32 = 0.5;
+
+/// Multiply each score of a heatmap with the factor for the center of its cell.
+///
+/// The cell at `row`,`col` of the heatmap covers the area starting at
+/// `x_min + col * granularity`,`y_min + row * granularity`.
+pub fn scale_heatmap(
+    heatmap: &mut [Vec<f32>],
+    (x_min, y_min): (i32, i32),
+    granularity: i32,
+    mut factor: impl FnMut(f64, f64) -> f32,
+) {
+    let cell_center = |start: i32, index: usize| {
+        #[allow(clippy::cast_precision_loss)]
+        // ok, because heatmaps are far smaller than 2^52 cells
+        let index = index as f64;
+        (index + 0.5).mul_add(f64::from(granularity), f64::from(start))
+    };
+    for (row, scores) in heatmap.iter_mut().enumerate() {
+        let y = cell_center(y_min, row);
+        for (col, score) in scores.iter_mut().enumerate() {
+            *score *= factor(cell_center(x_min, col), y);
+        }
+    }
+}
+
+/// Find the cell of a raster covering the point `x`,`y`.
+///
+/// The raster starts at `x_min`,`y_min` and consists of square cells with sides of `resolution`.
+#[allow(
+    clippy::cast_possible_truncation, // ok, because the number of cells of rasters is limited
+    clippy::cast_sign_loss            // ok, because negative indices are filtered
+)]
+#[must_use]
+pub fn raster_cell<T: Copy>(
+    cells: &[Vec<Option<T>>],
+    (x_min, y_min): (i32, i32),
+    resolution: i32,
+    (x, y): (f64, f64),
+) -> Option<T> {
+    let index = |value: f64, start: i32| {
+        let index = ((value - f64::from(start)) / f64::from(resolution)).floor();
+        (index >= 0.0).then_some(index as usize)
+    };
+    cells
+        .get(index(y, y_min)?)?
+        .get(index(x, x_min)?)
+        .copied()
+        .flatten()
+}
+
+/// The inputs and the raster computed from them by map and day.
+type Rasters<I, R> = HashMap<(i32, NaiveDate), (I, Arc<R>)>;
+
+/// Rasters of maps on a day, reused as long as the inputs they were computed from don't change.
+#[derive(Debug)]
+pub struct RasterCache<I, R> {
+    /// The cached rasters.
+    rasters: Mutex<Rasters<I, R>>,
+}
+
+impl<I, R> Default for RasterCache<I, R> {
+    fn default() -> Self {
+        Self {
+            rasters: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<I, R> RasterCache<I, R>
+where
+    I: PartialEq + Send + 'static,
+    R: Send + Sync + 'static,
+{
+    /// Get the raster of the map on the day or compute it on a blocking thread,
+    /// so the workers keep serving other requests meanwhile.
+    ///
+    /// # Errors
+    /// * If the raster could not be computed.
+    pub async fn get_or_compute(
+        &self,
+        map_id: i32,
+        date: NaiveDate,
+        inputs: I,
+        compute: impl FnOnce(&I) -> R + Send + 'static,
+    ) -> Result<Arc<R>, ServiceError> {
+        let key = (map_id, date);
+        let cached = self
+            .lock()
+            .get(&key)
+            .filter(|(cached_inputs, _)| *cached_inputs == inputs)
+            .map(|(_, raster)| Arc::clone(raster));
+        if let Some(raster) = cached {
+            return Ok(raster);
+        }
+
+        let (inputs, raster) = web::block(move || {
+            let raster = compute(&inputs);
+            (inputs, Arc::new(raster))
+        })
+        .await?;
+
+        let mut rasters = self.lock();
+        if rasters.len() >= MAX_CACHED_RASTERS && !rasters.contains_key(&key) {
+            rasters.clear();
+        }
+        rasters.insert(key, (inputs, Arc::clone(&raster)));
+        drop(rasters);
+        Ok(raster)
+    }
+
+    /// Lock the rasters, a panic while holding the lock can't leave them inconsistent.
+    fn lock(&self) -> MutexGuard<'_, Rasters<I, R>> {
+        self.rasters.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Whether the point `x`,`y` lies inside the polygon, but not inside one of its holes.
+///
+/// The first ring of the polygon is its outer boundary, all further rings are holes.
+#[must_use]
+pub fn polygon_contains(polygon: &Polygon<Point>, x: f64, y: f64) -> bool {
+    let ring_contains = |ring: &Vec<Point>| {
+        let mut inside = false;
+        for (start, end) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+            if (start.y > y) != (end.y > y)
+                && x < (end.x - start.x) * (y - start.y) / (end.y - start.y) + start.x
+            {
+                inside = !inside;
+            }
+        }
+        inside
+    };
+
+    let mut rings = polygon.rings.iter();
+    rings.next().into_iter().any(ring_contains) && !rings.any(ring_contains)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::NaiveDate;
 
     #[allow(clippy::shadow_unrelated, clippy::unwrap_used)]
     #[test]
@@ -79,4 +339,105 @@ mod tests {
         let date = NaiveDate::from_ymd_opt(2020, 12, 31).unwrap();
         assert_eq!(date.half_month_bucket(), 23);
     }
+
+    #[allow(clippy::unwrap_used)]
+    #[test]
+    fn test_half_month_bucket_start() {
+        for bucket in 0..24 {
+            let start = half_month_bucket_start(2023, bucket).unwrap();
+            assert_eq!(start.half_month_bucket(), bucket);
+            assert_eq!(
+                start.pred_opt().unwrap().half_month_bucket(),
+                (bucket + 23) % 24
+            );
+        }
+        assert_eq!(
+            half_month_bucket_start(2023, 3),
+            NaiveDate::from_ymd_opt(2023, 2, 15)
+        );
+        assert_eq!(half_month_bucket_start(2023, 24), None);
+        assert_eq!(half_month_bucket_start(2023, -1), None);
+    }
+
+    #[test]
+    fn test_rectangle_overlaps() {
+        let planting = Rectangle::around(0, 0, 100.0, 50.0);
+        assert!(planting.overlaps(&Rectangle::around(90, 0, 100.0, 50.0)));
+        assert!(!planting.overlaps(&Rectangle::around(100, 0, 100.0, 50.0)));
+        assert!(!planting.overlaps(&Rectangle::around(0, 60, 100.0, 50.0)));
+    }
+
+    #[test]
+    fn test_polygon_contains() {
+        let mut polygon = Polygon::new(None);
+        polygon.add_points([
+            Point::new(0.0, 0.0, None),
+            Point::new(100.0, 0.0, None),
+            Point::new(100.0, 100.0, None),
+            Point::new(0.0, 100.0, None),
+            Point::new(0.0, 0.0, None),
+        ]);
+        polygon.add_ring().add_points([
+            Point::new(40.0, 40.0, None),
+            Point::new(60.0, 40.0, None),
+            Point::new(60.0, 60.0, None),
+            Point::new(40.0, 60.0, None),
+            Point::new(40.0, 40.0, None),
+        ]);
+
+        assert!(polygon_contains(&polygon, 10.0, 10.0));
+        assert!(polygon_contains(&polygon, 99.0, 50.0));
+        assert!(!polygon_contains(&polygon, 50.0, 50.0));
+        assert!(!polygon_contains(&polygon, 150.0, 50.0));
+        assert!(!polygon_contains(&polygon, -1.0, 50.0));
+        assert!(!polygon_contains(&Polygon::new(None), 10.0, 10.0));
+    }
+
+    #[test]
+    fn test_rectangle_enclosing() {
+        let mut polygon = Polygon::new(None);
+        polygon.add_points([
+            Point::new(-20.0, 10.0, None),
+            Point::new(100.0, 0.0, None),
+            Point::new(50.0, 300.0, None),
+            Point::new(-20.0, 10.0, None),
+        ]);
+
+        assert_eq!(
+            Rectangle::enclosing(&polygon),
+            Some(Rectangle {
+                x_min: -20.0,
+                y_min: 0.0,
+                x_max: 100.0,
+                y_max: 300.0,
+            })
+        );
+        assert_eq!(Rectangle::enclosing(&Polygon::new(None)), None);
+    }
+
+    #[test]
+    fn test_raster_cell_finds_covering_cell() {
+        let cells = vec![vec![Some(1), None], vec![Some(3), Some(4)]];
+        assert_eq!(raster_cell(&cells, (-10, 0), 10, (-10.0, 0.0)), Some(1));
+        assert_eq!(raster_cell(&cells, (-10, 0), 10, (5.0, 19.9)), Some(4));
+        assert_eq!(raster_cell(&cells, (-10, 0), 10, (5.0, 5.0)), None);
+        assert_eq!(raster_cell(&cells, (-10, 0), 10, (-10.5, 5.0)), None);
+        assert_eq!(raster_cell(&cells, (-10, 0), 10, (0.0, 20.0)), None);
+    }
+
+    #[actix_rt::test]
+    async fn test_raster_cache_recomputes_changed_inputs() {
+        let cache = RasterCache::<i32, i32>::default();
+        let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap_or_default();
+
+        let first = cache.get_or_compute(1, date, 1, |_| 10).await;
+        let cached = cache.get_or_compute(1, date, 1, |_| 20).await;
+        let changed = cache.get_or_compute(1, date, 2, |_| 30).await;
+        let other_map = cache.get_or_compute(2, date, 2, |_| 40).await;
+
+        assert_eq!(first.ok().as_deref(), Some(&10));
+        assert_eq!(cached.ok().as_deref(), Some(&10));
+        assert_eq!(changed.ok().as_deref(), Some(&30));
+        assert_eq!(other_map.ok().as_deref(), Some(&40));
+    }
 }
@@ -0,0 +1,496 @@
+//! Service layer for the winds layer.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use actix_http::StatusCode;
+use actix_web::web::{self, Data};
+use chrono::{NaiveDate, Utc};
+use diesel_async::AsyncPgConnection;
+use postgis_diesel::types::{Point, Polygon};
+
+use crate::config::data::AppDataInner;
+use crate::error::ServiceError;
+use crate::model::dto::winds::{
+    UpdateWindRoseDto, WindCellDto, WindRasterDto, WindRasterParameters, WindRoseDto,
+    WindRoseEntryDto,
+};
+use crate::model::dto::LayerSearchParameters;
+use crate::model::entity::plantings::Planting;
+use crate::model::entity::wind_rose_entries::WindRoseEntry;
+use crate::model::entity::{Layer, Map};
+use crate::model::r#enum::layer_type::LayerType;
+
+use super::layer::check_layer;
+use super::util::{
+    exceeds_max_cells, mature_height, planting_area, polygon_contains, raster_cell, scale_heatmap,
+    RasterCache, Rectangle, SUITABILITY_PENALTY_PER_STEP,
+};
+
+/// The default size of a cell of the wind raster in cm.
+pub const DEFAULT_RESOLUTION: i32 = 100;
+
+/// The maximum number of cells of a wind raster, so responses stay reasonably small.
+const MAX_RASTER_CELLS: f64 = 250_000.0;
+
+/// The minimum height in cm of plantings acting as windbreaks.
+const MIN_WINDBREAK_HEIGHT: f64 = 150.0;
+
+/// How far behind a windbreak the wind is reduced, as multiple of the height of the windbreak.
+const SHELTER_HEIGHTS: f64 = 10.0;
+
+/// The reduction of the wind speed right behind a windbreak.
+/// Plants are porous windbreaks, so they never stop the wind completely.
+const MAX_SHELTER: f64 = 0.6;
+
+/// The wind rasters of maps used for scoring plant locations.
+pub type WindRasterCache = RasterCache<WindRasterInputs, WindRasterDto>;
+
+/// Everything the wind raster of a map on a day is computed from.
+#[derive(Debug, PartialEq)]
+pub struct WindRasterInputs {
+    /// The geometry of the map.
+    geometry: Polygon<Point>,
+    /// The wind rose of the main winds layer.
+    rose: Vec<WindRoseEntryDto>,
+    /// The plantings slowing down the wind.
+    windbreaks: Vec<Windbreak>,
+    /// The size of a cell in cm.
+    resolution: i32,
+}
+
+/// A planting slowing down the wind behind it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Windbreak {
+    /// The area covered by the planting.
+    area: Rectangle,
+    /// The height of the planting in cm.
+    height: f64,
+}
+
+/// Get the wind rose of a winds layer on the map.
+///
+/// # Errors
+/// * If the layer is not a winds layer of the map.
+/// * If the connection to the database could not be established.
+pub async fn find_rose(
+    map_id: i32,
+    layer_id: i32,
+    app_data: &Data<AppDataInner>,
+) -> Result<WindRoseDto, ServiceError> {
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, layer_id, &[LayerType::Winds], &mut conn).await?;
+    let entries = WindRoseEntry::find_by_layer(layer_id, &mut conn).await?;
+    Ok(WindRoseDto { layer_id, entries })
+}
+
+/// Replace the wind rose of a winds layer on the map.
+///
+/// # Errors
+/// * If a direction is out of range or occurs twice.
+/// * If a frequency or speed is out of range or the frequencies add up to more than 100 percent.
+/// * If the layer is not a winds layer of the map.
+/// * If the connection to the database could not be established.
+pub async fn replace_rose(
+    map_id: i32,
+    update_rose: UpdateWindRoseDto,
+    app_data: &Data<AppDataInner>,
+) -> Result<WindRoseDto, ServiceError> {
+    check_rose(&update_rose.entries)?;
+    let layer_id = update_rose.layer_id;
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, layer_id, &[LayerType::Winds], &mut conn).await?;
+    let entries = WindRoseEntry::replace(
+        layer_id,
+        update_rose
+            .entries
+            .into_iter()
+            .map(|entry| (layer_id, entry).into())
+            .collect(),
+        &mut conn,
+    )
+    .await?;
+    Ok(WindRoseDto { layer_id, entries })
+}
+
+/// Compute the wind exposure across the geometry of the map.
+///
+/// Plantings growing taller than [`MIN_WINDBREAK_HEIGHT`] on the given day act as windbreaks.
+///
+/// # Errors
+/// * If the resolution is not positive or results in too many cells.
+/// * If the layer is not a winds layer of the map.
+/// * If the connection to the database could not be established.
+/// * If the raster could not be computed.
+pub async fn raster(
+    map_id: i32,
+    parameters: WindRasterParameters,
+    app_data: &Data<AppDataInner>,
+) -> Result<WindRasterDto, ServiceError> {
+    let resolution = parameters.resolution.unwrap_or(DEFAULT_RESOLUTION);
+    if resolution <= 0 {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The resolution must be positive".to_owned(),
+        ));
+    }
+    let date = parameters.date.unwrap_or_else(|| Utc::now().date_naive());
+    let mut conn = app_data.pool.get().await?;
+    check_layer(map_id, parameters.layer_id, &[LayerType::Winds], &mut conn).await?;
+    let map = Map::find_by_id(map_id, &mut conn).await?;
+    if exceeds_max_cells(&map.geometry, resolution, MAX_RASTER_CELLS) {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            format!("The resolution {resolution} results in too many cells for the map"),
+        ));
+    }
+
+    let rose = WindRoseEntry::find_by_layer(parameters.layer_id, &mut conn).await?;
+    let windbreaks = find_windbreaks(map_id, date, &mut conn).await?;
+    let raster =
+        web::block(move || compute_raster(&map.geometry, &rose, &windbreaks, date, resolution))
+            .await?;
+    Ok(raster)
+}
+
+/// Compute the wind exposure of the map on a day for scoring plant locations.
+///
+/// Uses the wind rose of the main winds layer and the plantings of the map.
+/// Returns `None` if the map has no wind rose with any wind.
+/// The raster is reused from `cache` as long as the map, its wind rose and plantings don't change.
+///
+/// # Errors
+/// * If the connection to the database could not be established.
+/// * If the raster could not be computed.
+pub async fn main_layer_raster(
+    map_id: i32,
+    date: NaiveDate,
+    cache: &WindRasterCache,
+    conn: &mut AsyncPgConnection,
+) -> Result<Option<Arc<WindRasterDto>>, ServiceError> {
+    let layers = Layer::find(
+        LayerSearchParameters {
+            map_id: Some(map_id),
+            type_: Some(LayerType::Winds),
+            is_alternative: Some(false),
+            is_deleted: None,
+        },
+        conn,
+    )
+    .await?;
+    let Some(layer) = layers.first() else {
+        return Ok(None);
+    };
+    let rose = WindRoseEntry::find_by_layer(layer.id, conn).await?;
+    if open_ground_speed(&rose) <= 0.0 {
+        return Ok(None);
+    }
+
+    let map = Map::find_by_id(map_id, conn).await?;
+    let windbreaks = find_windbreaks(map_id, date, conn).await?;
+    let mut resolution = DEFAULT_RESOLUTION;
+    while exceeds_max_cells(&map.geometry, resolution, MAX_RASTER_CELLS) {
+        resolution *= 2;
+    }
+    let inputs = WindRasterInputs {
+        geometry: map.geometry,
+        rose,
+        windbreaks,
+        resolution,
+    };
+    let raster = cache
+        .get_or_compute(map_id, date, inputs, move |inputs| {
+            compute_raster(
+                &inputs.geometry,
+                &inputs.rose,
+                &inputs.windbreaks,
+                date,
+                inputs.resolution,
+            )
+        })
+        .await?;
+    Ok(Some(raster))
+}
+
+/// Lower the scores of a heatmap where plants not tolerating wind are exposed to it.
+///
+/// The score of fully exposed cells is lowered by [`SUITABILITY_PENALTY_PER_STEP`],
+/// see [`scale_heatmap`] for the layout of the heatmap.
+/// Plants tolerating wind or with unknown wind tolerance are not affected.
+pub fn apply_wind_suitability(
+    heatmap: &mut [Vec<f32>],
+    origin: (i32, i32),
+    granularity: i32,
+    winds: &WindRasterDto,
+    tolerates_wind: Option<bool>,
+) {
+    if tolerates_wind != Some(false) {
+        return;
+    }
+    scale_heatmap(heatmap, origin, granularity, |x, y| {
+        raster_cell(
+            &winds.cells,
+            (winds.x_min, winds.y_min),
+            winds.resolution,
+            (x, y),
+        )
+        .map_or(1.0, |cell| {
+            cell.exposure.mul_add(-SUITABILITY_PENALTY_PER_STEP, 1.0)
+        })
+    });
+}
+
+/// Check the directions, frequencies and speeds of a wind rose.
+fn check_rose(entries: &[WindRoseEntryDto]) -> Result<(), ServiceError> {
+    let mut directions = HashSet::new();
+    for entry in entries {
+        if !(0..360).contains(&entry.direction) || !directions.insert(entry.direction) {
+            return Err(ServiceError::new(
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "The direction {} must be between 0 and 359 degrees and occur only once",
+                    entry.direction
+                ),
+            ));
+        }
+        if !(0.0..=100.0).contains(&entry.frequency) || entry.speed.is_nan() || entry.speed < 0.0 {
+            return Err(ServiceError::new(
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "The frequency of direction {} must be between 0 and 100 percent and its speed must not be negative",
+                    entry.direction
+                ),
+            ));
+        }
+    }
+    if entries.iter().map(|entry| entry.frequency).sum::<f32>() > 100.0 {
+        return Err(ServiceError::new(
+            StatusCode::BAD_REQUEST,
+            "The frequencies must not add up to more than 100 percent".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Collect the plantings existing on the date which are tall enough to act as windbreaks.
+///
+/// Plantings are assumed to be fully grown.
+async fn find_windbreaks(
+    map_id: i32,
+    date: NaiveDate,
+    conn: &mut AsyncPgConnection,
+) -> Result<Vec<Windbreak>, ServiceError> {
+    let plantings = Planting::find_by_map_with_plants(map_id, date, date, conn).await?;
+    Ok(plantings
+        .into_iter()
+        .filter_map(|(planting, plant)| {
+            let height = plant.height.and_then(mature_height)?;
+            (height >= MIN_WINDBREAK_HEIGHT).then(|| Windbreak {
+                area: planting_area(&planting),
+                height,
+            })
+        })
+        .collect())
+}
+
+/// The mean wind speed in m/s on open ground, including calm periods.
+fn open_ground_speed(rose: &[WindRoseEntryDto]) -> f64 {
+    rose.iter()
+        .map(|entry| f64::from(entry.frequency) / 100.0 * f64::from(entry.speed))
+        .sum()
+}
+
+/// Compute the wind speed and exposure for each cell of a raster covering the geometry.
+#[allow(
+    clippy::cast_possible_truncation, // ok, because the raster is limited to `MAX_RASTER_CELLS` and map coordinates are i32
+    clippy::cast_sign_loss,           // ok, because the number of cells is never negative
+    clippy::cast_precision_loss       // ok, because the raster is limited to `MAX_RASTER_CELLS`
+)]
+fn compute_raster(
+    geometry: &Polygon<Point>,
+    rose: &[WindRoseEntryDto],
+    windbreaks: &[Windbreak],
+    date: NaiveDate,
+    resolution: i32,
+) -> WindRasterDto {
+    let Some(bounds) = Rectangle::enclosing(geometry) else {
+        return WindRasterDto {
+            x_min: 0,
+            y_min: 0,
+            resolution,
+            date,
+            cells: Vec::new(),
+        };
+    };
+    let (x_min, y_min) = (bounds.x_min.floor(), bounds.y_min.floor());
+    let size = f64::from(resolution);
+    let (num_cols, num_rows) = (
+        ((bounds.x_max - x_min) / size).ceil() as usize,
+        ((bounds.y_max - y_min) / size).ceil() as usize,
+    );
+    let open_speed = open_ground_speed(rose);
+
+    let cells = (0..num_rows)
+        .map(|row| {
+            let y = (row as f64 + 0.5).mul_add(size, y_min);
+            (0..num_cols)
+                .map(|col| {
+                    let x = (col as f64 + 0.5).mul_add(size, x_min);
+                    if !polygon_contains(geometry, x, y) {
+                        return None;
+                    }
+                    let speed = rose
+                        .iter()
+                        .map(|entry| {
+                            f64::from(entry.frequency) / 100.0
+                                * f64::from(entry.speed)
+                                * (1.0 - shelter(windbreaks, x, y, entry.direction))
+                        })
+                        .sum::<f64>();
+                    let exposure = if open_speed > 0.0 {
+                        speed / open_speed
+                    } else {
+                        0.0
+                    };
+                    Some(WindCellDto {
+                        speed: speed as f32,
+                        exposure: exposure as f32,
+                    })
+                })
+                .collect()
+        })
+        .collect();
+    WindRasterDto {
+        x_min: x_min as i32,
+        y_min: y_min as i32,
+        resolution,
+        date,
+        cells,
+    }
+}
+
+/// The reduction of the wind from the direction at `x`,`y` by the best windbreak upwind of it.
+///
+/// The reduction is the highest right behind a windbreak and vanishes linearly
+/// [`SHELTER_HEIGHTS`] times its height behind it.
+fn shelter(windbreaks: &[Windbreak], x: f64, y: f64, direction: i32) -> f64 {
+    // Looking upwind, where the x axis points east and the y axis points south.
+    let angle = f64::from(direction).to_radians();
+    let upwind = (angle.sin(), -angle.cos());
+    windbreaks
+        .iter()
+        .filter_map(|windbreak| {
+            let distance = upwind_distance(&windbreak.area, (x, y), upwind)?;
+            let reach = SHELTER_HEIGHTS * windbreak.height;
+            (distance < reach).then(|| MAX_SHELTER * (1.0 - distance / reach))
+        })
+        .fold(0.0, f64::max)
+}
+
+/// The distance from `x`,`y` along the direction to the area,
+/// 0 if the point is inside of it and `None` if the area is not in that direction.
+fn upwind_distance(area: &Rectangle, (x, y): (f64, f64), (dx, dy): (f64, f64)) -> Option<f64> {
+    let slab = |origin: f64, delta: f64, min: f64, max: f64| {
+        if delta.abs() < f64::EPSILON {
+            (min..=max)
+                .contains(&origin)
+                .then_some((f64::NEG_INFINITY, f64::INFINITY))
+        } else {
+            let (first, second) = ((min - origin) / delta, (max - origin) / delta);
+            Some((first.min(second), first.max(second)))
+        }
+    };
+    let (x_near, x_far) = slab(x, dx, area.x_min, area.x_max)?;
+    let (y_near, y_far) = slab(y, dy, area.y_min, area.y_max)?;
+    let (near, far) = (x_near.max(y_near), x_far.min(y_far));
+    (near <= far && far >= 0.0).then_some(near.max(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rectangle(width: f64, height: f64) -> Polygon<Point> {
+        let mut polygon = Polygon::new(None);
+        polygon.add_points([
+            Point::new(0.0, 0.0, None),
+            Point::new(width, 0.0, None),
+            Point::new(width, height, None),
+            Point::new(0.0, height, None),
+            Point::new(0.0, 0.0, None),
+        ]);
+        polygon
+    }
+
+    #[allow(clippy::unwrap_used, clippy::indexing_slicing)]
+    #[test]
+    fn test_windbreak_shelters_cells_downwind() {
+        let map = rectangle(1000.0, 1000.0);
+        // a hedge of 2 m height running east to west, with the wind coming from the north
+        let hedge = Windbreak {
+            area: Rectangle {
+                x_min: 0.0,
+                y_min: 200.0,
+                x_max: 1000.0,
+                y_max: 300.0,
+            },
+            height: 200.0,
+        };
+        let rose = [WindRoseEntryDto {
+            direction: 0,
+            frequency: 50.0,
+            speed: 4.0,
+        }];
+        let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+
+        let raster = compute_raster(&map, &rose, &[hedge], date, 100);
+        let north_of_hedge = raster.cells[0][5].unwrap();
+        let behind_hedge = raster.cells[3][5].unwrap();
+        let further_behind = raster.cells[9][5].unwrap();
+
+        assert!((north_of_hedge.exposure - 1.0).abs() < f32::EPSILON);
+        assert!((north_of_hedge.speed - 2.0).abs() < f32::EPSILON);
+        assert!(behind_hedge.exposure < further_behind.exposure);
+        assert!(further_behind.exposure < 1.0);
+        assert!((behind_hedge.exposure - 0.415).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_check_rose_rejects_invalid_entries() {
+        let entry = |direction, frequency| WindRoseEntryDto {
+            direction,
+            frequency,
+            speed: 3.0,
+        };
+
+        assert!(check_rose(&[entry(0, 40.0), entry(270, 60.0)]).is_ok());
+        assert!(check_rose(&[entry(0, 40.0), entry(0, 10.0)]).is_err());
+        assert!(check_rose(&[entry(360, 10.0)]).is_err());
+        assert!(check_rose(&[entry(0, 60.0), entry(180, 60.0)]).is_err());
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[test]
+    fn test_apply_wind_suitability_only_affects_sensitive_plants() {
+        let cell = |exposure| {
+            Some(WindCellDto {
+                speed: 4.0 * exposure,
+                exposure,
+            })
+        };
+        let winds = WindRasterDto {
+            x_min: 0,
+            y_min: 0,
+            resolution: 10,
+            date: NaiveDate::from_ymd_opt(2023, 6, 1).unwrap(),
+            cells: vec![vec![cell(1.0), cell(0.0)]],
+        };
+        let mut heatmap = vec![vec![0.8, 0.8]];
+
+        apply_wind_suitability(&mut heatmap, (0, 0), 10, &winds, Some(true));
+        assert_eq!(heatmap, vec![vec![0.8, 0.8]]);
+
+        apply_wind_suitability(&mut heatmap, (0, 0), 10, &winds, Some(false));
+        assert_eq!(heatmap, vec![vec![0.4, 0.8]]);
+    }
+}
@@ -0,0 +1,167 @@
+//! Tests for [`crate::controller::calendar`].
+
+use actix_http::StatusCode;
+use actix_web::{http::header, test};
+use chrono::NaiveDate;
+use diesel::ExpressionMethods;
+use diesel_async::{
+    pooled_connection::deadpool::Pool, scoped_futures::ScopedFutureExt, AsyncPgConnection,
+    RunQueryDsl,
+};
+use uuid::Uuid;
+
+use crate::{
+    model::dto::CalendarDto,
+    test::util::{data, init_test_app, init_test_database},
+};
+
+const PLANTING_ADDED: Uuid = Uuid::from_u128(1);
+const PLANTING_REMOVED: Uuid = Uuid::from_u128(2);
+const PLANTING_ALTERNATIVE: Uuid = Uuid::from_u128(3);
+
+async fn init_calendar_database() -> Pool<AsyncPgConnection> {
+    init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::maps::table)
+                .values(data::TestInsertableMap::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::layers::table)
+                .values(vec![
+                    data::TestInsertableLayer::default(),
+                    data::TestInsertableLayer {
+                        id: -2,
+                        name: "Test Layer 2".to_owned(),
+                        is_alternative: true,
+                        ..Default::default()
+                    },
+                ])
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plants::table)
+                .values(data::TestInsertablePlant::default())
+                .execute(conn)
+                .await?;
+            diesel::update(crate::schema::plants::table)
+                .filter(crate::schema::plants::id.eq(-1))
+                .set((
+                    crate::schema::plants::sowing_outdoors.eq(vec![Some(4_i16), Some(5)]),
+                    crate::schema::plants::harvest_time.eq(vec![Some(14_i16)]),
+                ))
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plantings::table)
+                .values(vec![
+                    data::TestInsertablePlanting {
+                        id: PLANTING_ADDED,
+                        add_date: NaiveDate::from_ymd_opt(2023, 4, 20),
+                        ..Default::default()
+                    },
+                    data::TestInsertablePlanting {
+                        id: PLANTING_REMOVED,
+                        add_date: NaiveDate::from_ymd_opt(2022, 1, 1),
+                        remove_date: NaiveDate::from_ymd_opt(2023, 3, 1),
+                        ..Default::default()
+                    },
+                    data::TestInsertablePlanting {
+                        id: PLANTING_ALTERNATIVE,
+                        layer_id: -2,
+                        ..Default::default()
+                    },
+                ])
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn test_get_calendar_succeeds() {
+    let pool = init_calendar_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let resp = test::TestRequest::get()
+        .uri("/api/maps/-1/calendar?year=2023")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let calendar: CalendarDto = test::read_body_json(resp).await;
+    assert_eq!(calendar.entries.len(), 24);
+    let entry_of = |half_month: i32| {
+        calendar
+            .entries
+            .iter()
+            .find(|entry| entry.half_month == half_month)
+            .unwrap()
+    };
+    let ids = |half_month: i32, kind: &str| {
+        let entry = entry_of(half_month);
+        let plantings = match kind {
+            "sow" => &entry.sow,
+            "harvest" => &entry.harvest,
+            "add" => &entry.add,
+            _ => &entry.remove,
+        };
+        plantings
+            .iter()
+            .map(|planting| planting.planting_id)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        entry_of(3).start,
+        NaiveDate::from_ymd_opt(2023, 2, 15).unwrap()
+    );
+    assert_eq!(
+        entry_of(3).end,
+        NaiveDate::from_ymd_opt(2023, 2, 28).unwrap()
+    );
+    assert_eq!(ids(4, "sow"), vec![PLANTING_REMOVED]);
+    assert!(ids(5, "sow").is_empty());
+    assert_eq!(ids(14, "harvest"), vec![PLANTING_ADDED]);
+    assert_eq!(ids(7, "add"), vec![PLANTING_ADDED]);
+    assert_eq!(ids(4, "remove"), vec![PLANTING_REMOVED]);
+    assert!(calendar.entries.iter().all(|entry| !entry
+        .sow
+        .iter()
+        .chain(&entry.harvest)
+        .any(|planting| planting.planting_id == PLANTING_ALTERNATIVE)));
+}
+
+#[actix_rt::test]
+async fn test_export_calendar_as_ics_succeeds() {
+    let pool = init_calendar_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let resp = test::TestRequest::get()
+        .uri("/api/maps/-1/calendar?year=2023&format=ics")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers().get(header::CONTENT_TYPE).unwrap(),
+        "text/calendar; charset=utf-8"
+    );
+
+    let body = test::read_body(resp).await;
+    let ics = std::str::from_utf8(&body).unwrap();
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert!(ics.contains(
+        "DTSTART;VALUE=DATE:20230801\r\nDTEND;VALUE=DATE:20230816\r\nSUMMARY:Harvest: Testplant\r\n"
+    ));
+    assert!(ics.contains("SUMMARY:Add Testplant\r\n"));
+
+    let missing_resp = test::TestRequest::get()
+        .uri("/api/maps/-2/calendar?year=2023")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(missing_resp.status(), StatusCode::NOT_FOUND);
+}
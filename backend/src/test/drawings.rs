@@ -0,0 +1,164 @@
+//! Tests for [`crate::controller::drawings`].
+
+use actix_http::StatusCode;
+use actix_web::{http::header, test};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+use crate::{
+    model::{
+        dto::drawings::{
+            DeleteDrawingDto, DrawingDto, NewDrawingDto, PolygonShapeDto, PolylineShapeDto,
+            RectangleShapeDto, ShapeDto, ShapePointDto, ShapeStyleDto, TextShapeDto,
+            UpdateDrawingDto,
+        },
+        r#enum::layer_type::LayerType,
+    },
+    test::util::{data, init_test_app, init_test_database},
+};
+
+fn new_drawing(layer_id: i32, shape: ShapeDto) -> NewDrawingDto {
+    NewDrawingDto {
+        id: None,
+        layer_id,
+        shape,
+        style: ShapeStyleDto {
+            stroke_color: "#000000".to_owned(),
+            stroke_width: 2.0,
+            fill_color: None,
+        },
+        action_id: Uuid::new_v4(),
+    }
+}
+
+#[actix_rt::test]
+async fn test_can_create_update_and_delete_drawings() {
+    let pool = init_test_database(|conn| {
+        data::insert_map_with_layer(LayerType::Drawing, conn).scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let rectangle = ShapeDto::Rectangle(RectangleShapeDto {
+        x: 100.0,
+        y: 50.0,
+        width: 300.0,
+        height: 200.0,
+        rotation: 0.0,
+    });
+    let created_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/drawings")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_drawing(-2, rectangle.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(created_resp.status(), StatusCode::CREATED);
+    let drawing: DrawingDto = test::read_body_json(created_resp).await;
+    assert_eq!(drawing.shape, rectangle);
+
+    let text = ShapeDto::Text(TextShapeDto {
+        position: ShapePointDto { x: 10.0, y: 20.0 },
+        text: "Pond".to_owned(),
+        font_size: 16.0,
+        rotation: 45.0,
+    });
+    let text_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/drawings")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_drawing(-2, text.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(text_resp.status(), StatusCode::CREATED);
+
+    let polygon = ShapeDto::Polygon(PolygonShapeDto {
+        points: vec![
+            ShapePointDto { x: 0.0, y: 0.0 },
+            ShapePointDto { x: 100.0, y: 0.0 },
+            ShapePointDto { x: 50.0, y: 80.0 },
+        ],
+    });
+    let updated_resp = test::TestRequest::patch()
+        .uri(&format!("/api/maps/-1/layers/drawings/{}", drawing.id))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(UpdateDrawingDto {
+            shape: Some(polygon.clone()),
+            style: None,
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(updated_resp.status(), StatusCode::OK);
+    let updated: DrawingDto = test::read_body_json(updated_resp).await;
+    assert_eq!(updated.shape, polygon);
+    assert_eq!(updated.style, drawing.style);
+
+    let deleted_resp = test::TestRequest::delete()
+        .uri(&format!("/api/maps/-1/layers/drawings/{}", drawing.id))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(DeleteDrawingDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(deleted_resp.status(), StatusCode::OK);
+
+    let drawings_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/drawings?layer_id=-2")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(drawings_resp.status(), StatusCode::OK);
+    let drawings: Vec<DrawingDto> = test::read_body_json(drawings_resp).await;
+    let [remaining] = drawings.as_slice() else {
+        panic!("expected exactly one drawing, got {drawings:?}");
+    };
+    assert_eq!(remaining.shape, text);
+}
+
+#[actix_rt::test]
+async fn test_create_drawing_on_plant_layer_fails() {
+    let pool = init_test_database(|conn| {
+        data::insert_map_with_layer(LayerType::Drawing, conn).scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/drawings")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(new_drawing(
+            -1,
+            ShapeDto::Polygon(PolygonShapeDto {
+                points: vec![
+                    ShapePointDto { x: 0.0, y: 0.0 },
+                    ShapePointDto { x: 100.0, y: 0.0 },
+                    ShapePointDto { x: 50.0, y: 80.0 },
+                ],
+            }),
+        ))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn test_create_polyline_with_one_point_fails() {
+    let pool = init_test_database(|conn| {
+        data::insert_map_with_layer(LayerType::Drawing, conn).scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/drawings")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(new_drawing(
+            -2,
+            ShapeDto::Polyline(PolylineShapeDto {
+                points: vec![ShapePointDto { x: 0.0, y: 0.0 }],
+            }),
+        ))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
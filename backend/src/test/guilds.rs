@@ -0,0 +1,325 @@
+//! Tests for [`crate::controller::guilds`].
+
+use actix_http::StatusCode;
+use actix_web::{http::header, test};
+use diesel_async::{
+    pooled_connection::deadpool::Pool, scoped_futures::ScopedFutureExt, AsyncPgConnection,
+    RunQueryDsl,
+};
+use uuid::Uuid;
+
+use crate::{
+    model::dto::{
+        guilds::{
+            ApplyGuildTemplateDto, DeleteGuildDto, DuplicateGuildDto, GuildDto, GuildTemplateDto,
+            MoveGuildDto, NewGuildDto, NewGuildTemplateDto, RotateGuildDto, UpdateGuildDto,
+        },
+        plantings::PlantingDto,
+        TimelinePage,
+    },
+    test::util::{data, init_test_app, init_test_database},
+};
+
+const PLANTING_TREE: Uuid = Uuid::from_u128(1);
+const PLANTING_COMPANION: Uuid = Uuid::from_u128(2);
+const PLANTING_OTHER_LAYER: Uuid = Uuid::from_u128(3);
+
+async fn init_guilds_database() -> Pool<AsyncPgConnection> {
+    init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::maps::table)
+                .values(vec![
+                    data::TestInsertableMap::default(),
+                    data::TestInsertableMap {
+                        id: -2,
+                        name: "Test Map 2".to_owned(),
+                        ..Default::default()
+                    },
+                ])
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::layers::table)
+                .values(vec![
+                    data::TestInsertableLayer::default(),
+                    data::TestInsertableLayer {
+                        id: -2,
+                        name: "Test Layer 2".to_owned(),
+                        is_alternative: true,
+                        ..Default::default()
+                    },
+                    data::TestInsertableLayer {
+                        id: -3,
+                        map_id: -2,
+                        name: "Test Layer 3".to_owned(),
+                        ..Default::default()
+                    },
+                ])
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plants::table)
+                .values(vec![
+                    data::TestInsertablePlant::default(),
+                    data::TestInsertablePlant {
+                        id: -2,
+                        unique_name: "Test Plant 2".to_owned(),
+                        ..Default::default()
+                    },
+                ])
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plantings::table)
+                .values(vec![
+                    data::TestInsertablePlanting {
+                        id: PLANTING_TREE,
+                        x: 100,
+                        y: 100,
+                        ..Default::default()
+                    },
+                    data::TestInsertablePlanting {
+                        id: PLANTING_COMPANION,
+                        plant_id: -2,
+                        x: 200,
+                        y: 100,
+                        ..Default::default()
+                    },
+                    data::TestInsertablePlanting {
+                        id: PLANTING_OTHER_LAYER,
+                        layer_id: -2,
+                        ..Default::default()
+                    },
+                ])
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+fn new_guild(planting_ids: Vec<Uuid>) -> NewGuildDto {
+    NewGuildDto {
+        id: None,
+        layer_id: -1,
+        name: "Apple guild".to_owned(),
+        planting_ids,
+        action_id: Uuid::new_v4(),
+    }
+}
+
+fn positions(guild: &GuildDto) -> Vec<(i32, i32)> {
+    let mut positions = guild
+        .plantings
+        .iter()
+        .map(|planting| (planting.x, planting.y))
+        .collect::<Vec<_>>();
+    positions.sort_unstable();
+    positions
+}
+
+#[actix_rt::test]
+async fn test_can_create_transform_and_ungroup_guild() {
+    let pool = init_guilds_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let created_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/plants/guilds")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_guild(vec![PLANTING_TREE, PLANTING_COMPANION]))
+        .send_request(&app)
+        .await;
+    assert_eq!(created_resp.status(), StatusCode::CREATED);
+    let guild: GuildDto = test::read_body_json(created_resp).await;
+    assert_eq!(positions(&guild), vec![(100, 100), (200, 100)]);
+
+    let moved_resp = test::TestRequest::patch()
+        .uri(&format!("/api/maps/-1/layers/plants/guilds/{}", guild.id))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(UpdateGuildDto::Move(MoveGuildDto {
+            dx: 10,
+            dy: -20,
+            action_id: Uuid::new_v4(),
+        }))
+        .send_request(&app)
+        .await;
+    assert_eq!(moved_resp.status(), StatusCode::OK);
+    let moved: GuildDto = test::read_body_json(moved_resp).await;
+    assert_eq!(positions(&moved), vec![(110, 80), (210, 80)]);
+
+    let rotated_resp = test::TestRequest::patch()
+        .uri(&format!("/api/maps/-1/layers/plants/guilds/{}", guild.id))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(UpdateGuildDto::Rotate(RotateGuildDto {
+            rotation: 90.0,
+            action_id: Uuid::new_v4(),
+        }))
+        .send_request(&app)
+        .await;
+    assert_eq!(rotated_resp.status(), StatusCode::OK);
+    let rotated: GuildDto = test::read_body_json(rotated_resp).await;
+    assert_eq!(positions(&rotated), vec![(160, 30), (160, 130)]);
+
+    let deleted_resp = test::TestRequest::delete()
+        .uri(&format!("/api/maps/-1/layers/plants/guilds/{}", guild.id))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(DeleteGuildDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(deleted_resp.status(), StatusCode::OK);
+
+    let plantings_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/plants/plantings?layer_id=-1&relative_to_date=2023-05-08")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(plantings_resp.status(), StatusCode::OK);
+    let page: TimelinePage<PlantingDto> = test::read_body_json(plantings_resp).await;
+    assert_eq!(page.results.len(), 2);
+    assert!(page
+        .results
+        .iter()
+        .all(|planting| planting.guild_id.is_none()));
+}
+
+#[actix_rt::test]
+async fn test_create_guild_with_planting_of_other_layer_fails() {
+    let pool = init_guilds_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/plants/guilds")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_guild(vec![PLANTING_TREE, PLANTING_OTHER_LAYER]))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let guilds_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/plants/guilds?layer_id=-1")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(guilds_resp.status(), StatusCode::OK);
+    let guilds: Vec<GuildDto> = test::read_body_json(guilds_resp).await;
+    assert!(guilds.is_empty());
+}
+
+#[actix_rt::test]
+async fn test_create_guild_with_planting_of_other_guild_fails() {
+    let pool = init_guilds_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let first_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/plants/guilds")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_guild(vec![PLANTING_TREE]))
+        .send_request(&app)
+        .await;
+    assert_eq!(first_resp.status(), StatusCode::CREATED);
+    let first: GuildDto = test::read_body_json(first_resp).await;
+
+    let second_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/plants/guilds")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_guild(vec![PLANTING_TREE, PLANTING_COMPANION]))
+        .send_request(&app)
+        .await;
+    assert_eq!(second_resp.status(), StatusCode::NOT_FOUND);
+
+    let guilds_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/plants/guilds?layer_id=-1")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    let guilds: Vec<GuildDto> = test::read_body_json(guilds_resp).await;
+    assert_eq!(
+        guilds
+            .iter()
+            .map(|guild| (guild.id, positions(guild)))
+            .collect::<Vec<_>>(),
+        vec![(first.id, vec![(100, 100)])]
+    );
+}
+
+#[actix_rt::test]
+async fn test_can_duplicate_guild_and_place_template_on_other_map() {
+    let pool = init_guilds_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let created_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/plants/guilds")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_guild(vec![PLANTING_TREE, PLANTING_COMPANION]))
+        .send_request(&app)
+        .await;
+    assert_eq!(created_resp.status(), StatusCode::CREATED);
+    let guild: GuildDto = test::read_body_json(created_resp).await;
+
+    let duplicated_resp = test::TestRequest::post()
+        .uri(&format!(
+            "/api/maps/-1/layers/plants/guilds/{}/duplicate",
+            guild.id
+        ))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(DuplicateGuildDto {
+            id: None,
+            dx: 0,
+            dy: 300,
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(duplicated_resp.status(), StatusCode::CREATED);
+    let duplicate: GuildDto = test::read_body_json(duplicated_resp).await;
+    assert_ne!(duplicate.id, guild.id);
+    assert_eq!(positions(&duplicate), vec![(100, 400), (200, 400)]);
+    assert!(duplicate
+        .plantings
+        .iter()
+        .all(|planting| planting.id != PLANTING_TREE && planting.id != PLANTING_COMPANION));
+
+    let template_resp = test::TestRequest::post()
+        .uri(&format!(
+            "/api/maps/-1/layers/plants/guilds/{}/template",
+            guild.id
+        ))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(NewGuildTemplateDto {
+            name: "Apple guild".to_owned(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(template_resp.status(), StatusCode::CREATED);
+    let template: GuildTemplateDto = test::read_body_json(template_resp).await;
+    assert_eq!(template.members.len(), 2);
+
+    let placed_resp = test::TestRequest::post()
+        .uri("/api/maps/-2/layers/plants/guilds/from-template")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(ApplyGuildTemplateDto {
+            id: None,
+            template_id: template.id,
+            layer_id: -3,
+            x: 50,
+            y: 50,
+            add_date: None,
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(placed_resp.status(), StatusCode::CREATED);
+    let placed: GuildDto = test::read_body_json(placed_resp).await;
+    assert_eq!(placed.layer_id, -3);
+    assert_eq!(positions(&placed), vec![(0, 50), (100, 50)]);
+
+    let templates_resp = test::TestRequest::get()
+        .uri("/api/guild-templates")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(templates_resp.status(), StatusCode::OK);
+    let templates: Vec<GuildTemplateDto> = test::read_body_json(templates_resp).await;
+    assert_eq!(templates.len(), 1);
+}
@@ -0,0 +1,359 @@
+//! Tests for [`crate::controller::harvests`].
+
+use actix_http::StatusCode;
+use actix_web::{http::header, test};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::{
+    pooled_connection::deadpool::Pool, scoped_futures::ScopedFutureExt, AsyncPgConnection,
+    RunQueryDsl,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    model::{
+        dto::{DeleteLayerDto, HarvestDto, HarvestSummaryDto, MapDto, RestoreLayerDto},
+        r#enum::harvest_unit::HarvestUnit,
+    },
+    test::util::{data, init_test_app, init_test_database},
+};
+
+const PLANTING_TOMATO: Uuid = Uuid::from_u128(1);
+const PLANTING_BEAN: Uuid = Uuid::from_u128(2);
+const PLANTING_ALTERNATIVE: Uuid = Uuid::from_u128(3);
+
+async fn init_harvests_database() -> Pool<AsyncPgConnection> {
+    init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::maps::table)
+                .values(data::TestInsertableMap::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::layers::table)
+                .values(vec![
+                    data::TestInsertableLayer::default(),
+                    data::TestInsertableLayer {
+                        id: -2,
+                        name: "Test Layer 2".to_owned(),
+                        is_alternative: true,
+                        ..Default::default()
+                    },
+                ])
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plants::table)
+                .values(vec![
+                    data::TestInsertablePlant::default(),
+                    data::TestInsertablePlant {
+                        id: -2,
+                        unique_name: "Test Plant 2".to_owned(),
+                        ..Default::default()
+                    },
+                ])
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plantings::table)
+                .values(vec![
+                    data::TestInsertablePlanting {
+                        id: PLANTING_TOMATO,
+                        ..Default::default()
+                    },
+                    data::TestInsertablePlanting {
+                        id: PLANTING_BEAN,
+                        plant_id: -2,
+                        ..Default::default()
+                    },
+                    data::TestInsertablePlanting {
+                        id: PLANTING_ALTERNATIVE,
+                        layer_id: -2,
+                        ..Default::default()
+                    },
+                ])
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+fn log_harvest(
+    token: &str,
+    planting_id: Uuid,
+    harvest_date: &str,
+    quantity: f32,
+    unit: &str,
+) -> test::TestRequest {
+    test::TestRequest::post()
+        .uri(&format!(
+            "/api/maps/-1/layers/plants/plantings/{planting_id}/harvests"
+        ))
+        .insert_header((header::AUTHORIZATION, token.to_owned()))
+        .set_json(json!({
+            "harvest_date": harvest_date,
+            "quantity": quantity,
+            "unit": unit,
+            "quality": "good",
+        }))
+}
+
+#[actix_rt::test]
+async fn test_log_and_delete_harvests_updates_map() {
+    let pool = init_harvests_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    for (planting_id, harvest_date, quantity, unit) in [
+        (PLANTING_TOMATO, "2023-07-01", 2.5, "kilograms"),
+        (PLANTING_TOMATO, "2023-08-01", 1.0, "kilograms"),
+        (PLANTING_BEAN, "2023-07-15", 3.0, "pieces"),
+    ] {
+        let created_resp = log_harvest(&token, planting_id, harvest_date, quantity, unit)
+            .send_request(&app)
+            .await;
+        assert_eq!(created_resp.status(), StatusCode::CREATED);
+    }
+
+    let invalid_resp = log_harvest(&token, PLANTING_TOMATO, "2023-08-01", 0.0, "kilograms")
+        .send_request(&app)
+        .await;
+    assert_eq!(invalid_resp.status(), StatusCode::BAD_REQUEST);
+    let missing_resp = log_harvest(&token, Uuid::from_u128(4), "2023-08-01", 1.0, "kilograms")
+        .send_request(&app)
+        .await;
+    assert_eq!(missing_resp.status(), StatusCode::NOT_FOUND);
+
+    let list_resp = test::TestRequest::get()
+        .uri(&format!(
+            "/api/maps/-1/layers/plants/plantings/{PLANTING_TOMATO}/harvests"
+        ))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(list_resp.status(), StatusCode::OK);
+    let harvests: Vec<HarvestDto> = test::read_body_json(list_resp).await;
+    let quantities = harvests
+        .iter()
+        .map(|harvest| harvest.quantity)
+        .collect::<Vec<_>>();
+    assert_eq!(quantities, vec![1.0, 2.5]);
+
+    let logged_resp = test::TestRequest::get()
+        .uri("/api/maps/-1")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    let logged_map: MapDto = test::read_body_json(logged_resp).await;
+    assert_eq!(logged_map.harvested, 2);
+
+    let bean_resp = test::TestRequest::get()
+        .uri(&format!(
+            "/api/maps/-1/layers/plants/plantings/{PLANTING_BEAN}/harvests"
+        ))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    let bean_harvests: Vec<HarvestDto> = test::read_body_json(bean_resp).await;
+    let bean_harvest_ids = bean_harvests
+        .iter()
+        .map(|harvest| harvest.id)
+        .collect::<Vec<_>>();
+    assert_eq!(bean_harvest_ids.len(), 1);
+    for harvest_id in bean_harvest_ids {
+        let delete_resp = test::TestRequest::delete()
+            .uri(&format!(
+                "/api/maps/-1/layers/plants/plantings/{PLANTING_BEAN}/harvests/{harvest_id}"
+            ))
+            .insert_header((header::AUTHORIZATION, token.clone()))
+            .send_request(&app)
+            .await;
+        assert_eq!(delete_resp.status(), StatusCode::OK);
+    }
+
+    let deleted_resp = test::TestRequest::get()
+        .uri("/api/maps/-1")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    let deleted_map: MapDto = test::read_body_json(deleted_resp).await;
+    assert_eq!(deleted_map.harvested, 1);
+}
+
+#[actix_rt::test]
+async fn test_summarize_harvests() {
+    let pool = init_harvests_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    for (planting_id, harvest_date, quantity, unit) in [
+        (PLANTING_TOMATO, "2022-07-01", 1.0, "kilograms"),
+        (PLANTING_TOMATO, "2023-07-01", 2.0, "kilograms"),
+        (PLANTING_BEAN, "2023-07-01", 3.0, "pieces"),
+        (PLANTING_ALTERNATIVE, "2023-07-01", 5.0, "kilograms"),
+    ] {
+        let created_resp = log_harvest(&token, planting_id, harvest_date, quantity, unit)
+            .send_request(&app)
+            .await;
+        assert_eq!(created_resp.status(), StatusCode::CREATED);
+    }
+
+    let summarize = |query: &str| {
+        test::TestRequest::get()
+            .uri(&format!("/api/maps/-1/harvests/summary?{query}"))
+            .insert_header((header::AUTHORIZATION, token.clone()))
+    };
+
+    let plant_resp = summarize("group_by=plant").send_request(&app).await;
+    assert_eq!(plant_resp.status(), StatusCode::OK);
+    let plant_summaries: Vec<HarvestSummaryDto> = test::read_body_json(plant_resp).await;
+    let by_plant = plant_summaries
+        .iter()
+        .map(|summary| {
+            (
+                summary.plant_id,
+                summary.unique_name.clone(),
+                summary.unit,
+                summary.total_quantity,
+                summary.harvests,
+                summary.plantings,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        by_plant,
+        vec![
+            (
+                Some(-1),
+                Some("Test Plant 1".to_owned()),
+                HarvestUnit::Kilograms,
+                3.0,
+                2,
+                1
+            ),
+            (
+                Some(-2),
+                Some("Test Plant 2".to_owned()),
+                HarvestUnit::Pieces,
+                3.0,
+                1,
+                1
+            ),
+        ]
+    );
+
+    let layer_resp = summarize("group_by=layer").send_request(&app).await;
+    let layer_summaries: Vec<HarvestSummaryDto> = test::read_body_json(layer_resp).await;
+    let by_layer = layer_summaries
+        .iter()
+        .map(|summary| (summary.layer_id, summary.unit, summary.total_quantity))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        by_layer,
+        vec![
+            (Some(-2), HarvestUnit::Kilograms, 5.0),
+            (Some(-1), HarvestUnit::Pieces, 3.0),
+            (Some(-1), HarvestUnit::Kilograms, 3.0),
+        ]
+    );
+
+    let season_resp = summarize("group_by=season&season=2023")
+        .send_request(&app)
+        .await;
+    let season_summaries: Vec<HarvestSummaryDto> = test::read_body_json(season_resp).await;
+    let by_season = season_summaries
+        .iter()
+        .map(|summary| (summary.season, summary.unit, summary.total_quantity))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        by_season,
+        vec![
+            (Some(2023), HarvestUnit::Pieces, 3.0),
+            (Some(2023), HarvestUnit::Kilograms, 2.0),
+        ]
+    );
+
+    let map_resp = summarize("group_by=map").send_request(&app).await;
+    let by_map: Vec<HarvestSummaryDto> = test::read_body_json(map_resp).await;
+    assert!(by_map
+        .iter()
+        .all(|summary| summary.plant_id.is_none() && summary.layer_id.is_none()));
+    assert_eq!(by_map.len(), 2);
+}
+
+#[actix_rt::test]
+async fn test_harvested_follows_moved_plantings_and_deleted_layers() {
+    let pool = init_harvests_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+    let harvested = || async {
+        let resp = test::TestRequest::get()
+            .uri("/api/maps/-1")
+            .insert_header((header::AUTHORIZATION, token.clone()))
+            .send_request(&app)
+            .await;
+        let map: MapDto = test::read_body_json(resp).await;
+        map.harvested
+    };
+
+    for planting_id in [PLANTING_TOMATO, PLANTING_ALTERNATIVE] {
+        let resp = log_harvest(&token, planting_id, "2023-08-01", 1.0, "kilograms")
+            .send_request(&app)
+            .await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+    assert_eq!(harvested().await, 2);
+
+    let delete_resp = test::TestRequest::delete()
+        .uri("/api/maps/-1/layers/-2")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(DeleteLayerDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(delete_resp.status(), StatusCode::OK);
+    assert_eq!(harvested().await, 1);
+
+    let restore_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/-2/restore")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(RestoreLayerDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(restore_resp.status(), StatusCode::OK);
+    assert_eq!(harvested().await, 2);
+
+    let mut conn = pool.get().await.unwrap();
+    diesel::insert_into(crate::schema::maps::table)
+        .values(data::TestInsertableMap {
+            id: -2,
+            name: "Test Map 2".to_owned(),
+            ..Default::default()
+        })
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    diesel::insert_into(crate::schema::layers::table)
+        .values(data::TestInsertableLayer {
+            id: -3,
+            map_id: -2,
+            ..Default::default()
+        })
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    diesel::update(crate::schema::plantings::table.find(PLANTING_ALTERNATIVE))
+        .set(crate::schema::plantings::layer_id.eq(-3))
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    let moved_harvested = crate::schema::maps::table
+        .find(-2)
+        .select(crate::schema::maps::harvested)
+        .get_result::<i16>(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(moved_harvested, 1);
+    drop(conn);
+    assert_eq!(harvested().await, 1);
+}
@@ -3,10 +3,14 @@
 use crate::{
     error::ServiceError,
     model::{
-        dto::{LayerDto, NewLayerDto},
+        dto::{
+            plantings::{MovePlantingDto, PlantingDto, UpdatePlantingDto},
+            DeleteLayerDto, LayerDiffDto, LayerDto, LayerOrderDto, NewAlternativeLayerDto,
+            NewLayerDto, PromoteLayerDto, RestoreLayerDto, TimelinePage, UpdateLayerDto,
+        },
         r#enum::{layer_type::LayerType, privacy_option::PrivacyOption},
     },
-    test::util::{init_test_app, init_test_database},
+    test::util::{data, init_test_app, init_test_database},
 };
 use actix_web::{
     http::{
@@ -15,7 +19,7 @@ use actix_web::{
     },
     test,
 };
-use chrono::Utc;
+use chrono::{Days, Utc};
 use diesel::ExpressionMethods;
 use diesel_async::{scoped_futures::ScopedFutureExt, AsyncPgConnection, RunQueryDsl};
 use uuid::Uuid;
@@ -152,10 +156,379 @@ async fn test_delete_by_id_succeeds() {
     let (token, app) = init_test_app(pool).await;
 
     let resp = test::TestRequest::delete()
+        .uri("/api/maps/-1/layers/-2")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(DeleteLayerDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[actix_rt::test]
+async fn test_delete_last_main_layer_fails() {
+    let pool = init_test_database(|conn| initial_db_values(conn).scope_boxed()).await;
+    let (token, app) = init_test_app(pool).await;
+
+    let resp = test::TestRequest::delete()
+        .uri("/api/maps/-1/layers/-1")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(DeleteLayerDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+}
+
+#[actix_rt::test]
+async fn test_delete_layer_of_other_map_fails() {
+    let pool = init_test_database(|conn| initial_db_values(conn).scope_boxed()).await;
+    let (token, app) = init_test_app(pool).await;
+
+    let resp = test::TestRequest::delete()
+        .uri("/api/maps/-2/layers/-2")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(DeleteLayerDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_rt::test]
+async fn test_restore_layer_that_is_not_deleted_fails() {
+    let pool = init_test_database(|conn| initial_db_values(conn).scope_boxed()).await;
+    let (token, app) = init_test_app(pool).await;
+
+    let resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/-2/restore")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(RestoreLayerDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn test_restore_layer_deleted_too_long_ago_fails() {
+    let pool = init_test_database(|conn| {
+        async {
+            initial_db_values(conn).await?;
+            diesel::update(crate::schema::layers::table)
+                .filter(crate::schema::layers::id.eq(-2))
+                .set(
+                    crate::schema::layers::deletion_date
+                        .eq(Utc::now().date_naive() - Days::new(31)),
+                )
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app(pool).await;
+
+    let resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/-2/restore")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(RestoreLayerDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn test_can_restore_deleted_layer_with_plantings() {
+    let pool =
+        init_test_database(|conn| initial_db_values_with_plantings(conn).scope_boxed()).await;
+    let (token, app) = init_test_app(pool).await;
+
+    let promote_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/-2/promote")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(PromoteLayerDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(promote_resp.status(), StatusCode::OK);
+
+    let delete_resp = test::TestRequest::delete()
+        .uri("/api/maps/-1/layers/-1")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(DeleteLayerDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(delete_resp.status(), StatusCode::OK);
+    let deleted: LayerDto = test::read_body_json(delete_resp).await;
+    assert!(deleted.deletion_date.is_some());
+
+    let plantings_uri =
+        "/api/maps/-1/layers/plants/plantings?layer_id=-1&relative_to_date=2023-05-08";
+    let hidden_resp = test::TestRequest::get()
+        .uri(plantings_uri)
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    let hidden: TimelinePage<PlantingDto> = test::read_body_json(hidden_resp).await;
+    assert!(hidden.results.is_empty());
+
+    let restore_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/-1/restore")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(RestoreLayerDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(restore_resp.status(), StatusCode::OK);
+    let restored: LayerDto = test::read_body_json(restore_resp).await;
+    assert!(restored.deletion_date.is_none());
+
+    let resp = test::TestRequest::get()
+        .uri(plantings_uri)
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    let page: TimelinePage<PlantingDto> = test::read_body_json(resp).await;
+    assert_eq!(page.results.len(), 2);
+}
+
+#[actix_rt::test]
+async fn test_update_layer_succeeds() {
+    let pool = init_test_database(|conn| initial_db_values(conn).scope_boxed()).await;
+    let (token, app) = init_test_app(pool).await;
+
+    let resp = test::TestRequest::patch()
+        .uri("/api/maps/-1/layers/-1")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(UpdateLayerDto {
+            name: Some("Fruit trees".to_owned()),
+            order_index: None,
+            is_visible: Some(false),
+            opacity: Some(0.5),
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let layer: LayerDto = test::read_body_json(resp).await;
+    assert_eq!(layer.name, "Fruit trees");
+    assert!(!layer.is_visible);
+    assert!((layer.opacity - 0.5).abs() < f32::EPSILON);
+}
+
+#[actix_rt::test]
+async fn test_update_layer_with_invalid_opacity_fails() {
+    let pool = init_test_database(|conn| initial_db_values(conn).scope_boxed()).await;
+    let (token, app) = init_test_app(pool).await;
+
+    let resp = test::TestRequest::patch()
+        .uri("/api/maps/-1/layers/-1")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(UpdateLayerDto {
+            name: None,
+            order_index: None,
+            is_visible: None,
+            opacity: Some(1.5),
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn test_update_layer_without_changes_fails() {
+    let pool = init_test_database(|conn| initial_db_values(conn).scope_boxed()).await;
+    let (token, app) = init_test_app(pool).await;
+
+    let resp = test::TestRequest::patch()
         .uri("/api/maps/-1/layers/-1")
         .insert_header((header::AUTHORIZATION, token))
+        .set_json(UpdateLayerDto {
+            name: None,
+            order_index: None,
+            is_visible: None,
+            opacity: None,
+            action_id: Uuid::new_v4(),
+        })
         .send_request(&app)
         .await;
 
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn test_reorder_layers_succeeds() {
+    let pool = init_test_database(|conn| initial_db_values(conn).scope_boxed()).await;
+    let (token, app) = init_test_app(pool).await;
+
+    let reorder_resp = test::TestRequest::put()
+        .uri("/api/maps/-1/layers/order")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(LayerOrderDto {
+            layer_ids: vec![-1, -2],
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(reorder_resp.status(), StatusCode::OK);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
     assert_eq!(resp.status(), StatusCode::OK);
+    let layers: Vec<LayerDto> = test::read_body_json(resp).await;
+    let order = layers
+        .iter()
+        .map(|layer| (layer.id, layer.order_index))
+        .collect::<Vec<_>>();
+    assert_eq!(order, vec![(-1, 0), (-2, 1)]);
+}
+
+#[actix_rt::test]
+async fn test_reorder_layers_with_missing_layer_fails() {
+    let pool = init_test_database(|conn| initial_db_values(conn).scope_boxed()).await;
+    let (token, app) = init_test_app(pool).await;
+
+    let resp = test::TestRequest::put()
+        .uri("/api/maps/-1/layers/order")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(LayerOrderDto {
+            layer_ids: vec![-1, -1],
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+async fn initial_db_values_with_plantings(
+    conn: &mut AsyncPgConnection,
+) -> Result<(), ServiceError> {
+    initial_db_values(conn).await?;
+    diesel::insert_into(crate::schema::plants::table)
+        .values(data::TestInsertablePlant::default())
+        .execute(conn)
+        .await?;
+    diesel::insert_into(crate::schema::plantings::table)
+        .values(vec![
+            data::TestInsertablePlanting {
+                id: Uuid::from_u128(1),
+                ..Default::default()
+            },
+            data::TestInsertablePlanting {
+                id: Uuid::from_u128(2),
+                x: 100,
+                ..Default::default()
+            },
+        ])
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn test_fork_compare_and_promote_alternative_layer_succeeds() {
+    let pool =
+        init_test_database(|conn| initial_db_values_with_plantings(conn).scope_boxed()).await;
+    let (token, app) = init_test_app(pool).await;
+
+    let fork_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/-1/alternatives")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(NewAlternativeLayerDto {
+            name: "Scheme B".to_owned(),
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(fork_resp.status(), StatusCode::CREATED);
+    let alternative: LayerDto = test::read_body_json(fork_resp).await;
+    assert!(alternative.is_alternative);
+
+    let plantings_resp = test::TestRequest::get()
+        .uri(&format!(
+            "/api/maps/-1/layers/plants/plantings?layer_id={}&relative_to_date=2023-05-08",
+            alternative.id
+        ))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(plantings_resp.status(), StatusCode::OK);
+    let page: TimelinePage<PlantingDto> = test::read_body_json(plantings_resp).await;
+    assert_eq!(page.results.len(), 2);
+    let copy = page
+        .results
+        .iter()
+        .find(|planting| planting.origin_id == Some(Uuid::from_u128(1)))
+        .expect("the planting should have been copied");
+
+    let move_resp = test::TestRequest::patch()
+        .uri(&format!("/api/maps/-1/layers/plants/plantings/{}", copy.id))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(UpdatePlantingDto::Move(MovePlantingDto {
+            x: 50,
+            y: 50,
+            action_id: Uuid::new_v4(),
+        }))
+        .send_request(&app)
+        .await;
+    assert_eq!(move_resp.status(), StatusCode::OK);
+
+    let compare_resp = test::TestRequest::get()
+        .uri(&format!(
+            "/api/maps/-1/layers/-1/compare?other_layer_id={}",
+            alternative.id
+        ))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(compare_resp.status(), StatusCode::OK);
+    let layer_diff: LayerDiffDto = test::read_body_json(compare_resp).await;
+    assert!(layer_diff.added.is_empty());
+    assert!(layer_diff.removed.is_empty());
+    let [moved] = layer_diff.moved.as_slice() else {
+        panic!("expected exactly one moved planting");
+    };
+    assert_eq!(moved.from.id, Uuid::from_u128(1));
+    assert_eq!((moved.to.x, moved.to.y), (50, 50));
+
+    let promote_resp = test::TestRequest::post()
+        .uri(&format!("/api/maps/-1/layers/{}/promote", alternative.id))
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(PromoteLayerDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(promote_resp.status(), StatusCode::OK);
+    let layers: Vec<LayerDto> = test::read_body_json(promote_resp).await;
+    let main_layers = layers
+        .iter()
+        .filter(|layer| !layer.is_alternative)
+        .map(|layer| layer.id)
+        .collect::<Vec<_>>();
+    assert_eq!(main_layers, vec![alternative.id]);
 }
@@ -2,10 +2,16 @@
 
 use crate::{
     model::{
-        dto::{MapDto, NewMapDto, Page, UpdateMapDto},
-        r#enum::privacy_option::PrivacyOption,
+        dto::{
+            map_collaborators::{MapCollaboratorDto, NewMapCollaboratorDto},
+            LayerDto, MapDto, NewMapDto, Page, UpdateMapDto,
+        },
+        r#enum::{layer_type::LayerType, privacy_option::PrivacyOption},
+    },
+    test::util::{
+        data, dummy_map_polygons::tall_rectangle, init_test_app, init_test_app_for_user,
+        init_test_database,
     },
-    test::util::{dummy_map_polygons::tall_rectangle, init_test_app, init_test_database},
 };
 use actix_web::{
     http::{header, StatusCode},
@@ -142,6 +148,8 @@ async fn test_can_create_map() {
         description: None,
         location: None,
         geometry: tall_rectangle(),
+        layer_types: None,
+        template_map_id: None,
     };
 
     let resp = test::TestRequest::post()
@@ -227,6 +235,8 @@ async fn test_can_update_map() {
         description: None,
         location: None,
         geometry: tall_rectangle(),
+        layer_types: None,
+        template_map_id: None,
     };
 
     let resp = test::TestRequest::post()
@@ -255,3 +265,189 @@ async fn test_can_update_map() {
     let updated_map: MapDto = test::read_body_json(resp).await;
     assert_ne!(updated_map.name, map.name)
 }
+
+fn new_map(name: &str) -> NewMapDto {
+    NewMapDto {
+        name: name.to_owned(),
+        creation_date: NaiveDate::from_ymd_opt(2023, 5, 8).expect("Could not parse date!"),
+        deletion_date: None,
+        last_visit: None,
+        is_inactive: false,
+        zoom_factor: 100,
+        honors: 0,
+        visits: 0,
+        harvested: 0,
+        privacy: PrivacyOption::Private,
+        description: None,
+        location: None,
+        geometry: tall_rectangle(),
+        layer_types: None,
+        template_map_id: None,
+    }
+}
+
+fn layer_names(layers: &[LayerDto]) -> Vec<(LayerType, &str)> {
+    layers
+        .iter()
+        .map(|layer| (layer.type_, layer.name.as_str()))
+        .collect()
+}
+
+#[actix_rt::test]
+async fn test_can_create_map_with_chosen_layers_and_from_template() {
+    let pool = init_test_database(|_| async { Ok(()) }.scope_boxed()).await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let template_resp = test::TestRequest::post()
+        .uri("/api/maps")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(NewMapDto {
+            layer_types: Some(vec![LayerType::Plants, LayerType::Plants]),
+            ..new_map("Template")
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(template_resp.status(), StatusCode::CREATED);
+    let template: MapDto = test::read_body_json(template_resp).await;
+
+    let template_layers_resp = test::TestRequest::get()
+        .uri(&format!("/api/maps/{}/layers", template.id))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    let template_layers: Vec<LayerDto> = test::read_body_json(template_layers_resp).await;
+    assert_eq!(
+        layer_names(&template_layers),
+        vec![(LayerType::Plants, "Plants Layer")]
+    );
+
+    let map_resp = test::TestRequest::post()
+        .uri("/api/maps")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(NewMapDto {
+            template_map_id: Some(template.id),
+            ..new_map("From template")
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(map_resp.status(), StatusCode::CREATED);
+    let map: MapDto = test::read_body_json(map_resp).await;
+
+    let layers_resp = test::TestRequest::get()
+        .uri(&format!("/api/maps/{}/layers", map.id))
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    let layers: Vec<LayerDto> = test::read_body_json(layers_resp).await;
+    assert_eq!(layer_names(&layers), layer_names(&template_layers));
+}
+
+#[actix_rt::test]
+async fn test_create_map_with_invalid_template_creates_nothing() {
+    let pool = init_test_database(|_| async { Ok(()) }.scope_boxed()).await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let missing_resp = test::TestRequest::post()
+        .uri("/api/maps")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(NewMapDto {
+            template_map_id: Some(-100),
+            ..new_map("Missing template")
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(missing_resp.status(), StatusCode::NOT_FOUND);
+
+    let conflicting_resp = test::TestRequest::post()
+        .uri("/api/maps")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(NewMapDto {
+            layer_types: Some(vec![LayerType::Base]),
+            template_map_id: Some(-100),
+            ..new_map("Conflicting options")
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(conflicting_resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/maps")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    let page: Page<MapDto> = test::read_body_json(resp).await;
+    assert!(page.results.is_empty());
+}
+
+#[actix_rt::test]
+async fn test_only_owner_and_collaborators_can_manage_collaborators() {
+    let collaborator = Uuid::from_u128(1);
+    let stranger = Uuid::from_u128(2);
+    let pool = init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::maps::table)
+                .values(data::TestInsertableMap::default())
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (owner_token, owner_app) = init_test_app_for_user(pool.clone(), Uuid::default()).await;
+    let (collaborator_token, collaborator_app) =
+        init_test_app_for_user(pool.clone(), collaborator).await;
+    let (stranger_token, stranger_app) = init_test_app_for_user(pool.clone(), stranger).await;
+
+    let stranger_add_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/collaborators")
+        .insert_header((header::AUTHORIZATION, stranger_token.clone()))
+        .set_json(NewMapCollaboratorDto { user_id: stranger })
+        .send_request(&stranger_app)
+        .await;
+    assert_eq!(stranger_add_resp.status(), StatusCode::FORBIDDEN);
+
+    let add_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/collaborators")
+        .insert_header((header::AUTHORIZATION, owner_token.clone()))
+        .set_json(NewMapCollaboratorDto {
+            user_id: collaborator,
+        })
+        .send_request(&owner_app)
+        .await;
+    assert_eq!(add_resp.status(), StatusCode::CREATED);
+
+    let stranger_find_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/collaborators")
+        .insert_header((header::AUTHORIZATION, stranger_token.clone()))
+        .send_request(&stranger_app)
+        .await;
+    assert_eq!(stranger_find_resp.status(), StatusCode::FORBIDDEN);
+
+    let find_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/collaborators")
+        .insert_header((header::AUTHORIZATION, collaborator_token.clone()))
+        .send_request(&collaborator_app)
+        .await;
+    assert_eq!(find_resp.status(), StatusCode::OK);
+    let collaborators: Vec<MapCollaboratorDto> = test::read_body_json(find_resp).await;
+    let user_ids = collaborators
+        .iter()
+        .map(|dto| dto.user_id)
+        .collect::<Vec<_>>();
+    assert_eq!(user_ids, vec![collaborator]);
+
+    let stranger_remove_resp = test::TestRequest::delete()
+        .uri(&format!("/api/maps/-1/collaborators/{collaborator}"))
+        .insert_header((header::AUTHORIZATION, stranger_token))
+        .send_request(&stranger_app)
+        .await;
+    assert_eq!(stranger_remove_resp.status(), StatusCode::FORBIDDEN);
+
+    let leave_resp = test::TestRequest::delete()
+        .uri(&format!("/api/maps/-1/collaborators/{collaborator}"))
+        .insert_header((header::AUTHORIZATION, collaborator_token))
+        .send_request(&collaborator_app)
+        .await;
+    assert_eq!(leave_resp.status(), StatusCode::OK);
+}
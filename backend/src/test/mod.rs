@@ -5,16 +5,27 @@
 mod auth;
 mod base_layer_image;
 mod blossoms;
+mod calendar;
 mod config;
+mod drawings;
 mod guided_tours;
+mod guilds;
+mod harvests;
 mod layers;
 mod map;
 mod pagination;
+mod photo_layer;
 mod plant;
 mod plant_layer;
 // mod plant_layer_heatmap;
 mod planting_suggestions;
 mod plantings;
+mod rotation;
 mod seed;
+mod shade_layer;
+mod soil_layer;
+mod spacing;
+mod todo_layer;
 mod users;
 pub mod util;
+mod winds_layer;
@@ -0,0 +1,177 @@
+//! Tests for [`crate::controller::photo_layer`].
+
+use actix_http::StatusCode;
+use actix_web::{http::header, test};
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel_async::{
+    pooled_connection::deadpool::Pool, scoped_futures::ScopedFutureExt, AsyncPgConnection,
+    RunQueryDsl,
+};
+use uuid::Uuid;
+
+use crate::{
+    model::{
+        dto::photos::{DeletePhotoDto, NewPhotoDto, PhotoDto, PhotoMetadataDto, UpdatePhotoDto},
+        r#enum::layer_type::LayerType,
+    },
+    test::util::{data, init_test_app, init_test_database},
+};
+
+const PLANTING: Uuid = Uuid::from_u128(1);
+
+async fn init_photo_database() -> Pool<AsyncPgConnection> {
+    init_test_database(|conn| {
+        async {
+            data::insert_map_with_layer(LayerType::Photo, conn).await?;
+            diesel::insert_into(crate::schema::plants::table)
+                .values(data::TestInsertablePlant::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plantings::table)
+                .values(data::TestInsertablePlanting {
+                    id: PLANTING,
+                    ..Default::default()
+                })
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+fn captured_at(month: u32) -> Option<NaiveDateTime> {
+    NaiveDate::from_ymd_opt(2023, month, 1).and_then(|date| date.and_hms_opt(10, 0, 0))
+}
+
+fn new_photo(layer_id: i32, month: u32, planting_id: Option<Uuid>) -> NewPhotoDto {
+    NewPhotoDto {
+        id: None,
+        layer_id,
+        x: 100,
+        y: 200,
+        planting_id,
+        path: format!("/photos/2023-{month:02}.jpg"),
+        description: None,
+        captured_at: captured_at(month),
+        action_id: Uuid::new_v4(),
+    }
+}
+
+#[actix_rt::test]
+async fn test_can_pin_find_by_date_and_delete_photos() {
+    let pool = init_photo_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let may_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/photos")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_photo(-2, 5, None))
+        .send_request(&app)
+        .await;
+    assert_eq!(may_resp.status(), StatusCode::CREATED);
+    let may: PhotoDto = test::read_body_json(may_resp).await;
+
+    let july_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/photos")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_photo(-2, 7, Some(PLANTING)))
+        .send_request(&app)
+        .await;
+    assert_eq!(july_resp.status(), StatusCode::CREATED);
+    let july: PhotoDto = test::read_body_json(july_resp).await;
+    assert_eq!(july.planting_id, Some(PLANTING));
+
+    let summer_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/photos?layer_id=-2&from=2023-06-01&to=2023-07-01")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(summer_resp.status(), StatusCode::OK);
+    let summer: Vec<PhotoDto> = test::read_body_json(summer_resp).await;
+    assert_eq!(
+        summer.iter().map(|photo| photo.id).collect::<Vec<_>>(),
+        vec![july.id]
+    );
+
+    let updated_resp = test::TestRequest::patch()
+        .uri(&format!("/api/maps/-1/layers/photos/{}", may.id))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(UpdatePhotoDto {
+            x: 300,
+            y: 400,
+            planting_id: Some(PLANTING),
+            description: Some("First leaves".to_owned()),
+            captured_at: captured_at(6),
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(updated_resp.status(), StatusCode::OK);
+    let updated: PhotoDto = test::read_body_json(updated_resp).await;
+    assert_eq!((updated.x, updated.y), (300, 400));
+    assert_eq!(updated.path, may.path);
+
+    let deleted_resp = test::TestRequest::delete()
+        .uri(&format!("/api/maps/-1/layers/photos/{}", july.id))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(DeletePhotoDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(deleted_resp.status(), StatusCode::OK);
+
+    let remaining_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/photos?layer_id=-2&from=2023-06-01&to=2023-07-01")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    let remaining: Vec<PhotoDto> = test::read_body_json(remaining_resp).await;
+    assert_eq!(
+        remaining.iter().map(|photo| photo.id).collect::<Vec<_>>(),
+        vec![may.id]
+    );
+}
+
+#[actix_rt::test]
+async fn test_photo_requires_photo_layer_and_supported_image() {
+    let pool = init_photo_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let plants_layer_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/photos")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_photo(-1, 5, None))
+        .send_request(&app)
+        .await;
+    assert_eq!(plants_layer_resp.status(), StatusCode::BAD_REQUEST);
+
+    let without_exif_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/photos/metadata")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .insert_header((header::CONTENT_TYPE, "application/octet-stream"))
+        .set_payload(vec![0xFF, 0xD8, 0xFF, 0xDA])
+        .send_request(&app)
+        .await;
+    assert_eq!(without_exif_resp.status(), StatusCode::OK);
+    let metadata: PhotoMetadataDto = test::read_body_json(without_exif_resp).await;
+    assert_eq!(
+        metadata,
+        PhotoMetadataDto {
+            captured_at: None,
+            x: None,
+            y: None,
+        }
+    );
+
+    let png_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/photos/metadata")
+        .insert_header((header::AUTHORIZATION, token))
+        .insert_header((header::CONTENT_TYPE, "application/octet-stream"))
+        .set_payload(b"\x89PNG\r\n".to_vec())
+        .send_request(&app)
+        .await;
+    assert_eq!(png_resp.status(), StatusCode::BAD_REQUEST);
+}
@@ -2,18 +2,29 @@
 
 use crate::{
     model::{
-        dto::{Page, PlantsSummaryDto},
-        r#enum::plant_spread::PlantSpread,
+        dto::{
+            FacetValueDto, FacetedPage, NewCustomPlantDto, NewSeedDto, Page, PlantDetailDto,
+            PlantsImportReportDto, PlantsSummaryDto,
+        },
+        r#enum::{
+            external_source::ExternalSource, flower_type::FlowerType, life_cycle::LifeCycle,
+            plant_height::PlantHeight, plant_spread::PlantSpread, quantity::Quantity,
+            relation_type::RelationType, soil_water_retention::SoilWaterRetention,
+        },
+    },
+    test::util::{
+        init_test_app, init_test_app_for_user, init_test_app_with_scope, init_test_database,
     },
-    test::util::{init_test_app, init_test_database},
 };
 use actix_web::{
     http::header::{self, CONTENT_TYPE},
     http::StatusCode,
     test,
 };
-use diesel::ExpressionMethods;
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::{ExpressionMethods, QueryDsl};
 use diesel_async::{scoped_futures::ScopedFutureExt, RunQueryDsl};
+use uuid::Uuid;
 
 #[actix_rt::test]
 async fn test_get_all_plants_succeeds() {
@@ -53,6 +64,7 @@ async fn test_get_all_plants_succeeds() {
         id: -1,
         unique_name: "Testia testia".to_string(),
         common_name_en: Some(vec![Some("Testplant".to_string())]),
+        common_name: Some(vec![Some("Testplant".to_owned())]),
         spread: Some(PlantSpread::Wide),
     };
 
@@ -102,6 +114,7 @@ async fn test_get_one_plant_succeeds() {
         id: -1,
         unique_name: "Testia testia".to_string(),
         common_name_en: Some(vec![Some("Testplant".to_string())]),
+        common_name: Some(vec![Some("Testplant".to_owned())]),
         spread: Some(PlantSpread::Wide),
     };
 
@@ -113,6 +126,86 @@ async fn test_get_one_plant_succeeds() {
     assert_eq!(dto, test_plant);
 }
 
+/// The time the article of the test plant was last modified.
+fn last_modified() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2023, 7, 1)
+        .and_then(|date| date.and_hms_opt(12, 0, 0))
+        .unwrap_or_default()
+}
+
+#[actix_rt::test]
+async fn test_get_plant_details_succeeds() {
+    let pool = init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::plants::table)
+                .values((
+                    &crate::schema::plants::id.eq(-1),
+                    &crate::schema::plants::unique_name.eq("Testia testia"),
+                    &crate::schema::plants::common_name_de
+                        .eq(Some(vec![Some("Testpflanze".to_owned())])),
+                    &crate::schema::plants::warning.eq("Toxic"),
+                    &crate::schema::plants::sowing_outdoors.eq(vec![Some(6_i16), Some(7)]),
+                    &crate::schema::plants::life_cycle.eq(vec![Some(LifeCycle::Annual)]),
+                    &crate::schema::plants::genus.eq("Testia"),
+                    &crate::schema::plants::material_uses_and_functions.eq("Fiber"),
+                    &crate::schema::plants::botanic.eq("Annual herb"),
+                    &crate::schema::plants::soil_water_retention
+                        .eq(vec![Some(SoilWaterRetention::Moist)]),
+                    &crate::schema::plants::flower_type.eq(FlowerType::Monoecious),
+                    &crate::schema::plants::plant_references
+                        .eq(vec![Some("https://example.com/testia".to_owned())]),
+                    &crate::schema::plants::is_tree.eq(false),
+                    &crate::schema::plants::article_last_modified_at.eq(last_modified()),
+                ))
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let resp = test::TestRequest::get()
+        .uri("/api/plants/-1/details")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let dto: PlantDetailDto = test::read_body_json(resp).await;
+    assert_eq!(dto.unique_name, "Testia testia");
+    assert_eq!(
+        dto.common_name_de,
+        Some(vec![Some("Testpflanze".to_owned())])
+    );
+    assert_eq!(dto.warning.as_deref(), Some("Toxic"));
+    assert_eq!(dto.sowing_outdoors, Some(vec![Some(6), Some(7)]));
+    assert_eq!(dto.life_cycle, Some(vec![Some(LifeCycle::Annual)]));
+    assert_eq!(dto.edible_uses_en, None);
+    assert_eq!(dto.genus.as_deref(), Some("Testia"));
+    assert_eq!(dto.material_uses_and_functions.as_deref(), Some("Fiber"));
+    assert_eq!(dto.botanic.as_deref(), Some("Annual herb"));
+    assert_eq!(
+        dto.soil_water_retention,
+        Some(vec![Some(SoilWaterRetention::Moist)])
+    );
+    assert_eq!(dto.flower_type, Some(FlowerType::Monoecious));
+    assert_eq!(
+        dto.plant_references,
+        Some(vec![Some("https://example.com/testia".to_owned())])
+    );
+    assert_eq!(dto.is_tree, Some(false));
+    assert_eq!(dto.article_last_modified_at, Some(last_modified()));
+
+    let missing_resp = test::TestRequest::get()
+        .uri("/api/plants/-2/details")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(missing_resp.status(), StatusCode::NOT_FOUND);
+}
+
 #[actix_rt::test]
 async fn test_search_plants_succeeds() {
     let pool = init_test_database(|conn| {
@@ -151,6 +244,7 @@ async fn test_search_plants_succeeds() {
         id: -1,
         unique_name: "Testia testia".to_string(),
         common_name_en: Some(vec![Some("Testplant".to_string())]),
+        common_name: Some(vec![Some("Testplant".to_owned())]),
         spread: Some(PlantSpread::Wide),
     };
 
@@ -161,3 +255,580 @@ async fn test_search_plants_succeeds() {
 
     assert!(page.results.contains(&test_plant));
 }
+
+#[actix_rt::test]
+async fn test_search_plants_in_german_succeeds() {
+    let pool = init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::plants::table)
+                .values(vec![
+                    (
+                        crate::schema::plants::id.eq(-1),
+                        crate::schema::plants::unique_name.eq("Testia testia"),
+                        crate::schema::plants::common_name_en
+                            .eq(Some(vec![Some("Testplant".to_owned())])),
+                        crate::schema::plants::common_name_de
+                            .eq(Some(vec![Some("Sauerkirsche".to_owned())])),
+                        crate::schema::plants::alternate_name.eq(None::<String>),
+                    ),
+                    (
+                        crate::schema::plants::id.eq(-2),
+                        crate::schema::plants::unique_name.eq("Testia secunda"),
+                        crate::schema::plants::common_name_en
+                            .eq(Some(vec![Some("Other".to_owned())])),
+                        crate::schema::plants::common_name_de.eq(None),
+                        crate::schema::plants::alternate_name
+                            .eq(Some("Sauerkirschbaum".to_owned())),
+                    ),
+                ])
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let resp = test::TestRequest::get()
+        .uri("/api/plants?name=Sauerkirsche&per_page=10")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .insert_header((header::ACCEPT_LANGUAGE, "de-DE,de;q=0.9,en;q=0.8"))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let page: Page<PlantsSummaryDto> = test::read_body_json(resp).await;
+    let names: Vec<_> = page
+        .results
+        .iter()
+        .map(|plant| (plant.id, plant.common_name.clone()))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            (-1, Some(vec![Some("Sauerkirsche".to_owned())])),
+            (-2, Some(vec![Some("Other".to_owned())])),
+        ]
+    );
+
+    let lang_resp = test::TestRequest::get()
+        .uri("/api/plants?name=Sauerkirsche&lang=en")
+        .insert_header((header::AUTHORIZATION, token))
+        .insert_header((header::ACCEPT_LANGUAGE, "de"))
+        .send_request(&app)
+        .await;
+    assert_eq!(lang_resp.status(), StatusCode::OK);
+    let lang_page: Page<PlantsSummaryDto> = test::read_body_json(lang_resp).await;
+    let lang_names: Vec<_> = lang_page
+        .results
+        .into_iter()
+        .map(|plant| plant.common_name)
+        .collect();
+    assert_eq!(
+        lang_names,
+        vec![
+            Some(vec![Some("Testplant".to_owned())]),
+            Some(vec![Some("Other".to_owned())]),
+        ]
+    );
+}
+
+#[actix_rt::test]
+async fn test_filter_plants_by_attributes_succeeds() {
+    let pool = init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::plants::table)
+                .values(vec![
+                    (
+                        &crate::schema::plants::id.eq(-1),
+                        &crate::schema::plants::unique_name.eq("Testia testia"),
+                        &crate::schema::plants::life_cycle.eq(Some(vec![Some(LifeCycle::Annual)])),
+                        &crate::schema::plants::spread.eq(PlantSpread::Narrow),
+                        &crate::schema::plants::hardiness_zone.eq("[4,7)"),
+                    ),
+                    (
+                        &crate::schema::plants::id.eq(-2),
+                        &crate::schema::plants::unique_name.eq("Testia testium"),
+                        &crate::schema::plants::life_cycle.eq(Some(vec![
+                            Some(LifeCycle::Annual),
+                            Some(LifeCycle::Perennial),
+                        ])),
+                        &crate::schema::plants::spread.eq(PlantSpread::Wide),
+                        &crate::schema::plants::hardiness_zone.eq("[7,9)"),
+                    ),
+                    (
+                        &crate::schema::plants::id.eq(-3),
+                        &crate::schema::plants::unique_name.eq("Testia testiae"),
+                        &crate::schema::plants::life_cycle
+                            .eq(Some(vec![Some(LifeCycle::Biennial)])),
+                        &crate::schema::plants::spread.eq(PlantSpread::Medium),
+                        &crate::schema::plants::hardiness_zone.eq("[2,3)"),
+                    ),
+                ])
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let life_cycle_resp = test::TestRequest::get()
+        .uri("/api/plants?life_cycle=annual&min_spread=medium")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(life_cycle_resp.status(), StatusCode::OK);
+    let life_cycle_page: FacetedPage<PlantsSummaryDto> =
+        test::read_body_json(life_cycle_resp).await;
+    assert_eq!(
+        life_cycle_page
+            .results
+            .iter()
+            .map(|p| p.id)
+            .collect::<Vec<_>>(),
+        vec![-2]
+    );
+
+    let zone_resp = test::TestRequest::get()
+        .uri("/api/plants?hardiness_zone=7")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(zone_resp.status(), StatusCode::OK);
+    let zone_page: FacetedPage<PlantsSummaryDto> = test::read_body_json(zone_resp).await;
+    let mut ids = zone_page.results.iter().map(|p| p.id).collect::<Vec<_>>();
+    ids.sort_unstable();
+    assert_eq!(ids, vec![-2, -1]);
+}
+
+#[actix_rt::test]
+async fn test_plant_facets_count_matching_plants() {
+    let pool = init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::plants::table)
+                .values(vec![
+                    (
+                        &crate::schema::plants::id.eq(-1),
+                        &crate::schema::plants::unique_name.eq("Testia testia"),
+                        &crate::schema::plants::life_cycle.eq(Some(vec![Some(LifeCycle::Annual)])),
+                        &crate::schema::plants::edible.eq(true),
+                    ),
+                    (
+                        &crate::schema::plants::id.eq(-2),
+                        &crate::schema::plants::unique_name.eq("Testia testium"),
+                        &crate::schema::plants::life_cycle.eq(Some(vec![
+                            Some(LifeCycle::Annual),
+                            Some(LifeCycle::Perennial),
+                        ])),
+                        &crate::schema::plants::edible.eq(false),
+                    ),
+                ])
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let all_resp = test::TestRequest::get()
+        .uri("/api/plants?per_page=1")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(all_resp.status(), StatusCode::OK);
+    let all_page: FacetedPage<PlantsSummaryDto> = test::read_body_json(all_resp).await;
+    assert_eq!(all_page.results.len(), 1);
+
+    let life_cycle = all_page
+        .facets
+        .iter()
+        .find(|facet| facet.attribute == "life_cycle")
+        .unwrap();
+    assert_eq!(
+        life_cycle.values,
+        vec![
+            FacetValueDto {
+                value: "annual".to_owned(),
+                count: 2
+            },
+            FacetValueDto {
+                value: "perennial".to_owned(),
+                count: 1
+            },
+        ]
+    );
+
+    let edible_resp = test::TestRequest::get()
+        .uri("/api/plants?edible=true")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(edible_resp.status(), StatusCode::OK);
+    let edible_page: FacetedPage<PlantsSummaryDto> = test::read_body_json(edible_resp).await;
+    let edible = edible_page
+        .facets
+        .iter()
+        .find(|facet| facet.attribute == "edible")
+        .unwrap();
+    assert_eq!(
+        edible.values,
+        vec![FacetValueDto {
+            value: "true".to_owned(),
+            count: 1
+        }]
+    );
+}
+
+#[actix_rt::test]
+async fn test_create_custom_plant_inherits_base_plant() {
+    let pool = init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::plants::table)
+                .values(vec![
+                    (
+                        crate::schema::plants::id.eq(-1),
+                        crate::schema::plants::unique_name.eq("Solanum lycopersicum"),
+                        crate::schema::plants::spread.eq(PlantSpread::Wide),
+                        crate::schema::plants::height.eq(PlantHeight::Medium),
+                    ),
+                    (
+                        crate::schema::plants::id.eq(-2),
+                        crate::schema::plants::unique_name.eq("Ocimum basilicum"),
+                        crate::schema::plants::spread.eq(PlantSpread::Narrow),
+                        crate::schema::plants::height.eq(PlantHeight::Low),
+                    ),
+                ])
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::relations::table)
+                .values((
+                    crate::schema::relations::plant1.eq(-1),
+                    crate::schema::relations::plant2.eq(-2),
+                    crate::schema::relations::relation.eq(RelationType::Companion),
+                ))
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let user_id = Uuid::new_v4();
+    let (token, app) = init_test_app_for_user(pool.clone(), user_id).await;
+
+    let new_plant = NewCustomPlantDto {
+        base_plant_id: -1,
+        unique_name: "Solanum lycopersicum 'Black Krim'".to_owned(),
+        common_name_en: Some(vec![Some("Black Krim".to_owned())]),
+        common_name_de: None,
+        height: Some(PlantHeight::High),
+        spread: None,
+        growth_rate: None,
+        sowing_outdoors: None,
+        harvest_time: None,
+        warning: None,
+    };
+    let resp = test::TestRequest::post()
+        .uri("/api/plants")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(new_plant)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let dto: PlantDetailDto = test::read_body_json(resp).await;
+    assert_eq!(dto.base_plant_id, Some(-1));
+    assert_eq!(dto.owner_id, Some(user_id));
+    assert_eq!(dto.height, Some(PlantHeight::High));
+    assert_eq!(dto.spread, Some(PlantSpread::Wide));
+    assert!(!dto.submitted_for_review);
+
+    let mut conn = pool.get().await.unwrap();
+    let relations = crate::schema::relations::table
+        .select((
+            crate::schema::relations::plant2,
+            crate::schema::relations::relation,
+        ))
+        .filter(crate::schema::relations::plant1.eq(dto.id))
+        .load::<(i32, RelationType)>(&mut conn)
+        .await
+        .unwrap();
+    assert!(matches!(
+        relations.as_slice(),
+        [(-2, RelationType::Companion)]
+    ));
+}
+
+#[actix_rt::test]
+async fn test_custom_plant_is_only_visible_to_owner() {
+    let owner_id = Uuid::new_v4();
+    let pool = init_test_database(|conn| {
+        async move {
+            diesel::insert_into(crate::schema::plants::table)
+                .values((
+                    crate::schema::plants::id.eq(-1),
+                    crate::schema::plants::unique_name.eq("Testia privata"),
+                    crate::schema::plants::owner_id.eq(owner_id),
+                ))
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (owner_token, owner_app) = init_test_app_for_user(pool.clone(), owner_id).await;
+    let (other_token, other_app) = init_test_app_for_user(pool.clone(), Uuid::new_v4()).await;
+
+    let resp = test::TestRequest::get()
+        .uri("/api/plants/-1")
+        .insert_header((header::AUTHORIZATION, other_token.clone()))
+        .send_request(&other_app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let search_resp = test::TestRequest::get()
+        .uri("/api/plants?name=Testia%20privata")
+        .insert_header((header::AUTHORIZATION, other_token.clone()))
+        .send_request(&other_app)
+        .await;
+    assert_eq!(search_resp.status(), StatusCode::OK);
+    let page: Page<PlantsSummaryDto> = test::read_body_json(search_resp).await;
+    assert!(page.results.iter().all(|plant| plant.id != -1));
+
+    let other_submit_resp = test::TestRequest::post()
+        .uri("/api/plants/-1/submit")
+        .insert_header((header::AUTHORIZATION, other_token))
+        .send_request(&other_app)
+        .await;
+    assert_eq!(other_submit_resp.status(), StatusCode::NOT_FOUND);
+
+    let owner_submit_resp = test::TestRequest::post()
+        .uri("/api/plants/-1/submit")
+        .insert_header((header::AUTHORIZATION, owner_token))
+        .send_request(&owner_app)
+        .await;
+    assert_eq!(owner_submit_resp.status(), StatusCode::OK);
+    let dto: PlantDetailDto = test::read_body_json(owner_submit_resp).await;
+    assert!(dto.submitted_for_review);
+}
+
+#[actix_rt::test]
+async fn test_custom_plant_names_are_unique_per_owner() {
+    let private_owner_id = Uuid::new_v4();
+    let pool = init_test_database(|conn| {
+        async move {
+            diesel::insert_into(crate::schema::plants::table)
+                .values(vec![
+                    (
+                        crate::schema::plants::id.eq(-1),
+                        crate::schema::plants::unique_name.eq("Solanum lycopersicum"),
+                        crate::schema::plants::owner_id.eq(None),
+                    ),
+                    (
+                        crate::schema::plants::id.eq(-2),
+                        crate::schema::plants::unique_name.eq("Solanum lycopersicum 'Secret'"),
+                        crate::schema::plants::owner_id.eq(Some(private_owner_id)),
+                    ),
+                ])
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app_for_user(pool.clone(), Uuid::new_v4()).await;
+    let variety = |unique_name: &str| NewCustomPlantDto {
+        base_plant_id: -1,
+        unique_name: unique_name.to_owned(),
+        common_name_en: None,
+        common_name_de: None,
+        height: None,
+        spread: None,
+        growth_rate: None,
+        sowing_outdoors: None,
+        harvest_time: None,
+        warning: None,
+    };
+
+    let other_owner_resp = test::TestRequest::post()
+        .uri("/api/plants")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(variety("Solanum lycopersicum 'Secret'"))
+        .send_request(&app)
+        .await;
+    assert_eq!(other_owner_resp.status(), StatusCode::CREATED);
+
+    let duplicate_resp = test::TestRequest::post()
+        .uri("/api/plants")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(variety("Solanum lycopersicum 'Secret'"))
+        .send_request(&app)
+        .await;
+    assert_eq!(duplicate_resp.status(), StatusCode::CONFLICT);
+
+    let private_seed_resp = test::TestRequest::post()
+        .uri("/api/seeds")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(NewSeedDto {
+            name: "Secret seeds".to_owned(),
+            variety: None,
+            plant_id: Some(-2),
+            harvest_year: 2022,
+            quantity: Quantity::Enough,
+            use_by: None,
+            origin: None,
+            taste: None,
+            yield_: None,
+            generation: None,
+            quality: None,
+            price: None,
+            notes: None,
+            parent_seed_id: None,
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(private_seed_resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn test_import_plants_dataset_is_idempotent() {
+    let pool = init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::plants::table)
+                .values(vec![
+                    (
+                        crate::schema::plants::id.eq(-1),
+                        crate::schema::plants::unique_name.eq("Testia obsoleta"),
+                        crate::schema::plants::external_source.eq(ExternalSource::PermaPeople),
+                        crate::schema::plants::version.eq(1),
+                    ),
+                    (
+                        crate::schema::plants::id.eq(-2),
+                        crate::schema::plants::unique_name.eq("Testia aliena"),
+                        crate::schema::plants::external_source.eq(ExternalSource::Reinsaat),
+                        crate::schema::plants::version.eq(1),
+                    ),
+                    (
+                        crate::schema::plants::id.eq(-3),
+                        crate::schema::plants::unique_name.eq("Testia mutata"),
+                        crate::schema::plants::external_source.eq(ExternalSource::PermaPeople),
+                        crate::schema::plants::version.eq(2),
+                    ),
+                ])
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app_with_scope(pool.clone(), "admin").await;
+
+    let dataset = r#"{
+        "plants": [
+            { "unique_name": "Testia nova", "life_cycle": ["annual"] },
+            { "unique_name": "Testia mutata", "spread": "wide" },
+            { "unique_name": "Testia aliena" }
+        ],
+        "relations": [
+            { "plant1": "Testia nova", "plant2": "Testia mutata", "relation": "companion" }
+        ]
+    }"#;
+
+    let resp = test::TestRequest::post()
+        .uri("/api/plants/import?external_source=permapeople&format=json")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_payload(dataset)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let report: PlantsImportReportDto = test::read_body_json(resp).await;
+    assert_eq!(report.added, vec!["Testia nova".to_owned()]);
+    assert_eq!(report.changed, vec!["Testia mutata".to_owned()]);
+    assert!(report.removed.contains(&"Testia obsoleta".to_owned()));
+    assert_eq!(report.skipped, vec!["Testia aliena".to_owned()]);
+    assert_eq!(report.relations, 1);
+
+    let version = crate::schema::plants::table
+        .select(crate::schema::plants::version)
+        .filter(crate::schema::plants::id.eq(-3))
+        .get_result::<Option<i16>>(&mut pool.get().await.unwrap())
+        .await
+        .unwrap();
+    assert_eq!(version, Some(3));
+
+    let repeated_resp = test::TestRequest::post()
+        .uri("/api/plants/import?external_source=permapeople&format=json")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_payload(dataset)
+        .send_request(&app)
+        .await;
+    assert_eq!(repeated_resp.status(), StatusCode::OK);
+    let repeated_report: PlantsImportReportDto = test::read_body_json(repeated_resp).await;
+    assert!(repeated_report.added.is_empty());
+    assert!(repeated_report.changed.is_empty());
+    assert!(repeated_report
+        .removed
+        .contains(&"Testia obsoleta".to_owned()));
+}
+
+#[actix_rt::test]
+async fn test_import_plants_csv_succeeds() {
+    let pool = init_test_database(|_| async { Ok(()) }.scope_boxed()).await;
+    let (token, app) = init_test_app_with_scope(pool.clone(), "admin").await;
+
+    let dataset = "unique_name,common_name_en,life_cycle,heat_zone,sowing_outdoors,unknown\n\
+                   Testia nova,\"Testplant, Other\",annual,7,\"[8,9]\",ignored\n";
+
+    let resp = test::TestRequest::post()
+        .uri("/api/plants/import?external_source=reinsaat&format=csv")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_payload(dataset)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let report: PlantsImportReportDto = test::read_body_json(resp).await;
+    assert_eq!(report.added, vec!["Testia nova".to_owned()]);
+
+    let mut conn = pool.get().await.unwrap();
+    let (common_name_en, heat_zone, sowing_outdoors) = crate::schema::plants::table
+        .select((
+            crate::schema::plants::common_name_en,
+            crate::schema::plants::heat_zone,
+            crate::schema::plants::sowing_outdoors,
+        ))
+        .filter(crate::schema::plants::unique_name.eq("Testia nova"))
+        .get_result::<(
+            Option<Vec<Option<String>>>,
+            Option<i16>,
+            Option<Vec<Option<i16>>>,
+        )>(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(
+        common_name_en,
+        Some(vec![Some("Testplant".to_owned()), Some("Other".to_owned())])
+    );
+    assert_eq!(heat_zone, Some(7));
+    assert_eq!(sowing_outdoors, Some(vec![Some(8), Some(9)]));
+}
+
+#[actix_rt::test]
+async fn test_import_plants_requires_admin() {
+    let pool = init_test_database(|_| async { Ok(()) }.scope_boxed()).await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let resp = test::TestRequest::post()
+        .uri("/api/plants/import?external_source=reinsaat&format=json")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_payload(r#"{ "plants": [] }"#)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
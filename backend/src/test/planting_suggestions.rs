@@ -2,10 +2,16 @@
 
 use crate::{
     model::{
-        dto::{Page, PlantsSummaryDto},
-        r#enum::{plant_spread::PlantSpread, quantity::Quantity},
+        dto::{
+            CompanionSuggestionDto, DiversityCriterion, DiversityGapDto, DiversitySuggestionDto,
+            Page, PlantsSummaryDto,
+        },
+        r#enum::{
+            herbaceous_or_woody::HerbaceousOrWoody, plant_spread::PlantSpread, quantity::Quantity,
+            relation_type::RelationType,
+        },
     },
-    test::util::{init_test_app_for_user, init_test_database},
+    test::util::{data, init_test_app_for_user, init_test_database},
 };
 use actix_web::{
     http::header::{self, CONTENT_TYPE},
@@ -14,7 +20,7 @@ use actix_web::{
 };
 use diesel::ExpressionMethods;
 use diesel_async::{scoped_futures::ScopedFutureExt, RunQueryDsl};
-use uuid::uuid;
+use uuid::{uuid, Uuid};
 
 /// Given a plant that is seasonal (no-data) and available (Enough)
 /// and a plant that is seasonal (no-data) and not available (Nothing)
@@ -98,6 +104,7 @@ async fn test_find_only_available_plants() {
         id: -1,
         unique_name: "Testia testia".to_string(),
         common_name_en: Some(vec![Some("Testplant1".to_string())]),
+        common_name: Some(vec![Some("Testplant1".to_owned())]),
         spread: Some(PlantSpread::Wide),
     };
 
@@ -188,8 +195,257 @@ async fn test_find_only_available_seasonal_plants() {
         id: -1,
         unique_name: "Testia testia".to_string(),
         common_name_en: Some(vec![Some("Testplant1".to_string())]),
+        common_name: Some(vec![Some("Testplant1".to_owned())]),
         spread: Some(PlantSpread::Wide),
     };
 
     assert!(page.results.contains(&test_plant));
 }
+
+/// Given plantings of rosaceae and lamiaceae that are mostly shrubs and woody
+/// return plants of a missing family first and skip plants that fill no gap
+#[actix_rt::test]
+async fn test_find_diversity_suggestions() {
+    let user_id = uuid!("00000000-0000-0000-0000-000000000000");
+    let pool = init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::maps::table)
+                .values(data::TestInsertableMap::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::layers::table)
+                .values(data::TestInsertableLayer::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plants::table)
+                .values(vec![
+                    (
+                        crate::schema::plants::id.eq(-1),
+                        crate::schema::plants::unique_name.eq("Testia planta"),
+                        crate::schema::plants::family.eq("Rosaceae"),
+                        crate::schema::plants::functions.eq("Fruit"),
+                        crate::schema::plants::ecosystem_niche.eq("Shrub"),
+                        crate::schema::plants::herbaceous_or_woody.eq(HerbaceousOrWoody::Woody),
+                    ),
+                    (
+                        crate::schema::plants::id.eq(-2),
+                        crate::schema::plants::unique_name.eq("Testia herba"),
+                        crate::schema::plants::family.eq("Lamiaceae"),
+                        crate::schema::plants::functions.eq("fruit"),
+                        crate::schema::plants::ecosystem_niche.eq("Herb"),
+                        crate::schema::plants::herbaceous_or_woody
+                            .eq(HerbaceousOrWoody::Herbaceous),
+                    ),
+                    (
+                        crate::schema::plants::id.eq(-3),
+                        crate::schema::plants::unique_name.eq("Testia fabacea"),
+                        crate::schema::plants::family.eq("Fabaceae"),
+                        crate::schema::plants::functions.eq("Nitrogen fixer, Fruit"),
+                        crate::schema::plants::ecosystem_niche.eq("Shrub"),
+                        crate::schema::plants::herbaceous_or_woody
+                            .eq(HerbaceousOrWoody::Herbaceous),
+                    ),
+                    (
+                        crate::schema::plants::id.eq(-4),
+                        crate::schema::plants::unique_name.eq("Testia rosacea"),
+                        crate::schema::plants::family.eq("Rosaceae"),
+                        crate::schema::plants::functions.eq("Fruit"),
+                        crate::schema::plants::ecosystem_niche.eq("Herb"),
+                        crate::schema::plants::herbaceous_or_woody.eq(HerbaceousOrWoody::Woody),
+                    ),
+                    (
+                        crate::schema::plants::id.eq(-5),
+                        crate::schema::plants::unique_name.eq("Testia nihil"),
+                        crate::schema::plants::family.eq("Rosaceae"),
+                        crate::schema::plants::functions.eq("Fruit"),
+                        crate::schema::plants::ecosystem_niche.eq("Shrub"),
+                        crate::schema::plants::herbaceous_or_woody.eq(HerbaceousOrWoody::Woody),
+                    ),
+                ])
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plantings::table)
+                .values(vec![
+                    data::TestInsertablePlanting {
+                        id: Uuid::new_v4(),
+                        plant_id: -1,
+                        ..Default::default()
+                    },
+                    data::TestInsertablePlanting {
+                        id: Uuid::new_v4(),
+                        plant_id: -1,
+                        ..Default::default()
+                    },
+                    data::TestInsertablePlanting {
+                        id: Uuid::new_v4(),
+                        plant_id: -2,
+                        ..Default::default()
+                    },
+                ])
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app_for_user(pool, user_id).await;
+
+    let resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/plants/suggestions?suggestion_type=diversity&relative_to_date=2023-04-01&layer_id=-1")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let page: Page<DiversitySuggestionDto> = test::read_body_json(resp).await;
+    let ranking = page
+        .results
+        .iter()
+        .map(|suggestion| (suggestion.plant.id, suggestion.score))
+        .collect::<Vec<_>>();
+    assert_eq!(ranking, vec![(-3, 5), (-4, 1)]);
+
+    let fabacea = page
+        .results
+        .iter()
+        .find(|suggestion| suggestion.plant.id == -3)
+        .unwrap();
+    assert!(fabacea.gaps.contains(&DiversityGapDto {
+        criterion: DiversityCriterion::Family,
+        value: "Fabaceae".to_owned(),
+        missing: true,
+    }));
+    assert!(fabacea.gaps.contains(&DiversityGapDto {
+        criterion: DiversityCriterion::HerbaceousOrWoody,
+        value: "herbaceous".to_owned(),
+        missing: false,
+    }));
+    assert!(fabacea
+        .explanation
+        .contains("missing function nitrogen fixer"));
+}
+
+/// Given plantings of basil and fennel
+/// return plants with more companion than antagonist plantings
+/// together with the plantings justifying the suggestion
+#[actix_rt::test]
+async fn test_find_companion_suggestions() {
+    let user_id = uuid!("00000000-0000-0000-0000-000000000000");
+    let basil_plantings = [Uuid::from_u128(1), Uuid::from_u128(2)];
+    let fennel_planting = Uuid::from_u128(3);
+    let pool = init_test_database(|conn| {
+        async move {
+            diesel::insert_into(crate::schema::maps::table)
+                .values(data::TestInsertableMap::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::layers::table)
+                .values(data::TestInsertableLayer::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plants::table)
+                .values(vec![
+                    (
+                        crate::schema::plants::id.eq(-1),
+                        crate::schema::plants::unique_name.eq("Testia basilicum"),
+                    ),
+                    (
+                        crate::schema::plants::id.eq(-2),
+                        crate::schema::plants::unique_name.eq("Testia foeniculum"),
+                    ),
+                    (
+                        crate::schema::plants::id.eq(-3),
+                        crate::schema::plants::unique_name.eq("Testia lycopersicum"),
+                    ),
+                    (
+                        crate::schema::plants::id.eq(-4),
+                        crate::schema::plants::unique_name.eq("Testia carota"),
+                    ),
+                    (
+                        crate::schema::plants::id.eq(-5),
+                        crate::schema::plants::unique_name.eq("Testia cepa"),
+                    ),
+                ])
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::relations::table)
+                .values(vec![
+                    (
+                        crate::schema::relations::plant1.eq(-3),
+                        crate::schema::relations::plant2.eq(-1),
+                        crate::schema::relations::relation.eq(RelationType::Companion),
+                    ),
+                    (
+                        crate::schema::relations::plant1.eq(-2),
+                        crate::schema::relations::plant2.eq(-3),
+                        crate::schema::relations::relation.eq(RelationType::Antagonist),
+                    ),
+                    (
+                        crate::schema::relations::plant1.eq(-2),
+                        crate::schema::relations::plant2.eq(-4),
+                        crate::schema::relations::relation.eq(RelationType::Companion),
+                    ),
+                    (
+                        crate::schema::relations::plant1.eq(-1),
+                        crate::schema::relations::plant2.eq(-5),
+                        crate::schema::relations::relation.eq(RelationType::Antagonist),
+                    ),
+                    (
+                        crate::schema::relations::plant1.eq(-1),
+                        crate::schema::relations::plant2.eq(-2),
+                        crate::schema::relations::relation.eq(RelationType::Companion),
+                    ),
+                ])
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plantings::table)
+                .values(vec![
+                    data::TestInsertablePlanting {
+                        id: basil_plantings[0],
+                        plant_id: -1,
+                        ..Default::default()
+                    },
+                    data::TestInsertablePlanting {
+                        id: basil_plantings[1],
+                        plant_id: -1,
+                        ..Default::default()
+                    },
+                    data::TestInsertablePlanting {
+                        id: fennel_planting,
+                        plant_id: -2,
+                        ..Default::default()
+                    },
+                ])
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app_for_user(pool, user_id).await;
+
+    let resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/plants/suggestions?suggestion_type=companion&relative_to_date=2023-04-01")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let page: Page<CompanionSuggestionDto> = test::read_body_json(resp).await;
+    let ranking = page
+        .results
+        .iter()
+        .map(|suggestion| (suggestion.plant.id, suggestion.score))
+        .collect::<Vec<_>>();
+    assert_eq!(ranking, vec![(-4, 1), (-3, 1)]);
+
+    let tomato = page
+        .results
+        .iter()
+        .find(|suggestion| suggestion.plant.id == -3)
+        .unwrap();
+    assert_eq!(tomato.companion_plantings, basil_plantings.to_vec());
+    assert_eq!(tomato.antagonist_plantings, vec![fennel_planting]);
+}
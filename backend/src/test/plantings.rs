@@ -5,6 +5,7 @@ use std::ops::Add;
 use actix_http::StatusCode;
 use actix_web::{http::header, test};
 use chrono::{Days, NaiveDate};
+use diesel::ExpressionMethods;
 use diesel_async::{scoped_futures::ScopedFutureExt, RunQueryDsl};
 use uuid::Uuid;
 
@@ -12,17 +13,19 @@ use crate::{
     model::{
         dto::{
             plantings::{
-                DeletePlantingDto, MovePlantingDto, NewPlantingDto, PlantingDto, UpdatePlantingDto,
+                DeletePlantingDto, MovePlantingDto, NewPlantingDto, PlantingDto,
+                UpdateNotesPlantingDto, UpdatePhotosPlantingDto, UpdatePlantingDto,
+                UpdateSeedPlantingDto, UpdateStatusPlantingDto,
             },
             TimelinePage,
         },
-        r#enum::layer_type::LayerType,
+        r#enum::{layer_type::LayerType, planting_status::PlantingStatus, quantity::Quantity},
     },
     service::plantings::TIME_LINE_LOADING_OFFSET_DAYS,
     test::util::data,
 };
 
-use crate::test::util::{init_test_app, init_test_database};
+use crate::test::util::{init_test_app, init_test_app_for_user, init_test_database};
 
 #[actix_rt::test]
 async fn test_can_search_plantings() {
@@ -443,3 +446,315 @@ async fn test_added_planting_outside_loading_offset_is_not_in_timeline() {
     let page: TimelinePage<PlantingDto> = test::read_body_json(resp).await;
     assert_eq!(page.results.len(), 0);
 }
+
+#[actix_rt::test]
+async fn test_search_plantings_with_invalid_area_fails() {
+    let pool = init_test_database(|_| async { Ok(()) }.scope_boxed()).await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    for area in [
+        "x_min=0&y_min=0&x_max=100",
+        "x_min=100&y_min=0&x_max=0&y_max=100",
+        "center_x=0&center_y=0",
+        "center_x=0&center_y=0&radius=-1",
+        "polygon=0,0,100,0",
+        "polygon=0,0,100,0,100,a",
+    ] {
+        let resp = test::TestRequest::get()
+            .uri(&format!(
+                "/api/maps/-1/layers/plants/plantings?relative_to_date=2023-05-08&{area}"
+            ))
+            .insert_header((header::AUTHORIZATION, token.clone()))
+            .send_request(&app)
+            .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{area}");
+    }
+}
+
+#[actix_rt::test]
+async fn test_can_search_plantings_in_area() {
+    let origin = Uuid::from_u128(1);
+    let near = Uuid::from_u128(2);
+    let center = Uuid::from_u128(3);
+    let far = Uuid::from_u128(4);
+    let pool = init_test_database(|conn| {
+        async move {
+            diesel::insert_into(crate::schema::maps::table)
+                .values(data::TestInsertableMap::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::layers::table)
+                .values(data::TestInsertableLayer::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plants::table)
+                .values(data::TestInsertablePlant::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plantings::table)
+                .values(
+                    [
+                        (origin, 0, 0),
+                        (near, 100, 100),
+                        (center, 500, 500),
+                        (far, 1000, 0),
+                    ]
+                    .into_iter()
+                    .map(|(id, x, y)| data::TestInsertablePlanting {
+                        id,
+                        x,
+                        y,
+                        ..Default::default()
+                    })
+                    .collect::<Vec<_>>(),
+                )
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    for (area, expected) in [
+        ("x_min=0&y_min=0&x_max=200&y_max=200", vec![origin, near]),
+        (
+            "x_min=400&y_min=-100&x_max=1000&y_max=500",
+            vec![center, far],
+        ),
+        ("center_x=0&center_y=0&radius=150", vec![origin, near]),
+        ("center_x=500&center_y=500&radius=100", vec![center]),
+        ("polygon=900,-100,1100,-100,1100,100", vec![far]),
+        ("polygon=0,0,1000,0,1000,600", vec![origin, far]),
+        (
+            "x_min=0&y_min=0&x_max=1000&y_max=1000&center_x=0&center_y=0&radius=150",
+            vec![origin, near],
+        ),
+    ] {
+        let resp = test::TestRequest::get()
+            .uri(&format!(
+                "/api/maps/-1/layers/plants/plantings?relative_to_date=2023-05-08&{area}"
+            ))
+            .insert_header((header::AUTHORIZATION, token.clone()))
+            .send_request(&app)
+            .await;
+        assert_eq!(resp.status(), StatusCode::OK, "{area}");
+
+        let page: TimelinePage<PlantingDto> = test::read_body_json(resp).await;
+        let mut ids = page
+            .results
+            .iter()
+            .map(|planting| planting.id)
+            .collect::<Vec<_>>();
+        ids.sort();
+        assert_eq!(ids, expected, "{area}");
+    }
+}
+
+#[actix_rt::test]
+async fn test_can_update_planting_metadata() {
+    let planting_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let pool = init_test_database(|conn| {
+        async move {
+            diesel::insert_into(crate::schema::maps::table)
+                .values(data::TestInsertableMap::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::layers::table)
+                .values(data::TestInsertableLayer::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plants::table)
+                .values(data::TestInsertablePlant::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plantings::table)
+                .values(data::TestInsertablePlanting {
+                    id: planting_id,
+                    ..Default::default()
+                })
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::seeds::table)
+                .values((
+                    &crate::schema::seeds::id.eq(-1),
+                    &crate::schema::seeds::plant_id.eq(-1),
+                    &crate::schema::seeds::name.eq("Test Seed"),
+                    &crate::schema::seeds::harvest_year.eq(2022),
+                    &crate::schema::seeds::quantity.eq(Quantity::Enough),
+                    &crate::schema::seeds::owner_id.eq(user_id),
+                ))
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app_for_user(pool.clone(), user_id).await;
+
+    for update_object in [
+        UpdatePlantingDto::UpdateNotes(UpdateNotesPlantingDto {
+            notes: Some("Sown too late".to_owned()),
+            action_id: Uuid::new_v4(),
+        }),
+        UpdatePlantingDto::UpdateStatus(UpdateStatusPlantingDto {
+            status: PlantingStatus::Sown,
+            action_id: Uuid::new_v4(),
+        }),
+        UpdatePlantingDto::UpdateSeed(UpdateSeedPlantingDto {
+            seed_id: Some(-1),
+            action_id: Uuid::new_v4(),
+        }),
+        UpdatePlantingDto::UpdatePhotos(UpdatePhotosPlantingDto {
+            photos: vec!["Photos/Garden/sprouts.jpg".to_owned()],
+            action_id: Uuid::new_v4(),
+        }),
+    ] {
+        let resp = test::TestRequest::patch()
+            .uri(&format!(
+                "/api/maps/-1/layers/plants/plantings/{planting_id}"
+            ))
+            .insert_header((header::AUTHORIZATION, token.clone()))
+            .set_json(update_object)
+            .send_request(&app)
+            .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    let resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/plants/plantings?relative_to_date=2023-05-08")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let page: TimelinePage<PlantingDto> = test::read_body_json(resp).await;
+    let [planting] = page.results.as_slice() else {
+        panic!("Expected exactly one planting");
+    };
+    assert_eq!(planting.notes.as_deref(), Some("Sown too late"));
+    assert_eq!(planting.status, PlantingStatus::Sown);
+    assert_eq!(planting.seed_id, Some(-1));
+    assert_eq!(
+        planting.photos,
+        vec!["Photos/Garden/sprouts.jpg".to_owned()]
+    );
+}
+
+#[actix_rt::test]
+async fn test_update_planting_with_seed_of_other_plant_fails() {
+    let planting_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let pool = init_test_database(|conn| {
+        async move {
+            diesel::insert_into(crate::schema::maps::table)
+                .values(data::TestInsertableMap::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::layers::table)
+                .values(data::TestInsertableLayer::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plants::table)
+                .values(vec![
+                    data::TestInsertablePlant::default(),
+                    data::TestInsertablePlant {
+                        id: -2,
+                        unique_name: "Test Plant 2".to_owned(),
+                        ..Default::default()
+                    },
+                ])
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plantings::table)
+                .values(data::TestInsertablePlanting {
+                    id: planting_id,
+                    ..Default::default()
+                })
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::seeds::table)
+                .values((
+                    &crate::schema::seeds::id.eq(-1),
+                    &crate::schema::seeds::plant_id.eq(-2),
+                    &crate::schema::seeds::name.eq("Test Seed"),
+                    &crate::schema::seeds::harvest_year.eq(2022),
+                    &crate::schema::seeds::quantity.eq(Quantity::Enough),
+                    &crate::schema::seeds::owner_id.eq(user_id),
+                ))
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app_for_user(pool.clone(), user_id).await;
+
+    let resp = test::TestRequest::patch()
+        .uri(&format!(
+            "/api/maps/-1/layers/plants/plantings/{planting_id}"
+        ))
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(UpdatePlantingDto::UpdateSeed(UpdateSeedPlantingDto {
+            seed_id: Some(-1),
+            action_id: Uuid::new_v4(),
+        }))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn test_create_planting_of_private_plant_of_other_user_fails() {
+    let pool = init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::maps::table)
+                .values(data::TestInsertableMap::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::layers::table)
+                .values(data::TestInsertableLayer::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plants::table)
+                .values(data::TestInsertablePlant::default())
+                .execute(conn)
+                .await?;
+            diesel::update(crate::schema::plants::table)
+                .set(crate::schema::plants::owner_id.eq(Uuid::new_v4()))
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let new_planting = NewPlantingDto {
+        id: Some(Uuid::new_v4()),
+        action_id: Uuid::new_v4(),
+        layer_id: -1,
+        plant_id: -1,
+        x: 0,
+        y: 0,
+        width: 0,
+        height: 0,
+        rotation: 0.0,
+        scale_x: 0.0,
+        scale_y: 0.0,
+        add_date: None,
+    };
+
+    let resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/plants/plantings")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(new_planting)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
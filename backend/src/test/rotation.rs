@@ -0,0 +1,243 @@
+//! Tests for [`crate::controller::rotation`].
+
+use actix_http::StatusCode;
+use actix_web::{http::header, test};
+use chrono::NaiveDate;
+use diesel::ExpressionMethods;
+use diesel_async::{
+    pooled_connection::deadpool::Pool, scoped_futures::ScopedFutureExt, AsyncPgConnection,
+    RunQueryDsl,
+};
+use uuid::Uuid;
+
+use crate::{
+    model::{
+        dto::{RotationConflictDto, RotationConflictKind, RotationGroup, RotationScheduleDto},
+        r#enum::nutrition_demand::NutritionDemand,
+    },
+    test::util::{data, init_test_app, init_test_database},
+};
+
+const PLANTING_TOMATO: Uuid = Uuid::from_u128(1);
+const PLANTING_BEAN: Uuid = Uuid::from_u128(2);
+const PLANTING_CARROT: Uuid = Uuid::from_u128(3);
+
+async fn init_rotation_database() -> Pool<AsyncPgConnection> {
+    init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::maps::table)
+                .values(data::TestInsertableMap::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::layers::table)
+                .values(data::TestInsertableLayer::default())
+                .execute(conn)
+                .await?;
+            let plants = [
+                (
+                    -1,
+                    "Solanum lycopersicum",
+                    "Solanaceae",
+                    Some(NutritionDemand::HeavyFeeder),
+                ),
+                (
+                    -2,
+                    "Solanum tuberosum",
+                    "Solanaceae",
+                    Some(NutritionDemand::HeavyFeeder),
+                ),
+                (
+                    -3,
+                    "Phaseolus vulgaris",
+                    "Fabaceae",
+                    Some(NutritionDemand::LightFeeder),
+                ),
+                (
+                    -4,
+                    "Daucus carota",
+                    "Apiaceae",
+                    Some(NutritionDemand::ModerateFeeder),
+                ),
+            ];
+            for (id, unique_name, family, nutrition_demand) in plants {
+                diesel::insert_into(crate::schema::plants::table)
+                    .values(data::TestInsertablePlant {
+                        id,
+                        unique_name: unique_name.to_owned(),
+                        ..Default::default()
+                    })
+                    .execute(conn)
+                    .await?;
+                diesel::update(crate::schema::plants::table)
+                    .filter(crate::schema::plants::id.eq(id))
+                    .set((
+                        crate::schema::plants::family.eq(family),
+                        crate::schema::plants::nutrition_demand.eq(nutrition_demand),
+                    ))
+                    .execute(conn)
+                    .await?;
+            }
+            let planting = |id, plant_id, x, year| data::TestInsertablePlanting {
+                id,
+                plant_id,
+                x,
+                width: 100,
+                height: 100,
+                scale_x: 1.0,
+                scale_y: 1.0,
+                add_date: NaiveDate::from_ymd_opt(year, 4, 1),
+                remove_date: NaiveDate::from_ymd_opt(year, 10, 1),
+                ..Default::default()
+            };
+            diesel::insert_into(crate::schema::plantings::table)
+                .values(vec![
+                    planting(PLANTING_TOMATO, -1, 0, 2022),
+                    planting(PLANTING_BEAN, -3, 1000, 2022),
+                    planting(PLANTING_CARROT, -4, 0, 2020),
+                ])
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn test_check_rotation_finds_conflicts() {
+    let pool = init_rotation_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+    let uri = |x: i32, planting_id: &str| {
+        format!("/api/maps/-1/layers/plants/rotation/check?layer_id=-1&plant_id=-2&x={x}&y=0&width=100&height=100&scale_x=1&scale_y=1&add_date=2023-04-01{planting_id}")
+    };
+
+    let resp = test::TestRequest::get()
+        .uri(&uri(50, ""))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let conflicts: Vec<RotationConflictDto> = test::read_body_json(resp).await;
+    let kinds = conflicts
+        .iter()
+        .map(|conflict| (conflict.kind, conflict.planting_id))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![
+            (RotationConflictKind::SameFamily, PLANTING_TOMATO),
+            (RotationConflictKind::HeavyFeeder, PLANTING_TOMATO),
+        ]
+    );
+    assert!(conflicts
+        .iter()
+        .all(|conflict| conflict.message.contains("Solanum lycopersicum")));
+
+    let moved_resp = test::TestRequest::get()
+        .uri(&uri(50, &format!("&planting_id={PLANTING_TOMATO}")))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(moved_resp.status(), StatusCode::OK);
+    let moved_conflicts: Vec<RotationConflictDto> = test::read_body_json(moved_resp).await;
+    assert!(moved_conflicts.is_empty());
+
+    let elsewhere_resp = test::TestRequest::get()
+        .uri(&uri(1000, ""))
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(elsewhere_resp.status(), StatusCode::OK);
+    let elsewhere_conflicts: Vec<RotationConflictDto> = test::read_body_json(elsewhere_resp).await;
+    assert!(elsewhere_conflicts.is_empty());
+}
+
+#[actix_rt::test]
+async fn test_rotation_schedule_continues_history() {
+    let pool = init_rotation_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/plants/rotation/schedule?layer_id=-1&x_min=-100&y_min=-100&x_max=100&y_max=100&start_year=2023")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let schedule: RotationScheduleDto = test::read_body_json(resp).await;
+
+    let history = schedule
+        .history
+        .iter()
+        .map(|year| (year.year, year.families.clone(), year.groups.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        history,
+        vec![
+            (
+                2020,
+                vec!["Apiaceae".to_owned()],
+                vec![RotationGroup::ModerateFeeder]
+            ),
+            (2021, vec![], vec![]),
+            (
+                2022,
+                vec!["Solanaceae".to_owned()],
+                vec![RotationGroup::HeavyFeeder]
+            ),
+        ]
+    );
+    let years = schedule
+        .schedule
+        .iter()
+        .map(|year| (year.year, year.group, year.avoid_families.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        years,
+        vec![
+            (
+                2023,
+                RotationGroup::ModerateFeeder,
+                vec!["Apiaceae".to_owned(), "Solanaceae".to_owned()]
+            ),
+            (
+                2024,
+                RotationGroup::LightFeeder,
+                vec!["Solanaceae".to_owned()]
+            ),
+            (
+                2025,
+                RotationGroup::SoilImprover,
+                vec!["Solanaceae".to_owned()]
+            ),
+            (2026, RotationGroup::HeavyFeeder, vec![]),
+        ]
+    );
+
+    let invalid_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/plants/rotation/schedule?layer_id=-1&x_min=100&y_min=-100&x_max=100&y_max=100&start_year=2023")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(invalid_resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn test_rotation_rejects_layer_of_other_map() {
+    let pool = init_rotation_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let check_resp = test::TestRequest::get()
+        .uri("/api/maps/-2/layers/plants/rotation/check?layer_id=-1&plant_id=-2&x=50&y=0&width=100&height=100&add_date=2023-04-01")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(check_resp.status(), StatusCode::BAD_REQUEST);
+
+    let schedule_resp = test::TestRequest::get()
+        .uri("/api/maps/-2/layers/plants/rotation/schedule?layer_id=-1&x_min=-100&y_min=-100&x_max=100&y_max=100&start_year=2023")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(schedule_resp.status(), StatusCode::BAD_REQUEST);
+}
@@ -2,7 +2,7 @@
 
 use crate::{
     model::{
-        dto::{NewSeedDto, Page, SeedDto},
+        dto::{NewSeedDto, Page, SeedDto, SeedLineageDto},
         r#enum::quantity::Quantity,
     },
     test::util::{init_test_app, init_test_app_for_user, init_test_database},
@@ -329,6 +329,7 @@ async fn test_create_seed_ok() {
         quality: None,
         price: None,
         notes: None,
+        parent_seed_id: None,
     };
 
     let resp = test::TestRequest::post()
@@ -410,3 +411,155 @@ async fn test_delete_by_non_existing_id_succeeds() {
 
     assert_eq!(resp.status(), StatusCode::OK);
 }
+
+#[actix_rt::test]
+async fn test_create_seed_derives_generation_from_parent() {
+    let user_id = uuid!("00000000-0000-0000-0000-000000000000");
+    let pool = init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::seeds::table)
+                .values((
+                    &crate::schema::seeds::id.eq(-1),
+                    &crate::schema::seeds::name.eq("Testia testia"),
+                    &crate::schema::seeds::harvest_year.eq(2022),
+                    &crate::schema::seeds::quantity.eq(Quantity::Enough),
+                    &crate::schema::seeds::generation.eq(3),
+                    &crate::schema::seeds::owner_id.eq(user_id),
+                ))
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app_for_user(pool, user_id).await;
+
+    let new_seed = NewSeedDto {
+        name: "Testia testia".to_owned(),
+        variety: None,
+        plant_id: None,
+        harvest_year: 2023,
+        quantity: Quantity::Enough,
+        use_by: None,
+        origin: None,
+        taste: None,
+        yield_: None,
+        generation: Some(1),
+        quality: None,
+        price: None,
+        notes: None,
+        parent_seed_id: Some(-1),
+    };
+
+    let resp = test::TestRequest::post()
+        .uri("/api/seeds")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(new_seed)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let seed: SeedDto = test::read_body_json(resp).await;
+    assert_eq!(seed.parent_seed_id, Some(-1));
+    assert_eq!(seed.generation, Some(4));
+}
+
+#[actix_rt::test]
+async fn test_create_seed_fails_with_foreign_parent() {
+    let pool = init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::seeds::table)
+                .values((
+                    &crate::schema::seeds::id.eq(-1),
+                    &crate::schema::seeds::name.eq("Testia testia"),
+                    &crate::schema::seeds::harvest_year.eq(2022),
+                    &crate::schema::seeds::quantity.eq(Quantity::Enough),
+                    &crate::schema::seeds::owner_id
+                        .eq(uuid!("00000000-0000-0000-0000-000000000001")),
+                ))
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) =
+        init_test_app_for_user(pool, uuid!("00000000-0000-0000-0000-000000000000")).await;
+
+    let resp = test::TestRequest::post()
+        .uri("/api/seeds")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(NewSeedDto {
+            name: "Testia testia".to_owned(),
+            variety: None,
+            plant_id: None,
+            harvest_year: 2023,
+            quantity: Quantity::Enough,
+            use_by: None,
+            origin: None,
+            taste: None,
+            yield_: None,
+            generation: None,
+            quality: None,
+            price: None,
+            notes: None,
+            parent_seed_id: Some(-1),
+        })
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_rt::test]
+async fn test_find_lineage_succeeds() {
+    let user_id = uuid!("00000000-0000-0000-0000-000000000000");
+    let pool = init_test_database(|conn| {
+        async {
+            for (id, parent_id) in [(-1, None), (-2, Some(-1)), (-3, Some(-2)), (-4, Some(-2))] {
+                diesel::insert_into(crate::schema::seeds::table)
+                    .values((
+                        &crate::schema::seeds::id.eq(id),
+                        &crate::schema::seeds::name.eq("Testia testia"),
+                        &crate::schema::seeds::harvest_year.eq(2022),
+                        &crate::schema::seeds::quantity.eq(Quantity::Enough),
+                        &crate::schema::seeds::owner_id.eq(user_id),
+                        &crate::schema::seeds::parent_seed_id.eq(parent_id),
+                    ))
+                    .execute(conn)
+                    .await?;
+            }
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await;
+    let (token, app) = init_test_app_for_user(pool, user_id).await;
+
+    let resp = test::TestRequest::get()
+        .uri("/api/seeds/-2/lineage")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let lineage: SeedLineageDto = test::read_body_json(resp).await;
+    assert_eq!(lineage.seed.id, -2);
+    assert_eq!(
+        lineage.ancestors.iter().map(|s| s.id).collect::<Vec<_>>(),
+        vec![-1]
+    );
+    let mut descendant_ids = lineage
+        .descendants
+        .iter()
+        .map(|node| node.seed.id)
+        .collect::<Vec<_>>();
+    descendant_ids.sort_unstable();
+    assert_eq!(descendant_ids, vec![-4, -3]);
+    assert!(lineage
+        .descendants
+        .iter()
+        .all(|node| node.children.is_empty()));
+}
@@ -0,0 +1,163 @@
+//! Tests for [`crate::controller::shade_layer`].
+
+use actix_http::StatusCode;
+use actix_web::{http::header, test};
+use diesel::ExpressionMethods;
+use diesel_async::{
+    pooled_connection::deadpool::Pool, scoped_futures::ScopedFutureExt, AsyncPgConnection,
+    RunQueryDsl,
+};
+use postgis_diesel::types::{Point, Polygon};
+use uuid::Uuid;
+
+use crate::{
+    model::{
+        dto::shade::{
+            DeleteShadeObstacleDto, NewShadeObstacleDto, ShadeCellDto, ShadeObstacleDto,
+            ShadeRasterDto, UpdateShadeObstacleDto,
+        },
+        r#enum::{layer_type::LayerType, shade::Shade},
+    },
+    test::util::{data, init_test_app, init_test_database},
+};
+
+async fn init_shade_database(location: Option<Point>) -> Pool<AsyncPgConnection> {
+    init_test_database(move |conn| {
+        async move {
+            data::insert_map_with_layer(LayerType::Shade, conn).await?;
+            diesel::update(crate::schema::maps::table)
+                .set(crate::schema::maps::location.eq(location))
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+fn wall(y: f64) -> Polygon<Point> {
+    let mut polygon = Polygon::new(Some(4326));
+    polygon.add_points([
+        Point::new(0.0, y, Some(4326)),
+        Point::new(500.0, y, Some(4326)),
+        Point::new(500.0, y + 20.0, Some(4326)),
+        Point::new(0.0, y + 20.0, Some(4326)),
+        Point::new(0.0, y, Some(4326)),
+    ]);
+    polygon
+}
+
+fn new_obstacle(layer_id: i32, y: f64) -> NewShadeObstacleDto {
+    NewShadeObstacleDto {
+        id: None,
+        layer_id,
+        name: "Garden wall".to_owned(),
+        geometry: wall(y),
+        height: 300,
+        action_id: Uuid::new_v4(),
+    }
+}
+
+fn raster_cell(raster: &ShadeRasterDto, row: usize) -> Option<ShadeCellDto> {
+    raster
+        .cells
+        .get(row)
+        .and_then(|cells| cells.get(2))
+        .copied()
+        .flatten()
+}
+
+#[actix_rt::test]
+async fn test_can_create_update_obstacles_and_compute_shade() {
+    let pool = init_shade_database(Some(Point::new(16.4, 48.2, Some(4326)))).await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let created_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/shade/obstacles")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_obstacle(-2, 300.0))
+        .send_request(&app)
+        .await;
+    assert_eq!(created_resp.status(), StatusCode::CREATED);
+    let obstacle: ShadeObstacleDto = test::read_body_json(created_resp).await;
+
+    let updated_resp = test::TestRequest::patch()
+        .uri(&format!(
+            "/api/maps/-1/layers/shade/obstacles/{}",
+            obstacle.id
+        ))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(UpdateShadeObstacleDto {
+            name: None,
+            geometry: Some(wall(500.0)),
+            height: None,
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(updated_resp.status(), StatusCode::OK);
+    let updated: ShadeObstacleDto = test::read_body_json(updated_resp).await;
+    assert_eq!(updated.name, "Garden wall");
+    assert_eq!(updated.height, 300);
+
+    let raster_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/shade/raster?layer_id=-2&date=2023-12-21")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(raster_resp.status(), StatusCode::OK);
+    let raster: ShadeRasterDto = test::read_body_json(raster_resp).await;
+    assert_eq!(raster.cells.len(), 10);
+    assert_eq!(
+        raster_cell(&raster, 4).map(|cell| cell.shade),
+        Some(Shade::PermanentDeepShade)
+    );
+    assert_eq!(
+        raster_cell(&raster, 6).map(|cell| cell.shade),
+        Some(Shade::NoShade)
+    );
+
+    let deleted_resp = test::TestRequest::delete()
+        .uri(&format!(
+            "/api/maps/-1/layers/shade/obstacles/{}",
+            obstacle.id
+        ))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(DeleteShadeObstacleDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(deleted_resp.status(), StatusCode::OK);
+
+    let obstacles_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/shade/obstacles?layer_id=-2")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(obstacles_resp.status(), StatusCode::OK);
+    let obstacles: Vec<ShadeObstacleDto> = test::read_body_json(obstacles_resp).await;
+    assert!(obstacles.is_empty());
+}
+
+#[actix_rt::test]
+async fn test_shade_requires_shade_layer_and_map_location() {
+    let pool = init_shade_database(None).await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let plants_layer_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/shade/obstacles")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_obstacle(-1, 500.0))
+        .send_request(&app)
+        .await;
+    assert_eq!(plants_layer_resp.status(), StatusCode::BAD_REQUEST);
+
+    let raster_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/shade/raster?layer_id=-2&date=2023-12-21")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(raster_resp.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,151 @@
+//! Tests for [`crate::controller::soil_layer`].
+
+use actix_http::StatusCode;
+use actix_web::{http::header, test};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+use crate::{
+    model::{
+        dto::soil_samples::{
+            DeleteSoilSampleDto, NewSoilSampleDto, SoilCellDto, SoilRasterDto, SoilSampleDto,
+            UpdateSoilSampleDto,
+        },
+        r#enum::{
+            layer_type::LayerType, soil_ph::SoilPh, soil_texture::SoilTexture,
+            soil_water_retention::SoilWaterRetention,
+        },
+    },
+    test::util::{data, init_test_app, init_test_database},
+};
+
+fn new_sample(layer_id: i32, x: i32, y: i32, ph: f32) -> NewSoilSampleDto {
+    NewSoilSampleDto {
+        id: None,
+        layer_id,
+        x,
+        y,
+        ph: Some(ph),
+        soil_texture: Some(SoilTexture::Loamy),
+        water_retention: Some(SoilWaterRetention::Moist),
+        organic_matter: None,
+        sample_date: None,
+        action_id: Uuid::new_v4(),
+    }
+}
+
+#[actix_rt::test]
+async fn test_can_create_update_and_interpolate_soil_samples() {
+    let pool =
+        init_test_database(|conn| data::insert_map_with_layer(LayerType::Soil, conn).scope_boxed())
+            .await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let top_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/soil/samples")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_sample(-2, 250, 0, 5.0))
+        .send_request(&app)
+        .await;
+    assert_eq!(top_resp.status(), StatusCode::CREATED);
+    let top: SoilSampleDto = test::read_body_json(top_resp).await;
+
+    let bottom_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/soil/samples")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_sample(-2, 250, 1000, 6.0))
+        .send_request(&app)
+        .await;
+    assert_eq!(bottom_resp.status(), StatusCode::CREATED);
+    let bottom: SoilSampleDto = test::read_body_json(bottom_resp).await;
+
+    let updated_resp = test::TestRequest::patch()
+        .uri(&format!("/api/maps/-1/layers/soil/samples/{}", bottom.id))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(UpdateSoilSampleDto {
+            x: 250,
+            y: 1000,
+            ph: Some(8.0),
+            soil_texture: Some(SoilTexture::Clay),
+            water_retention: None,
+            organic_matter: Some(4.5),
+            sample_date: None,
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(updated_resp.status(), StatusCode::OK);
+    let updated: SoilSampleDto = test::read_body_json(updated_resp).await;
+    assert_eq!(updated.ph, Some(8.0));
+    assert_eq!(updated.water_retention, None);
+
+    let raster_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/soil/raster?layer_id=-2&resolution=250")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(raster_resp.status(), StatusCode::OK);
+    let raster: SoilRasterDto = test::read_body_json(raster_resp).await;
+    assert_eq!(raster.cells.len(), 4);
+    assert!(raster.cells.iter().all(|row| row.len() == 2));
+    let first_cell = |row: Option<&Vec<Option<SoilCellDto>>>| {
+        row.and_then(|cells| <[_]>::first(cells))
+            .copied()
+            .flatten()
+            .unwrap()
+    };
+    let top_cell = first_cell(<[_]>::first(&raster.cells));
+    let bottom_cell = first_cell(raster.cells.last());
+    assert_eq!(top_cell.soil_ph, Some(SoilPh::Acidic));
+    assert_eq!(top_cell.soil_texture, Some(SoilTexture::Loamy));
+    assert_eq!(top_cell.water_retention, Some(SoilWaterRetention::Moist));
+    assert_eq!(bottom_cell.soil_ph, Some(SoilPh::Alkaline));
+    assert_eq!(bottom_cell.soil_texture, Some(SoilTexture::Clay));
+    assert_eq!(bottom_cell.organic_matter, Some(4.5));
+
+    let deleted_resp = test::TestRequest::delete()
+        .uri(&format!("/api/maps/-1/layers/soil/samples/{}", top.id))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(DeleteSoilSampleDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(deleted_resp.status(), StatusCode::OK);
+
+    let samples_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/soil/samples?layer_id=-2")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(samples_resp.status(), StatusCode::OK);
+    let samples: Vec<SoilSampleDto> = test::read_body_json(samples_resp).await;
+    let [remaining] = samples.as_slice() else {
+        panic!("expected exactly one sample, got {samples:?}");
+    };
+    assert_eq!(remaining.id, bottom.id);
+}
+
+#[actix_rt::test]
+async fn test_create_soil_sample_with_invalid_values_fails() {
+    let pool =
+        init_test_database(|conn| data::insert_map_with_layer(LayerType::Soil, conn).scope_boxed())
+            .await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let plant_layer_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/soil/samples")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_sample(-1, 0, 0, 7.0))
+        .send_request(&app)
+        .await;
+    assert_eq!(plant_layer_resp.status(), StatusCode::BAD_REQUEST);
+
+    let ph_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/soil/samples")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(new_sample(-2, 0, 0, 15.0))
+        .send_request(&app)
+        .await;
+    assert_eq!(ph_resp.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,219 @@
+//! Tests for [`crate::controller::spacing`].
+
+use actix_http::StatusCode;
+use actix_web::{http::header, test};
+use chrono::NaiveDate;
+use diesel::ExpressionMethods;
+use diesel_async::{
+    pooled_connection::deadpool::Pool, scoped_futures::ScopedFutureExt, AsyncPgConnection,
+    RunQueryDsl,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    model::{
+        dto::{SpacingWarningDto, SpacingWarningKind},
+        r#enum::plant_spread::PlantSpread,
+    },
+    test::util::{data, init_test_app, init_test_database},
+};
+
+const PLANTING_TREE: Uuid = Uuid::from_u128(1);
+const PLANTING_HERB: Uuid = Uuid::from_u128(2);
+const PLANTING_REMOVED: Uuid = Uuid::from_u128(3);
+
+async fn init_spacing_database() -> Pool<AsyncPgConnection> {
+    init_test_database(|conn| {
+        async {
+            diesel::insert_into(crate::schema::maps::table)
+                .values(data::TestInsertableMap::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::layers::table)
+                .values(data::TestInsertableLayer::default())
+                .execute(conn)
+                .await?;
+            for (id, unique_name, spread) in [
+                (-1, "Malus domestica", PlantSpread::Wide),
+                (-2, "Thymus vulgaris", PlantSpread::Narrow),
+            ] {
+                diesel::insert_into(crate::schema::plants::table)
+                    .values(data::TestInsertablePlant {
+                        id,
+                        unique_name: unique_name.to_owned(),
+                        ..Default::default()
+                    })
+                    .execute(conn)
+                    .await?;
+                diesel::update(crate::schema::plants::table)
+                    .filter(crate::schema::plants::id.eq(id))
+                    .set(crate::schema::plants::spread.eq(spread))
+                    .execute(conn)
+                    .await?;
+            }
+            diesel::insert_into(crate::schema::plantings::table)
+                .values(vec![
+                    data::TestInsertablePlanting {
+                        id: PLANTING_TREE,
+                        x: 100,
+                        y: 100,
+                        add_date: NaiveDate::from_ymd_opt(2023, 1, 1),
+                        ..Default::default()
+                    },
+                    data::TestInsertablePlanting {
+                        id: PLANTING_HERB,
+                        plant_id: -2,
+                        x: 400,
+                        y: 400,
+                        add_date: NaiveDate::from_ymd_opt(2023, 1, 1),
+                        ..Default::default()
+                    },
+                    data::TestInsertablePlanting {
+                        id: PLANTING_REMOVED,
+                        x: 130,
+                        y: 100,
+                        add_date: NaiveDate::from_ymd_opt(2022, 1, 1),
+                        remove_date: NaiveDate::from_ymd_opt(2022, 12, 31),
+                        ..Default::default()
+                    },
+                ])
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+fn check_uri(plant_id: i32, x: i32, y: i32, extra: &str) -> String {
+    format!("/api/maps/-1/layers/plants/spacing/check?layer_id=-1&plant_id={plant_id}&x={x}&y={y}&width=10&height=10&date=2023-06-01{extra}")
+}
+
+#[actix_rt::test]
+async fn test_check_spacing_finds_warnings() {
+    let pool = init_spacing_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    for (plant_id, x, y, expected) in [
+        (
+            -1,
+            150,
+            100,
+            vec![(SpacingWarningKind::Crowding, Some(PLANTING_TREE))],
+        ),
+        (
+            -1,
+            200,
+            100,
+            vec![(SpacingWarningKind::CanopyOverlap, Some(PLANTING_TREE))],
+        ),
+        (-2, 400, 420, vec![]),
+        (-2, 600, 420, vec![(SpacingWarningKind::OutsideMap, None)]),
+    ] {
+        let resp = test::TestRequest::get()
+            .uri(&check_uri(plant_id, x, y, ""))
+            .insert_header((header::AUTHORIZATION, token.clone()))
+            .send_request(&app)
+            .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let warnings: Vec<SpacingWarningDto> = test::read_body_json(resp).await;
+        let kinds = warnings
+            .iter()
+            .map(|warning| (warning.kind, warning.planting_id))
+            .collect::<Vec<_>>();
+        assert_eq!(kinds, expected, "{x}, {y}");
+    }
+
+    let moved_resp = test::TestRequest::get()
+        .uri(&check_uri(
+            -1,
+            150,
+            100,
+            &format!("&planting_id={PLANTING_TREE}&mode=strict"),
+        ))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(moved_resp.status(), StatusCode::OK);
+
+    let strict_resp = test::TestRequest::get()
+        .uri(&check_uri(-1, 150, 100, "&mode=strict"))
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(strict_resp.status(), StatusCode::CONFLICT);
+}
+
+#[actix_rt::test]
+async fn test_strict_spacing_rejects_plantings() {
+    let pool = init_spacing_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+    let new_planting = json!({
+        "layerId": -1,
+        "plantId": -1,
+        "x": 150,
+        "y": 100,
+        "width": 10,
+        "height": 10,
+        "rotation": 0.0,
+        "scaleX": 1.0,
+        "scaleY": 1.0,
+        "addDate": "2023-06-01",
+        "actionId": Uuid::new_v4(),
+    });
+
+    let strict_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/plants/plantings?spacing=strict")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_planting.clone())
+        .send_request(&app)
+        .await;
+    assert_eq!(strict_resp.status(), StatusCode::CONFLICT);
+
+    let advisory_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/plants/plantings")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_planting)
+        .send_request(&app)
+        .await;
+    assert_eq!(advisory_resp.status(), StatusCode::CREATED);
+
+    let move_resp = test::TestRequest::patch()
+        .uri(&format!(
+            "/api/maps/-1/layers/plants/plantings/{PLANTING_HERB}?spacing=strict"
+        ))
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(json!({
+            "type": "Move",
+            "content": { "x": 110, "y": 100, "actionId": Uuid::new_v4() },
+        }))
+        .send_request(&app)
+        .await;
+    assert_eq!(move_resp.status(), StatusCode::CONFLICT);
+}
+
+#[actix_rt::test]
+async fn test_check_spacing_requires_plant_layer_of_map() {
+    let pool = init_spacing_database().await;
+    let mut conn = pool.get().await.unwrap();
+    diesel::insert_into(crate::schema::maps::table)
+        .values(data::TestInsertableMap {
+            id: -2,
+            name: "Test Map 2".to_owned(),
+            ..Default::default()
+        })
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    drop(conn);
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let other_map_resp = test::TestRequest::get()
+        .uri(&check_uri(-1, 150, 100, "").replace("/maps/-1/", "/maps/-2/"))
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(other_map_resp.status(), StatusCode::BAD_REQUEST);
+}
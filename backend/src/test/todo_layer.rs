@@ -0,0 +1,193 @@
+//! Tests for [`crate::controller::todo_layer`].
+
+use actix_http::StatusCode;
+use actix_web::{http::header, test};
+use chrono::{Days, NaiveDate, Utc};
+use diesel_async::{
+    pooled_connection::deadpool::Pool, scoped_futures::ScopedFutureExt, AsyncPgConnection,
+    RunQueryDsl,
+};
+use uuid::Uuid;
+
+use crate::{
+    model::{
+        dto::{
+            map_collaborators::{MapCollaboratorDto, NewMapCollaboratorDto},
+            todos::{AssignedTodoDto, DeleteTodoDto, NewTodoDto, TodoDto, UpdateTodoDto},
+        },
+        r#enum::{layer_type::LayerType, todo_status::TodoStatus},
+    },
+    test::util::{data, init_test_app, init_test_app_for_user, init_test_database},
+};
+
+const COLLABORATOR: Uuid = Uuid::from_u128(1);
+const PLANTING: Uuid = Uuid::from_u128(2);
+
+async fn init_todo_database() -> Pool<AsyncPgConnection> {
+    init_test_database(|conn| {
+        async {
+            data::insert_map_with_layer(LayerType::Todo, conn).await?;
+            diesel::insert_into(crate::schema::plants::table)
+                .values(data::TestInsertablePlant::default())
+                .execute(conn)
+                .await?;
+            diesel::insert_into(crate::schema::plantings::table)
+                .values(data::TestInsertablePlanting {
+                    id: PLANTING,
+                    ..Default::default()
+                })
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+fn new_todo(position: Option<(i32, i32)>, planting_id: Option<Uuid>) -> NewTodoDto {
+    NewTodoDto {
+        id: None,
+        layer_id: -2,
+        x: position.map(|(x, _)| x),
+        y: position.map(|(_, y)| y),
+        planting_id,
+        title: "Water the tomatoes".to_owned(),
+        description: None,
+        due_date: NaiveDate::from_ymd_opt(2023, 7, 1),
+        assignee_id: Some(COLLABORATOR),
+        recurrence_days: Some(3),
+        action_id: Uuid::new_v4(),
+    }
+}
+
+#[actix_rt::test]
+async fn test_can_assign_complete_and_delete_todos() {
+    let pool = init_todo_database().await;
+    let (token, app) = init_test_app_for_user(pool.clone(), Uuid::default()).await;
+
+    let collaborator_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/collaborators")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(NewMapCollaboratorDto {
+            user_id: COLLABORATOR,
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(collaborator_resp.status(), StatusCode::CREATED);
+    let collaborator: MapCollaboratorDto = test::read_body_json(collaborator_resp).await;
+    assert_eq!(collaborator.user_id, COLLABORATOR);
+
+    let position_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/todos")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_todo(Some((100, 200)), None))
+        .send_request(&app)
+        .await;
+    assert_eq!(position_resp.status(), StatusCode::CREATED);
+    let watering: TodoDto = test::read_body_json(position_resp).await;
+    assert_eq!(watering.status, TodoStatus::Open);
+
+    let planting_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/todos")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_todo(None, Some(PLANTING)))
+        .send_request(&app)
+        .await;
+    assert_eq!(planting_resp.status(), StatusCode::CREATED);
+    let pruning: TodoDto = test::read_body_json(planting_resp).await;
+
+    let (collaborator_token, collaborator_app) =
+        init_test_app_for_user(pool.clone(), COLLABORATOR).await;
+    let assigned_resp = test::TestRequest::get()
+        .uri("/api/todos")
+        .insert_header((header::AUTHORIZATION, collaborator_token.clone()))
+        .send_request(&collaborator_app)
+        .await;
+    assert_eq!(assigned_resp.status(), StatusCode::OK);
+    let assigned: Vec<AssignedTodoDto> = test::read_body_json(assigned_resp).await;
+    assert_eq!(assigned.len(), 2);
+    assert!(assigned.iter().all(|todo| todo.map_id == -1));
+
+    let done_resp = test::TestRequest::patch()
+        .uri(&format!("/api/maps/-1/layers/todos/{}", watering.id))
+        .insert_header((header::AUTHORIZATION, collaborator_token))
+        .set_json(UpdateTodoDto {
+            x: watering.x,
+            y: watering.y,
+            planting_id: None,
+            title: watering.title,
+            description: Some("Use rain water".to_owned()),
+            due_date: watering.due_date,
+            assignee_id: watering.assignee_id,
+            status: TodoStatus::Done,
+            recurrence_days: watering.recurrence_days,
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&collaborator_app)
+        .await;
+    assert_eq!(done_resp.status(), StatusCode::OK);
+    let rescheduled: TodoDto = test::read_body_json(done_resp).await;
+    assert_eq!(rescheduled.status, TodoStatus::Open);
+    assert_eq!(
+        rescheduled.due_date,
+        Utc::now().date_naive().checked_add_days(Days::new(3))
+    );
+
+    let deleted_resp = test::TestRequest::delete()
+        .uri(&format!("/api/maps/-1/layers/todos/{}", pruning.id))
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(DeleteTodoDto {
+            action_id: Uuid::new_v4(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(deleted_resp.status(), StatusCode::OK);
+
+    let todos_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/todos?layer_id=-2&status=open")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(todos_resp.status(), StatusCode::OK);
+    let todos: Vec<TodoDto> = test::read_body_json(todos_resp).await;
+    assert_eq!(todos.len(), 1);
+    assert!(todos.iter().all(|todo| todo.id == watering.id));
+}
+
+#[actix_rt::test]
+async fn test_todo_requires_single_anchor_and_collaborating_assignee() {
+    let pool = init_todo_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let unknown_assignee_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/todos")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(new_todo(Some((100, 200)), None))
+        .send_request(&app)
+        .await;
+    assert_eq!(unknown_assignee_resp.status(), StatusCode::BAD_REQUEST);
+
+    let two_anchors_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/layers/todos")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(NewTodoDto {
+            assignee_id: None,
+            ..new_todo(Some((100, 200)), Some(PLANTING))
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(two_anchors_resp.status(), StatusCode::BAD_REQUEST);
+
+    let (collaborator_token, collaborator_app) =
+        init_test_app_for_user(pool.clone(), COLLABORATOR).await;
+    let not_owner_resp = test::TestRequest::post()
+        .uri("/api/maps/-1/collaborators")
+        .insert_header((header::AUTHORIZATION, collaborator_token))
+        .set_json(NewMapCollaboratorDto {
+            user_id: COLLABORATOR,
+        })
+        .send_request(&collaborator_app)
+        .await;
+    assert_eq!(not_owner_resp.status(), StatusCode::FORBIDDEN);
+}
@@ -17,9 +17,11 @@ use dotenvy::dotenv;
 
 use crate::config::{app, data::AppDataInner, routes};
 use crate::error::ServiceError;
+use crate::service::shade_layer::ShadeRasterCache;
+use crate::service::winds_layer::WindRasterCache;
 use crate::sse::broadcaster::Broadcaster;
 
-use self::token::{generate_token, generate_token_for_user};
+use self::token::{generate_token, generate_token_for_user, generate_token_with_scope};
 
 pub mod data;
 pub mod dummy_map_polygons;
@@ -92,6 +94,19 @@ pub async fn init_test_app_for_user(
     (format!("Bearer {token}"), app)
 }
 
+pub async fn init_test_app_with_scope(
+    pool: Pool<AsyncPgConnection>,
+    scope: &str,
+) -> (
+    String,
+    impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+) {
+    let app = init_test_app_impl(pool).await;
+    let token = setup_auth_with_scope(scope);
+
+    (format!("Bearer {token}"), app)
+}
+
 async fn init_test_app_impl(
     pool: Pool<AsyncPgConnection>,
 ) -> impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error> {
@@ -100,6 +115,8 @@ async fn init_test_app_impl(
             .app_data(Data::new(AppDataInner {
                 pool,
                 broadcaster: Broadcaster::new(),
+                shade_rasters: ShadeRasterCache::default(),
+                wind_rasters: WindRasterCache::default(),
             }))
             .configure(routes::config),
     )
@@ -115,3 +132,8 @@ fn setup_auth_for_user(user_id: uuid::Uuid) -> String {
     let jwk = jwks::init_auth();
     generate_token_for_user(jwk, 300, user_id)
 }
+
+fn setup_auth_with_scope(scope: &str) -> String {
+    let jwk = jwks::init_auth();
+    generate_token_with_scope(jwk, 300, scope)
+}
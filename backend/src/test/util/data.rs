@@ -2,9 +2,11 @@
 
 use chrono::NaiveDate;
 use diesel::Insertable;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use postgis_diesel::types::{Point, Polygon};
 use uuid::Uuid;
 
+use crate::error::ServiceError;
 use crate::model::r#enum::{layer_type::LayerType, privacy_option::PrivacyOption};
 
 use super::dummy_map_polygons::tall_rectangle;
@@ -118,3 +120,30 @@ impl Default for TestInsertablePlanting {
         }
     }
 }
+
+/// Insert the default map with its plant layer -1 and the layer -2 of the given type.
+///
+/// # Errors
+/// * Unknown, diesel doesn't say why it might error.
+pub async fn insert_map_with_layer(
+    type_: LayerType,
+    conn: &mut AsyncPgConnection,
+) -> Result<(), ServiceError> {
+    diesel::insert_into(crate::schema::maps::table)
+        .values(TestInsertableMap::default())
+        .execute(conn)
+        .await?;
+    diesel::insert_into(crate::schema::layers::table)
+        .values(vec![
+            TestInsertableLayer::default(),
+            TestInsertableLayer {
+                id: -2,
+                type_,
+                name: "Test Layer 2".to_owned(),
+                ..Default::default()
+            },
+        ])
+        .execute(conn)
+        .await?;
+    Ok(())
+}
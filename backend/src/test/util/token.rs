@@ -33,6 +33,20 @@ pub fn generate_token_for_user(jwk: JsonWebKey, exp_offset: i64, user_id: Uuid)
     .unwrap()
 }
 
+pub fn generate_token_with_scope(jwk: JsonWebKey, exp_offset: i64, scope: &str) -> String {
+    let mut header = jsonwebtoken::Header::new(jwk.algorithm.unwrap().into());
+    header.kid = Some(jwk.key_id.clone().unwrap());
+
+    jsonwebtoken::encode(
+        &header,
+        &TokenClaims::new()
+            .with_exp_offset(exp_offset)
+            .with_scope(scope),
+        &jwk.key.to_encoding_key(),
+    )
+    .unwrap()
+}
+
 #[derive(Debug, Clone, Serialize)]
 struct TokenClaims {
     exp: u64,
@@ -61,4 +75,11 @@ impl TokenClaims {
     fn with_sub(self, sub: Uuid) -> Self {
         Self { sub, ..self }
     }
+
+    fn with_scope(self, scope: &str) -> Self {
+        Self {
+            scope: scope.to_owned(),
+            ..self
+        }
+    }
 }
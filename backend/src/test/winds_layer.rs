@@ -0,0 +1,168 @@
+//! Tests for [`crate::controller::winds_layer`].
+
+use actix_http::StatusCode;
+use actix_web::{http::header, test};
+use diesel::ExpressionMethods;
+use diesel_async::{
+    pooled_connection::deadpool::Pool, scoped_futures::ScopedFutureExt, AsyncPgConnection,
+    RunQueryDsl,
+};
+use uuid::Uuid;
+
+use crate::{
+    model::{
+        dto::winds::{
+            UpdateWindRoseDto, WindCellDto, WindRasterDto, WindRoseDto, WindRoseEntryDto,
+        },
+        r#enum::{layer_type::LayerType, plant_height::PlantHeight},
+    },
+    test::util::{data, init_test_app, init_test_database},
+};
+
+async fn init_winds_database() -> Pool<AsyncPgConnection> {
+    init_test_database(|conn| {
+        async {
+            data::insert_map_with_layer(LayerType::Winds, conn).await?;
+            diesel::insert_into(crate::schema::plants::table)
+                .values(data::TestInsertablePlant::default())
+                .execute(conn)
+                .await?;
+            diesel::update(crate::schema::plants::table)
+                .set(crate::schema::plants::height.eq(PlantHeight::High))
+                .execute(conn)
+                .await?;
+            // a hedge across the whole width of the map
+            diesel::insert_into(crate::schema::plantings::table)
+                .values(data::TestInsertablePlanting {
+                    x: 250,
+                    y: 300,
+                    width: 500,
+                    height: 100,
+                    scale_x: 1.0,
+                    scale_y: 1.0,
+                    ..Default::default()
+                })
+                .execute(conn)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+fn update_rose(layer_id: i32, entries: Vec<WindRoseEntryDto>) -> UpdateWindRoseDto {
+    UpdateWindRoseDto {
+        layer_id,
+        entries,
+        action_id: Uuid::new_v4(),
+    }
+}
+
+fn raster_cell(raster: &WindRasterDto, row: usize) -> Option<WindCellDto> {
+    raster
+        .cells
+        .get(row)
+        .and_then(|cells| cells.get(2))
+        .copied()
+        .flatten()
+}
+
+#[actix_rt::test]
+async fn test_can_replace_wind_rose_and_compute_exposure() {
+    let pool = init_winds_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+
+    let initial_resp = test::TestRequest::put()
+        .uri("/api/maps/-1/layers/winds/rose")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(update_rose(
+            -2,
+            vec![
+                WindRoseEntryDto {
+                    direction: 90,
+                    frequency: 10.0,
+                    speed: 2.0,
+                },
+                WindRoseEntryDto {
+                    direction: 0,
+                    frequency: 50.0,
+                    speed: 4.0,
+                },
+            ],
+        ))
+        .send_request(&app)
+        .await;
+    assert_eq!(initial_resp.status(), StatusCode::OK);
+
+    let replaced_resp = test::TestRequest::put()
+        .uri("/api/maps/-1/layers/winds/rose")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(update_rose(
+            -2,
+            vec![WindRoseEntryDto {
+                direction: 0,
+                frequency: 50.0,
+                speed: 4.0,
+            }],
+        ))
+        .send_request(&app)
+        .await;
+    assert_eq!(replaced_resp.status(), StatusCode::OK);
+
+    let rose_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/winds/rose?layer_id=-2")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(rose_resp.status(), StatusCode::OK);
+    let rose: WindRoseDto = test::read_body_json(rose_resp).await;
+    assert_eq!(
+        rose.entries
+            .iter()
+            .map(|entry| entry.direction)
+            .collect::<Vec<_>>(),
+        vec![0]
+    );
+
+    let raster_resp = test::TestRequest::get()
+        .uri("/api/maps/-1/layers/winds/raster?layer_id=-2")
+        .insert_header((header::AUTHORIZATION, token))
+        .send_request(&app)
+        .await;
+    assert_eq!(raster_resp.status(), StatusCode::OK);
+    let raster: WindRasterDto = test::read_body_json(raster_resp).await;
+    assert_eq!(raster.cells.len(), 10);
+    let windward = raster_cell(&raster, 0).unwrap();
+    let leeward = raster_cell(&raster, 4).unwrap();
+    assert!((windward.exposure - 1.0).abs() < f32::EPSILON);
+    assert!((windward.speed - 2.0).abs() < f32::EPSILON);
+    assert!(leeward.exposure < 0.5);
+}
+
+#[actix_rt::test]
+async fn test_wind_rose_requires_winds_layer_and_valid_frequencies() {
+    let pool = init_winds_database().await;
+    let (token, app) = init_test_app(pool.clone()).await;
+    let entry = |direction| WindRoseEntryDto {
+        direction,
+        frequency: 60.0,
+        speed: 3.0,
+    };
+
+    let plants_layer_resp = test::TestRequest::put()
+        .uri("/api/maps/-1/layers/winds/rose")
+        .insert_header((header::AUTHORIZATION, token.clone()))
+        .set_json(update_rose(-1, vec![entry(0)]))
+        .send_request(&app)
+        .await;
+    assert_eq!(plants_layer_resp.status(), StatusCode::BAD_REQUEST);
+
+    let too_frequent_resp = test::TestRequest::put()
+        .uri("/api/maps/-1/layers/winds/rose")
+        .insert_header((header::AUTHORIZATION, token))
+        .set_json(update_rose(-2, vec![entry(0), entry(180)]))
+        .send_request(&app)
+        .await;
+    assert_eq!(too_frequent_resp.status(), StatusCode::BAD_REQUEST);
+}